@@ -1,20 +1,40 @@
 fn main() {
     println!("cargo:rustc-check-cfg=cfg(coverage)");
     println!("cargo:rustc-check-cfg=cfg(coverage_nightly)");
+    println!("cargo:rustc-check-cfg=cfg(has_saturating)");
     println!("cargo:rustc-check-cfg=cfg(nightly)");
-    if is_nightly().unwrap_or(false) {
+
+    let version = rustc_version();
+
+    if version.as_deref().map_or(false, is_nightly) {
         println!("cargo:rustc-cfg=nightly");
     }
+
+    // `core::num::Saturating` was stabilised in Rust 1.74
+    if version.as_deref().and_then(minor_version).unwrap_or(0) >= 74 {
+        println!("cargo:rustc-cfg=has_saturating");
+    }
 }
 
-fn is_nightly() -> Option<bool> {
+fn rustc_version() -> Option<String> {
     let rustc = std::env::var_os("RUSTC")?;
     let output = std::process::Command::new(rustc)
         .arg("--version")
         .output()
         .ok()?;
-    let version = core::str::from_utf8(&output.stdout).ok()?;
-    let nightly = version.contains("nightly") || version.contains("dev");
+    String::from_utf8(output.stdout).ok()
+}
+
+fn is_nightly(version: &str) -> bool {
+    version.contains("nightly") || version.contains("dev")
+}
 
-    Some(nightly)
+fn minor_version(version: &str) -> Option<u32> {
+    version
+        .split_whitespace()
+        .nth(1)?
+        .split('.')
+        .nth(1)?
+        .parse()
+        .ok()
 }
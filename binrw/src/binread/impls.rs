@@ -1,9 +1,13 @@
 use crate::{
     io::{self, Read, Seek},
-    BinRead, BinResult, Endian, Error, NamedArgs,
+    BinRead, BinResult, Endian, Error, NamedArgs, ReadSequential,
 };
+use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{borrow::Cow, rc::Rc};
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
     NonZeroU32, NonZeroU64, NonZeroU8,
@@ -69,7 +73,59 @@ binread_nonzero_impl! {
     NonZeroI8, i8, NonZeroI16, i16, NonZeroI32, i32, NonZeroI64, i64, NonZeroI128, i128,
 }
 
-/// Named arguments for the [`BinRead::read_options()`] implementation of [`Vec`].
+macro_rules! binread_atomic_impl {
+    ($($Ty:ident, $Int:ty, $size:literal),* $(,)?) => {
+        $(
+            #[cfg(target_has_atomic = $size)]
+            impl BinRead for core::sync::atomic::$Ty {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    endian: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    <$Int>::read_options(reader, endian, ()).map(Self::new)
+                }
+            }
+        )+
+    }
+}
+
+binread_atomic_impl! {
+    AtomicU8, u8, "8", AtomicU16, u16, "16", AtomicU32, u32, "32", AtomicU64, u64, "64",
+    AtomicI8, i8, "8", AtomicI16, i16, "16", AtomicI32, i32, "32", AtomicI64, i64, "64",
+}
+
+impl<T: BinRead> BinRead for core::num::Wrapping<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, endian, args).map(Self)
+    }
+}
+
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: BinRead> BinRead for core::num::Saturating<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, endian, args).map(Self)
+    }
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of [`Vec`]
+/// and other collections.
 ///
 /// # Examples
 ///
@@ -143,7 +199,56 @@ where
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        crate::helpers::count_with(args.count, B::read_options)(reader, endian, args.inner)
+        crate::helpers::count_read_options(args.count, B::read_options)(reader, endian, args.inner)
+    }
+}
+
+macro_rules! binread_collection_impl {
+    ($($(#[$attr:meta])* $Ty:ident $(: $bound:path)?),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<B> BinRead for $Ty<B>
+        where
+            B: BinRead $(+ $bound)? + 'static,
+            for<'a> B::Args<'a>: Clone,
+        {
+            type Args<'a> = VecArgs<B::Args<'a>>;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                crate::helpers::count_read_options(args.count, B::read_options)(reader, endian, args.inner)
+            }
+        }
+    )+}
+}
+
+binread_collection_impl!(
+    VecDeque,
+    BinaryHeap: Ord,
+    /// Reads `count` elements. Duplicate elements are merged, so the set may
+    /// contain fewer than `count` elements.
+    BTreeSet: Ord,
+);
+
+/// Reads `count` elements. Duplicate elements are merged, so the set may
+/// contain fewer than `count` elements.
+#[cfg(feature = "std")]
+impl<B, S> BinRead for std::collections::HashSet<B, S>
+where
+    B: BinRead + Eq + core::hash::Hash + 'static,
+    S: core::hash::BuildHasher + Default + 'static,
+    for<'a> B::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<B::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        crate::helpers::count_read_options(args.count, B::read_options)(reader, endian, args.inner)
     }
 }
 
@@ -210,6 +315,63 @@ impl<T: BinRead> BinRead for Box<T> {
     }
 }
 
+macro_rules! binread_shared_impl {
+    ($($(#[$attr:meta])* $Ty:ident),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<T: BinRead> BinRead for $Ty<T> {
+            type Args<'a> = T::Args<'a>;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                Ok($Ty::new(T::read_options(reader, endian, args)?))
+            }
+        }
+
+        $(#[$attr])*
+        impl<T> BinRead for $Ty<[T]>
+        where
+            Vec<T>: BinRead,
+        {
+            type Args<'a> = <Vec<T> as BinRead>::Args<'a>;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                Ok(Vec::<T>::read_options(reader, endian, args)?.into())
+            }
+        }
+    )+}
+}
+
+binread_shared_impl!(
+    Rc,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc,
+);
+
+/// Reads the owned form of the value, so the result is always
+/// [`Cow::Owned`].
+impl<B> BinRead for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: BinRead,
+{
+    type Args<'a> = <B::Owned as BinRead>::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Cow::Owned(B::Owned::read_options(reader, endian, args)?))
+    }
+}
+
 impl<T: BinRead> BinRead for Option<T> {
     type Args<'a> = T::Args<'a>;
 
@@ -229,3 +391,141 @@ impl<T> BinRead for core::marker::PhantomData<T> {
         Ok(core::marker::PhantomData)
     }
 }
+
+macro_rules! read_sequential_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl ReadSequential for $Ty {})+
+    }
+}
+
+read_sequential_impl!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    ()
+);
+
+macro_rules! read_sequential_atomic_impl {
+    ($($Ty:ident, $size:literal),* $(,)?) => {
+        $(
+            #[cfg(target_has_atomic = $size)]
+            impl ReadSequential for core::sync::atomic::$Ty {}
+        )+
+    }
+}
+
+read_sequential_atomic_impl! {
+    AtomicU8, "8", AtomicU16, "16", AtomicU32, "32", AtomicU64, "64",
+    AtomicI8, "8", AtomicI16, "16", AtomicI32, "32", AtomicI64, "64",
+}
+
+impl<T: ReadSequential> ReadSequential for core::num::Wrapping<T> {}
+
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: ReadSequential> ReadSequential for core::num::Saturating<T> {}
+
+impl<B> ReadSequential for Vec<B>
+where
+    B: ReadSequential + 'static,
+    for<'a> B::Args<'a>: Clone,
+{
+}
+
+macro_rules! read_sequential_collection_impl {
+    ($($Ty:ident $(: $bound:path)?),+ $(,)?) => {$(
+        impl<B> ReadSequential for $Ty<B>
+        where
+            B: ReadSequential $(+ $bound)? + 'static,
+            for<'a> B::Args<'a>: Clone,
+        {
+        }
+    )+}
+}
+
+read_sequential_collection_impl!(VecDeque, BinaryHeap: Ord, BTreeSet: Ord);
+
+#[cfg(feature = "std")]
+impl<B, S> ReadSequential for std::collections::HashSet<B, S>
+where
+    B: ReadSequential + Eq + core::hash::Hash + 'static,
+    S: core::hash::BuildHasher + Default + 'static,
+    for<'a> B::Args<'a>: Clone,
+{
+}
+
+impl<B, const N: usize> ReadSequential for [B; N]
+where
+    B: ReadSequential,
+    for<'a> B::Args<'a>: Clone,
+{
+}
+
+macro_rules! read_sequential_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<
+            Args: Clone,
+            $type1: for<'a> BinRead<Args<'a> = Args> + ReadSequential,
+            $($types: for<'a> BinRead<Args<'a> = Args> + ReadSequential),*
+        > ReadSequential for ($type1, $($types),*) {}
+
+        read_sequential_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+read_sequential_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
+
+impl<T: ReadSequential> ReadSequential for Box<T> {}
+
+macro_rules! read_sequential_shared_impl {
+    ($($(#[$attr:meta])* $Ty:ident),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<T: ReadSequential> ReadSequential for $Ty<T> {}
+
+        $(#[$attr])*
+        impl<T> ReadSequential for $Ty<[T]> where Vec<T>: ReadSequential {}
+    )+}
+}
+
+read_sequential_shared_impl!(
+    Rc,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc,
+);
+
+impl<B> ReadSequential for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: ReadSequential,
+{
+}
+
+impl<T: ReadSequential> ReadSequential for Option<T> {}
+
+impl<T> ReadSequential for core::marker::PhantomData<T> {}
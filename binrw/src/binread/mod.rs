@@ -1,10 +1,11 @@
 mod impls;
 
 use crate::{
-    io::{Read, Seek},
-    BinResult, Endian,
     __private::Required,
+    error::CustomError,
+    io::{NoSeek, Read, Seek},
     meta::ReadEndian,
+    BinResult, Endian, Error,
 };
 pub use impls::VecArgs;
 
@@ -113,7 +114,11 @@ pub trait BinRead: Sized {
     where
         Self: ReadEndian,
     {
-        Self::read_options(reader, Endian::Little, args)
+        Self::read_options(
+            reader,
+            Self::ENDIAN.endian().unwrap_or(Endian::Little),
+            args,
+        )
     }
 
     /// Read `Self` from the reader, assuming big-endian byte order, using the
@@ -194,6 +199,190 @@ pub trait BinRead: Sized {
     ) -> BinResult<Self>;
 }
 
+/// A marker trait for [`BinRead`] types which never seek while reading, so
+/// they can be read from streams which only implement [`Read`], such as
+/// sockets and pipes.
+///
+/// The provided methods wrap the reader in a [`NoSeek`], so positions in
+/// errors are relative to where reading started.
+///
+/// # Derivable
+///
+/// This trait is implemented by `#[derive(BinRead)]` for structs which do not
+/// use any directives that may seek, such as [`seek_before`], [`pad_before`],
+/// [`restore_position`], or [`try`], nor any directives that read a different
+/// type, such as [`map`] or [`parse_with`]. The implementation is only usable
+/// if every field type also implements `ReadSequential`.
+///
+/// [`map`]: crate::docs::attribute#map
+/// [`pad_before`]: crate::docs::attribute#padding-and-alignment
+/// [`parse_with`]: crate::docs::attribute#custom-parserswriters
+/// [`restore_position`]: crate::docs::attribute#restore-position
+/// [`seek_before`]: crate::docs::attribute#padding-and-alignment
+/// [`try`]: crate::docs::attribute#try
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, ReadSequential};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// // A byte slice implements `Read` but not `Seek`
+/// let point = Point::read_sequential(&mut b"\0\x01\0\x02".as_slice()).unwrap();
+/// assert_eq!((point.x, point.y), (1, 2));
+/// ```
+pub trait ReadSequential: BinRead {
+    /// Read `Self` from the reader using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn read_sequential<R: Read>(reader: &mut R) -> BinResult<Self>
+    where
+        Self: ReadEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        Self::read_sequential_args(reader, Self::Args::args())
+    }
+
+    /// Read `Self` from the reader using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn read_sequential_args<R: Read>(reader: &mut R, args: Self::Args<'_>) -> BinResult<Self>
+    where
+        Self: ReadEndian,
+    {
+        Self::read_sequential_options(
+            reader,
+            Self::ENDIAN.endian().unwrap_or(Endian::Little),
+            args,
+        )
+    }
+
+    /// Read `Self` from the reader using the given [`Endian`] and
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn read_sequential_options<R: Read>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Self::read_options(&mut NoSeek::sequential(reader), endian, args)
+    }
+}
+
+/// The `TryRead` trait reads objects which report failures using their own
+/// error type instead of [`Error`].
+///
+/// This trait is automatically implemented for derived types with an
+/// [`error`](crate::docs::attribute#error-type) directive.
+///
+/// If the [root cause](Error::root_cause) of a failure is a
+/// [custom error](Error::Custom) of type [`Self::Error`](TryRead::Error), such
+/// as one raised by an [`assert`](crate::docs::attribute#assert), that error is
+/// returned as-is. Otherwise, the [`Error`] is converted using [`From`].
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, TryRead};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ImageError {
+///     TooLarge,
+///     Malformed(String),
+/// }
+///
+/// impl From<binrw::Error> for ImageError {
+///     fn from(error: binrw::Error) -> Self {
+///         ImageError::Malformed(error.to_string())
+///     }
+/// }
+///
+/// impl core::fmt::Display for ImageError {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         write!(f, "{self:?}")
+///     }
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big, error = ImageError)]
+/// struct Image {
+///     #[br(assert(width <= 1024, ImageError::TooLarge))]
+///     width: u16,
+/// }
+///
+/// let error = Image::try_read(&mut binrw::io::Cursor::new(b"\x10\0")).err();
+/// assert_eq!(error, Some(ImageError::TooLarge));
+/// ```
+pub trait TryRead: BinRead {
+    /// The type used to report failures.
+    type Error: CustomError + From<Error> + 'static;
+
+    /// Read `Self` from the reader using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, a [`Self::Error`](TryRead::Error) will be returned.
+    #[inline]
+    fn try_read<R: Read + Seek>(reader: &mut R) -> Result<Self, Self::Error>
+    where
+        Self: ReadEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        Self::try_read_args(reader, Self::Args::args())
+    }
+
+    /// Read `Self` from the reader using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, a [`Self::Error`](TryRead::Error) will be returned.
+    #[inline]
+    fn try_read_args<R: Read + Seek>(
+        reader: &mut R,
+        args: Self::Args<'_>,
+    ) -> Result<Self, Self::Error>
+    where
+        Self: ReadEndian,
+    {
+        Self::try_read_options(
+            reader,
+            Self::ENDIAN.endian().unwrap_or(Endian::Little),
+            args,
+        )
+    }
+
+    /// Read `Self` from the reader using the given [`Endian`] and
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, a [`Self::Error`](TryRead::Error) will be returned.
+    #[inline]
+    fn try_read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> Result<Self, Self::Error> {
+        Self::read_options(reader, endian, args).map_err(crate::__private::into_error)
+    }
+}
+
 /// Extension methods for reading [`BinRead`] objects directly from a reader.
 ///
 /// # Examples
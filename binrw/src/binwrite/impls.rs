@@ -1,9 +1,13 @@
 use crate::{
     io::{Seek, Write},
-    BinResult, BinWrite, Endian,
+    BinResult, BinWrite, Endian, WriteSequential,
 };
+use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{borrow::Cow, rc::Rc};
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
 use core::{
     any::Any,
     marker::PhantomData,
@@ -74,6 +78,68 @@ binwrite_nonzero_num_impl!(
     NonZeroI128 => i128,
 );
 
+macro_rules! binwrite_atomic_impl {
+    ($($atomic_type:ident => $type_name:ty, $size:literal),*$(,)?) => {
+        $(
+            /// Writes the value loaded with [`Ordering::Relaxed`](core::sync::atomic::Ordering::Relaxed).
+            #[cfg(target_has_atomic = $size)]
+            impl BinWrite for core::sync::atomic::$atomic_type {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    endian: Endian,
+                    (): Self::Args<'_>,
+                ) -> BinResult<()> {
+                    self.load(core::sync::atomic::Ordering::Relaxed)
+                        .write_options(writer, endian, ())
+                }
+            }
+        )*
+    };
+}
+
+binwrite_atomic_impl!(
+    AtomicU8  => u8, "8",
+    AtomicU16 => u16, "16",
+    AtomicU32 => u32, "32",
+    AtomicU64 => u64, "64",
+    AtomicI8  => i8, "8",
+    AtomicI16 => i16, "16",
+    AtomicI32 => i32, "32",
+    AtomicI64 => i64, "64",
+);
+
+impl<T: BinWrite> BinWrite for core::num::Wrapping<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, endian, args)
+    }
+}
+
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: BinWrite> BinWrite for core::num::Saturating<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, endian, args)
+    }
+}
+
 impl<T, const N: usize> BinWrite for [T; N]
 where
     T: BinWrite + 'static,
@@ -138,9 +204,96 @@ where
         } else if let Some(this) = <dyn Any>::downcast_ref::<Vec<i8>>(self) {
             writer.write_all(bytemuck::cast_slice(this.as_slice()))?;
         } else {
-            for item in self {
-                T::write_options(item, writer, endian, args.clone())?;
+            vec_fast_int!(try (i16 u16 i32 u32 i64 u64 i128 u128) using (self, writer, endian) else {
+                for item in self {
+                    T::write_options(item, writer, endian, args.clone())?;
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! vec_fast_int {
+    (try ($($Ty:ty)+) using ($list:expr, $writer:expr, $endian:expr) else { $($else:tt)* }) => {
+        $(if let Some(list) = <dyn Any>::downcast_ref::<Vec<$Ty>>($list) {
+            if (cfg!(target_endian = "big") && $endian == Endian::Little)
+                || (cfg!(target_endian = "little") && $endian == Endian::Big)
+            {
+                // Swapping through a fixed buffer avoids allocating a copy of
+                // the whole list
+                let mut buf = [0; 0x100 / core::mem::size_of::<$Ty>()];
+                for chunk in list.chunks(buf.len()) {
+                    let buf = &mut buf[..chunk.len()];
+                    for (out, value) in buf.iter_mut().zip(chunk) {
+                        *out = value.swap_bytes();
+                    }
+                    $writer.write_all(bytemuck::cast_slice::<$Ty, u8>(buf))?;
+                }
+            } else {
+                $writer.write_all(bytemuck::cast_slice::<$Ty, u8>(list))?;
             }
+        } else)* {
+            $($else)*
+        }
+    }
+}
+
+use vec_fast_int;
+
+macro_rules! binwrite_collection_impl {
+    ($($(#[$attr:meta])* $Ty:ident),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<T> BinWrite for $Ty<T>
+        where
+            T: BinWrite,
+            for<'a> T::Args<'a>: Clone,
+        {
+            type Args<'a> = T::Args<'a>;
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<()> {
+                for item in self {
+                    T::write_options(item, writer, endian, args.clone())?;
+                }
+
+                Ok(())
+            }
+        }
+    )+}
+}
+
+binwrite_collection_impl!(
+    VecDeque,
+    /// Writes the elements in the arbitrary order used by
+    /// [`BinaryHeap::iter`].
+    BinaryHeap,
+    BTreeSet,
+);
+
+/// Writes the elements in the arbitrary order used by
+/// [`HashSet::iter`](std::collections::HashSet::iter).
+#[cfg(feature = "std")]
+impl<T, S> BinWrite for std::collections::HashSet<T, S>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Clone,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for item in self {
+            T::write_options(item, writer, endian, args.clone())?;
         }
 
         Ok(())
@@ -179,6 +332,67 @@ impl<T: BinWrite + ?Sized + 'static> BinWrite for Box<T> {
     }
 }
 
+macro_rules! binwrite_shared_impl {
+    ($($(#[$attr:meta])* $Ty:ident),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<T: BinWrite + ?Sized + 'static> BinWrite for $Ty<T> {
+            type Args<'a> = T::Args<'a>;
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                endian: Endian,
+                args: Self::Args<'_>,
+            ) -> BinResult<()> {
+                if let Some(this) = <dyn Any>::downcast_ref::<$Ty<[u8]>>(self) {
+                    writer.write_all(this)?;
+                } else {
+                    (**self).write_options(writer, endian, args)?;
+                }
+
+                Ok(())
+            }
+        }
+    )+}
+}
+
+binwrite_shared_impl!(
+    Rc,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc,
+);
+
+impl<B> BinWrite for Cow<'_, B>
+where
+    B: BinWrite + ToOwned + ?Sized,
+{
+    type Args<'a> = B::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, endian, args)
+    }
+}
+
+/// Writes the UTF-8 bytes of the string, without a length or terminator.
+impl BinWrite for str {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(self.as_bytes())?;
+        Ok(())
+    }
+}
+
 impl<T: BinWrite> BinWrite for Option<T> {
     type Args<'a> = T::Args<'a>;
 
@@ -258,3 +472,142 @@ binwrite_tuple_impl!(
     b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
     b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
 );
+
+macro_rules! write_sequential_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl WriteSequential for $Ty {})+
+    }
+}
+
+write_sequential_impl!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    (),
+    str
+);
+
+macro_rules! write_sequential_atomic_impl {
+    ($($Ty:ident, $size:literal),* $(,)?) => {
+        $(
+            #[cfg(target_has_atomic = $size)]
+            impl WriteSequential for core::sync::atomic::$Ty {}
+        )+
+    }
+}
+
+write_sequential_atomic_impl! {
+    AtomicU8, "8", AtomicU16, "16", AtomicU32, "32", AtomicU64, "64",
+    AtomicI8, "8", AtomicI16, "16", AtomicI32, "32", AtomicI64, "64",
+}
+
+impl<T: WriteSequential> WriteSequential for core::num::Wrapping<T> {}
+
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: WriteSequential> WriteSequential for core::num::Saturating<T> {}
+
+impl<T, const N: usize> WriteSequential for [T; N]
+where
+    T: WriteSequential + 'static,
+    for<'a> T::Args<'a>: Clone,
+{
+}
+
+impl<T> WriteSequential for [T]
+where
+    T: WriteSequential,
+    for<'a> T::Args<'a>: Clone,
+{
+}
+
+impl<T> WriteSequential for Vec<T>
+where
+    T: WriteSequential + 'static,
+    for<'a> T::Args<'a>: Clone,
+{
+}
+
+macro_rules! write_sequential_collection_impl {
+    ($($Ty:ident),+ $(,)?) => {$(
+        impl<T> WriteSequential for $Ty<T>
+        where
+            T: WriteSequential,
+            for<'a> T::Args<'a>: Clone,
+        {
+        }
+    )+}
+}
+
+write_sequential_collection_impl!(VecDeque, BinaryHeap, BTreeSet);
+
+#[cfg(feature = "std")]
+impl<T, S> WriteSequential for std::collections::HashSet<T, S>
+where
+    T: WriteSequential,
+    for<'a> T::Args<'a>: Clone,
+{
+}
+
+impl<T: WriteSequential + ?Sized> WriteSequential for &T {}
+
+impl<T: WriteSequential + ?Sized + 'static> WriteSequential for Box<T> {}
+
+macro_rules! write_sequential_shared_impl {
+    ($($(#[$attr:meta])* $Ty:ident),+ $(,)?) => {$(
+        $(#[$attr])*
+        impl<T: WriteSequential + ?Sized + 'static> WriteSequential for $Ty<T> {}
+    )+}
+}
+
+write_sequential_shared_impl!(
+    Rc,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc,
+);
+
+impl<B> WriteSequential for Cow<'_, B> where B: WriteSequential + ToOwned + ?Sized {}
+
+impl<T: WriteSequential> WriteSequential for Option<T> {}
+
+impl<T> WriteSequential for PhantomData<T> {}
+
+macro_rules! write_sequential_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<
+            Args: Clone,
+            $type1: for<'a> BinWrite<Args<'a> = Args> + WriteSequential,
+            $($types: for<'a> BinWrite<Args<'a> = Args> + WriteSequential),*
+        > WriteSequential for ($type1, $($types),*) {}
+
+        write_sequential_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+write_sequential_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
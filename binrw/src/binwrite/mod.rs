@@ -1,9 +1,9 @@
 mod impls;
 
 use crate::{
-    io::{Seek, Write},
-    BinResult, Endian,
     __private::Required,
+    io::{NoSeek, Seek, Write},
+    BinResult, Endian,
 };
 
 /// The `BinWrite` trait serialises objects and writes them to streams.
@@ -96,7 +96,11 @@ pub trait BinWrite {
     where
         Self: crate::meta::WriteEndian,
     {
-        self.write_options(writer, Endian::Little, args)
+        self.write_options(
+            writer,
+            Self::ENDIAN.endian().unwrap_or(Endian::Little),
+            args,
+        )
     }
 
     /// Write `Self` to the writer, assuming big-endian byte order, using the
@@ -143,6 +147,95 @@ pub trait BinWrite {
     ) -> BinResult<()>;
 }
 
+/// A marker trait for [`BinWrite`] types which never seek while writing, so
+/// they can be written to streams which only implement [`Write`], such as
+/// sockets and pipes.
+///
+/// The provided methods wrap the writer in a [`NoSeek`], so positions in
+/// errors are relative to where writing started.
+///
+/// # Derivable
+///
+/// This trait is implemented by `#[derive(BinWrite)]` for structs which do
+/// not use any directives that may seek, such as [`seek_before`],
+/// [`pad_before`], [`restore_position`], or [`checksum`], nor any directives
+/// that write a different type, such as [`map`] or [`write_with`]. The
+/// implementation is only usable if every field type also implements
+/// `WriteSequential`.
+///
+/// [`checksum`]: crate::docs::attribute#checksum
+/// [`map`]: crate::docs::attribute#map
+/// [`pad_before`]: crate::docs::attribute#padding-and-alignment
+/// [`restore_position`]: crate::docs::attribute#restore-position
+/// [`seek_before`]: crate::docs::attribute#padding-and-alignment
+/// [`write_with`]: crate::docs::attribute#custom-parserswriters
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, WriteSequential};
+///
+/// #[derive(BinWrite)]
+/// #[bw(big)]
+/// struct Point {
+///     x: u16,
+///     y: u16,
+/// }
+///
+/// // A `Vec` implements `Write` but not `Seek`
+/// let mut out = Vec::new();
+/// Point { x: 1, y: 2 }.write_sequential(&mut out).unwrap();
+/// assert_eq!(out, b"\0\x01\0\x02");
+/// ```
+pub trait WriteSequential: BinWrite {
+    /// Write `Self` to the writer using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn write_sequential<W: Write>(&self, writer: &mut W) -> BinResult<()>
+    where
+        Self: crate::meta::WriteEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        self.write_sequential_args(writer, Self::Args::args())
+    }
+
+    /// Write `Self` to the writer using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn write_sequential_args<W: Write>(&self, writer: &mut W, args: Self::Args<'_>) -> BinResult<()>
+    where
+        Self: crate::meta::WriteEndian,
+    {
+        self.write_sequential_options(
+            writer,
+            Self::ENDIAN.endian().unwrap_or(Endian::Little),
+            args,
+        )
+    }
+
+    /// Write `Self` to the writer using the given [`Endian`] and
+    /// arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn write_sequential_options<W: Write>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.write_options(&mut NoSeek::sequential(writer), endian, args)
+    }
+}
+
 /// Extension methods for writing [`BinWrite`] objects directly to a writer.
 ///
 /// # Examples
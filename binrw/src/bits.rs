@@ -0,0 +1,322 @@
+//! Types for reading and writing values which are not a whole number of bytes,
+//! such as the fields of a flags word.
+//!
+//! These are used by the [`bits`](crate::docs::attribute#bit-fields) directive,
+//! and can also be used directly from a custom parser or writer.
+//!
+//! The order of the bits in each byte is given by a [`BitOrder`]. With
+//! [`BitOrder::Msb0`], values are read starting from the most significant bit
+//! of each byte, and the first bit read is the most significant bit of the
+//! value. With [`BitOrder::Lsb0`], values are read starting from the least
+//! significant bit of each byte, and the first bit read is the least
+//! significant bit of the value. In both cases, a value with a whole number of
+//! bytes which starts on a byte boundary has the same encoding as an integer of
+//! that size with the matching byte order.
+//!
+//! A byte is never shared between bit orders. If the bit order changes after
+//! part of a byte has been used, the next value starts at the next byte.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{bits::{BitOrder, BitReader}, io::Cursor};
+//! let mut reader = Cursor::new(b"\xa5\x0f");
+//! let mut bits = BitReader::new();
+//! assert_eq!(bits.read::<_, u8>(&mut reader, BitOrder::Msb0, 3).unwrap(), 0b101);
+//! assert_eq!(bits.read::<_, u8>(&mut reader, BitOrder::Msb0, 5).unwrap(), 0b00101);
+//! assert_eq!(bits.read::<_, u16>(&mut reader, BitOrder::Lsb0, 8).unwrap(), 0x0f);
+//! ```
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinResult, Endian, Error,
+};
+
+/// The order of bits within each byte.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BitOrder {
+    /// The most significant bit comes first, as in most network protocols and
+    /// MPEG streams.
+    Msb0,
+    /// The least significant bit comes first, as in DEFLATE streams.
+    Lsb0,
+}
+
+impl From<Endian> for BitOrder {
+    /// Returns the bit order which is used by default for the given byte
+    /// order: [`Msb0`](Self::Msb0) for big-endian and [`Lsb0`](Self::Lsb0) for
+    /// little-endian.
+    fn from(endian: Endian) -> Self {
+        match endian {
+            Endian::Big => Self::Msb0,
+            Endian::Little => Self::Lsb0,
+        }
+    }
+}
+
+/// A type which can be read from or written to a given number of bits.
+///
+/// This trait is implemented for all integer types and for [`bool`]. Signed
+/// integers are stored in two's complement, so a signed value read from fewer
+/// bits than its type is sign-extended.
+pub trait BitField: Sized {
+    /// The largest number of bits which can be used for this type.
+    const BITS: u32;
+
+    /// Converts the low `len` bits of `bits` to a value.
+    ///
+    /// `len` is between 1 and [`BITS`](Self::BITS), and the other bits of
+    /// `bits` are zero.
+    fn from_bits(bits: u64, len: u32) -> Self;
+
+    /// Converts this value to `len` bits, or returns `None` if it does not
+    /// fit.
+    ///
+    /// `len` is between 1 and [`BITS`](Self::BITS).
+    fn to_bits(&self, len: u32) -> Option<u64>;
+}
+
+macro_rules! bit_field_unsigned_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl BitField for $Ty {
+            const BITS: u32 = <$Ty>::BITS;
+
+            // Lint: `bits` has at most `BITS` bits
+            #[allow(clippy::cast_possible_truncation)]
+            fn from_bits(bits: u64, _: u32) -> Self {
+                bits as $Ty
+            }
+
+            fn to_bits(&self, len: u32) -> Option<u64> {
+                let bits = u64::from(*self);
+                (bits & !mask(len) == 0).then_some(bits)
+            }
+        })+
+    }
+}
+
+bit_field_unsigned_impl!(u8, u16, u32, u64);
+
+macro_rules! bit_field_signed_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl BitField for $Ty {
+            const BITS: u32 = <$Ty>::BITS;
+
+            // Lint: The value is sign-extended from `len` bits, which is at
+            // most `BITS`, so it fits in the type
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            fn from_bits(bits: u64, len: u32) -> Self {
+                let shift = 64 - len;
+                (((bits << shift) as i64) >> shift) as $Ty
+            }
+
+            fn to_bits(&self, len: u32) -> Option<u64> {
+                let value = i64::from(*self);
+                let shift = 64 - len;
+                // Lint: The casts reinterpret the two's complement bits
+                #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+                let fits = (((value as u64) << shift) as i64) >> shift == value;
+                // Lint: The bits are masked to `len` bits
+                #[allow(clippy::cast_sign_loss)]
+                let bits = value as u64 & mask(len);
+                fits.then_some(bits)
+            }
+        })+
+    }
+}
+
+bit_field_signed_impl!(i8, i16, i32, i64);
+
+impl BitField for bool {
+    const BITS: u32 = 1;
+
+    fn from_bits(bits: u64, _: u32) -> Self {
+        bits != 0
+    }
+
+    fn to_bits(&self, _: u32) -> Option<u64> {
+        Some(u64::from(*self))
+    }
+}
+
+/// Reads values which are not a whole number of bytes from a stream.
+///
+/// Each call to [`read`](Self::read) continues from the bit after the last
+/// one that was read, reading more bytes from the stream as needed. Any bits
+/// left over in the last byte which was read are ignored when the
+/// `BitReader` is dropped.
+///
+/// See the [module documentation](self) for the order of the bits.
+#[derive(Clone, Debug, Default)]
+pub struct BitReader {
+    /// The last byte which was read from the stream.
+    byte: u8,
+    /// The number of bits in `byte` which have not been read yet.
+    left: u32,
+    /// The bit order of `byte`.
+    order: Option<BitOrder>,
+}
+
+impl BitReader {
+    /// Creates a new `BitReader` which starts at the next byte of the stream.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a value from the next `len` bits.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error::Io`] is returned. If `len` is zero or
+    /// larger than [`T::BITS`](BitField::BITS), an [`Error::AssertFail`] is
+    /// returned.
+    pub fn read<R: Read + Seek, T: BitField>(
+        &mut self,
+        reader: &mut R,
+        order: BitOrder,
+        len: u32,
+    ) -> BinResult<T> {
+        check_len::<_, T>(reader, len)?;
+        if self.order != Some(order) {
+            self.left = 0;
+            self.order = Some(order);
+        }
+
+        let mut value = 0;
+        let mut done = 0;
+        while done < len {
+            if self.left == 0 {
+                let mut byte = [0];
+                reader.read_exact(&mut byte)?;
+                self.byte = byte[0];
+                self.left = 8;
+            }
+
+            let take = self.left.min(len - done);
+            let used = 8 - self.left;
+            match order {
+                BitOrder::Msb0 => {
+                    let bits = u64::from(self.byte >> (self.left - take)) & mask(take);
+                    value = (value << take) | bits;
+                }
+                BitOrder::Lsb0 => {
+                    let bits = u64::from(self.byte >> used) & mask(take);
+                    value |= bits << done;
+                }
+            }
+            self.left -= take;
+            done += take;
+        }
+
+        Ok(T::from_bits(value, len))
+    }
+}
+
+/// Writes values which are not a whole number of bytes to a stream.
+///
+/// Each call to [`write`](Self::write) continues from the bit after the last
+/// one that was written. Whole bytes are written to the stream as soon as they
+/// are complete, and the last partial byte is written by
+/// [`flush`](Self::flush), with the unused bits set to zero.
+///
+/// See the [module documentation](self) for the order of the bits.
+#[derive(Clone, Debug, Default)]
+pub struct BitWriter {
+    /// The bits of the byte which has not been written yet.
+    byte: u8,
+    /// The number of bits in `byte` which have been written.
+    used: u32,
+    /// The bit order of `byte`.
+    order: Option<BitOrder>,
+}
+
+impl BitWriter {
+    /// Creates a new `BitWriter` which starts at the next byte of the stream.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes a value to the next `len` bits.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error::Io`] is returned. If `len` is zero or
+    /// larger than [`T::BITS`](BitField::BITS), or the value does not fit in
+    /// `len` bits, an [`Error::AssertFail`] is returned.
+    pub fn write<W: Write + Seek, T: BitField>(
+        &mut self,
+        writer: &mut W,
+        order: BitOrder,
+        value: &T,
+        len: u32,
+    ) -> BinResult<()> {
+        check_len::<_, T>(writer, len)?;
+        let Some(value) = value.to_bits(len) else {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!("value does not fit in {len} bits"),
+            });
+        };
+        if self.order != Some(order) {
+            self.flush(writer)?;
+            self.order = Some(order);
+        }
+
+        let mut done = 0;
+        while done < len {
+            let space = 8 - self.used;
+            let take = space.min(len - done);
+            let (bits, shift) = match order {
+                BitOrder::Msb0 => (value >> (len - done - take), space - take),
+                BitOrder::Lsb0 => (value >> done, self.used),
+            };
+            // Lint: The bits are masked to at most 8 bits
+            #[allow(clippy::cast_possible_truncation)]
+            let bits = (bits & mask(take)) as u8;
+            self.byte |= bits << shift;
+            self.used += take;
+            done += take;
+
+            if self.used == 8 {
+                self.flush(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the last partial byte, if there is one, so that the next value
+    /// starts at the next byte of the stream.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error::Io`] is returned.
+    pub fn flush<W: Write>(&mut self, writer: &mut W) -> BinResult<()> {
+        if self.used != 0 {
+            writer.write_all(&[self.byte])?;
+            self.byte = 0;
+            self.used = 0;
+        }
+        Ok(())
+    }
+}
+
+fn check_len<S: Seek, T: BitField>(stream: &mut S, len: u32) -> BinResult<()> {
+    if len == 0 || len > T::BITS {
+        Err(Error::AssertFail {
+            pos: stream.stream_position()?,
+            message: alloc::format!(
+                "bit field length must be between 1 and {}, but it is {len}",
+                T::BITS
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn mask(len: u32) -> u64 {
+    u64::MAX >> (64 - len)
+}
@@ -0,0 +1,104 @@
+//! Checksum functions for use with the
+//! [`checksum`](crate::docs::attribute#checksum) directive.
+//!
+//! Each function calculates a checksum over a byte slice. Any function or
+//! closure with the same shape can be used with the directive, so other
+//! algorithms, such as those from another crate, can be used too.
+
+/// Calculates the CRC-32 (ISO-HDLC) checksum of `bytes`.
+///
+/// This is the CRC used by zlib, gzip, PNG, and ZIP.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::checksum::crc32(b"123456789"), 0xcbf4_3926);
+/// ```
+#[must_use]
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[usize::from(crc.to_le_bytes()[0] ^ byte)] ^ (crc >> 8)
+    })
+}
+
+/// Calculates the CRC-16 (ARC) checksum of `bytes`.
+///
+/// This is the CRC sometimes called CRC-16/IBM or CRC-16/LHA.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::checksum::crc16(b"123456789"), 0xbb3d);
+/// ```
+#[must_use]
+pub fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0, |crc, &byte| {
+        CRC16_TABLE[usize::from(crc.to_le_bytes()[0] ^ byte)] ^ (crc >> 8)
+    })
+}
+
+/// Calculates the Adler-32 checksum of `bytes`.
+///
+/// This is the checksum used at the end of a zlib stream.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::checksum::adler32(b"Wikipedia"), 0x11e6_0398);
+/// ```
+#[must_use]
+pub fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    // This is the largest number of bytes which can be summed before the sums
+    // have to be reduced to avoid overflowing a `u32`.
+    const CHUNK: usize = 5552;
+
+    let (mut a, mut b) = (1, 0);
+    for chunk in bytes.chunks(CHUNK) {
+        for &byte in chunk {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+const CRC32_TABLE: [u32; 256] = crc_table(0xedb8_8320);
+
+// Lint: The table is built from 16-bit values, so every entry fits in a `u16`.
+#[allow(clippy::cast_possible_truncation)]
+const CRC16_TABLE: [u16; 256] = {
+    let wide = crc_table(0xa001);
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = wide[i] as u16;
+        i += 1;
+    }
+    table
+};
+
+// Builds the lookup table for a reflected CRC with the given polynomial.
+const fn crc_table(poly: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        // Lint: `i` is always less than 256.
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ poly
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
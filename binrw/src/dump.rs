@@ -0,0 +1,376 @@
+//! Types for rendering values as text with the
+//! [`dump`](crate::docs::attribute#dump) directive.
+//!
+//! The text format is deterministic and is not affected by how [`Debug`] is
+//! implemented, so it can be used for golden-file tests and bug reports. Each
+//! field, element, or entry is written on its own line, indented by four
+//! spaces for each level of nesting:
+//!
+//! ```text
+//! Header {
+//!     version: 2
+//!     name: "abc"
+//!     flags: Some(Flags(
+//!         0: 1
+//!     ))
+//!     items: [
+//!         1
+//!         2
+//!     ]
+//! }
+//! ```
+//!
+//! When a struct is read using a `read_dump` function generated by the
+//! directive, each top-level field is also annotated with the range of bytes
+//! it was read from, like `version @ 0x0..0x2: 2`.
+
+use crate::{file_ptr::IntoSeekFrom, FilePtr, PosValue};
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc, vec::Vec};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, string::ToString};
+use core::{
+    fmt::{self, Write},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU8,
+    },
+};
+
+/// A type which can be rendered in the text [dump format](self).
+///
+/// This trait can be derived for structs and enums with the
+/// [`dump`](crate::docs::attribute#dump) directive.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::dump::{self, Dump, Dumper};
+///
+/// struct Point(i32, i32);
+///
+/// impl Dump for Point {
+///     fn dump(&self, dumper: &mut Dumper<'_>) {
+///         dumper.record("Point").field("x", &self.0).field("y", &self.1).finish();
+///     }
+/// }
+///
+/// assert_eq!(dump::to_text(&Point(1, -2)), "Point {\n    x: 1\n    y: -2\n}");
+/// ```
+pub trait Dump {
+    /// Renders this value using the given dumper.
+    fn dump(&self, dumper: &mut Dumper<'_>);
+}
+
+/// Renders a value in the text [dump format](self).
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(binrw::dump::to_text(&(1u8, Some("a"))), "(\n    1\n    Some(\"a\")\n)");
+/// ```
+pub fn to_text<T: Dump + ?Sized>(value: &T) -> String {
+    render(value, None)
+}
+
+/// Renders a value, annotating its top-level fields with the given spans.
+pub(crate) fn render<T: Dump + ?Sized>(
+    value: &T,
+    spans: Option<&[(&'static str, u64, u64)]>,
+) -> String {
+    let mut out = String::new();
+    value.dump(&mut Dumper {
+        out: &mut out,
+        indent: 0,
+        spans,
+    });
+    out
+}
+
+/// A renderer for the text [dump format](self).
+///
+/// This is passed to [`Dump::dump`]. Values which have no fields are written
+/// with [`value`](Self::value), and values with fields or elements are written
+/// with [`record`](Self::record), [`tuple`](Self::tuple), or
+/// [`list`](Self::list).
+pub struct Dumper<'a> {
+    /// The rendered text.
+    out: &'a mut String,
+    /// The current level of nesting.
+    indent: usize,
+    /// The byte ranges of the fields of the outermost value, if known.
+    spans: Option<&'a [(&'static str, u64, u64)]>,
+}
+
+impl<'a> Dumper<'a> {
+    /// Writes a value without any fields.
+    pub fn value(&mut self, value: impl fmt::Display) {
+        self.write(format_args!("{value}"));
+    }
+
+    /// Starts writing a value with named fields, like `Name { field: value }`.
+    pub fn record<'d>(&'d mut self, name: &str) -> Block<'d, 'a> {
+        Block::new(self, format_args!("{name} {{"), "}")
+    }
+
+    /// Starts writing a value with positional fields, like `Name(value)`.
+    ///
+    /// Use an empty name for an anonymous tuple.
+    pub fn tuple<'d>(&'d mut self, name: &str) -> Block<'d, 'a> {
+        Block::new(self, format_args!("{name}("), ")")
+    }
+
+    /// Starts writing a sequence of elements, like `[value]`.
+    pub fn list<'d>(&'d mut self) -> Block<'d, 'a> {
+        Block::new(self, format_args!("["), "]")
+    }
+
+    fn write(&mut self, args: fmt::Arguments<'_>) {
+        // Writing to a `String` cannot fail
+        let _ = self.out.write_fmt(args);
+    }
+
+    fn new_line(&mut self) {
+        self.write(format_args!("\n{:1$}", "", self.indent * 4));
+    }
+}
+
+/// A helper for writing a value with fields or elements.
+///
+/// This struct is created by [`Dumper::record`], [`Dumper::tuple`], and
+/// [`Dumper::list`].
+#[must_use = "the closing delimiter is only written by `finish`"]
+pub struct Block<'d, 'a> {
+    dumper: &'d mut Dumper<'a>,
+    close: &'static str,
+    spans: Option<&'a [(&'static str, u64, u64)]>,
+    is_empty: bool,
+}
+
+impl<'d, 'a> Block<'d, 'a> {
+    fn new(dumper: &'d mut Dumper<'a>, open: fmt::Arguments<'_>, close: &'static str) -> Self {
+        dumper.write(open);
+        let spans = dumper.spans.take();
+        dumper.indent += 1;
+        Self {
+            dumper,
+            close,
+            spans,
+            is_empty: true,
+        }
+    }
+
+    /// Writes a named field, like `field: value`.
+    pub fn field<T: Dump + ?Sized>(&mut self, name: &str, value: &T) -> &mut Self {
+        self.dumper.new_line();
+        self.dumper.write(format_args!("{name}"));
+        if let Some(&(_, start, end)) = self
+            .spans
+            .and_then(|spans| spans.iter().find(|(field, ..)| *field == name))
+        {
+            self.dumper.write(format_args!(" @ {start:#x}..{end:#x}"));
+        }
+        self.dumper.write(format_args!(": "));
+        value.dump(self.dumper);
+        self.is_empty = false;
+        self
+    }
+
+    /// Writes an element without a name.
+    pub fn entry<T: Dump + ?Sized>(&mut self, value: &T) -> &mut Self {
+        self.dumper.new_line();
+        value.dump(self.dumper);
+        self.is_empty = false;
+        self
+    }
+
+    /// Writes each element of an iterator with [`entry`](Self::entry).
+    pub fn entries<T: Dump, I: IntoIterator<Item = T>>(&mut self, entries: I) -> &mut Self {
+        for entry in entries {
+            self.entry(&entry);
+        }
+        self
+    }
+
+    /// Finishes writing the value.
+    pub fn finish(&mut self) {
+        self.dumper.indent -= 1;
+        if !self.is_empty {
+            self.dumper.new_line();
+        }
+        self.dumper.write(format_args!("{}", self.close));
+    }
+}
+
+macro_rules! dump_display_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl Dump for $Ty {
+            fn dump(&self, dumper: &mut Dumper<'_>) {
+                dumper.value(self);
+            }
+        })+
+    }
+}
+
+dump_display_impl!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    bool,
+);
+
+// Floats use `Debug` so that whole numbers keep their decimal point, and
+// characters and strings so that they are quoted and escaped.
+macro_rules! dump_debug_impl {
+    ($($Ty:ty),+ $(,)?) => {
+        $(impl Dump for $Ty {
+            fn dump(&self, dumper: &mut Dumper<'_>) {
+                dumper.value(format_args!("{self:?}"));
+            }
+        })+
+    }
+}
+
+dump_debug_impl!(f32, f64, char, str, String);
+
+impl Dump for () {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        dumper.value("()");
+    }
+}
+
+impl<T: ?Sized> Dump for PhantomData<T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        dumper.value("PhantomData");
+    }
+}
+
+impl<T: Dump> Dump for Option<T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        if let Some(value) = self {
+            dumper.value("Some(");
+            value.dump(dumper);
+            dumper.value(")");
+        } else {
+            dumper.value("None");
+        }
+    }
+}
+
+impl<T: Dump> Dump for [T] {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        dumper.list().entries(self).finish();
+    }
+}
+
+impl<T: Dump, const N: usize> Dump for [T; N] {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        self.as_slice().dump(dumper);
+    }
+}
+
+impl<T: Dump> Dump for Vec<T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        self.as_slice().dump(dumper);
+    }
+}
+
+impl<T: Dump + ?Sized> Dump for &T {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        (**self).dump(dumper);
+    }
+}
+
+macro_rules! dump_pointer_impl {
+    ($($Ty:ident),+ $(,)?) => {
+        $(impl<T: Dump + ?Sized> Dump for $Ty<T> {
+            fn dump(&self, dumper: &mut Dumper<'_>) {
+                (**self).dump(dumper);
+            }
+        })+
+    }
+}
+
+dump_pointer_impl!(Box, Rc, Arc);
+
+impl<T: Dump + ToOwned + ?Sized> Dump for Cow<'_, T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        (**self).dump(dumper);
+    }
+}
+
+macro_rules! dump_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<$type1: Dump, $($types: Dump),*> Dump for ($type1, $($types),*) {
+            fn dump(&self, dumper: &mut Dumper<'_>) {
+                let ($type1, $($types),*) = self;
+                dumper.tuple("").entry($type1)$(.entry($types))*.finish();
+            }
+        }
+
+        dump_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+dump_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
+
+// The lossy conversion is used so that invalid strings can still be rendered.
+macro_rules! dump_string_impl {
+    ($([$($generics:tt)*] $Ty:ty),+ $(,)?) => {
+        $(impl<$($generics)*> Dump for $Ty {
+            fn dump(&self, dumper: &mut Dumper<'_>) {
+                dumper.value(format_args!("{:?}", self.to_string()));
+            }
+        })+
+    }
+}
+
+dump_string_impl!(
+    [] crate::NullString,
+    [] crate::NullWideString,
+    [const N: usize] crate::strings::FixedString<N>,
+    [L] crate::strings::PascalString<L>,
+);
+
+impl<T: Dump> Dump for PosValue<T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        dumper
+            .record("PosValue")
+            .field("val", &self.val)
+            .field("pos", &self.pos)
+            .finish();
+    }
+}
+
+impl<Ptr: IntoSeekFrom + Dump, T: Dump> Dump for FilePtr<Ptr, T> {
+    fn dump(&self, dumper: &mut Dumper<'_>) {
+        dumper
+            .record("FilePtr")
+            .field("ptr", &self.ptr)
+            .field("value", &self.value)
+            .finish();
+    }
+}
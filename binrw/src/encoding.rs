@@ -0,0 +1,852 @@
+//! Text encodings for reading and writing strings.
+//!
+//! An [`Encoding`] converts between Rust strings and the bytes used by a
+//! format. The helper functions in this module combine an encoding with a way
+//! of finding the end of a string, and can be used with
+//! [`parse_with`](crate::docs::attribute#custom-parserswriters) and
+//! [`write_with`](crate::docs::attribute#custom-parserswriters), so the
+//! encoding is chosen where the field is defined:
+//!
+//! * [`read_null_terminated`] and [`write_null_terminated`] for strings which
+//!   end with a null code unit, or [`read_terminated`] and
+//!   [`write_terminated`] for strings which end with some other byte sequence;
+//! * [`read_sized`] and [`write_sized`] for strings which take up a fixed
+//!   number of bytes, padded with nulls;
+//! * [`read_prefixed`] and [`write_prefixed`] for strings which start with
+//!   their length in bytes.
+//!
+//! Strings from Windows formats, such as registry hives and NTFS file names,
+//! may be UTF-16 with unpaired surrogates, which cannot be stored in a
+//! [`String`]. [`read_wide_sized`], [`read_wide_prefixed`], and their writers
+//! store these as the raw `Vec<u16>` code units instead, in the same way as
+//! [`NullWideString`](crate::NullWideString) does for null-terminated strings.
+//!
+//! The [`PascalString`](crate::PascalString) type also accepts an encoding as
+//! an argument. With the `encoding_rs` feature, the encodings of the
+//! [`encoding_rs`](https://docs.rs/encoding_rs) crate, such as Shift-JIS and
+//! other legacy code pages, can be used as well. Other encodings can be used by
+//! implementing [`Encoding`].
+//!
+//! Encodings are strict by default, so reading or writing a string which is
+//! not valid in the encoding is an error. Wrapping an encoding in [`Lossy`]
+//! replaces invalid data instead.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{prelude::*, io::Cursor};
+//! use binrw::encoding::{self, Latin1, Utf16Le};
+//!
+//! #[binrw]
+//! #[brw(little)]
+//! struct Record {
+//!     #[br(parse_with = encoding::read_null_terminated(Latin1))]
+//!     #[bw(write_with = encoding::write_null_terminated(Latin1))]
+//!     name: String,
+//!     #[br(parse_with = encoding::read_prefixed::<u8, _, _>(Utf16Le))]
+//!     #[bw(write_with = encoding::write_prefixed::<u8, _, _, _>(Utf16Le))]
+//!     title: String,
+//! }
+//!
+//! let data = b"Jos\xe9\0\x04h\0i\0";
+//! let record = Record::read(&mut Cursor::new(data)).unwrap();
+//! assert_eq!(record.name, "José");
+//! assert_eq!(record.title, "hi");
+//!
+//! let mut out = Cursor::new(Vec::new());
+//! record.write(&mut out).unwrap();
+//! assert_eq!(out.into_inner(), data);
+//! ```
+
+use crate::{
+    io::{Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+/// A text encoding.
+pub trait Encoding {
+    /// The name of the encoding, used in error messages.
+    fn name(&self) -> &'static str;
+
+    /// The size of one code unit of the encoding, in bytes.
+    ///
+    /// Null terminators are one code unit long, and the byte length of an
+    /// encoded string is always a multiple of this size.
+    fn unit_size(&self) -> usize {
+        1
+    }
+
+    /// Decodes `bytes` into a string, or returns `None` if they are not valid
+    /// in this encoding.
+    fn decode(&self, bytes: &[u8]) -> Option<String>;
+
+    /// Encodes `text` into bytes, or returns `None` if it contains characters
+    /// which cannot be represented in this encoding.
+    fn encode(&self, text: &str) -> Option<Vec<u8>>;
+
+    /// Decodes `bytes` into a string, replacing invalid data with
+    /// U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// The default implementation decodes each code unit separately if the
+    /// whole string is invalid, so encodings with multi-unit sequences should
+    /// override it.
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        if let Some(text) = self.decode(bytes) {
+            return text;
+        }
+
+        bytes
+            .chunks(self.unit_size())
+            .map(|unit| {
+                self.decode(unit)
+                    .unwrap_or_else(|| char::REPLACEMENT_CHARACTER.into())
+            })
+            .collect()
+    }
+
+    /// Encodes `text` into bytes, replacing characters which cannot be
+    /// represented in this encoding with `?`.
+    fn encode_lossy(&self, text: &str) -> Vec<u8> {
+        if let Some(bytes) = self.encode(text) {
+            return bytes;
+        }
+
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut buf = [0; 4];
+        for c in text.chars() {
+            let encoded = self
+                .encode(c.encode_utf8(&mut buf))
+                .or_else(|| self.encode("?"))
+                .unwrap_or_default();
+            bytes.extend_from_slice(&encoded);
+        }
+        bytes
+    }
+}
+
+impl<E: Encoding + ?Sized> Encoding for &E {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn unit_size(&self) -> usize {
+        (**self).unit_size()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        (**self).decode(bytes)
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        (**self).encode(text)
+    }
+
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        (**self).decode_lossy(bytes)
+    }
+
+    fn encode_lossy(&self, text: &str) -> Vec<u8> {
+        (**self).encode_lossy(text)
+    }
+}
+
+/// An encoding which replaces invalid data instead of failing.
+///
+/// When decoding, invalid data is replaced with U+FFFD REPLACEMENT CHARACTER.
+/// When encoding, characters which cannot be represented in the wrapped
+/// encoding are replaced with `?`.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::encoding::{Encoding, Latin1, Lossy, Utf8};
+///
+/// assert_eq!(Lossy(Utf8).decode(b"caf\xff").unwrap(), "caf\u{fffd}");
+/// assert_eq!(Lossy(Latin1).encode("5 €").unwrap(), b"5 ?");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Lossy<E>(pub E);
+
+impl<E: Encoding> Encoding for Lossy<E> {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn unit_size(&self) -> usize {
+        self.0.unit_size()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        Some(self.0.decode_lossy(bytes))
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        Some(self.0.encode_lossy(text))
+    }
+}
+
+/// The UTF-8 encoding.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Utf8;
+
+impl Encoding for Utf8 {
+    fn name(&self) -> &'static str {
+        "UTF-8"
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        core::str::from_utf8(bytes).ok().map(Into::into)
+    }
+
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        Some(text.as_bytes().to_vec())
+    }
+}
+
+/// The little-endian UTF-16 encoding.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Utf16Le;
+
+impl Encoding for Utf16Le {
+    fn name(&self) -> &'static str {
+        "UTF-16LE"
+    }
+
+    fn unit_size(&self) -> usize {
+        2
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        decode_utf16(bytes, u16::from_le_bytes)
+    }
+
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        decode_utf16_lossy(bytes, u16::from_le_bytes)
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        Some(text.encode_utf16().flat_map(u16::to_le_bytes).collect())
+    }
+}
+
+/// The big-endian UTF-16 encoding.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Utf16Be;
+
+impl Encoding for Utf16Be {
+    fn name(&self) -> &'static str {
+        "UTF-16BE"
+    }
+
+    fn unit_size(&self) -> usize {
+        2
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        decode_utf16(bytes, u16::from_be_bytes)
+    }
+
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        decode_utf16_lossy(bytes, u16::from_be_bytes)
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        Some(text.encode_utf16().flat_map(u16::to_be_bytes).collect())
+    }
+}
+
+/// The Latin-1 (ISO 8859-1) encoding.
+///
+/// Every byte is decoded to the Unicode character with the same value, so
+/// decoding never fails. Characters above U+00FF cannot be encoded.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Latin1;
+
+impl Encoding for Latin1 {
+    fn name(&self) -> &'static str {
+        "Latin-1"
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        Some(bytes.iter().copied().map(char::from).collect())
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        text.chars().map(|c| u8::try_from(c).ok()).collect()
+    }
+}
+
+/// The Windows-1252 encoding.
+///
+/// This is the same as [`Latin1`], except that bytes 0x80 to 0x9F are
+/// decoded to printable characters such as `€` and `“`, following the
+/// [WHATWG Encoding Standard](https://encoding.spec.whatwg.org/#windows-1252).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Windows1252;
+
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+impl Encoding for Windows1252 {
+    fn name(&self) -> &'static str {
+        "Windows-1252"
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        Some(
+            bytes
+                .iter()
+                .map(|&byte| match byte {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                    _ => char::from(byte),
+                })
+                .collect(),
+        )
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        text.chars()
+            .map(|c| match u8::try_from(c) {
+                Ok(byte) if !(0x80..=0x9f).contains(&byte) => Some(byte),
+                _ => (0x80..=0x9f)
+                    .zip(WINDOWS_1252_HIGH)
+                    .find_map(|(byte, high)| (high == c).then_some(byte)),
+            })
+            .collect()
+    }
+}
+
+/// The encodings of the [`encoding_rs`] crate, such as
+/// [`SHIFT_JIS`](encoding_rs::SHIFT_JIS) and
+/// [`WINDOWS_1251`](encoding_rs::WINDOWS_1251).
+///
+/// A byte order mark is not treated specially, and decoding is strict unless
+/// the encoding is wrapped in [`Lossy`]. Unlike the [`encoding_rs`] encoders,
+/// which only write UTF-8 for UTF-16 encodings, UTF-16LE and UTF-16BE are
+/// encoded as UTF-16.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::encoding;
+///
+/// #[binrw]
+/// struct Record {
+///     #[br(parse_with = encoding::read_null_terminated(encoding_rs::SHIFT_JIS))]
+///     #[bw(write_with = encoding::write_null_terminated(encoding_rs::SHIFT_JIS))]
+///     name: String,
+/// }
+///
+/// let data = b"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd\0";
+/// let record = Record::read_le(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(record.name, "こんにちは");
+///
+/// let mut out = Cursor::new(Vec::new());
+/// record.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[cfg(feature = "encoding_rs")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "encoding_rs")))]
+impl Encoding for &'static encoding_rs::Encoding {
+    fn name(&self) -> &'static str {
+        (*self).name()
+    }
+
+    fn unit_size(&self) -> usize {
+        if *self == encoding_rs::UTF_16LE || *self == encoding_rs::UTF_16BE {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<String> {
+        self.decode_without_bom_handling_and_without_replacement(bytes)
+            .map(alloc::borrow::Cow::into_owned)
+    }
+
+    fn decode_lossy(&self, bytes: &[u8]) -> String {
+        self.decode_without_bom_handling(bytes).0.into_owned()
+    }
+
+    fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        if *self == encoding_rs::UTF_16LE {
+            Utf16Le.encode(text)
+        } else if *self == encoding_rs::UTF_16BE {
+            Utf16Be.encode(text)
+        } else if self.output_encoding() == *self {
+            let (bytes, _, had_errors) = (*self).encode(text);
+            (!had_errors).then(|| bytes.into_owned())
+        } else {
+            None
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+
+    char::decode_utf16(
+        bytes
+            .chunks_exact(2)
+            .map(|unit| from_bytes([unit[0], unit[1]])),
+    )
+    .collect::<Result<_, _>>()
+    .ok()
+}
+
+fn decode_utf16_lossy(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2);
+    let trailing = !units.remainder().is_empty();
+    let mut text = char::decode_utf16(units.map(|unit| from_bytes([unit[0], unit[1]])))
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect::<String>();
+    if trailing {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+    text
+}
+
+/// Creates a parser that reads a string in the given encoding until a null
+/// code unit.
+///
+/// The null terminator is consumed and not included in the value.
+///
+/// # Errors
+///
+/// If the string is not valid in the encoding, an [`Error::AssertFail`] is
+/// returned with the position of the start of the string.
+pub fn read_null_terminated<R, E>(encoding: E) -> impl Fn(&mut R, Endian, ()) -> BinResult<String>
+where
+    R: Read + Seek,
+    E: Encoding,
+{
+    let terminator = vec![0; encoding.unit_size()];
+    read_terminated(encoding, terminator)
+}
+
+/// Creates a writer that writes a string in the given encoding followed by a
+/// null code unit.
+///
+/// # Errors
+///
+/// If the string cannot be represented in the encoding, an
+/// [`Error::AssertFail`] is returned.
+pub fn write_null_terminated<W, E, S>(
+    encoding: E,
+) -> impl Fn(&S, &mut W, Endian, ()) -> BinResult<()>
+where
+    W: Write + Seek,
+    E: Encoding,
+    S: AsRef<str> + ?Sized,
+{
+    let terminator = vec![0; encoding.unit_size()];
+    write_terminated(encoding, terminator)
+}
+
+/// Creates a parser that reads a string in the given encoding until the
+/// byte sequence `terminator`.
+///
+/// The string is read one code unit at a time, and the terminator is only
+/// matched at the end of a whole code unit. The terminator is consumed and not
+/// included in the value.
+///
+/// # Errors
+///
+/// If the string is not valid in the encoding, an [`Error::AssertFail`] is
+/// returned with the position of the start of the string.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::encoding::{self, Latin1};
+///
+/// #[binrw]
+/// struct Line {
+///     #[br(parse_with = encoding::read_terminated(Latin1, b"\r\n"))]
+///     #[bw(write_with = encoding::write_terminated(Latin1, b"\r\n"))]
+///     text: String,
+/// }
+///
+/// let line = Line::read_le(&mut Cursor::new(b"caf\xe9\r\n")).unwrap();
+/// assert_eq!(line.text, "café");
+/// ```
+pub fn read_terminated<R, E, K>(
+    encoding: E,
+    terminator: K,
+) -> impl Fn(&mut R, Endian, ()) -> BinResult<String>
+where
+    R: Read + Seek,
+    E: Encoding,
+    K: AsRef<[u8]>,
+{
+    move |reader, _, ()| {
+        let pos = reader.stream_position()?;
+        let terminator = terminator.as_ref();
+        let mut unit = vec![0; encoding.unit_size()];
+        let mut bytes = Vec::new();
+
+        while !bytes.ends_with(terminator) {
+            reader.read_exact(&mut unit)?;
+            bytes.extend_from_slice(&unit);
+        }
+        bytes.truncate(bytes.len() - terminator.len());
+
+        crate::memory::reserve(bytes.len())?;
+        decode(&encoding, &bytes, pos)
+    }
+}
+
+/// Creates a writer that writes a string in the given encoding followed by the
+/// byte sequence `terminator`.
+///
+/// # Errors
+///
+/// If the string cannot be represented in the encoding, an
+/// [`Error::AssertFail`] is returned.
+pub fn write_terminated<W, E, K, S>(
+    encoding: E,
+    terminator: K,
+) -> impl Fn(&S, &mut W, Endian, ()) -> BinResult<()>
+where
+    W: Write + Seek,
+    E: Encoding,
+    K: AsRef<[u8]>,
+    S: AsRef<str> + ?Sized,
+{
+    move |text, writer, _, ()| {
+        let mut bytes = encode(&encoding, text.as_ref(), writer)?;
+        bytes.extend_from_slice(terminator.as_ref());
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Creates a parser that reads a string in the given encoding which takes up
+/// the number of bytes given in the argument.
+///
+/// Null code units at the end of the string are padding and are not included
+/// in the value.
+///
+/// # Errors
+///
+/// If the string is not valid in the encoding, an [`Error::AssertFail`] is
+/// returned with the position of the start of the string.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::encoding::{self, Latin1};
+///
+/// #[binrw]
+/// struct Entry {
+///     #[br(parse_with = encoding::read_sized(Latin1), args_raw = 8)]
+///     #[bw(write_with = encoding::write_sized(Latin1), args_raw = 8)]
+///     name: String,
+/// }
+///
+/// let entry = Entry::read_le(&mut Cursor::new(b"caf\xe9\0\0\0\0")).unwrap();
+/// assert_eq!(entry.name, "café");
+/// ```
+pub fn read_sized<R, E>(encoding: E) -> impl Fn(&mut R, Endian, usize) -> BinResult<String>
+where
+    R: Read + Seek,
+    E: Encoding,
+{
+    move |reader, _, len| {
+        let pos = reader.stream_position()?;
+        let bytes = read_bytes(reader, len)?;
+        let unit_size = encoding.unit_size();
+        let mut end = bytes.len() - bytes.len() % unit_size;
+        while end >= unit_size && bytes[end - unit_size..end].iter().all(|&byte| byte == 0) {
+            end -= unit_size;
+        }
+
+        decode(&encoding, &bytes[..end], pos)
+    }
+}
+
+/// Creates a writer that writes a string in the given encoding, padded with
+/// nulls to the number of bytes given in the argument.
+///
+/// # Errors
+///
+/// If the string cannot be represented in the encoding, or it is longer than
+/// the given number of bytes, an [`Error::AssertFail`] is returned.
+pub fn write_sized<W, E, S>(encoding: E) -> impl Fn(&S, &mut W, Endian, usize) -> BinResult<()>
+where
+    W: Write + Seek,
+    E: Encoding,
+    S: AsRef<str> + ?Sized,
+{
+    move |text, writer, _, len| {
+        let mut bytes = encode(&encoding, text.as_ref(), writer)?;
+        if bytes.len() > len {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!(
+                    "encoded string is {} bytes long, but only {len} bytes are available",
+                    bytes.len()
+                ),
+            });
+        }
+        bytes.resize(len, 0);
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Creates a parser that reads a string in the given encoding which starts
+/// with its length in bytes, read as an `L`.
+///
+/// # Errors
+///
+/// If the string is not valid in the encoding, an [`Error::AssertFail`] is
+/// returned with the position of the start of the string after the length.
+pub fn read_prefixed<L, R, E>(encoding: E) -> impl Fn(&mut R, Endian, ()) -> BinResult<String>
+where
+    L: for<'a> BinRead<Args<'a> = ()> + TryInto<usize>,
+    R: Read + Seek,
+    E: Encoding,
+{
+    move |reader, endian, ()| {
+        let len_pos = reader.stream_position()?;
+        let len = L::read_options(reader, endian, ())?
+            .try_into()
+            .map_err(|_| Error::AssertFail {
+                pos: len_pos,
+                message: "string length does not fit in a usize".into(),
+            })?;
+        let pos = reader.stream_position()?;
+        let bytes = read_bytes(reader, len)?;
+        decode(&encoding, &bytes, pos)
+    }
+}
+
+/// Creates a writer that writes a string in the given encoding, preceded by
+/// its length in bytes written as an `L`.
+///
+/// # Errors
+///
+/// If the string cannot be represented in the encoding, or its length does not
+/// fit in an `L`, an [`Error::AssertFail`] is returned.
+pub fn write_prefixed<L, W, E, S>(encoding: E) -> impl Fn(&S, &mut W, Endian, ()) -> BinResult<()>
+where
+    L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+    W: Write + Seek,
+    E: Encoding,
+    S: AsRef<str> + ?Sized,
+{
+    move |text, writer, endian, ()| {
+        let bytes = encode(&encoding, text.as_ref(), writer)?;
+        let Ok(len) = L::try_from(bytes.len()) else {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!(
+                    "encoded string length {} does not fit in the length prefix",
+                    bytes.len()
+                ),
+            });
+        };
+        len.write_options(writer, endian, ())?;
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Creates a parser that reads UTF-16 code units which take up the number of
+/// bytes given in the argument, without checking that they are valid UTF-16.
+///
+/// The code units use the byte order of the surrounding data. Null code units
+/// at the end of the string are padding and are not included in the value.
+///
+/// # Errors
+///
+/// If the number of bytes is odd, an [`Error::AssertFail`] is returned with
+/// the position of the start of the string.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::encoding;
+///
+/// #[binrw]
+/// #[brw(little)]
+/// struct Entry {
+///     #[br(parse_with = encoding::read_wide_sized, args_raw = 8)]
+///     #[bw(write_with = encoding::write_wide_sized, args_raw = 8)]
+///     name: Vec<u16>,
+/// }
+///
+/// // An unpaired high surrogate followed by "a"
+/// let entry = Entry::read(&mut Cursor::new(b"\0\xd8a\0\0\0\0\0")).unwrap();
+/// assert_eq!(entry.name, [0xd800, 0x61]);
+/// assert!(String::from_utf16(&entry.name).is_err());
+/// ```
+pub fn read_wide_sized<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    len: usize,
+) -> BinResult<Vec<u16>> {
+    let mut units = read_wide(reader, endian, len)?;
+    while units.last() == Some(&0) {
+        units.pop();
+    }
+    Ok(units)
+}
+
+/// Writes UTF-16 code units, padded with nulls to the number of bytes given
+/// in the argument, without checking that they are valid UTF-16.
+///
+/// # Errors
+///
+/// If the string is longer than the given number of bytes, an
+/// [`Error::AssertFail`] is returned.
+pub fn write_wide_sized<W, S>(
+    units: &S,
+    writer: &mut W,
+    endian: Endian,
+    len: usize,
+) -> BinResult<()>
+where
+    W: Write + Seek,
+    S: AsRef<[u16]> + ?Sized,
+{
+    let units = units.as_ref();
+    let size = units.len().saturating_mul(2);
+    if size > len {
+        return Err(Error::AssertFail {
+            pos: writer.stream_position()?,
+            message: alloc::format!(
+                "string is {size} bytes long, but only {len} bytes are available"
+            ),
+        });
+    }
+    units.write_options(writer, endian, ())?;
+    crate::__private::write_zeroes(writer, (len - size) as u64)
+}
+
+/// Reads UTF-16 code units which start with their length in bytes, read as an
+/// `L`, without checking that they are valid UTF-16.
+///
+/// # Errors
+///
+/// If the length is odd, an [`Error::AssertFail`] is returned with the
+/// position of the start of the string after the length.
+pub fn read_wide_prefixed<L, R>(reader: &mut R, endian: Endian, _: ()) -> BinResult<Vec<u16>>
+where
+    L: for<'a> BinRead<Args<'a> = ()> + TryInto<usize>,
+    R: Read + Seek,
+{
+    let len_pos = reader.stream_position()?;
+    let len = L::read_options(reader, endian, ())?
+        .try_into()
+        .map_err(|_| Error::AssertFail {
+            pos: len_pos,
+            message: "string length does not fit in a usize".into(),
+        })?;
+    read_wide(reader, endian, len)
+}
+
+/// Writes UTF-16 code units preceded by their length in bytes, written as an
+/// `L`, without checking that they are valid UTF-16.
+///
+/// # Errors
+///
+/// If the length does not fit in an `L`, an [`Error::AssertFail`] is
+/// returned.
+pub fn write_wide_prefixed<L, W, S>(
+    units: &S,
+    writer: &mut W,
+    endian: Endian,
+    _: (),
+) -> BinResult<()>
+where
+    L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+    W: Write + Seek,
+    S: AsRef<[u16]> + ?Sized,
+{
+    let units = units.as_ref();
+    let size = units.len().saturating_mul(2);
+    let Ok(len) = L::try_from(size) else {
+        return Err(Error::AssertFail {
+            pos: writer.stream_position()?,
+            message: alloc::format!("string length {size} does not fit in the length prefix"),
+        });
+    };
+    len.write_options(writer, endian, ())?;
+    units.write_options(writer, endian, ())?;
+    Ok(())
+}
+
+fn read_wide<R: Read + Seek>(reader: &mut R, endian: Endian, len: usize) -> BinResult<Vec<u16>> {
+    let pos = reader.stream_position()?;
+    if len % 2 != 0 {
+        return Err(Error::AssertFail {
+            pos,
+            message: alloc::format!("UTF-16 string length {len} is not a multiple of 2"),
+        });
+    }
+    let bytes = read_bytes(reader, len)?;
+    let from_bytes = match endian {
+        Endian::Big => u16::from_be_bytes,
+        Endian::Little => u16::from_le_bytes,
+    };
+    Ok(bytes
+        .chunks_exact(2)
+        .map(|unit| from_bytes([unit[0], unit[1]]))
+        .collect())
+}
+
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> BinResult<Vec<u8>> {
+    crate::memory::reserve(len)?;
+    // The length usually comes from the data, so the buffer is left to grow as
+    // bytes are actually read instead of being allocated up front
+    let mut bytes = Vec::new();
+    let read = reader.take(len as u64).read_to_end(&mut bytes)?;
+    if read == len {
+        Ok(bytes)
+    } else {
+        Err(Error::Io(crate::io::Error::new(
+            crate::io::ErrorKind::UnexpectedEof,
+            "unexpected end of file while reading a string",
+        )))
+    }
+}
+
+fn decode<E: Encoding>(encoding: &E, bytes: &[u8], pos: u64) -> BinResult<String> {
+    let text = encoding.decode(bytes).ok_or_else(|| Error::AssertFail {
+        pos,
+        message: alloc::format!("invalid {} string", encoding.name()),
+    })?;
+    Ok(text)
+}
+
+fn encode<E: Encoding, W: Seek>(encoding: &E, text: &str, writer: &mut W) -> BinResult<Vec<u8>> {
+    match encoding.encode(text) {
+        Some(bytes) => Ok(bytes),
+        None => Err(Error::AssertFail {
+            pos: writer.stream_position()?,
+            message: alloc::format!(
+                "string contains characters which cannot be encoded as {}",
+                encoding.name()
+            ),
+        }),
+    }
+}
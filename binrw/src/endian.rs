@@ -6,7 +6,7 @@ use alloc::boxed::Box;
 pub use Endian::{Big as BE, Little as LE};
 
 /// Defines the order of bytes in a multi-byte type.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Endian {
     /// The most significant byte is stored first.
     Big,
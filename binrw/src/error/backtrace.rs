@@ -119,7 +119,11 @@ impl fmt::Display for Backtrace {
 }
 
 /// A captured backtrace frame.
+///
+/// New kinds of frame may be added in the future, so matches on this type must
+/// include a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum BacktraceFrame {
     /// A standard frame.
     Full {
@@ -137,6 +141,36 @@ pub enum BacktraceFrame {
         line: u32,
     },
 
+    /// A frame for a field of a type which derives
+    /// [`BinRead`](crate::BinRead).
+    ///
+    /// Together, the field frames of a backtrace give the path from the
+    /// outermost object to the field where the error occurred.
+    Field {
+        /// The name of the struct or enum which contains the field.
+        type_name: &'static str,
+
+        /// The name of the enum variant which contains the field, if the field
+        /// is part of an enum.
+        variant_name: Option<&'static str>,
+
+        /// The name of the field, or its index if it is a tuple field.
+        field_name: &'static str,
+
+        /// The position of the start of the struct or enum which contains the
+        /// field.
+        pos: u64,
+
+        /// The code at the location where the frame was generated.
+        code: Option<&'static str>,
+
+        /// The origin filename.
+        file: &'static str,
+
+        /// The origin line number.
+        line: u32,
+    },
+
     /// A message-only frame.
     Message(Cow<'static, str>),
 
@@ -154,6 +188,9 @@ impl BacktraceFrame {
         match self {
             BacktraceFrame::Full {
                 code, file, line, ..
+            }
+            | BacktraceFrame::Field {
+                code, file, line, ..
             } => {
                 writeln!(
                     f,
@@ -174,9 +211,23 @@ impl BacktraceFrame {
         self.display_with_message(f, &self.message(), index)
     }
 
-    fn message(&self) -> Cow<'_, str> {
+    pub(super) fn message(&self) -> Cow<'_, str> {
         match self {
             BacktraceFrame::Full { message: msg, .. } | BacktraceFrame::Message(msg) => msg.clone(),
+            BacktraceFrame::Field {
+                type_name,
+                variant_name,
+                field_name,
+                pos,
+                ..
+            } => {
+                let separator = if variant_name.is_some() { "::" } else { "" };
+                format!(
+                    "While parsing field '{field_name}' in {type_name}{separator}{} at {pos:#x}",
+                    variant_name.unwrap_or_default()
+                )
+                .into()
+            }
             BacktraceFrame::Custom(context) => context.to_string().into(),
         }
     }
@@ -1,13 +1,18 @@
 //! Functions and type definitions for handling errors.
 
 mod backtrace;
+mod snippet;
 
 use crate::{io, BinResult};
 use alloc::borrow::Cow;
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 pub use backtrace::*;
-use core::{any::Any, fmt};
+use core::{
+    any::Any,
+    fmt::{self, Write},
+};
+pub use snippet::Snippet;
 
 /// The `ContextExt` trait allows extra information to be added to errors.
 ///
@@ -241,6 +246,16 @@ pub enum Error {
         variant_errors: Vec<(&'static str, Error)>,
     },
 
+    /// More data was read than allowed by a
+    /// [read limit](crate::io::limit_reads).
+    LimitExceeded {
+        /// The byte position of the read which exceeded the limit.
+        pos: u64,
+
+        /// The maximum number of bytes which could be read.
+        limit: u64,
+    },
+
     /// An error with additional frames of context used to construct a backtrace
     Backtrace(Backtrace),
 }
@@ -256,6 +271,34 @@ impl Error {
         }
     }
 
+    /// Returns the byte position in the stream where this error occurred.
+    ///
+    /// For a [`Backtrace`](Self::Backtrace), this is the position of the
+    /// source error. If the source error is an [`Io`](Self::Io) error, which
+    /// has no position of its own, the position of the start of the innermost
+    /// object in the [field path](BacktraceFrame::Field) is used instead. Since
+    /// every field of a derived [`BinRead`](crate::BinRead) implementation adds
+    /// a frame to the backtrace, this returns `None` only for an `Io` error
+    /// which did not come from a derived implementation.
+    #[must_use]
+    pub fn position(&self) -> Option<u64> {
+        match self {
+            Self::BadMagic { pos, .. }
+            | Self::AssertFail { pos, .. }
+            | Self::Custom { pos, .. }
+            | Self::NoVariantMatch { pos }
+            | Self::EnumErrors { pos, .. }
+            | Self::LimitExceeded { pos, .. } => Some(*pos),
+            Self::Io(_) => None,
+            Self::Backtrace(backtrace) => backtrace.error.position().or_else(|| {
+                backtrace.frames.iter().find_map(|frame| match frame {
+                    BacktraceFrame::Field { pos, .. } => Some(*pos),
+                    _ => None,
+                })
+            }),
+        }
+    }
+
     /// Check if the [root cause][`Self::root_cause`] of this error is an [`Error::Io`] and an
     /// [`io::ErrorKind::UnexpectedEof`].
     #[must_use]
@@ -302,15 +345,45 @@ impl fmt::Display for Error {
             } => {
                 write!(f, "no variants matched at 0x{pos:x}:")?;
                 for (name, err) in variant_errors {
-                    write!(f, "\n  {name}: {err}")?;
+                    write!(f, "\n  {name}: ")?;
+                    // Errors from nested enums span several lines, so they
+                    // are indented under the name of their variant, and
+                    // backtraces are reduced to one line for each frame
+                    let mut f = Indented(f);
+                    if let Self::Backtrace(backtrace) = err {
+                        write!(f, "{}", backtrace.error)?;
+                        for frame in &backtrace.frames {
+                            write!(f, "\n  {}", frame.message())?;
+                        }
+                    } else {
+                        write!(f, "{err}")?;
+                    }
                 }
                 Ok(())
             }
+            Self::LimitExceeded { pos, limit } => {
+                write!(f, "read limit of {limit} bytes exceeded at 0x{pos:x}")
+            }
             Self::Backtrace(backtrace) => fmt::Display::fmt(backtrace, f),
         }
     }
 }
 
+/// Indents every line written to a formatter after the first.
+struct Indented<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl fmt::Write for Indented<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i != 0 {
+                self.0.write_str("\n  ")?;
+            }
+            self.0.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <Error as fmt::Display>::fmt(self, f)
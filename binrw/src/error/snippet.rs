@@ -0,0 +1,90 @@
+use super::Error;
+use core::fmt;
+
+/// The number of bytes shown on each line of a snippet.
+const BYTES_PER_LINE: usize = 16;
+
+/// The number of lines shown before and after the line containing the error.
+const CONTEXT_LINES: usize = 1;
+
+/// An error rendered together with a hex dump of the bytes around the
+/// position where it occurred.
+///
+/// This is returned by [`Error::snippet`].
+pub struct Snippet<'a> {
+    error: &'a Error,
+    data: &'a [u8],
+}
+
+impl Error {
+    /// Returns an object which displays this error followed by a hex dump of
+    /// the bytes in `data` around the [position](Self::position) where the
+    /// error occurred, with the byte at that position marked.
+    ///
+    /// `data` should be the entire input which was being read when the error
+    /// occurred, so that stream positions are also positions in `data`. If the
+    /// error has no position, or the position is past the end of `data`, only
+    /// the error is displayed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::Error;
+    /// let data = b"\0\x01\x02\x03";
+    /// let error = Error::AssertFail { pos: 2, message: "bad value".into() };
+    /// assert_eq!(
+    ///     error.snippet(data).to_string(),
+    ///     "bad value at 0x2\n\n00000000  00 01 02 03\n                ^^",
+    /// );
+    /// ```
+    #[must_use]
+    pub fn snippet<'a>(&'a self, data: &'a [u8]) -> Snippet<'a> {
+        Snippet { error: self, data }
+    }
+}
+
+impl fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.error, f)?;
+
+        let Some(pos) = self
+            .error
+            .position()
+            .and_then(|pos| usize::try_from(pos).ok())
+            .filter(|pos| *pos <= self.data.len())
+        else {
+            return Ok(());
+        };
+
+        let error_line = pos / BYTES_PER_LINE;
+        let first_line = error_line.saturating_sub(CONTEXT_LINES);
+        let last_line = (error_line + CONTEXT_LINES).min(self.data.len() / BYTES_PER_LINE);
+
+        f.write_str("\n")?;
+        for line in first_line..=last_line {
+            let start = line * BYTES_PER_LINE;
+            let bytes = &self.data[start..self.data.len().min(start + BYTES_PER_LINE)];
+            if bytes.is_empty() && line != error_line {
+                continue;
+            }
+
+            write!(f, "\n{start:08x} ")?;
+            for byte in bytes {
+                write!(f, " {byte:02x}")?;
+            }
+
+            if line == error_line {
+                let column = 10 + 3 * (pos - start);
+                write!(f, "\n{:column$}^^", "")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
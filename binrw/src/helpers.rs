@@ -1,11 +1,11 @@
 //! Helper functions for reading and writing data.
 
 use crate::{
-    io::{self, Read, Seek},
-    BinRead, BinResult, Endian, Error,
+    io::{self, Read, Seek, TakeSeekExt, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
 };
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::iter::from_fn;
 
 /// Creates a parser that reads items into a collection until a condition is
@@ -45,6 +45,8 @@ where
 /// until a condition is met. The terminal item is added to the collection.
 ///
 /// The given `read` function should return one item each time it is called.
+/// The arguments passed to the parser are cloned and forwarded to `read` for
+/// each item.
 ///
 /// This helper can be used to read into any collection type that implements
 /// [`FromIterator`].
@@ -89,7 +91,7 @@ where
                         if cond(&value) {
                             last = true;
                         }
-                        Some(Ok(value))
+                        Some(counted(value))
                     }
                     err => Some(err),
                 }
@@ -176,7 +178,7 @@ where
                 if cond(&value) {
                     None
                 } else {
-                    Some(Ok(value))
+                    Some(counted(value))
                 }
             }
             err => Some(err),
@@ -186,12 +188,180 @@ where
     }
 }
 
+/// Creates a parser that reads items into a collection until the next bytes
+/// in the input stream are `terminator`. The terminator is consumed and not
+/// included in the collection.
+///
+/// Unlike [`until_exclusive`], the terminator does not need to be a valid
+/// item, so this can be used for lists which end with a sentinel of a
+/// different size than the items, or with a multi-byte sequence.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`]. Use [`write_terminated`] to write the collection back.
+///
+/// # Errors
+///
+/// If reading fails, including reaching the end of the input before the
+/// terminator is found, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{until_terminator, write_terminated}, io::Cursor};
+/// #[binrw]
+/// # #[derive(Debug, PartialEq)]
+/// #[brw(little)]
+/// struct Palette {
+///     #[br(parse_with = until_terminator(b"\xff\xff"))]
+///     #[bw(write_with = write_terminated(b"\xff\xff"))]
+///     colors: Vec<[u8; 3]>,
+/// }
+///
+/// let data = b"\x01\x02\x03\x04\x05\x06\xff\xff";
+/// let palette = Palette::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(palette.colors, [[1, 2, 3], [4, 5, 6]]);
+///
+/// let mut out = Cursor::new(vec![]);
+/// palette.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+pub fn until_terminator<Reader, T, K, Arg, Ret>(
+    terminator: K,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    K: AsRef<[u8]>,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    until_terminator_with(terminator, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until the next bytes in the input stream are `terminator`. The terminator
+/// is consumed and not included in the collection.
+///
+/// The given `read` function should return one item each time it is called.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If reading fails, including reaching the end of the input before the
+/// terminator is found, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// Reading a Windows multi-string value, which is a list of null-terminated
+/// UTF-16 strings followed by an empty string:
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_terminator_with, io::Cursor};
+/// use binrw::encoding::{self, Utf16Le};
+///
+/// #[derive(BinRead)]
+/// struct MultiString {
+///     #[br(parse_with = until_terminator_with(b"\0\0", encoding::read_null_terminated(Utf16Le)))]
+///     values: Vec<String>,
+/// }
+///
+/// # let mut x = Cursor::new(b"a\0\0\0b\0c\0\0\0\0\0");
+/// # let x = MultiString::read_le(&mut x).unwrap();
+/// # assert_eq!(x.values, ["a", "bc"]);
+/// ```
+pub fn until_terminator_with<Reader, T, K, Arg, ReadFn, Ret>(
+    terminator: K,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    Reader: Read + Seek,
+    K: AsRef<[u8]>,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let terminator = terminator.as_ref();
+        let mut bytes = vec![0; terminator.len()];
+        from_fn(|| match at_terminator(reader, terminator, &mut bytes) {
+            Ok(true) => None,
+            Ok(false) => Some(read(reader, endian, args.clone()).and_then(counted)),
+            Err(err) => Some(Err(err)),
+        })
+        .fuse()
+        .collect()
+    }
+}
+
+/// Creates a writer that writes each item of a collection followed by
+/// `terminator`.
+///
+/// See [`until_terminator`] for an example.
+pub fn write_terminated<W, T, K, Arg, S>(
+    terminator: K,
+) -> impl Fn(&S, &mut W, Endian, Arg) -> BinResult<()>
+where
+    W: Write + Seek,
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+    K: AsRef<[u8]>,
+    Arg: Clone,
+    S: AsRef<[T]> + ?Sized,
+{
+    write_terminated_with(terminator, T::write_options)
+}
+
+/// Creates a writer that uses a given function to write each item of a
+/// collection, followed by `terminator`.
+///
+/// # Examples
+///
+/// Writing a Windows multi-string value:
+///
+/// ```
+/// # use binrw::{BinWrite, helpers::write_terminated_with, io::Cursor};
+/// use binrw::encoding::{self, Utf16Le};
+///
+/// #[derive(BinWrite)]
+/// struct MultiString {
+///     #[bw(write_with = write_terminated_with(b"\0\0", encoding::write_null_terminated(Utf16Le)))]
+///     values: Vec<String>,
+/// }
+///
+/// # let mut x = Cursor::new(vec![]);
+/// # MultiString { values: vec!["a".into(), "bc".into()] }.write_le(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"a\0\0\0b\0c\0\0\0\0\0");
+/// ```
+pub fn write_terminated_with<W, T, K, Arg, WriteFn, S>(
+    terminator: K,
+    write: WriteFn,
+) -> impl Fn(&S, &mut W, Endian, Arg) -> BinResult<()>
+where
+    W: Write + Seek,
+    K: AsRef<[u8]>,
+    Arg: Clone,
+    WriteFn: Fn(&T, &mut W, Endian, Arg) -> BinResult<()>,
+    S: AsRef<[T]> + ?Sized,
+{
+    move |items, writer, endian, args| {
+        for item in items.as_ref() {
+            write(item, writer, endian, args.clone())?;
+        }
+        writer.write_all(terminator.as_ref())?;
+        Ok(())
+    }
+}
+
 /// Creates a parser that reads items into a collection until the end of the
 /// input stream.
 ///
 /// This helper can be used to read into any collection type that implements
 /// [`FromIterator`].
 ///
+/// When the items are bytes, the remaining data is read in bulk as with
+/// [`read_to_end`].
+///
 /// # Errors
 ///
 /// If reading fails for a reason other than reaching the end of the input, an
@@ -217,12 +387,20 @@ pub fn until_eof<Reader, T, Arg, Ret>(
     args: Arg,
 ) -> BinResult<Ret>
 where
-    T: for<'a> BinRead<Args<'a> = Arg>,
+    T: for<'a> BinRead<Args<'a> = Arg> + 'static,
     Reader: Read + Seek,
     Arg: Clone,
     Ret: FromIterator<T>,
 {
-    until_eof_with(T::read_options)(reader, endian, args)
+    // Checking the item type instead of the collection type keeps borrowed
+    // collections usable
+    let mut items = Vec::<T>::new();
+    if let Some(bytes) = <dyn core::any::Any>::downcast_mut::<Vec<u8>>(&mut items) {
+        *bytes = read_to_end(reader, endian, ())?;
+        Ok(items.into_iter().collect())
+    } else {
+        until_eof_with(T::read_options)(reader, endian, args)
+    }
 }
 
 /// Creates a parser that uses a given function to read items into a collection
@@ -267,7 +445,7 @@ where
 {
     move |reader, endian, args| {
         from_fn(|| match read(reader, endian, args.clone()) {
-            ok @ Ok(_) => Some(ok),
+            Ok(value) => Some(counted(value)),
             Err(err) if err.is_eof() => None,
             err => Some(err),
         })
@@ -276,6 +454,44 @@ where
     }
 }
 
+/// Reads all remaining bytes from the input stream.
+///
+/// This is the fastest way to capture a trailing blob of unparsed data, since
+/// the data is read in bulk instead of one byte at a time.
+///
+/// # Errors
+///
+/// If reading fails, or the data is larger than the remaining
+/// [memory limit](crate::memory), an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::read_to_end, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Chunk {
+///     kind: u8,
+///     #[br(parse_with = read_to_end)]
+///     trailer: Vec<u8>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x01\x02\x03\x04");
+/// # let x: Chunk = x.read_be().unwrap();
+/// # assert_eq!(x.trailer, &[2, 3, 4]);
+/// ```
+pub fn read_to_end<R: Read + Seek>(reader: &mut R, _: Endian, _: ()) -> BinResult<Vec<u8>> {
+    // Reading at most one byte past the remaining memory limit is enough to
+    // tell whether the limit would be exceeded without reading the rest of an
+    // arbitrarily large stream.
+    let limit = u64::try_from(crate::memory::remaining())
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    let mut data = Vec::new();
+    reader.take(limit).read_to_end(&mut data)?;
+    crate::memory::reserve(data.len())?;
+    Ok(data)
+}
+
 /// Creates a parser that builds a collection using items from the given
 /// iterable object as arguments for the parser.
 ///
@@ -372,7 +588,7 @@ where
 {
     move |reader, options, ()| {
         it.into_iter()
-            .map(|arg| read(reader, options, arg))
+            .map(|arg| read(reader, options, arg).and_then(counted))
             .collect()
     }
 }
@@ -407,7 +623,7 @@ where
     Arg: Clone,
     Ret: FromIterator<T> + 'static,
 {
-    count_with(n, T::read_options)
+    count_read_options(n, T::read_options)
 }
 
 /// Creates a parser that uses a given function to read N items into a
@@ -443,6 +659,28 @@ pub fn count_with<R, T, Arg, ReadFn, Ret>(
     n: usize,
     read: ReadFn,
 ) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    R: Read + Seek,
+    Arg: Clone,
+    ReadFn: Fn(&mut R, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        crate::memory::reserve(n.saturating_mul(core::mem::size_of::<T>()))?;
+        core::iter::repeat_with(|| read(reader, endian, args.clone()))
+            .take(n)
+            .collect()
+    }
+}
+
+// This is the same as `count_with`, except that integer and byte collections
+// are read in bulk instead of by calling `read` once per element. It must only
+// be used when `read` is the `BinRead` implementation of `T`, since a custom
+// parser would otherwise be silently ignored.
+pub(crate) fn count_read_options<R, T, Arg, ReadFn, Ret>(
+    n: usize,
+    read: ReadFn,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
 where
     R: Read + Seek,
     Arg: Clone,
@@ -450,6 +688,7 @@ where
     Ret: FromIterator<T> + 'static,
 {
     move |reader, endian, args| {
+        crate::memory::reserve(n.saturating_mul(core::mem::size_of::<T>()))?;
         let mut container = core::iter::empty::<T>().collect::<Ret>();
 
         vec_fast_int!(try (i8 i16 u16 i32 u32 i64 u64 i128 u128) using (container, reader, endian, n) else {
@@ -557,6 +796,555 @@ pub fn write_u24(value: &u32) -> binrw::BinResult<()> {
     writer.write_all(&buf[range]).map_err(Into::into)
 }
 
+/// Creates a parser that reads a presence flag followed by an optional value.
+///
+/// The flag is read first using the same byte order as the value. If it is
+/// equal to `absent`, no more data is read and the result is [`None`].
+/// Otherwise, the value is read and the result is [`Some`].
+///
+/// Use [`write_flagged`] to write values in the same format. To decide whether
+/// a value is present using a condition instead, such as a bit in a flags field
+/// which was read earlier, use the [`if`](crate::docs::attribute#conditional-values)
+/// or [`present_if`](crate::docs::attribute#flag-gated-values) directive.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{read_flagged, write_flagged}, io::Cursor};
+/// #[derive(BinRead, BinWrite)]
+/// # #[derive(Debug, PartialEq)]
+/// #[brw(big)]
+/// struct Test {
+///     #[br(parse_with = read_flagged(0u8))]
+///     #[bw(write_with = write_flagged(0u8, 1u8))]
+///     value: Option<u16>,
+/// }
+///
+/// # assert_eq!(
+/// #     Test::read(&mut Cursor::new(b"\x01\0\x02")).unwrap(),
+/// #     Test { value: Some(2) }
+/// # );
+/// # assert_eq!(Test::read(&mut Cursor::new(b"\0")).unwrap(), Test { value: None });
+/// # let mut data = Cursor::new(vec![]);
+/// # Test { value: Some(2) }.write(&mut data).unwrap();
+/// # assert_eq!(data.into_inner(), b"\x01\0\x02");
+/// # let mut data = Cursor::new(vec![]);
+/// # Test { value: None }.write(&mut data).unwrap();
+/// # assert_eq!(data.into_inner(), b"\0");
+/// ```
+pub fn read_flagged<R, F, T, Arg>(absent: F) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Option<T>>
+where
+    R: Read + Seek,
+    F: for<'a> BinRead<Args<'a> = ()> + PartialEq,
+    T: for<'a> BinRead<Args<'a> = Arg>,
+{
+    move |reader, endian, args| {
+        if F::read_options(reader, endian, ())? == absent {
+            Ok(None)
+        } else {
+            T::read_options(reader, endian, args).map(Some)
+        }
+    }
+}
+
+/// Creates a writer that writes a presence flag followed by an optional value.
+///
+/// If the value is [`Some`], `present` is written followed by the value.
+/// Otherwise, only `absent` is written. The flag is written using the same byte
+/// order as the value.
+///
+/// See [`read_flagged`] for an example.
+pub fn write_flagged<W, F, T, Arg>(
+    absent: F,
+    present: F,
+) -> impl Fn(&Option<T>, &mut W, Endian, Arg) -> BinResult<()>
+where
+    W: Write + Seek,
+    F: for<'a> BinWrite<Args<'a> = ()>,
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+{
+    move |value, writer, endian, args| match value {
+        Some(value) => {
+            present.write_options(writer, endian, ())?;
+            value.write_options(writer, endian, args)
+        }
+        None => absent.write_options(writer, endian, ()),
+    }
+}
+
+/// Creates a parser that reads items into a collection until the given number
+/// of bytes have been consumed.
+///
+/// This is useful for the common layout where a record occupies a slot of a
+/// known size, and a header is followed by a variable number of items which
+/// fill the rest of the slot, similar to a flexible array member in C.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If reading fails, or an item does not end exactly at the end of the region,
+/// an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{until_size, written_size}, io::Cursor, Endian};
+/// #[binrw]
+/// # #[derive(Debug, PartialEq)]
+/// #[brw(big)]
+/// struct Record {
+///     #[bw(try_calc = written_size(items, Endian::Big, ()).map(|size| size + 2))]
+///     #[br(temp)]
+///     size: u64,
+///     kind: u16,
+///     #[br(parse_with = until_size(size - 2))]
+///     items: Vec<u16>,
+/// }
+///
+/// let data = b"\0\0\0\0\0\0\0\x06\0\x01\0\x02\0\x03";
+/// let record = Record::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(record, Record { kind: 1, items: vec![2, 3] });
+///
+/// let mut out = Cursor::new(vec![]);
+/// record.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+pub fn until_size<Reader, T, Arg, Ret>(
+    size: u64,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    until_size_with(size, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until the given number of bytes have been consumed.
+///
+/// The given `read` function should return one item each time it is called
+/// and must consume at least one byte.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If reading fails, or an item does not end exactly at the end of the region,
+/// an [`Error`] variant will be returned.
+pub fn until_size_with<Reader, T, Arg, ReadFn, Ret>(
+    size: u64,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    Reader: Read + Seek,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let start = reader.stream_position()?;
+        let end = start.saturating_add(size);
+        let mut pos = start;
+        from_fn(|| {
+            if pos >= end {
+                return None;
+            }
+
+            let item_pos = pos;
+            Some(read(reader, endian, args.clone()).and_then(|value| {
+                pos = reader.stream_position()?;
+                if pos == item_pos || pos > end {
+                    Err(Error::AssertFail {
+                        pos: item_pos,
+                        message: alloc::format!(
+                            "item does not fit in the {size} bytes starting at {start:#x}"
+                        ),
+                    })
+                } else {
+                    counted(value)
+                }
+            }))
+        })
+        .fuse()
+        .collect()
+    }
+}
+
+/// Creates a parser that reads a value from a block of the given number of
+/// bytes, then skips to the end of the block.
+///
+/// The value cannot read past the end of the block, and any bytes at the end of
+/// the block which it does not read are skipped. This is useful for chunked
+/// formats like RIFF or PNG, where a chunk may be larger than the data a parser
+/// understands.
+///
+/// # Errors
+///
+/// If reading fails, including because the value tries to read past the end of
+/// the block, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::sized_block, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(little)]
+/// struct Format {
+///     channels: u16,
+///     sample_rate: u32,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(little, magic = b"fmt ")]
+/// struct FormatChunk {
+///     size: u32,
+///     #[br(parse_with = sized_block(size.into()))]
+///     format: Format,
+///     next: [u8; 4],
+/// }
+///
+/// let data = b"fmt \x08\0\0\0\x02\0\x44\xac\0\0\xff\xffdata";
+/// let chunk = FormatChunk::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(chunk.format.sample_rate, 44100);
+/// assert_eq!(&chunk.next, b"data");
+/// ```
+pub fn sized_block<Reader, T, Arg>(size: u64) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<T>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+{
+    sized_block_with(size, |reader, endian, args| {
+        T::read_options(reader, endian, args)
+    })
+}
+
+/// Creates a parser that uses a given function to read a value from a block of
+/// the given number of bytes, then skips to the end of the block.
+///
+/// The given `read` function is called with a [`TakeSeek`](io::TakeSeek)
+/// which ends at the end of the block, so it cannot read past it. Any bytes at
+/// the end of the block which it does not read are skipped. Since the function
+/// must accept a `TakeSeek` with any lifetime, it is usually a closure.
+///
+/// # Errors
+///
+/// If reading fails, including because the value tries to read past the end of
+/// the block, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{sized_block_with, until_eof}, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Chunk {
+///     len: u8,
+///     #[br(parse_with = sized_block_with(len.into(), |reader, endian, ()| until_eof(reader, endian, ())))]
+///     values: Vec<u16>,
+///     trailer: u8,
+/// }
+///
+/// let chunk = Chunk::read(&mut Cursor::new(b"\x04\0\x01\0\x02\x03")).unwrap();
+/// assert_eq!(chunk.values, [1, 2]);
+/// assert_eq!(chunk.trailer, 3);
+/// ```
+pub fn sized_block_with<Reader, T, Arg, ReadFn>(
+    size: u64,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<T>
+where
+    Reader: Read + Seek,
+    ReadFn: Fn(&mut io::TakeSeek<&mut Reader>, Endian, Arg) -> BinResult<T>,
+{
+    move |reader, endian, args| {
+        let start = reader.stream_position()?;
+        let value = read(&mut reader.take_seek(size), endian, args)?;
+        reader.seek(io::SeekFrom::Start(start.saturating_add(size)))?;
+        Ok(value)
+    }
+}
+
+/// Creates a parser that reads a value from an encoded block of the given
+/// number of bytes, such as a compressed section of an archive.
+///
+/// The block is read into memory and passed to `decode`, which returns the
+/// decoded bytes. The value is then read from the decoded bytes, so it can
+/// seek within them even if the encoding is not seekable. This helper does not
+/// implement any encodings itself; `decode` is usually a function from another
+/// crate, such as a zlib decoder from `flate2`. Use [`encoded_block`] to write
+/// the value.
+///
+/// Positions in errors from reading the value are offsets into the decoded
+/// bytes instead of positions in the input stream.
+///
+/// # Errors
+///
+/// If reading the block or decoding fails, an [`Error::Io`] is returned. If
+/// the block or the decoded bytes are larger than the remaining
+/// [memory limit](crate::memory), or reading the value fails, an [`Error`]
+/// variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{decoded_block, encoded_block}, io::Cursor};
+/// // A run-length encoding of (count, byte) pairs
+/// fn decode(data: &[u8]) -> binrw::io::Result<Vec<u8>> {
+///     Ok(data
+///         .chunks(2)
+///         .flat_map(|run| core::iter::repeat(run[1]).take(run[0].into()))
+///         .collect())
+/// }
+///
+/// fn encode(data: &[u8]) -> binrw::io::Result<Vec<u8>> {
+///     Ok(data.iter().flat_map(|&byte| [1, byte]).collect())
+/// }
+///
+/// #[binrw]
+/// #[brw(big)]
+/// struct Section {
+///     size: u8,
+///     #[br(parse_with = decoded_block(size.into(), decode))]
+///     #[bw(write_with = encoded_block(encode))]
+///     values: [u16; 2],
+/// }
+///
+/// let data = b"\x08\x01\x00\x01\x00\x01\x00\x01\x01";
+/// let section = Section::read(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(section.values, [0, 1]);
+///
+/// let mut out = Cursor::new(Vec::new());
+/// section.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+pub fn decoded_block<Reader, T, Arg, DecodeFn>(
+    size: u64,
+    decode: DecodeFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<T>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    DecodeFn: Fn(&[u8]) -> io::Result<Vec<u8>>,
+{
+    move |reader, endian, args| {
+        crate::memory::reserve(usize::try_from(size).unwrap_or(usize::MAX))?;
+        let mut data = Vec::new();
+        reader.take(size).read_to_end(&mut data)?;
+        if (data.len() as u64) < size {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "encoded block is past the end of the stream",
+            )
+            .into());
+        }
+
+        let data = decode(&data)?;
+        crate::memory::reserve(data.len())?;
+        T::read_options(&mut io::Cursor::new(data), endian, args)
+    }
+}
+
+/// Creates a writer that writes a value as an encoded block, such as a
+/// compressed section of an archive.
+///
+/// The value is written to memory and the bytes are passed to `encode`, which
+/// returns the encoded bytes to write. This is the reverse of
+/// [`decoded_block`]; see it for an example.
+///
+/// # Errors
+///
+/// If encoding fails, an [`Error::Io`] is returned. If writing fails, an
+/// [`Error`] variant will be returned.
+pub fn encoded_block<W, T, Arg, EncodeFn>(
+    encode: EncodeFn,
+) -> impl Fn(&T, &mut W, Endian, Arg) -> BinResult<()>
+where
+    W: Write + Seek,
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+    EncodeFn: Fn(&[u8]) -> io::Result<Vec<u8>>,
+{
+    move |value, writer, endian, args| {
+        let mut data = io::Cursor::new(Vec::new());
+        value.write_options(&mut data, endian, args)?;
+        writer.write_all(&encode(&data.into_inner())?)?;
+        Ok(())
+    }
+}
+
+/// Returns the number of bytes which would be written by writing `value`.
+///
+/// This is useful for calculating size fields when writing a header which is
+/// followed by a variable amount of data, such as the items read by
+/// [`until_size`].
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`] variant will be returned.
+pub fn written_size<T>(value: &T, endian: Endian, args: T::Args<'_>) -> BinResult<u64>
+where
+    T: BinWrite + ?Sized,
+{
+    let mut counter = SizeCounter::default();
+    value.write_options(&mut counter, endian, args)?;
+    Ok(counter.end)
+}
+
+/// A writer which discards data, keeping track of the furthest position which
+/// was written to.
+#[derive(Default)]
+struct SizeCounter {
+    pos: u64,
+    end: u64,
+}
+
+impl Write for SizeCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pos += buf.len() as u64;
+        self.end = self.end.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SizeCounter {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            io::SeekFrom::Start(pos) => Some(pos),
+            io::SeekFrom::End(offset) => self.end.checked_add_signed(offset),
+            io::SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+        self.pos = pos.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.pos)
+    }
+}
+
+/// Creates a parser that borrows the number of bytes given in the argument
+/// from `source` instead of copying them.
+///
+/// `source` must be the data that the reader is reading, so that the position
+/// of the reader is also an offset into `source`. This is the case when
+/// reading from a [`Cursor`](crate::io::Cursor) over `source`. The reader is
+/// moved past the borrowed bytes.
+///
+/// Since the source data has to be passed to every value which borrows from
+/// it, it is usually accepted with
+/// [`import`](crate::docs::attribute#arguments) and passed on to nested
+/// values with [`args`](crate::docs::attribute#arguments). Collections of
+/// values which borrow from `source` can be read with [`args_iter`], since the
+/// [`BinRead`] implementation of [`Vec`] requires items which are `'static`.
+///
+/// `source` can be any byte slice, including a memory-mapped file from a crate
+/// such as `memmap2`. Reading from `Cursor::new(&map[..])` with `&map[..]` as
+/// `source` parses a mapped file without copying the borrowed bytes to the
+/// heap.
+///
+/// # Errors
+///
+/// If the bytes are not all within `source`, an [`Error::Io`] with the kind
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::{borrow_bytes, borrow_str}, io::Cursor};
+/// #[derive(BinRead)]
+/// #[br(import(source: &'a [u8]))]
+/// struct Entry<'a> {
+///     name_len: u8,
+///     #[br(parse_with = borrow_str(source), args_raw = name_len.into())]
+///     name: &'a str,
+///     data_len: u8,
+///     #[br(parse_with = borrow_bytes(source), args_raw = data_len.into())]
+///     data: &'a [u8],
+/// }
+///
+/// let source = b"\x04name\x02\x01\x02";
+/// let entry = Entry::read_le_args(&mut Cursor::new(source), (source,)).unwrap();
+/// assert_eq!(entry.name, "name");
+/// assert_eq!(entry.data, [1, 2]);
+/// ```
+pub fn borrow_bytes<'a, R: Read + Seek>(
+    source: &'a [u8],
+) -> impl Fn(&mut R, Endian, usize) -> BinResult<&'a [u8]> {
+    move |reader, _, len| {
+        let pos = reader.stream_position()?;
+        let range = usize::try_from(pos)
+            .ok()
+            .and_then(|start| Some(start..start.checked_add(len)?))
+            .filter(|range| range.end <= source.len())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "borrowed bytes are outside of the source data",
+                )
+            })?;
+        reader.seek(io::SeekFrom::Start(range.end as u64))?;
+        Ok(&source[range])
+    }
+}
+
+/// Creates a parser that borrows a UTF-8 string which is the number of bytes
+/// long given in the argument from `source` instead of copying it.
+///
+/// See [`borrow_bytes`] for the requirements on `source`.
+///
+/// # Errors
+///
+/// If the bytes are not all within `source`, an [`Error::Io`] with the kind
+/// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned. If they are
+/// not valid UTF-8, an [`Error::AssertFail`] is returned with the position of
+/// the start of the string.
+pub fn borrow_str<'a, R: Read + Seek>(
+    source: &'a [u8],
+) -> impl Fn(&mut R, Endian, usize) -> BinResult<&'a str> {
+    let borrow = borrow_bytes(source);
+    move |reader, endian, len| {
+        let pos = reader.stream_position()?;
+        let bytes = borrow(reader, endian, len)?;
+        core::str::from_utf8(bytes).map_err(|err| Error::AssertFail {
+            pos,
+            message: alloc::format!("invalid UTF-8 string: {err}"),
+        })
+    }
+}
+
+/// Accounts for the memory used by an item which is about to be added to a
+/// collection.
+// Consumes the terminator if it is next in the stream, or otherwise leaves the
+// stream where it was.
+fn at_terminator<R: Read + Seek>(
+    reader: &mut R,
+    terminator: &[u8],
+    buf: &mut [u8],
+) -> BinResult<bool> {
+    let pos = reader.stream_position()?;
+    reader.read_exact(buf)?;
+    if buf == terminator {
+        Ok(true)
+    } else {
+        reader.seek(io::SeekFrom::Start(pos))?;
+        Ok(false)
+    }
+}
+
+fn counted<T>(value: T) -> BinResult<T> {
+    crate::memory::reserve(core::mem::size_of::<T>())?;
+    Ok(value)
+}
+
 fn not_enough_bytes<T>(_: T) -> Error {
     Error::Io(io::Error::new(
         io::ErrorKind::UnexpectedEof,
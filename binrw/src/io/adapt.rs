@@ -0,0 +1,373 @@
+//! Types for composing stream adapters.
+
+use super::{Read, Result, Seek, SeekFrom, TakeSeek, TakeSeekExt, Write};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A stream adapter built from a chain of other adapters.
+///
+/// This struct is generally created by importing the [`AdaptExt`] extension
+/// and calling [`adapt`] on a stream. Each method wraps the current stream in
+/// another adapter, so layered encodings can be described in a single
+/// expression, such as in a [`map_stream`] directive. Adapters from other
+/// crates, such as decompressors, can be added with [`map`](Self::map).
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::{AdaptExt, Cursor}};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Container {
+///     len: u8,
+///     #[br(map_stream = |s| s.adapt().take(len.into()).xor([0xff]))]
+///     #[br(parse_with = binrw::helpers::until_eof)]
+///     data: Vec<u8>,
+///     trailer: u8,
+/// }
+///
+/// let container = Container::read(&mut Cursor::new(b"\x02\xfe\xfd\x03")).unwrap();
+/// assert_eq!(container.data, [1, 2]);
+/// assert_eq!(container.trailer, 3);
+/// ```
+///
+/// [`adapt`]: AdaptExt::adapt
+/// [`map_stream`]: crate::docs::attribute#stream-access-and-manipulation
+#[derive(Debug)]
+pub struct Adapt<T>(T);
+
+impl<T> Adapt<T> {
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+
+    /// Consumes this wrapper, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Wraps the stream using the given function.
+    pub fn map<U, F>(self, f: F) -> Adapt<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Adapt(f(self.0))
+    }
+
+    /// Limits the stream to the next `limit` bytes.
+    ///
+    /// See [`TakeSeek`] for details.
+    pub fn take(self, limit: u64) -> Adapt<TakeSeek<T>>
+    where
+        T: Read + Seek,
+    {
+        Adapt(self.0.take_seek(limit))
+    }
+
+    /// XORs all data passing through the stream with a repeating `key`.
+    ///
+    /// See [`Xor`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream returns an error from `stream_position`.
+    pub fn xor<K: AsRef<[u8]>>(self, key: K) -> Adapt<Xor<T, K>>
+    where
+        T: Seek,
+    {
+        Adapt(Xor::new(self.0, key))
+    }
+
+    /// Applies `transform` to all data passing through the stream.
+    ///
+    /// See [`Transform`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream returns an error from `stream_position`.
+    pub fn transform<F>(self, transform: F) -> Adapt<Transform<T, F>>
+    where
+        T: Seek,
+        F: FnMut(u64, &mut [u8]),
+    {
+        Adapt(Transform::new(self.0, transform))
+    }
+}
+
+impl<T: Read> Read for Adapt<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<T: Write> Write for Adapt<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()
+    }
+}
+
+impl<T: Seek> Seek for Adapt<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.0.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.0.stream_position()
+    }
+}
+
+/// An extension trait that implements `adapt()` for compatible streams.
+pub trait AdaptExt {
+    /// Starts a chain of adapters for the stream.
+    fn adapt(self) -> Adapt<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: Seek> AdaptExt for T {
+    fn adapt(self) -> Adapt<Self>
+    where
+        Self: Sized,
+    {
+        Adapt(self)
+    }
+}
+
+/// Stream adapter which XORs all data read from or written to an underlying
+/// stream with a repeating key.
+///
+/// The key starts at the position of the stream when the adapter is created,
+/// and seeking keeps the key aligned to that position. An empty key leaves the
+/// data unchanged.
+///
+/// This struct is generally created by calling [`xor`](Adapt::xor) on an
+/// [`Adapt`] chain.
+#[derive(Debug)]
+pub struct Xor<T, K> {
+    inner: T,
+    key: K,
+    start: u64,
+    pos: u64,
+}
+
+impl<T: Seek, K: AsRef<[u8]>> Xor<T, K> {
+    /// Creates a new XOR adapter for the given stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream returns an error from `stream_position`.
+    pub fn new(mut inner: T, key: K) -> Self {
+        let pos = inner
+            .stream_position()
+            .expect("cannot get position for `xor`");
+
+        Self {
+            inner,
+            key,
+            start: pos,
+            pos,
+        }
+    }
+}
+
+impl<T, K: AsRef<[u8]>> Xor<T, K> {
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Care should be taken to avoid modifying the position of the underlying
+    /// stream as doing so will misalign the key.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn apply(&self, buf: &mut [u8]) {
+        let key = self.key.as_ref();
+        if key.is_empty() {
+            return;
+        }
+
+        let len = key.len() as u64;
+        let offset = if self.pos >= self.start {
+            (self.pos - self.start) % len
+        } else {
+            (len - (self.start - self.pos) % len) % len
+        };
+
+        // Lint: `offset` is less than the length of the key, which is a
+        // `usize`
+        #[allow(clippy::cast_possible_truncation)]
+        let key = key.iter().cycle().skip(offset as usize);
+        for (byte, key) in buf.iter_mut().zip(key) {
+            *byte ^= key;
+        }
+    }
+}
+
+impl<T: Read, K: AsRef<[u8]>> Read for Xor<T, K> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.apply(&mut buf[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Write, K: AsRef<[u8]>> Write for Xor<T, K> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut data = Vec::from(buf);
+        self.apply(&mut data);
+        let n = self.inner.write(&data)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek, K> Seek for Xor<T, K> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// Stream adapter which applies a transform to all data read from or written
+/// to an underlying stream.
+///
+/// The transform is called with the position of the first byte in the stream
+/// and the bytes to change in place. This can be used to decode data which is
+/// obfuscated with a keystream, such as a stream cipher. If a keystream can
+/// only be generated in order, the transform can re-key by starting again from
+/// the beginning when the position is not the one which follows the previous
+/// call.
+///
+/// The transform may be called more than once for the same position. This
+/// happens when the same data is read again after a seek, or when a write
+/// does not write all of the transformed bytes. The transform must give the
+/// same result each time.
+///
+/// This struct is generally created by calling [`transform`](Adapt::transform)
+/// on an [`Adapt`] chain.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::{AdaptExt, Cursor}};
+/// #[derive(BinRead)]
+/// struct Obfuscated {
+///     // Each byte is XORed with its position
+///     #[br(map_stream = |s| s.adapt().transform(|pos, buf| {
+///         for (byte, pos) in buf.iter_mut().zip(pos..) {
+///             *byte ^= pos as u8;
+///         }
+///     }))]
+///     #[br(count = 3)]
+///     data: Vec<u8>,
+/// }
+///
+/// let value = Obfuscated::read_le(&mut Cursor::new(b"\x07\x06\x05")).unwrap();
+/// assert_eq!(value.data, [7, 7, 7]);
+/// ```
+pub struct Transform<T, F> {
+    inner: T,
+    f: F,
+    pos: u64,
+}
+
+impl<T: Seek, F: FnMut(u64, &mut [u8])> Transform<T, F> {
+    /// Creates a new transform adapter for the given stream.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream returns an error from `stream_position`.
+    pub fn new(mut inner: T, transform: F) -> Self {
+        let pos = inner
+            .stream_position()
+            .expect("cannot get position for `transform`");
+
+        Self {
+            inner,
+            f: transform,
+            pos,
+        }
+    }
+}
+
+impl<T, F> Transform<T, F> {
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Care should be taken to avoid modifying the position of the underlying
+    /// stream as doing so will give the wrong positions to the transform.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read, F: FnMut(u64, &mut [u8])> Read for Transform<T, F> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        (self.f)(self.pos, &mut buf[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Write, F: FnMut(u64, &mut [u8])> Write for Transform<T, F> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut data = Vec::from(buf);
+        (self.f)(self.pos, &mut data);
+        let n = self.inner.write(&data)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek, F> Seek for Transform<T, F> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
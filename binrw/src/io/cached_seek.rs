@@ -0,0 +1,136 @@
+//! Wrapper type to cache the position of a seekable stream.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+
+/// A wrapper that caches the position of a [`Seek`] stream.
+///
+/// binrw asks for the current position of the stream often, such as to record
+/// where a value started so that it can rewind on failure. For streams like
+/// files, every position query and seek is a system call.
+/// This wrapper keeps track of the position itself, so position queries and
+/// seeks to the current position do not use the underlying stream at all.
+///
+/// Unlike [`BufReader`](super::BufReader), this wrapper does no buffering, so
+/// it can also be used for writing and for streams which are read from and
+/// written to.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::{CachedSeek, Cursor}};
+/// let mut stream = CachedSeek::new(Cursor::new(b"\x00\x01\x00\x02"));
+/// let values = <[u16; 2]>::read_be(&mut stream).unwrap();
+/// assert_eq!(values, [1, 2]);
+/// ```
+pub struct CachedSeek<T> {
+    /// The original stream.
+    inner: T,
+    /// The position of the original stream, if known.
+    pos: Option<u64>,
+}
+
+impl<T> CachedSeek<T> {
+    /// Creates a new position-caching wrapper for the given stream.
+    pub fn new(inner: T) -> Self {
+        CachedSeek { inner, pos: None }
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Since the underlying stream may be moved using the returned reference,
+    /// this clears the cached position.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.pos = None;
+        &mut self.inner
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Keeps the cached position in sync after an operation which moved the
+    /// underlying stream by `n` bytes, or clears it if the operation failed.
+    fn advance<U>(&mut self, result: Result<U>, n: impl FnOnce(&U) -> usize) -> Result<U> {
+        match &result {
+            Ok(value) => {
+                let n = n(value) as u64;
+                self.pos = self.pos.and_then(|pos| pos.checked_add(n));
+            }
+            Err(_) => self.pos = None,
+        }
+        result
+    }
+}
+
+impl<T: Seek> Seek for CachedSeek<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match (pos, self.pos) {
+            (SeekFrom::Start(n), _) => Some(n),
+            (SeekFrom::Current(n), Some(old)) => {
+                Some(old.checked_add_signed(n).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?)
+            }
+            (SeekFrom::Current(_) | SeekFrom::End(_), _) => None,
+        };
+
+        if let (Some(target), Some(old)) = (target, self.pos) {
+            if target == old {
+                return Ok(old);
+            }
+        }
+
+        let result = self.inner.seek(pos);
+        self.pos = result.as_ref().ok().copied();
+        result
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(match self.pos {
+            None => {
+                let pos = self.inner.stream_position()?;
+                self.pos = Some(pos);
+                pos
+            }
+            Some(pos) => pos,
+        })
+    }
+}
+
+impl<T: Read> Read for CachedSeek<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let result = self.inner.read(buf);
+        self.advance(result, |n| *n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let len = buf.len();
+        let result = self.inner.read_exact(buf);
+        self.advance(result, |()| len)
+    }
+}
+
+impl<T: Write> Write for CachedSeek<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let result = self.inner.write(buf);
+        self.advance(result, |n| *n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        let result = self.inner.write_all(buf);
+        self.advance(result, |()| buf.len())
+    }
+}
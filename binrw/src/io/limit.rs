@@ -0,0 +1,125 @@
+//! Types for limiting the total number of bytes read during a parse.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use crate::BinResult;
+
+/// Calls `f` with a reader which fails once more than `max_bytes` bytes have
+/// been read from `reader` in total.
+///
+/// Unlike [`TakeSeek`](super::TakeSeek), which limits the *range* of the
+/// stream which can be read, this limits the total *amount* of data read, so
+/// data which is read more than once, for example after seeking backwards
+/// when an enum variant fails to parse, counts towards the limit each time.
+/// This is useful as a simple defence when parsing untrusted data, along with
+/// [`memory::limit`](crate::memory::limit).
+///
+/// # Errors
+///
+/// If `f` returns an error after the limit was exceeded, an
+/// [`Error::LimitExceeded`](crate::Error::LimitExceeded) is returned instead.
+/// Other errors are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::{self, Cursor}};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Samples {
+///     len: u32,
+///     #[br(count = len)]
+///     data: Vec<u8>,
+/// }
+///
+/// let data = b"\0\0\0\x04\x01\x02\x03\x04";
+/// let result = io::limit_reads(Cursor::new(data), 6, Samples::read);
+/// assert!(matches!(result, Err(binrw::Error::LimitExceeded { limit: 6, .. })));
+///
+/// let result = io::limit_reads(Cursor::new(data), 8, Samples::read);
+/// assert_eq!(result.unwrap().data, [1, 2, 3, 4]);
+/// ```
+pub fn limit_reads<R, T, F>(reader: R, max_bytes: u64, f: F) -> BinResult<T>
+where
+    R: Read + Seek,
+    F: FnOnce(&mut ReadLimit<R>) -> BinResult<T>,
+{
+    let mut reader = ReadLimit {
+        inner: reader,
+        remaining: max_bytes,
+        exceeded_at: None,
+    };
+
+    match (f(&mut reader), reader.exceeded_at) {
+        (Err(_), Some(pos)) => Err(crate::Error::LimitExceeded {
+            pos,
+            limit: max_bytes,
+        }),
+        (result, _) => result,
+    }
+}
+
+/// Read adapter which fails once a total number of bytes have been read from
+/// an underlying reader.
+///
+/// This struct is created by [`limit_reads`].
+#[derive(Debug)]
+pub struct ReadLimit<T> {
+    inner: T,
+    remaining: u64,
+    exceeded_at: Option<u64>,
+}
+
+impl<T> ReadLimit<T> {
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Data read directly from the underlying reader does not count towards
+    /// the limit.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the number of bytes that can still be read before this instance
+    /// fails.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+
+impl<T: Read + Seek> Read for ReadLimit<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            if self.exceeded_at.is_none() {
+                self.exceeded_at = Some(self.inner.stream_position()?);
+            }
+            return Err(Error::new(ErrorKind::InvalidData, "read limit exceeded"));
+        }
+
+        // Lint: It is impossible for this cast to truncate because the value
+        // being cast is the minimum of two values, and one of the value types
+        // is already `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        let max = (buf.len() as u64).min(self.remaining) as usize;
+        let n = self.inner.read(&mut buf[..max])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for ReadLimit<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
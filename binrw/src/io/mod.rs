@@ -4,22 +4,42 @@
 //! used by binrw. In `no_std` environments, a compatible subset API is exposed
 //! instead.
 
+mod adapt;
 #[cfg(feature = "std")]
 mod bufreader;
+mod cached_seek;
+mod limit;
 #[cfg(not(feature = "std"))]
 mod no_std;
 pub mod prelude;
 mod seek;
+mod segment;
 mod take_seek;
+mod transaction;
 
+pub use adapt::{Adapt, AdaptExt, Transform, Xor};
 #[cfg(feature = "std")]
 pub use bufreader::BufReader;
 #[cfg(all(doc, not(feature = "std")))]
 #[doc(hidden)]
 pub struct BufReader;
+pub use cached_seek::CachedSeek;
+pub use limit::{limit_reads, ReadLimit};
 #[cfg(not(feature = "std"))]
 pub use no_std::*;
-pub use seek::NoSeek;
+pub use seek::{ForwardSeek, NoSeek};
+pub use segment::SegmentWriter;
 #[cfg(feature = "std")]
 pub use std::io::{Bytes, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
 pub use take_seek::*;
+pub use transaction::TransactionalWriter;
+
+/// A stream which can be both read and seeked.
+///
+/// This trait is automatically implemented for every type which implements
+/// [`Read`] and [`Seek`]. It exists so that `&mut dyn ReadSeek` can be used as
+/// a single reader type, as is done by the
+/// [`dyn_reader`](crate::docs::attribute#dynamic-readers) directive.
+pub trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek + ?Sized> ReadSeek for T {}
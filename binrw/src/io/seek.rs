@@ -1,4 +1,5 @@
-//! Wrapper type that provides a fake [`Seek`](crate::io::Seek) implementation.
+//! Wrapper types that provide limited [`Seek`](crate::io::Seek)
+//! implementations for unseekable streams.
 
 use super::{Error, ErrorKind, SeekFrom};
 #[cfg(not(feature = "std"))]
@@ -10,17 +11,52 @@ use alloc::{string::String, vec::Vec};
 ///
 /// This is useful when reading or writing from unseekable streams where binrw
 /// does not *actually* need to seek to successfully parse or write the data.
+/// For reading formats which skip forward, such as with padding or offsets,
+/// use [`ForwardSeek`] instead.
+///
+/// A read of a single byte can be undone by seeking back one byte immediately
+/// afterwards, which allows checking for the end of the stream.
 pub struct NoSeek<T> {
     /// The original stream.
     inner: T,
     /// The virtual position of the seekable stream.
     pos: u64,
+    /// Whether seeking backward is ignored instead of returning an error.
+    ignore_rewind: bool,
+    /// The byte from the last read, if it read exactly one byte.
+    last: Option<u8>,
+    /// A byte which was read and then seeked back over.
+    unread: Option<u8>,
 }
 
 impl<T> NoSeek<T> {
     /// Creates a new seekable wrapper for the given value.
     pub fn new(inner: T) -> Self {
-        NoSeek { inner, pos: 0 }
+        NoSeek {
+            inner,
+            pos: 0,
+            ignore_rewind: false,
+            last: None,
+            unread: None,
+        }
+    }
+
+    /// Creates a new seekable wrapper for reading or writing a single value
+    /// which never seeks.
+    ///
+    /// The only backward seeks made by such a value are to rewind after a
+    /// failure, which would otherwise replace the original error with a seek
+    /// error. Since the value is not read or written again afterwards, these
+    /// seeks are ignored. An ignored seek does not move the stream, so it
+    /// returns the current position instead of the requested one.
+    pub(crate) fn sequential(inner: T) -> Self {
+        NoSeek {
+            inner,
+            pos: 0,
+            ignore_rewind: true,
+            last: None,
+            unread: None,
+        }
     }
 
     /// Gets a mutable reference to the underlying value.
@@ -43,7 +79,13 @@ impl<T> super::Seek for NoSeek<T> {
     fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
         match pos {
             SeekFrom::Start(n) if self.pos == n => Ok(n),
+            SeekFrom::Start(n) if self.ignore_rewind && n < self.pos => Ok(self.pos),
             SeekFrom::Current(0) => Ok(self.pos),
+            SeekFrom::Current(-1) if self.last.is_some() => {
+                self.unread = self.last.take();
+                self.pos -= 1;
+                Ok(self.pos)
+            }
             // https://github.com/rust-lang/rust/issues/86442
             _ => Err(Error::new(ErrorKind::Other, "seek on unseekable file")),
         }
@@ -56,39 +98,85 @@ impl<T> super::Seek for NoSeek<T> {
 
 impl<T: super::Read> super::Read for NoSeek<T> {
     fn read(&mut self, buf: &mut [u8]) -> super::Result<usize> {
-        let n = self.inner.read(buf)?;
+        self.last = None;
+        let n = match (self.unread.take(), buf.first_mut()) {
+            (Some(byte), Some(first)) => {
+                *first = byte;
+                1
+            }
+            (unread, _) => {
+                self.unread = unread;
+                self.inner.read(buf)?
+            }
+        };
         self.pos += n as u64;
+        self.last = (n == 1).then(|| buf[0]);
         Ok(n)
     }
 
     #[cfg(feature = "std")]
     fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> super::Result<usize> {
+        self.last = None;
+        if self.unread.is_some() {
+            return match bufs.iter_mut().find(|buf| !buf.is_empty()) {
+                Some(buf) => self.read(buf),
+                None => Ok(0),
+            };
+        }
+
         let n = self.inner.read_vectored(bufs)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> super::Result<usize> {
-        let n = self.inner.read_to_end(buf)?;
+        self.last = None;
+        let unread = self.unread.take();
+        buf.extend(unread);
+        let n = self.inner.read_to_end(buf)? + usize::from(unread.is_some());
         self.pos += n as u64;
         Ok(n)
     }
 
     fn read_to_string(&mut self, buf: &mut String) -> super::Result<usize> {
-        let n = self.inner.read_to_string(buf)?;
-        self.pos += n as u64;
+        self.last = None;
+        if self.unread.is_none() {
+            let n = self.inner.read_to_string(buf)?;
+            self.pos += n as u64;
+            return Ok(n);
+        }
+
+        let mut bytes = Vec::new();
+        let n = self.read_to_end(&mut bytes)?;
+        let text = core::str::from_utf8(&bytes).map_err(|_| {
+            Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+        })?;
+        buf.push_str(text);
         Ok(n)
     }
 
     fn read_exact(&mut self, buf: &mut [u8]) -> super::Result<()> {
-        self.inner.read_exact(buf)?;
-        self.pos += buf.len() as u64;
+        self.last = None;
+        let rest = match (self.unread.take(), buf.split_first_mut()) {
+            (Some(byte), Some((first, rest))) => {
+                *first = byte;
+                self.pos += 1;
+                rest
+            }
+            (unread, _) => {
+                self.unread = unread;
+                buf
+            }
+        };
+        self.inner.read_exact(rest)?;
+        self.pos += rest.len() as u64;
         Ok(())
     }
 }
 
 impl<T: super::Write> super::Write for NoSeek<T> {
     fn write(&mut self, buf: &[u8]) -> super::Result<usize> {
+        self.last = None;
         let n = self.inner.write(buf)?;
         self.pos += n as u64;
         Ok(n)
@@ -100,14 +188,136 @@ impl<T: super::Write> super::Write for NoSeek<T> {
 
     #[cfg(feature = "std")]
     fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> super::Result<usize> {
+        self.last = None;
         let n = self.inner.write_vectored(bufs)?;
         self.pos += n as u64;
         Ok(n)
     }
 
     fn write_all(&mut self, buf: &[u8]) -> super::Result<()> {
+        self.last = None;
         self.inner.write_all(buf)?;
         self.pos += buf.len() as u64;
         Ok(())
     }
 }
+
+/// A wrapper that provides a forward-only implementation of
+/// [`Seek`](crate::io::Seek) for unseekable [`Read`](crate::io::Read) streams,
+/// such as sockets and pipes.
+///
+/// Seeking forward reads and discards data from the stream until the target
+/// position is reached. Seeking backward, or relative to the end of the
+/// stream, returns an error.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::ForwardSeek};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     offset: u8,
+///     #[br(seek_before = binrw::io::SeekFrom::Start(offset.into()))]
+///     value: u16,
+/// }
+///
+/// let stream = b"\x03\xff\xff\x00\x01".as_slice();
+/// let header = Header::read(&mut ForwardSeek::new(stream)).unwrap();
+/// assert_eq!(header.value, 1);
+/// ```
+pub struct ForwardSeek<T> {
+    /// The original stream.
+    inner: T,
+    /// The virtual position of the seekable stream.
+    pos: u64,
+}
+
+impl<T> ForwardSeek<T> {
+    /// Creates a new forward-seekable wrapper for the given value.
+    pub fn new(inner: T) -> Self {
+        ForwardSeek { inner, pos: 0 }
+    }
+
+    /// Gets a mutable reference to the underlying value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the underlying value.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: super::Read> ForwardSeek<T> {
+    fn skip(&mut self, mut n: u64) -> super::Result<()> {
+        const BUF_SIZE: usize = 0x100;
+        let mut buf = [0; BUF_SIZE];
+        while n != 0 {
+            // Lint: The value being cast is at most `BUF_SIZE`.
+            #[allow(clippy::cast_possible_truncation)]
+            let len = n.min(BUF_SIZE as u64) as usize;
+            match self.inner.read(&mut buf[..len]) {
+                Ok(0) => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "seek past the end of unseekable file",
+                    ))
+                }
+                Ok(read) => {
+                    self.pos += read as u64;
+                    n -= read as u64;
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: super::Read> super::Seek for ForwardSeek<T> {
+    fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(_) => None,
+        };
+
+        match target {
+            Some(target) if target >= self.pos => {
+                self.skip(target - self.pos)?;
+                Ok(self.pos)
+            }
+            // https://github.com/rust-lang/rust/issues/86442
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "seek backward or from the end of unseekable file",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> super::Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl<T: super::Read> super::Read for ForwardSeek<T> {
+    fn read(&mut self, buf: &mut [u8]) -> super::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> super::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.pos += buf.len() as u64;
+        Ok(())
+    }
+}
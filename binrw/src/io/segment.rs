@@ -0,0 +1,209 @@
+//! Wrapper type that buffers segments of an unseekable write stream so they
+//! can be patched before being written.
+
+use super::{Error, ErrorKind, Result, SeekFrom};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A wrapper that provides a limited implementation of
+/// [`Seek`](crate::io::Seek) for unseekable [`Write`](crate::io::Write)
+/// streams by buffering *segments* of the output in memory.
+///
+/// Outside of a segment, data is written directly to the underlying stream and
+/// only no-op seeks are allowed, like [`NoSeek`](crate::io::NoSeek). Inside a
+/// segment, data is held in memory, and the stream can be freely seeked
+/// anywhere between the start of the outermost segment and the furthest
+/// position written so far. When the outermost segment ends, the buffered data
+/// is written to the underlying stream.
+///
+/// This makes it possible to write formats which contain placeholder values
+/// that are patched later (e.g. a length prefix for a block of data) to pipes
+/// and sockets, while keeping memory use bounded by the size of the largest
+/// segment instead of the size of the entire output.
+///
+/// Segments can be nested. Only the outermost segment controls when buffered
+/// data is written to the underlying stream.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{Seek, SeekFrom, SegmentWriter}};
+///
+/// let mut writer = SegmentWriter::new(Vec::new());
+/// 0xffu8.write_le(&mut writer).unwrap();
+///
+/// writer.begin_segment();
+/// let start = writer.stream_position().unwrap();
+/// 0u16.write_le(&mut writer).unwrap(); // length placeholder
+/// b"hello".write_le(&mut writer).unwrap();
+/// let end = writer.stream_position().unwrap();
+/// writer.seek(SeekFrom::Start(start)).unwrap();
+/// ((end - start - 2) as u16).write_le(&mut writer).unwrap();
+/// writer.seek(SeekFrom::Start(end)).unwrap();
+/// writer.end_segment().unwrap();
+///
+/// assert_eq!(writer.into_inner().unwrap(), b"\xff\x05\0hello");
+/// ```
+pub struct SegmentWriter<T> {
+    /// The original stream.
+    inner: T,
+    /// The number of bytes written to the original stream.
+    pos: u64,
+    /// The data of the current segment. This always starts at `pos`.
+    buffer: Vec<u8>,
+    /// The position of the stream relative to the start of `buffer`.
+    cursor: usize,
+    /// The number of currently open segments.
+    depth: usize,
+}
+
+impl<T> SegmentWriter<T> {
+    /// Creates a new segment writer for the given value.
+    pub fn new(inner: T) -> Self {
+        SegmentWriter {
+            inner,
+            pos: 0,
+            buffer: Vec::new(),
+            cursor: 0,
+            depth: 0,
+        }
+    }
+
+    /// Gets a mutable reference to the underlying value.
+    ///
+    /// Writing directly to the underlying value will corrupt the output of any
+    /// open segment.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the underlying value.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns `true` if a segment is currently open.
+    pub fn in_segment(&self) -> bool {
+        self.depth != 0
+    }
+
+    /// Starts buffering a new segment at the current position of the stream.
+    pub fn begin_segment(&mut self) {
+        self.depth += 1;
+    }
+}
+
+impl<T: super::Write> SegmentWriter<T> {
+    /// Ends the most recently started segment. If it is the outermost segment,
+    /// its data is written to the underlying stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no segment is open, if the stream is not positioned
+    /// at the end of the buffered data when the outermost segment ends, or if
+    /// writing to the underlying stream fails.
+    pub fn end_segment(&mut self) -> Result<()> {
+        match self.depth {
+            0 => Err(Error::new(ErrorKind::InvalidInput, "no segment to end")),
+            1 => {
+                if self.cursor != self.buffer.len() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "segment ended before the end of its data",
+                    ));
+                }
+                self.write_buffer()?;
+                self.depth = 0;
+                Ok(())
+            }
+            _ => {
+                self.depth -= 1;
+                Ok(())
+            }
+        }
+    }
+
+    /// Ends all open segments, writes any buffered data to the underlying
+    /// stream, and returns the underlying stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the underlying stream fails.
+    pub fn into_inner(mut self) -> Result<T> {
+        self.write_buffer()?;
+        Ok(self.inner)
+    }
+
+    fn write_buffer(&mut self) -> Result<()> {
+        self.inner.write_all(&self.buffer)?;
+        self.pos += self.buffer.len() as u64;
+        self.buffer.clear();
+        self.cursor = 0;
+        Ok(())
+    }
+}
+
+impl<T> super::Seek for SegmentWriter<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let end = self.buffer.len() as u64;
+        let cursor = self.cursor as u64;
+        let target = match pos {
+            SeekFrom::Start(n) => n.checked_sub(self.pos),
+            SeekFrom::Current(n) => cursor.checked_add_signed(n),
+            SeekFrom::End(n) => end.checked_add_signed(n),
+        };
+
+        match target {
+            Some(target) if target <= end => {
+                // Lint: `target` is no larger than the length of `buffer`.
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    self.cursor = target as usize;
+                }
+                Ok(self.pos + target)
+            }
+            // https://github.com/rust-lang/rust/issues/86442
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "seek outside of buffered segment",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos + self.cursor as u64)
+    }
+}
+
+impl<T: super::Write> super::Write for SegmentWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.depth == 0 {
+            let n = self.inner.write(buf)?;
+            self.pos += n as u64;
+            Ok(n)
+        } else {
+            let overlap = buf.len().min(self.buffer.len() - self.cursor);
+            let (overwrite, extend) = buf.split_at(overlap);
+            self.buffer[self.cursor..self.cursor + overlap].copy_from_slice(overwrite);
+            self.buffer.extend_from_slice(extend);
+            self.cursor += buf.len();
+            Ok(buf.len())
+        }
+    }
+
+    /// Flushes the underlying stream. Data in an open segment is not written
+    /// until the segment ends.
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        if self.depth == 0 {
+            self.inner.write_all(buf)?;
+            self.pos += buf.len() as u64;
+            Ok(())
+        } else {
+            self.write(buf).map(|_| ())
+        }
+    }
+}
@@ -6,6 +6,11 @@ use super::{Read, Result, Seek, SeekFrom};
 /// Read adapter which limits the bytes read from an underlying reader, with
 /// seek support.
 ///
+/// Unlike `std::io::Take`, this adapter implements [`Seek`], so it can be
+/// used to parse a bounded region of a stream. Positions are the same as in
+/// the underlying reader. Seeking past the end of the region moves to the end
+/// of the region instead.
+///
 /// This struct is generally created by importing the [`TakeSeekExt`] extension
 /// and calling [`take_seek`] on a reader.
 ///
@@ -90,9 +95,11 @@ impl<T: Read> Read for TakeSeek<T> {
 
 impl<T: Seek> Seek for TakeSeek<T> {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Seeks past the end of the window stop at the end, so that a parser
+        // which skips over data cannot move outside of it
         let pos = match pos {
             SeekFrom::End(end) => match self.end.checked_add_signed(end) {
-                Some(pos) => SeekFrom::Start(pos),
+                Some(pos) => SeekFrom::Start(pos.min(self.end)),
                 None => {
                     return Err(super::Error::new(
                         super::ErrorKind::InvalidInput,
@@ -100,6 +107,15 @@ impl<T: Seek> Seek for TakeSeek<T> {
                     ))
                 }
             },
+            SeekFrom::Start(pos) if pos > self.end => SeekFrom::Start(self.end),
+            SeekFrom::Current(offset)
+                if self
+                    .pos
+                    .checked_add_signed(offset)
+                    .map_or(offset > 0, |pos| pos > self.end) =>
+            {
+                SeekFrom::Start(self.end)
+            }
             pos => pos,
         };
         self.pos = self.inner.seek(pos)?;
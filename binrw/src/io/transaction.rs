@@ -0,0 +1,174 @@
+//! Wrapper type that holds writes in memory until they are committed.
+
+use super::{Result, Seek, SeekFrom, Write};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A wrapper that records writes to a stream in memory and only applies them
+/// to the underlying stream when they are [committed](Self::commit).
+///
+/// If writing a value fails part way through, for example because an
+/// [`assert`](crate::docs::attribute#assert) fails, dropping the wrapper (or
+/// calling [`rollback`](Self::rollback)) discards everything written since it
+/// was created, so the underlying stream does not end up with a half-written
+/// record. The underlying stream is left at the position it was at before the
+/// first write.
+///
+/// Each write is recorded along with its position, so the wrapper can be
+/// seeked freely, including to patch placeholder values, and seeking past
+/// data which was not written leaves that data in the underlying stream
+/// unchanged when committed.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binwrite, BinWrite, io::{Cursor, TransactionalWriter}};
+///
+/// #[binwrite]
+/// #[bw(big)]
+/// struct Record {
+///     id: u16,
+///     #[bw(assert(*len < 4))]
+///     len: u8,
+/// }
+///
+/// let mut out = Cursor::new(Vec::new());
+///
+/// let mut writer = TransactionalWriter::new(&mut out);
+/// Record { id: 1, len: 2 }.write(&mut writer).unwrap();
+/// writer.commit().unwrap();
+///
+/// let mut writer = TransactionalWriter::new(&mut out);
+/// assert!(Record { id: 2, len: 8 }.write(&mut writer).is_err());
+/// writer.rollback();
+///
+/// assert_eq!(out.into_inner(), b"\0\x01\x02");
+/// ```
+#[derive(Debug)]
+pub struct TransactionalWriter<T> {
+    /// The original stream.
+    inner: T,
+    /// The position of the original stream and the current position of this
+    /// stream, once they are known.
+    pos: Option<(u64, u64)>,
+    /// The position and data of each write, in order.
+    journal: Vec<(u64, Vec<u8>)>,
+}
+
+impl<T> TransactionalWriter<T> {
+    /// Creates a new transactional writer for the given stream.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            pos: None,
+            journal: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Data written directly to the underlying stream is not part of the
+    /// transaction, and changing the position of the underlying stream
+    /// changes where a rollback leaves it.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Discards all uncommitted writes, returning the underlying stream.
+    ///
+    /// This is the same as dropping the wrapper, except that the underlying
+    /// stream is returned.
+    pub fn rollback(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Write + Seek> TransactionalWriter<T> {
+    /// Applies all writes to the underlying stream, returning the underlying
+    /// stream positioned at the current position of this stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to or seeking the underlying stream fails.
+    /// In that case, every write before the one which failed has already been
+    /// applied.
+    pub fn commit(mut self) -> Result<T> {
+        if let Some((_, pos)) = self.pos {
+            for (pos, data) in &self.journal {
+                self.inner.seek(SeekFrom::Start(*pos))?;
+                self.inner.write_all(data)?;
+            }
+            self.inner.seek(SeekFrom::Start(pos))?;
+        }
+        Ok(self.inner)
+    }
+
+    fn positions(&mut self) -> Result<(u64, u64)> {
+        if let Some(pos) = self.pos {
+            Ok(pos)
+        } else {
+            let start = self.inner.stream_position()?;
+            Ok(*self.pos.insert((start, start)))
+        }
+    }
+}
+
+impl<T: Write + Seek> Seek for TransactionalWriter<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (start, current) = self.positions()?;
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => current.checked_add_signed(n),
+            SeekFrom::End(n) => {
+                let end = self.inner.seek(SeekFrom::End(0))?;
+                self.inner.seek(SeekFrom::Start(start))?;
+                self.journal
+                    .iter()
+                    .map(|(pos, data)| pos + data.len() as u64)
+                    .fold(end, u64::max)
+                    .checked_add_signed(n)
+            }
+        };
+
+        match target {
+            Some(target) => {
+                self.pos = Some((start, target));
+                Ok(target)
+            }
+            // https://github.com/rust-lang/rust/issues/86442
+            None => Err(super::Error::new(
+                super::ErrorKind::Other,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.positions().map(|(_, pos)| pos)
+    }
+}
+
+impl<T: Write + Seek> Write for TransactionalWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let (start, pos) = self.positions()?;
+        match self.journal.last_mut() {
+            Some((last, data)) if *last + data.len() as u64 == pos => {
+                data.extend_from_slice(buf);
+            }
+            _ => self.journal.push((pos, buf.to_vec())),
+        }
+        self.pos = Some((start, pos + buf.len() as u64));
+        Ok(buf.len())
+    }
+
+    /// Does nothing, since data is not written to the underlying stream until
+    /// it is committed.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
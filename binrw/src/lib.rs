@@ -7,6 +7,8 @@
 #![warn(rust_2018_idioms)]
 // Lint: This is not beneficial for code organisation.
 #![allow(clippy::module_name_repetitions)]
+// Lint: `binrw::parser` uses a bare `...` to mark raw arguments.
+#![allow(unknown_lints, varargs_without_pattern)]
 
 extern crate alloc;
 // This extern crate declaration is required to use binrw_derive macros like
@@ -22,19 +24,56 @@ extern crate std;
 pub mod __private;
 mod binread;
 mod binwrite;
+pub mod bits;
+pub mod checksum;
 pub mod docs;
+pub mod dump;
+pub mod encoding;
 pub mod endian;
 pub mod error;
 pub mod file_ptr;
 pub mod helpers;
 pub mod io;
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+pub mod memory;
+#[cfg(not(feature = "std"))]
+mod memory {
+    // Memory accounting uses thread-local storage, so it is a no-op without
+    // `std`.
+    // Lint: This matches the signature of the `std` version.
+    #[allow(clippy::unnecessary_wraps)]
+    pub(crate) fn reserve(_: usize) -> crate::BinResult<()> {
+        Ok(())
+    }
+
+    pub(crate) fn remaining() -> usize {
+        usize::MAX
+    }
+}
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+pub mod memo;
 pub mod meta;
 mod named_args;
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+pub mod net;
+#[doc(hidden)]
+pub mod partial;
 #[doc(hidden)]
 pub mod pos_value;
 pub mod punctuated;
+pub mod schema;
 #[doc(hidden)]
 pub mod strings;
+pub mod testing;
+pub mod time;
+#[cfg(feature = "uuid")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "uuid")))]
+pub mod uuid;
+pub mod value;
+pub mod wire_eq;
 
 #[cfg(all(doc, not(feature = "std")))]
 use alloc::vec::Vec;
@@ -46,8 +85,13 @@ pub use {
     error::Error,
     file_ptr::{FilePtr, FilePtr128, FilePtr16, FilePtr32, FilePtr64, FilePtr8},
     named_args::NamedArgs,
-    pos_value::PosValue,
-    strings::{NullString, NullWideString},
+    partial::Partial,
+    pos_value::{PosValue, RelPos, RelPosArgs},
+    strings::{
+        BomWideString, CStringArgs, FixedString, FixedStringArgs, NullString, NullStringArgs,
+        NullWideString, NullWideStringArgs, PascalString, PascalStringArgs,
+    },
+    value::Value,
 };
 
 /// Derive macro generating an impl of the trait [`BinRead`].
@@ -0,0 +1,211 @@
+//! Memoization of values which are read more than once.
+//!
+//! Some formats refer to the same structure from many places, such as a shared
+//! header or an entry in a table which other tables point into, so following
+//! every reference parses the same bytes over and over again. Inside a
+//! [`scope`], [`read`] parses each value only once for each combination of
+//! type, position, byte order, and arguments, and returns a clone of the
+//! first result every time after that.
+//!
+//! Cached values are keyed by their position, not the stream they were read
+//! from, so a scope should only be used to read from a single stream.
+//! Memoization is per thread.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{prelude::*, io::Cursor, memo, FilePtr8};
+//! #[derive(BinRead, Clone, Debug, PartialEq)]
+//! #[br(big)]
+//! struct Header {
+//!     version: u16,
+//! }
+//!
+//! #[derive(BinRead)]
+//! #[br(big)]
+//! struct Entries {
+//!     // Both entries point to the same header, which is only parsed once
+//!     #[br(parse_with = FilePtr8::parse_with(memo::read))]
+//!     first: Header,
+//!     #[br(parse_with = FilePtr8::parse_with(memo::read))]
+//!     second: Header,
+//! }
+//!
+//! let entries = memo::scope(|| Entries::read(&mut Cursor::new(b"\x02\x02\0\x01"))).unwrap();
+//! assert_eq!(entries.first, Header { version: 1 });
+//! assert_eq!(entries.first, entries.second);
+//! ```
+
+use crate::{
+    io::{Read, Seek, SeekFrom},
+    BinRead, BinResult, Endian, Error,
+};
+use core::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    hash::{Hash, Hasher},
+};
+use std::collections::{hash_map::DefaultHasher, HashMap};
+
+/// The type, position, byte order, and hash of the arguments of a value.
+type Key = (TypeId, u64, Endian, u64);
+
+/// A cached value and the arguments it was read with. Arguments with the same
+/// hash are kept in the same list, and only the entry with equal arguments is
+/// reused.
+struct Cached {
+    args: Box<dyn Any>,
+    slot: Slot,
+}
+
+enum Slot {
+    /// The value is currently being read.
+    Pending,
+    /// The value and the position after it.
+    Done(Box<dyn Any>, u64),
+}
+
+std::thread_local! {
+    /// The cache for the innermost active scope, or `None` if there is no
+    /// active scope.
+    static CACHE: RefCell<Option<HashMap<Key, Vec<Cached>>>> = const { RefCell::new(None) };
+}
+
+/// Calls `f`, memoizing values read with [`read`] inside it, and returns its
+/// output.
+///
+/// Calls can be nested. Each call has its own cache, so values read inside an
+/// inner call are not reused once it returns.
+pub fn scope<T>(f: impl FnOnce() -> T) -> T {
+    let outer = CACHE.with(|cache| cache.replace(Some(HashMap::new())));
+    let _scope = Scope { outer };
+    f()
+}
+
+/// Restores the cache of the enclosing scope when a scope ends, even if it
+/// ends by unwinding.
+struct Scope {
+    outer: Option<HashMap<Key, Vec<Cached>>>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        CACHE.with(|cache| cache.replace(self.outer.take()));
+    }
+}
+
+/// Reads a `T`, or returns a clone of the value which was already read at the
+/// same position with the same byte order and arguments inside the current
+/// [`scope`].
+///
+/// When a cached value is returned, the reader is moved to the position after
+/// the value, as if it had been read again. Outside of a scope, this is the
+/// same as [`T::read_options`](BinRead::read_options).
+///
+/// A copy of the arguments is stored with each cached value and compared with
+/// the arguments of later reads, so the arguments cannot borrow data.
+///
+/// # Errors
+///
+/// If reading fails, an [`Error`] variant will be returned. Errors are not
+/// cached, so a value which failed to read is read again the next time.
+///
+/// If a value refers back to itself while it is being read, such as through a
+/// cycle of file pointers, an [`Error::AssertFail`] is returned instead of
+/// reading it forever.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor, memo, FilePtr8};
+/// #[derive(BinRead, Clone, Debug)]
+/// struct Node {
+///     value: u8,
+///     #[br(parse_with = FilePtr8::parse_with(memo::read))]
+///     next: Box<Node>,
+/// }
+///
+/// let error = memo::scope(|| Node::read_le(&mut Cursor::new(b"\x01\0"))).unwrap_err();
+/// assert!(matches!(error.root_cause(), binrw::Error::AssertFail { pos: 0, .. }));
+/// ```
+pub fn read<R, T>(reader: &mut R, endian: Endian, args: T::Args<'_>) -> BinResult<T>
+where
+    R: Read + Seek,
+    T: BinRead + Clone + 'static,
+    for<'a> T::Args<'a>: Hash + Eq + Clone + 'static,
+{
+    let pos = reader.stream_position()?;
+    let mut hasher = DefaultHasher::new();
+    args.hash(&mut hasher);
+    let key = (TypeId::of::<T>(), pos, endian, hasher.finish());
+
+    let lookup = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let Some(cache) = cache.as_mut() else {
+            return Lookup::Inactive;
+        };
+
+        let entries = cache.entry(key).or_default();
+        let cached = entries
+            .iter()
+            .find(|cached| cached.args.downcast_ref::<T::Args<'_>>() == Some(&args));
+        match cached.map(|cached| &cached.slot) {
+            Some(Slot::Pending) => Lookup::Cycle,
+            Some(Slot::Done(value, end)) => value
+                .downcast_ref::<T>()
+                .map_or(Lookup::Inactive, |value| Lookup::Hit(value.clone(), *end)),
+            None => {
+                entries.push(Cached {
+                    args: Box::new(args.clone()),
+                    slot: Slot::Pending,
+                });
+                Lookup::Miss
+            }
+        }
+    });
+
+    match lookup {
+        Lookup::Inactive => T::read_options(reader, endian, args),
+        Lookup::Hit(value, end) => {
+            reader.seek(SeekFrom::Start(end))?;
+            Ok(value)
+        }
+        Lookup::Cycle => Err(Error::AssertFail {
+            pos,
+            message: "value refers to itself while it is being read".into(),
+        }),
+        Lookup::Miss => {
+            let result = T::read_options(reader, endian, args.clone())
+                .and_then(|value| Ok((value, reader.stream_position()?)));
+            CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                let Some(entries) = cache.as_mut().and_then(|cache| cache.get_mut(&key)) else {
+                    return;
+                };
+                let Some(index) = entries
+                    .iter()
+                    .position(|cached| cached.args.downcast_ref::<T::Args<'_>>() == Some(&args))
+                else {
+                    return;
+                };
+                if let Ok((value, end)) = &result {
+                    entries[index].slot = Slot::Done(Box::new(value.clone()), *end);
+                } else {
+                    entries.swap_remove(index);
+                }
+            });
+            result.map(|(value, _)| value)
+        }
+    }
+}
+
+enum Lookup<T> {
+    /// There is no active scope.
+    Inactive,
+    /// The value is not cached yet.
+    Miss,
+    /// The value and the position after it.
+    Hit(T, u64),
+    /// The value is currently being read.
+    Cycle,
+}
@@ -0,0 +1,128 @@
+//! Accounting for memory allocated while reading.
+//!
+//! A small input can describe a much larger in-memory value, for example with a
+//! length prefix that tells a [`Vec`] to hold billions of elements. When
+//! parsing untrusted data, [`limit`] can be used to cap the number of bytes
+//! which binrw allocates for collections and strings during a parse, and
+//! [`measure`] can be used to find out how many bytes were allocated.
+//!
+//! The accounted size of a collection is the size of its elements, so memory
+//! owned by the elements themselves is accounted separately when it is read,
+//! and allocator overhead is not included. Allocations made by user code, such
+//! as [`map`](crate::docs::attribute#map) functions, are also not included.
+//!
+//! Accounting is per thread.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{prelude::*, io::Cursor, memory};
+//! #[derive(BinRead)]
+//! #[br(big)]
+//! struct Samples {
+//!     len: u32,
+//!     #[br(count = len)]
+//!     data: Vec<u32>,
+//! }
+//!
+//! let (result, _) = memory::limit(1024, || {
+//!     Samples::read(&mut Cursor::new(b"\xff\xff\xff\xff\0\0\0\0"))
+//! });
+//! assert!(result.is_err());
+//!
+//! let (result, used) = memory::measure(|| {
+//!     Samples::read(&mut Cursor::new(b"\0\0\0\x02\0\0\0\x01\0\0\0\x02"))
+//! });
+//! assert_eq!(result.unwrap().data, [1, 2]);
+//! assert_eq!(used, 8);
+//! ```
+
+use crate::{io, BinResult, Error};
+use core::cell::Cell;
+
+std::thread_local! {
+    /// The number of bytes accounted so far and the maximum number of bytes
+    /// for the innermost active scope, or `None` if there is no active scope.
+    static USAGE: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+/// Calls `f`, accounting the number of bytes allocated by binrw for any values
+/// read inside it, and returns its output together with the number of bytes.
+///
+/// This is the same as [`limit`] with no maximum.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    limit(usize::MAX, f)
+}
+
+/// Calls `f`, failing any read inside it which would cause the total number of
+/// bytes allocated by binrw to exceed `max_bytes`, and returns its output
+/// together with the number of bytes.
+///
+/// Reads which exceed the limit return an [`Error::Io`] with the kind
+/// [`InvalidData`](io::ErrorKind::InvalidData). The limit is checked before
+/// memory is allocated whenever the size is known in advance, such as when
+/// reading a [`Vec`] with a `count`. Null-terminated strings are checked while
+/// they are read, so a string with a missing terminator returns an
+/// [`Error::AssertFail`] once it reaches the limit instead of reading to the
+/// end of the input.
+///
+/// Calls can be nested. The bytes used by an inner call count towards the
+/// limit of the outer call, and the inner call cannot use more memory than
+/// remains in the outer call.
+pub fn limit<T>(max_bytes: usize, f: impl FnOnce() -> T) -> (T, usize) {
+    let outer = USAGE.with(|usage| {
+        let outer = usage.get();
+        let max_bytes = outer.map_or(max_bytes, |(used, max)| max_bytes.min(max - used));
+        usage.set(Some((0, max_bytes)));
+        outer
+    });
+
+    let scope = Scope { outer };
+    let value = f();
+    let used = USAGE.with(|usage| usage.get().map_or(0, |(used, _)| used));
+    drop(scope);
+    (value, used)
+}
+
+/// Restores the accounting of the enclosing scope when a scope ends, even if
+/// it ends by unwinding.
+struct Scope {
+    outer: Option<(usize, usize)>,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        USAGE.with(|usage| {
+            let inner_used = usage.get().map_or(0, |(used, _)| used);
+            usage.set(
+                self.outer
+                    .map(|(used, max)| (used.saturating_add(inner_used), max)),
+            );
+        });
+    }
+}
+
+/// Returns the number of bytes which can still be accounted before the limit of
+/// the innermost active scope is reached.
+pub(crate) fn remaining() -> usize {
+    USAGE.with(|usage| usage.get().map_or(usize::MAX, |(used, max)| max - used))
+}
+
+/// Accounts for `bytes` bytes of memory which are about to be allocated.
+pub(crate) fn reserve(bytes: usize) -> BinResult<()> {
+    USAGE.with(|usage| match usage.get() {
+        Some((used, max)) => {
+            let used = used.saturating_add(bytes);
+            if used > max {
+                Err(Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "memory limit exceeded",
+                )))
+            } else {
+                usage.set(Some((used, max)));
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    })
+}
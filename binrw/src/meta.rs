@@ -10,9 +10,15 @@
 //! [`BinWrite`]: crate::BinWrite
 
 use crate::Endian;
+use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{borrow::Cow, rc::Rc};
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, vec::Vec};
-use core::marker::PhantomData;
+use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+#[cfg(has_saturating)]
+use core::num::Saturating;
+use core::{marker::PhantomData, num::Wrapping};
 
 /// Types that require a magic number when parsed.
 ///
@@ -93,14 +99,52 @@ macro_rules! endian_impl {
     )+)+}
 }
 
-endian_impl!(() i8 u8 core::num::NonZeroU8 core::num::NonZeroI8 crate::strings::NullString => EndianKind::None);
+endian_impl!(() i8 u8 core::num::NonZeroU8 core::num::NonZeroI8 crate::strings::NullString alloc::ffi::CString => EndianKind::None);
 
-impl<T: ReadEndian + ?Sized> ReadEndian for Box<T> {
-    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+impl WriteEndian for core::ffi::CStr {
+    const ENDIAN: EndianKind = EndianKind::None;
 }
 
-impl<T: WriteEndian + ?Sized> WriteEndian for Box<T> {
-    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+impl<const N: usize> ReadEndian for crate::strings::FixedString<N> {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl<const N: usize> WriteEndian for crate::strings::FixedString<N> {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+#[cfg(target_has_atomic = "8")]
+endian_impl!(core::sync::atomic::AtomicU8 core::sync::atomic::AtomicI8 => EndianKind::None);
+
+macro_rules! endian_pointer_impl {
+    ($($(#[$attr:meta])* $Ty:ident)+) => {$(
+        $(#[$attr])*
+        impl<T: ReadEndian + ?Sized> ReadEndian for $Ty<T> {
+            const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+        }
+
+        $(#[$attr])*
+        impl<T: WriteEndian + ?Sized> WriteEndian for $Ty<T> {
+            const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+        }
+    )+}
+}
+
+endian_pointer_impl!(Box Rc #[cfg(target_has_atomic = "ptr")] Arc);
+
+impl<B: ToOwned + ?Sized> ReadEndian for Cow<'_, B>
+where
+    B::Owned: ReadEndian,
+{
+    const ENDIAN: EndianKind = <B::Owned as ReadEndian>::ENDIAN;
+}
+
+impl<B: WriteEndian + ToOwned + ?Sized> WriteEndian for Cow<'_, B> {
+    const ENDIAN: EndianKind = <B as WriteEndian>::ENDIAN;
+}
+
+impl WriteEndian for str {
+    const ENDIAN: EndianKind = EndianKind::None;
 }
 
 impl<T: ReadEndian> ReadEndian for [T] {
@@ -131,7 +175,30 @@ macro_rules! endian_generic_impl {
     )+}
 }
 
-endian_generic_impl!(Option Vec PhantomData);
+endian_generic_impl!(Option Vec VecDeque BinaryHeap BTreeSet PhantomData Wrapping);
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: ReadEndian> ReadEndian for Saturating<T> {
+    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+}
+
+// Lint: This is only compiled by versions of Rust which have `Saturating`.
+#[allow(clippy::incompatible_msrv)]
+#[cfg(has_saturating)]
+impl<T: WriteEndian> WriteEndian for Saturating<T> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
+
+#[cfg(feature = "std")]
+impl<T: ReadEndian, S> ReadEndian for std::collections::HashSet<T, S> {
+    const ENDIAN: EndianKind = <T as ReadEndian>::ENDIAN;
+}
+
+#[cfg(feature = "std")]
+impl<T: WriteEndian, S> WriteEndian for std::collections::HashSet<T, S> {
+    const ENDIAN: EndianKind = <T as WriteEndian>::ENDIAN;
+}
 
 macro_rules! endian_tuple_impl {
     ($type1:ident $(, $types:ident)*) => {
@@ -0,0 +1,213 @@
+//! Type definitions for reading and writing network addresses.
+//!
+//! IP addresses are always stored as octets in network byte order, regardless
+//! of the byte order used for the rest of the data. Socket addresses are
+//! stored as an IP address followed by a 16-bit port number, and the port
+//! number uses the byte order of the data.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{prelude::*, io::Cursor};
+//! use binrw::net::IpVersion;
+//! use std::net::{IpAddr, Ipv4Addr, SocketAddrV4};
+//!
+//! #[derive(BinRead)]
+//! #[br(big)]
+//! struct Peer {
+//!     is_v6: u8,
+//!     #[br(args_raw = if is_v6 == 0 { IpVersion::V4 } else { IpVersion::V6 })]
+//!     addr: IpAddr,
+//!     relay: SocketAddrV4,
+//! }
+//!
+//! let peer = Peer::read(&mut Cursor::new(b"\0\x7f\0\0\x01\xc0\xa8\0\x01\x1f\x90")).unwrap();
+//! assert_eq!(peer.addr, Ipv4Addr::LOCALHOST);
+//! assert_eq!(peer.relay, SocketAddrV4::new(Ipv4Addr::new(192, 168, 0, 1), 8080));
+//! ```
+
+use crate::{
+    io::{self, Read, Seek, Write},
+    meta::{EndianKind, ReadEndian, WriteEndian},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+/// The version of an [`IpAddr`], which determines its width.
+///
+/// This is the argument type for the [`BinRead`] and [`BinWrite`]
+/// implementations of [`IpAddr`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IpVersion {
+    /// A 4-byte IPv4 address.
+    V4,
+    /// A 16-byte IPv6 address.
+    V6,
+}
+
+impl BinRead for Ipv4Addr {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <[u8; 4]>::read_options(reader, endian, ()).map(Self::from)
+    }
+}
+
+impl BinWrite for Ipv4Addr {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.octets().write_options(writer, endian, ())
+    }
+}
+
+impl BinRead for Ipv6Addr {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <[u8; 16]>::read_options(reader, endian, ()).map(Self::from)
+    }
+}
+
+impl BinWrite for Ipv6Addr {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.octets().write_options(writer, endian, ())
+    }
+}
+
+impl BinRead for IpAddr {
+    type Args<'a> = IpVersion;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        version: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(match version {
+            IpVersion::V4 => Self::V4(Ipv4Addr::read_options(reader, endian, ())?),
+            IpVersion::V6 => Self::V6(Ipv6Addr::read_options(reader, endian, ())?),
+        })
+    }
+}
+
+impl BinWrite for IpAddr {
+    type Args<'a> = IpVersion;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        version: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match (self, version) {
+            (Self::V4(addr), IpVersion::V4) => addr.write_options(writer, endian, ()),
+            (Self::V6(addr), IpVersion::V6) => addr.write_options(writer, endian, ()),
+            _ => Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "IP address does not match the requested IP version",
+            ))),
+        }
+    }
+}
+
+impl BinRead for SocketAddrV4 {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ip = Ipv4Addr::read_options(reader, endian, ())?;
+        let port = u16::read_options(reader, endian, ())?;
+        Ok(Self::new(ip, port))
+    }
+}
+
+impl BinWrite for SocketAddrV4 {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.ip().write_options(writer, endian, ())?;
+        self.port().write_options(writer, endian, ())
+    }
+}
+
+/// The flow information and scope ID of the address are not stored, and are
+/// zero after reading.
+impl BinRead for SocketAddrV6 {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ip = Ipv6Addr::read_options(reader, endian, ())?;
+        let port = u16::read_options(reader, endian, ())?;
+        Ok(Self::new(ip, port, 0, 0))
+    }
+}
+
+/// The flow information and scope ID of the address are not stored.
+impl BinWrite for SocketAddrV6 {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.ip().write_options(writer, endian, ())?;
+        self.port().write_options(writer, endian, ())
+    }
+}
+
+macro_rules! endian_impl {
+    ($($Ty:ty)+) => {$(
+        impl ReadEndian for $Ty {
+            const ENDIAN: EndianKind = EndianKind::None;
+        }
+
+        impl WriteEndian for $Ty {
+            const ENDIAN: EndianKind = EndianKind::None;
+        }
+    )+}
+}
+
+endian_impl!(Ipv4Addr Ipv6Addr IpAddr);
+
+macro_rules! sequential_impl {
+    ($($Ty:ty)+) => {$(
+        impl crate::ReadSequential for $Ty {}
+        impl crate::WriteSequential for $Ty {}
+    )+}
+}
+
+sequential_impl!(Ipv4Addr Ipv6Addr IpAddr SocketAddrV4 SocketAddrV6);
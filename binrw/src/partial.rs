@@ -0,0 +1,145 @@
+use crate::{
+    io::{Read, Seek, SeekFrom, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, VecArgs,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A wrapper that keeps the part of a value which was read before an error.
+///
+/// Reading a `Partial<Vec<T>>` reads up to `count` elements like a [`Vec`],
+/// but stops at the first element which fails to parse instead of failing.
+/// The elements before it are kept in `value`, the error is kept in `error`,
+/// and the reader is left at the start of the element which failed, so the
+/// readable prefix of a corrupt table can be salvaged.
+///
+/// Reading a `Partial` never fails because of its elements. It only fails if
+/// the reader cannot seek back to the start of the element which failed.
+///
+/// Writing a `Partial` writes the `value` only.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, Partial, io::Cursor};
+///
+/// #[derive(BinRead, Debug, PartialEq)]
+/// #[br(big)]
+/// struct Entry {
+///     #[br(assert(id != 0))]
+///     id: u8,
+///     len: u8,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Table {
+///     count: u8,
+///     #[br(count = count)]
+///     entries: Partial<Vec<Entry>>,
+/// }
+///
+/// let table = Table::read(&mut Cursor::new(b"\x03\x01\x10\x02\x20\0\x30")).unwrap();
+/// assert_eq!(table.entries.value, [Entry { id: 1, len: 0x10 }, Entry { id: 2, len: 0x20 }]);
+/// assert_eq!(table.entries.error.unwrap().position(), Some(5));
+/// ```
+pub struct Partial<T> {
+    /// The part of the value which was read.
+    pub value: T,
+
+    /// The error which stopped reading, or `None` if the whole value was read.
+    pub error: Option<Error>,
+}
+
+impl<T> Partial<T> {
+    /// Returns `true` if the whole value was read.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Converts this `Partial` into a [`BinResult`], discarding the value if
+    /// reading stopped early.
+    ///
+    /// # Errors
+    ///
+    /// If reading stopped early, the error which stopped it is returned.
+    pub fn into_result(self) -> BinResult<T> {
+        match self.error {
+            None => Ok(self.value),
+            Some(error) => Err(error),
+        }
+    }
+}
+
+impl<B> BinRead for Partial<Vec<B>>
+where
+    B: BinRead + 'static,
+    for<'a> B::Args<'a>: Clone,
+{
+    type Args<'a> = VecArgs<B::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // Memory is reserved one element at a time, since the count of a
+        // corrupt table is as likely to be wrong as its elements
+        let mut value = Vec::new();
+        for _ in 0..args.count {
+            let pos = reader.stream_position()?;
+            let item = crate::memory::reserve(core::mem::size_of::<B>())
+                .and_then(|()| B::read_options(reader, endian, args.inner.clone()));
+            match item {
+                Ok(item) => value.push(item),
+                Err(error) => {
+                    reader.seek(SeekFrom::Start(pos))?;
+                    return Ok(Self {
+                        value,
+                        error: Some(error),
+                    });
+                }
+            }
+        }
+
+        Ok(Self { value, error: None })
+    }
+}
+
+impl<T: BinWrite> BinWrite for Partial<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.value.write_options(writer, endian, args)
+    }
+}
+
+impl<T> core::ops::Deref for Partial<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> core::ops::DerefMut for Partial<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Partial<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Partial")
+            .field("value", &self.value)
+            .field("error", &self.error)
+            .finish()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    io::{Read, Seek},
-    BinRead, BinResult, Endian,
+    io::{self, Read, Seek},
+    BinRead, BinResult, Endian, Error, NamedArgs,
 };
 use core::fmt;
 
@@ -80,3 +80,115 @@ impl<U, T: PartialEq<U>> PartialEq<U> for PosValue<T> {
         self.val == *other
     }
 }
+
+/// A wrapper that stores a value’s position relative to an anchor position
+/// alongside the value.
+///
+/// This is like [`PosValue`], except that the position is relative to the
+/// `anchor` [argument](crate::BinRead::Args) instead of the start of the
+/// stream. A zero-sized [`PosValue`] can be used to record an anchor, such as
+/// the start of the enclosing struct.
+///
+/// Reading fails with an [`Error::Io`] if the value starts before the anchor.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{binread, BinReaderExt, PosValue, RelPos, io::Cursor};
+///
+/// #[binread]
+/// struct Chunk {
+///     #[br(temp)]
+///     start: PosValue<()>,
+///     kind: u16,
+///     #[br(args { anchor: start.pos })]
+///     body: RelPos<u8>,
+/// }
+///
+/// let mut data = Cursor::new(b"\xFF\xFF\0\x01\x02");
+/// data.set_position(2);
+/// let val = data.read_be::<Chunk>().unwrap();
+/// assert_eq!(val.body.pos, 2);
+/// assert_eq!(*val.body, 2);
+/// ```
+pub struct RelPos<T> {
+    /// The read value.
+    pub val: T,
+
+    /// The byte position of the start of the value, relative to the anchor.
+    pub pos: u64,
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of
+/// [`RelPos`].
+#[derive(Clone, Default, NamedArgs)]
+pub struct RelPosArgs<Inner> {
+    /// The absolute byte position which the position of the value is relative
+    /// to.
+    #[named_args(default = 0)]
+    pub anchor: u64,
+
+    /// The [arguments](crate::BinRead::Args) for the inner type.
+    #[named_args(try_optional)]
+    pub inner: Inner,
+}
+
+impl<T: BinRead> BinRead for RelPos<T> {
+    type Args<'a> = RelPosArgs<T::Args<'a>>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader
+            .stream_position()?
+            .checked_sub(args.anchor)
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "value starts before its anchor",
+                ))
+            })?;
+
+        Ok(RelPos {
+            pos,
+            val: T::read_options(reader, endian, args.inner)?,
+        })
+    }
+}
+
+impl<T> core::ops::Deref for RelPos<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.val
+    }
+}
+
+impl<T> core::ops::DerefMut for RelPos<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.val
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RelPos<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.val.fmt(f)
+    }
+}
+
+impl<T: Clone> Clone for RelPos<T> {
+    fn clone(&self) -> Self {
+        Self {
+            val: self.val.clone(),
+            pos: self.pos,
+        }
+    }
+}
+
+impl<U, T: PartialEq<U>> PartialEq<U> for RelPos<T> {
+    fn eq(&self, other: &U) -> bool {
+        self.val == *other
+    }
+}
@@ -1,14 +1,25 @@
 use crate::{
+    dump::Dump,
     error::{Backtrace, BacktraceFrame, CustomError},
     io::{Read, Seek, SeekFrom, Write},
+    wire_eq::FieldDiff,
     BinRead, BinResult, BinWrite, Endian, Error,
 };
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, string::String};
+use alloc::boxed::Box;
+use core::{
+    borrow::Borrow,
+    ops::{BitAnd, Range},
+};
 
 pub use crate::named_args::{
     builder_helper, passthrough_helper, Needed, Optional, Satisfied, SatisfiedOrOptional,
 };
+pub use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+pub use std::fs::File;
+#[cfg(feature = "tracing")]
+pub use tracing;
 
 // This is some nonsense to improve the diagnostic output for types that require
 // arguments so that the emitted output is clearer about this fact. Because this
@@ -51,7 +62,7 @@ where
     if test {
         Ok(())
     } else {
-        Err(match error_fn {
+        let error = match error_fn {
             AssertErrorFn::Message(error_fn) => Error::AssertFail {
                 pos,
                 message: error_fn().into(),
@@ -60,10 +71,367 @@ where
                 pos,
                 err: Box::new(error_fn()),
             },
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(pos, %error, "assertion failed");
+        Err(error)
+    }
+}
+
+pub fn assert_offset<S, T>(stream: &mut S, expected: T, what: &str) -> BinResult<()>
+where
+    S: Seek,
+    T: Copy + core::fmt::Display,
+    u64: TryFrom<T>,
+{
+    let pos = stream.stream_position()?;
+    let message = match u64::try_from(expected) {
+        Ok(expected) if pos == expected => return Ok(()),
+        Ok(expected) => alloc::format!("expected {what} at {expected:#x}, but it is at {pos:#x}"),
+        Err(_) => alloc::format!("expected {what} at {expected}, which is not a stream position"),
+    };
+    Err(Error::AssertFail { pos, message })
+}
+
+pub fn assert_align<S, T>(stream: &mut S, align: T, what: &str) -> BinResult<()>
+where
+    S: Seek,
+    T: Copy + core::fmt::Display,
+    u64: TryFrom<T>,
+{
+    let pos = stream.stream_position()?;
+    let message = match u64::try_from(align).ok().filter(|align| *align != 0) {
+        Some(align) if pos % align == 0 => return Ok(()),
+        Some(align) => {
+            alloc::format!("expected {what} at a multiple of {align:#x}, but it is at {pos:#x}")
+        }
+        None => {
+            alloc::format!("expected {what} at a multiple of {align}, which is not an alignment")
+        }
+    };
+    Err(Error::AssertFail { pos, message })
+}
+
+// A buffered reader is used since reading from a file directly is slow, and
+// the file is then moved back to the end of the value so it is left in the
+// same state as if it had been read directly.
+#[cfg(feature = "std")]
+pub fn read_file<T>(file: &mut File) -> BinResult<T>
+where
+    T: BinRead + crate::meta::ReadEndian,
+    for<'a> T::Args<'a>: Required,
+{
+    let mut reader = crate::io::BufReader::new(file);
+    let value = T::read(&mut reader);
+    let pos = reader.stream_position()?;
+    reader.into_inner().seek(SeekFrom::Start(pos))?;
+    value
+}
+
+// A custom error of the target type is unwrapped instead of converted so that
+// errors raised by directives like `assert` come back as the original value.
+#[must_use]
+pub fn into_error<E: CustomError + From<Error> + 'static>(error: Error) -> E {
+    if error.custom_err::<E>().is_none() {
+        return E::from(error);
+    }
+
+    let root_cause = match error {
+        Error::Backtrace(backtrace) => *backtrace.error,
+        error => error,
+    };
+    match root_cause {
+        Error::Custom { err, .. } => match err.downcast::<E>() {
+            Ok(err) => *err,
+            Err(_) => unreachable!("custom error type was already checked"),
+        },
+        _ => unreachable!("root cause was already checked"),
+    }
+}
+
+// The flags value is taken by `Borrow` so that the same `present_if` expression
+// works both when reading, where fields are owned values, and when writing,
+// where fields are references.
+pub fn flag_present<F, T>(flags: F, mask: T) -> bool
+where
+    F: Borrow<T>,
+    T: Copy + BitAnd<Output = T> + PartialEq,
+{
+    (*flags.borrow() & mask) == mask
+}
+
+// Reads the bytes of a `preserve_unknown` object which follow its known
+// fields, up to the end of the object if it has a size and otherwise up to the
+// end of the stream.
+pub fn read_unknown<R, S>(
+    reader: &mut R,
+    endian: Endian,
+    (start, size): (u64, Option<S>),
+) -> BinResult<Vec<u8>>
+where
+    R: Read + Seek,
+    S: TryInto<u64>,
+{
+    let Some(size) = size else {
+        return crate::helpers::read_to_end(reader, endian, ());
+    };
+
+    let pos = reader.stream_position()?;
+    let len = size
+        .try_into()
+        .ok()
+        .and_then(|size| start.checked_add(size))
+        .and_then(|end| end.checked_sub(pos))
+        .and_then(|len| usize::try_from(len).ok())
+        .ok_or_else(|| Error::AssertFail {
+            pos,
+            message: "the known fields end after the end of the object".into(),
+        })?;
+
+    crate::memory::reserve(len)?;
+    let mut data = Vec::new();
+    if reader.take(len as u64).read_to_end(&mut data)? == len {
+        Ok(data)
+    } else {
+        Err(Error::Io(crate::io::Error::new(
+            crate::io::ErrorKind::UnexpectedEof,
+            "unexpected end of file while reading unknown data",
+        )))
+    }
+}
+
+pub fn verify_checksum<R, F, C, S>(
+    reader: &mut R,
+    start: u64,
+    end: u64,
+    checksum: F,
+    stored: &S,
+) -> BinResult<()>
+where
+    R: Read + Seek,
+    F: FnOnce(&[u8]) -> C,
+    C: PartialEq<S> + core::fmt::Debug,
+    S: core::fmt::Debug,
+{
+    let pos = reader.stream_position()?;
+    let len = usize::try_from(end.saturating_sub(start)).map_err(|_| Error::AssertFail {
+        pos: start,
+        message: "checksum range does not fit in memory".into(),
+    })?;
+    crate::memory::reserve(len)?;
+    let mut data = alloc::vec![0; len];
+    reader.seek(SeekFrom::Start(start))?;
+    reader.read_exact(&mut data)?;
+    reader.seek(SeekFrom::Start(pos))?;
+
+    let calculated = checksum(&data);
+    if calculated == *stored {
+        Ok(())
+    } else {
+        Err(Error::AssertFail {
+            pos: start,
+            message: alloc::format!(
+                "checksum mismatch over {start:#x}..{end:#x}: stored {stored:#x?}, calculated {calculated:#x?}"
+            ),
         })
     }
 }
 
+// Writes are recorded as they pass through so that the bytes of a checksum
+// range can be rebuilt afterwards, since the output stream cannot be read.
+pub struct ChecksumWriter<W> {
+    inner: W,
+    pos: Option<u64>,
+    journal: Vec<(u64, Vec<u8>)>,
+}
+
+impl<W> ChecksumWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pos: None,
+            journal: Vec::new(),
+        }
+    }
+
+    // Bytes in the range which were seeked over instead of written are zero.
+    fn bytes(&self, start: u64, end: u64) -> BinResult<Vec<u8>> {
+        let len = usize::try_from(end.saturating_sub(start)).map_err(|_| Error::AssertFail {
+            pos: start,
+            message: "checksum range does not fit in memory".into(),
+        })?;
+        crate::memory::reserve(len)?;
+        let mut data = alloc::vec![0; len];
+        for (pos, written) in &self.journal {
+            let from = (*pos).max(start);
+            let to = (pos + written.len() as u64).min(end);
+            if from < to {
+                // Lint: Both ranges are within buffers which are already in
+                // memory.
+                #[allow(clippy::cast_possible_truncation)]
+                data[(from - start) as usize..(to - start) as usize]
+                    .copy_from_slice(&written[(from - pos) as usize..(to - pos) as usize]);
+            }
+        }
+        Ok(data)
+    }
+}
+
+impl<W: Write + Seek> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
+        let pos = match self.pos {
+            Some(pos) => pos,
+            None => self.inner.stream_position()?,
+        };
+        let n = self.inner.write(buf)?;
+        match self.journal.last_mut() {
+            Some((last, data)) if *last + data.len() as u64 == pos => {
+                data.extend_from_slice(&buf[..n]);
+            }
+            _ => self.journal.push((pos, buf[..n].to_vec())),
+        }
+        self.pos = Some(pos + n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> crate::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for ChecksumWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> crate::io::Result<u64> {
+        let pos = self.inner.seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+
+    fn stream_position(&mut self) -> crate::io::Result<u64> {
+        match self.pos {
+            Some(pos) => Ok(pos),
+            None => self.inner.stream_position(),
+        }
+    }
+}
+
+// The checksum field is written first with its own value as a placeholder,
+// which is overwritten here once the whole range has been written.
+pub fn write_checksum<W, F, C>(
+    writer: &mut ChecksumWriter<W>,
+    start: u64,
+    end: u64,
+    checksum: F,
+    placeholder: (u64, u64),
+    endian: Endian,
+) -> BinResult<()>
+where
+    W: Write + Seek,
+    F: FnOnce(&[u8]) -> C,
+    C: for<'a> BinWrite<Args<'a> = ()>,
+{
+    let calculated = checksum(&writer.bytes(start, end)?);
+    let (field_start, field_end) = placeholder;
+    let pos = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(field_start))?;
+    calculated.write_options(writer, endian, ())?;
+    let written_end = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(pos))?;
+
+    if written_end == field_end {
+        Ok(())
+    } else {
+        Err(Error::AssertFail {
+            pos: field_start,
+            message: alloc::format!(
+                "checksum is {} bytes but its placeholder is {} bytes",
+                written_end.saturating_sub(field_start),
+                field_end.saturating_sub(field_start)
+            ),
+        })
+    }
+}
+
+// The position of each field is recorded while reading so that the bytes of
+// the same field in two streams can be compared afterwards, or so that each
+// field can be annotated with its position in a dump.
+#[derive(Default)]
+pub struct WireSpans(Vec<(&'static str, u64, u64)>);
+
+impl WireSpans {
+    pub fn push(&mut self, field: &'static str, start: u64, end: u64) {
+        self.0.push((field, start, end));
+    }
+}
+
+pub fn dump_with_spans<T: Dump>(value: &T, spans: &WireSpans) -> String {
+    crate::dump::render(value, Some(&spans.0))
+}
+
+pub fn wire_diff<A, B>(
+    a: &mut A,
+    a_spans: &WireSpans,
+    b: &mut B,
+    b_spans: &WireSpans,
+) -> BinResult<Option<FieldDiff>>
+where
+    A: Read + Seek,
+    B: Read + Seek,
+{
+    let (a_pos, b_pos) = (a.stream_position()?, b.stream_position()?);
+
+    let mut diff = None;
+    for (&(field, a_start, a_end), &(_, b_start, b_end)) in a_spans.0.iter().zip(&b_spans.0) {
+        if !same_bytes(a, a_start..a_end, b, b_start..b_end)? {
+            diff = Some(FieldDiff {
+                field,
+                a: a_start..a_end,
+                b: b_start..b_end,
+            });
+            break;
+        }
+    }
+
+    a.seek(SeekFrom::Start(a_pos))?;
+    b.seek(SeekFrom::Start(b_pos))?;
+    Ok(diff)
+}
+
+fn same_bytes<A, B>(
+    a: &mut A,
+    a_range: Range<u64>,
+    b: &mut B,
+    b_range: Range<u64>,
+) -> BinResult<bool>
+where
+    A: Read + Seek,
+    B: Read + Seek,
+{
+    const BUF_SIZE: usize = 0x100;
+
+    let len = a_range.end.saturating_sub(a_range.start);
+    if len != b_range.end.saturating_sub(b_range.start) {
+        return Ok(false);
+    }
+
+    a.seek(SeekFrom::Start(a_range.start))?;
+    b.seek(SeekFrom::Start(b_range.start))?;
+    let (mut a_buf, mut b_buf) = ([0; BUF_SIZE], [0; BUF_SIZE]);
+    let mut remaining = len;
+    while remaining != 0 {
+        // Lint: The value is at most `BUF_SIZE`.
+        #[allow(clippy::cast_possible_truncation)]
+        let n = remaining.min(BUF_SIZE as u64) as usize;
+        a.read_exact(&mut a_buf[..n])?;
+        b.read_exact(&mut b_buf[..n])?;
+        if a_buf[..n] != b_buf[..n] {
+            return Ok(false);
+        }
+        remaining -= n as u64;
+    }
+
+    Ok(true)
+}
+
 // This validates the map function return value by trying to coerce it into
 // a function with the expected return type. If this is not done, the
 // compiler will emit the diagnostic on the `#[derive]`d attribute instead of
@@ -77,6 +445,17 @@ where
     f
 }
 
+/// Returns true if there are no more bytes to read from the reader, without
+/// moving its position.
+pub fn at_eof<R: Read + Seek>(reader: &mut R) -> BinResult<bool> {
+    if reader.read(&mut [0])? == 0 {
+        Ok(true)
+    } else {
+        reader.seek(SeekFrom::Current(-1))?;
+        Ok(false)
+    }
+}
+
 pub fn magic<R, B>(reader: &mut R, expected: B, endian: Endian) -> BinResult<()>
 where
     B: for<'a> BinRead<Args<'a> = ()>
@@ -101,6 +480,100 @@ where
     }
 }
 
+pub fn magic_masked<R, B>(reader: &mut R, expected: B, mask: B, endian: Endian) -> BinResult<()>
+where
+    B: for<'a> BinRead<Args<'a> = ()> + MagicMask + core::fmt::Debug + Sync + Send + Copy + 'static,
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let val = B::read_options(reader, endian, ())?;
+    if val.masked_eq(expected, mask) {
+        Ok(())
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: Box::new(val) as _,
+        })
+    }
+}
+
+pub fn magic_any<R, B>(reader: &mut R, expected: &[B], endian: Endian) -> BinResult<B>
+where
+    B: for<'a> BinRead<Args<'a> = ()>
+        + core::fmt::Debug
+        + PartialEq
+        + Sync
+        + Send
+        + Clone
+        + Copy
+        + 'static,
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let val = B::read_options(reader, endian, ())?;
+    if expected.contains(&val) {
+        Ok(val)
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: Box::new(val) as _,
+        })
+    }
+}
+
+pub fn magic_any_masked<R, B>(
+    reader: &mut R,
+    expected: &[B],
+    mask: B,
+    endian: Endian,
+) -> BinResult<B>
+where
+    B: for<'a> BinRead<Args<'a> = ()> + MagicMask + core::fmt::Debug + Sync + Send + Copy + 'static,
+    R: Read + Seek,
+{
+    let pos = reader.stream_position()?;
+    let val = B::read_options(reader, endian, ())?;
+    if expected
+        .iter()
+        .any(|expected| val.masked_eq(*expected, mask))
+    {
+        Ok(val)
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            found: Box::new(val) as _,
+        })
+    }
+}
+
+/// A magic type which can be compared with some of its bits ignored.
+pub trait MagicMask: Copy {
+    /// Returns true if the bits of `self` which are set in `mask` are equal to
+    /// the same bits of `expected`.
+    fn masked_eq(self, expected: Self, mask: Self) -> bool;
+}
+
+macro_rules! magic_mask_impl {
+    ($($ty:ty),+) => {
+        $(impl MagicMask for $ty {
+            fn masked_eq(self, expected: Self, mask: Self) -> bool {
+                self & mask == expected & mask
+            }
+        })+
+    };
+}
+
+magic_mask_impl!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl<const N: usize> MagicMask for [u8; N] {
+    fn masked_eq(self, expected: Self, mask: Self) -> bool {
+        self.iter()
+            .zip(expected)
+            .zip(mask)
+            .all(|((a, b), mask)| a & mask == b & mask)
+    }
+}
+
 pub fn parse_fn_type_hint<Ret, ParseFn, R, Args>(f: ParseFn) -> ParseFn
 where
     R: Read + Seek,
@@ -117,7 +590,7 @@ where
     a
 }
 
-pub fn write_function_args_type_hint<T, W, Args, F>(_: F, a: Args) -> Args
+pub fn write_function_args_type_hint<T, W, Args, F>(_: &F, a: Args) -> Args
 where
     W: Write + Seek,
     F: FnOnce(&T, &mut W, Endian, Args) -> BinResult<()>,
@@ -216,6 +689,110 @@ where
     args
 }
 
+// An `args(...)` list with `N` arguments is passed as a tuple which is
+// converted to the arguments type of the field through this trait. The list
+// type is only known once the arguments type is, so unlike `Into`, the values
+// in the list can be coerced (e.g. a closure reference to `&dyn Fn`). Types
+// generated for an `import(...)` list with default values implement it for
+// each number of arguments they accept.
+pub trait ArgsList<const N: usize>: Sized {
+    type List;
+
+    fn from_list(list: Self::List) -> Self;
+}
+
+macro_rules! args_list_impl {
+    ($($types:ident),*) => {
+        #[allow(non_camel_case_types)]
+        impl<$($types),*> ArgsList<{ 0 $(+ args_list_impl!(@one $types))* }> for ($($types,)*) {
+            type List = Self;
+
+            fn from_list(list: Self) -> Self {
+                list
+            }
+        }
+    };
+
+    (@one $type:ident) => { 1 };
+}
+
+macro_rules! args_list_impls {
+    ($type1:ident $(, $types:ident)*) => {
+        args_list_impl!($type1 $(, $types)*);
+        args_list_impls!($($types),*);
+    };
+
+    () => {
+        args_list_impl!();
+    };
+}
+
+args_list_impls!(
+    a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11, a12, a13, a14, a15, a16, a17, a18, a19, a20, a21,
+    a22, a23, a24, a25, a26, a27, a28, a29, a30, a31, a32
+);
+
+pub fn parse_function_args_list_type_hint<R, Res, Args, F, const N: usize>(
+    _: F,
+    list: <Args as ArgsList<N>>::List,
+) -> Args
+where
+    R: Read + Seek,
+    F: FnOnce(&mut R, Endian, Args) -> BinResult<Res>,
+    Args: ArgsList<N>,
+{
+    Args::from_list(list)
+}
+
+pub fn write_function_args_list_type_hint<T, W, Args, F, const N: usize>(
+    _: &F,
+    list: <Args as ArgsList<N>>::List,
+) -> Args
+where
+    W: Write + Seek,
+    F: FnOnce(&T, &mut W, Endian, Args) -> BinResult<()>,
+    Args: ArgsList<N>,
+{
+    Args::from_list(list)
+}
+
+pub fn map_args_list_type_hint<Input, Output, MapFn, Args, const N: usize>(
+    _: &MapFn,
+    list: <Args as ArgsList<N>>::List,
+) -> Args
+where
+    MapFn: FnOnce(Input) -> Output,
+    Input: for<'a> BinRead<Args<'a> = Args>,
+    Args: ArgsList<N>,
+{
+    Args::from_list(list)
+}
+
+pub fn write_map_args_list_type_hint<Input, Output, MapFn, Args, const N: usize>(
+    _: &MapFn,
+    list: <Args as ArgsList<N>>::List,
+) -> Args
+where
+    MapFn: FnOnce(Input) -> Output,
+    Output: for<'a> BinWrite<Args<'a> = Args>,
+    Args: ArgsList<N>,
+{
+    Args::from_list(list)
+}
+
+pub fn write_try_map_args_list_type_hint<Input, Output, Error, MapFn, Args, const N: usize>(
+    _: &MapFn,
+    list: <Args as ArgsList<N>>::List,
+) -> Args
+where
+    Error: CustomError,
+    MapFn: FnOnce(Input) -> Result<Output, Error>,
+    Output: for<'a> BinWrite<Args<'a> = Args>,
+    Args: ArgsList<N>,
+{
+    Args::from_list(list)
+}
+
 pub fn write_map_fn_input_type_hint<Input, Output, MapFn>(func: MapFn) -> MapFn
 where
     MapFn: FnOnce(Input) -> Output,
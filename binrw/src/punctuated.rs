@@ -1,6 +1,9 @@
 //! Type definitions for wrappers which parse interleaved data.
 
-use crate::{BinRead, BinResult, VecArgs};
+use crate::{
+    io::{Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, VecArgs,
+};
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 use core::fmt;
@@ -11,6 +14,10 @@ use core::fmt;
 /// To use this parser, you must specify the parsing strategy by selecting
 /// either [`separated()`] or [`separated_trailing()`] using [`parse_with`].
 ///
+/// When writing, each value is followed by the separator with the same index,
+/// so a `Punctuated` which was read with either strategy is written back
+/// unchanged.
+///
 /// [`separated()`]: Self::separated
 /// [`separated_trailing()`]: Self::separated_trailing
 /// [`parse_with`]: crate::docs::attribute#custom-parserswriters
@@ -36,7 +43,7 @@ use core::fmt;
 /// # assert_eq!(*y.x, vec![3, 2, 1]);
 /// # assert_eq!(y.x.separators, vec![0, 1]);
 /// ```
-pub struct Punctuated<T: BinRead, P: BinRead> {
+pub struct Punctuated<T, P> {
     /// The data values.
     data: Vec<T>,
 
@@ -44,6 +51,31 @@ pub struct Punctuated<T: BinRead, P: BinRead> {
     pub separators: Vec<P>,
 }
 
+impl<T, P> Punctuated<T, P> {
+    /// Creates a new `Punctuated` from data values and separator values.
+    ///
+    /// To be written, there must be either one fewer separator than there are
+    /// values, or the same number of separators and values for a trailing
+    /// separator.
+    #[must_use]
+    pub fn new(data: Vec<T>, separators: Vec<P>) -> Self {
+        Self { data, separators }
+    }
+
+    /// Consumes this object, returning the data values while dropping the
+    /// separator values.
+    ///
+    /// If you never use the separator values, consider using the [`pad_after`]
+    /// directive to skip over data while parsing instead of reading it into
+    /// memory and then discarding it.
+    ///
+    /// [`pad_after`]: crate::docs::attribute#padding-and-alignment
+    #[must_use]
+    pub fn into_values(self) -> Vec<T> {
+        self.data
+    }
+}
+
 impl<T, P> Punctuated<T, P>
 where
     T: BinRead,
@@ -117,28 +149,50 @@ where
 
         Ok(Self { data, separators })
     }
+}
 
-    /// Consumes this object, returning the data values while dropping the
-    /// separator values.
-    ///
-    /// If you never use the separator values, consider using the [`pad_after`]
-    /// directive to skip over data while parsing instead of reading it into
-    /// memory and then discarding it.
-    ///
-    /// [`pad_after`]: crate::docs::attribute#padding-and-alignment
-    #[must_use]
-    pub fn into_values(self) -> Vec<T> {
-        self.data
+impl<T, P> BinWrite for Punctuated<T, P>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Clone,
+    P: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let (data, separators) = (self.data.len(), self.separators.len());
+        if separators != data && separators + 1 != data {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!(
+                    "cannot interleave {data} values with {separators} separators"
+                ),
+            });
+        }
+
+        for (i, value) in self.data.iter().enumerate() {
+            value.write_options(writer, endian, args.clone())?;
+            if let Some(separator) = self.separators.get(i) {
+                separator.write_options(writer, endian, ())?;
+            }
+        }
+
+        Ok(())
     }
 }
 
-impl<T: BinRead + fmt::Debug, P: BinRead> fmt::Debug for Punctuated<T, P> {
+impl<T: fmt::Debug, P> fmt::Debug for Punctuated<T, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.data.fmt(f)
     }
 }
 
-impl<T: BinRead, P: BinRead> core::ops::Deref for Punctuated<T, P> {
+impl<T, P> core::ops::Deref for Punctuated<T, P> {
     type Target = Vec<T>;
 
     fn deref(&self) -> &Self::Target {
@@ -146,7 +200,7 @@ impl<T: BinRead, P: BinRead> core::ops::Deref for Punctuated<T, P> {
     }
 }
 
-impl<T: BinRead, P: BinRead> core::ops::DerefMut for Punctuated<T, P> {
+impl<T, P> core::ops::DerefMut for Punctuated<T, P> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.data
     }
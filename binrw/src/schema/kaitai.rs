@@ -0,0 +1,398 @@
+use super::{as_enum, switch_magic, Body, Field, Layout, Magic, Schema, TypeDef, Types, Variant};
+use crate::Endian;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// Describes the layout of `T` as a [Kaitai Struct](https://kaitai.io) YAML
+/// schema with the given top-level `id`.
+///
+/// Every named type used by `T` is defined in the `types` section of the
+/// schema, and unit enums with a `repr` or numeric magics are defined in the
+/// `enums` section. Data enums whose variants all have a magic of the same
+/// type are read with a `switch-on` the magic. Other data enums are described
+/// by a `doc` only, since Kaitai Struct cannot try each variant in turn.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, schema};
+///
+/// #[derive(BinRead)]
+/// #[br(big, magic = b"PT", schema)]
+/// struct Points {
+///     count: u16,
+///     #[br(count = count)]
+///     points: Vec<Point>,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(schema)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(schema::to_kaitai::<Points>("points"), "\
+/// meta:
+///   id: points
+///   endian: be
+/// seq:
+///   - id: magic
+///     contents: [0x50, 0x54]
+///   - id: count
+///     type: u2
+///   - id: points
+///     type: point
+///     repeat: expr
+///     repeat-expr: 'count'
+/// types:
+///   point:
+///     seq:
+///       - id: x
+///         type: s4
+///       - id: y
+///         type: s4
+/// ");
+/// ```
+#[must_use]
+pub fn to_kaitai<T: Schema + ?Sized>(id: &str) -> String {
+    let mut types = Types::new();
+    let root = types.layout_of::<T>();
+    let root_name = match &root {
+        Layout::Named(name) => Some(name.as_str()),
+        _ => None,
+    };
+
+    let mut out = Writer {
+        types: &types,
+        out: String::new(),
+    };
+
+    out.line(0, format_args!("meta:"));
+    out.line(1, format_args!("id: {}", kaitai_id(id)));
+    let root_def = root_name.and_then(|name| types.get(name));
+    match root_def {
+        Some(def) if as_enum(def).is_none() => {
+            if let Some(endian) = def.endian {
+                out.line(1, format_args!("endian: {}", endian_suffix(endian)));
+            }
+            out.type_body(0, id, def);
+        }
+        _ => {
+            out.line(0, format_args!("seq:"));
+            out.field(1, &Field::new("value", root.clone()));
+        }
+    }
+
+    let mut defined_types = Vec::new();
+    let mut defined_enums = Vec::new();
+    for (name, def) in types.iter() {
+        if let Some(values) = as_enum(def) {
+            defined_enums.push((name, values));
+        } else if Some(name) != root_name {
+            defined_types.push((name, def));
+        }
+    }
+
+    let variant_types = types
+        .iter()
+        .filter_map(|(name, def)| match &def.body {
+            Body::Enum { variants, .. } if as_enum(def).is_none() => Some(
+                variants
+                    .iter()
+                    .filter(|variant| !variant.fields.is_empty())
+                    .map(move |variant| (name, variant)),
+            ),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    if !defined_types.is_empty() || !variant_types.is_empty() {
+        out.line(0, format_args!("types:"));
+        for (name, def) in defined_types {
+            out.line(1, format_args!("{}:", kaitai_id(name)));
+            if let Some(endian) = def.endian {
+                out.line(2, format_args!("meta:"));
+                out.line(3, format_args!("endian: {}", endian_suffix(endian)));
+            }
+            out.type_body(2, name, def);
+        }
+        for (name, variant) in variant_types {
+            let name = if Some(name) == root_name { id } else { name };
+            out.line(1, format_args!("{}:", variant_id(name, variant)));
+            out.line(2, format_args!("seq:"));
+            for field in &variant.fields {
+                out.field(3, field);
+            }
+        }
+    }
+
+    if !defined_enums.is_empty() {
+        out.line(0, format_args!("enums:"));
+        for (name, (_, values)) in defined_enums {
+            out.line(1, format_args!("{}:", kaitai_id(name)));
+            for (value, variant) in values {
+                out.line(2, format_args!("{value}: {}", kaitai_id(variant)));
+            }
+        }
+    }
+
+    out.out
+}
+
+struct Writer<'a> {
+    types: &'a Types,
+    out: String,
+}
+
+impl Writer<'_> {
+    fn line(&mut self, indent: usize, args: fmt::Arguments<'_>) {
+        // Writing to a `String` cannot fail
+        let _ = writeln!(self.out, "{:1$}{args}", "", indent * 2);
+    }
+
+    /// Writes the `seq` and `doc` of a type whose keys are at `indent`.
+    fn type_body(&mut self, indent: usize, name: &str, def: &TypeDef) {
+        let mut seq = Vec::new();
+        if let Some(magic) = &def.magic {
+            seq.push(Entry::Magic("magic", magic));
+        }
+
+        let mut doc = None;
+        match &def.body {
+            Body::Struct(fields) => seq.extend(fields.iter().map(Entry::Field)),
+            Body::Enum { variants, .. } => {
+                if let Some(magic) = switch_magic(variants) {
+                    seq.push(Entry::Switch(name, magic, variants));
+                } else {
+                    let names = variants
+                        .iter()
+                        .map(|variant| variant.name.as_str())
+                        .collect::<Vec<_>>();
+                    doc = Some(format!("One of: {}", names.join(", ")));
+                }
+            }
+        }
+
+        if let Some(doc) = doc {
+            self.line(indent, format_args!("doc: {}", quote(&doc)));
+        }
+        if !seq.is_empty() {
+            self.line(indent, format_args!("seq:"));
+            for entry in seq {
+                match entry {
+                    Entry::Magic(id, magic) => self.magic(indent + 1, id, magic),
+                    Entry::Field(field) => self.field(indent + 1, field),
+                    Entry::Switch(name, magic, variants) => {
+                        self.magic_tag(indent + 1, magic);
+                        self.switch(indent + 1, name, variants);
+                    }
+                }
+            }
+        }
+    }
+
+    fn magic(&mut self, indent: usize, id: &str, magic: &Magic) {
+        self.line(indent, format_args!("- id: {id}"));
+        match magic {
+            Magic::Bytes(bytes) => {
+                let bytes = bytes
+                    .iter()
+                    .map(|byte| format!("{byte:#04x}"))
+                    .collect::<Vec<_>>();
+                self.line(indent + 1, format_args!("contents: [{}]", bytes.join(", ")));
+            }
+            Magic::Value { layout, value } => {
+                self.layout(indent + 1, layout, None);
+                self.line(indent + 1, format_args!("valid: {value}"));
+            }
+        }
+    }
+
+    /// Writes the field which holds the magic of the selected variant of an
+    /// enum.
+    fn magic_tag(&mut self, indent: usize, magic: &Magic) {
+        self.line(indent, format_args!("- id: magic"));
+        match magic {
+            Magic::Bytes(bytes) => self.line(indent + 1, format_args!("size: {}", bytes.len())),
+            Magic::Value { layout, .. } => self.layout(indent + 1, layout, None),
+        }
+    }
+
+    fn switch(&mut self, indent: usize, name: &str, variants: &[Variant]) {
+        self.line(indent, format_args!("- id: body"));
+        self.line(indent + 1, format_args!("type:"));
+        self.line(indent + 2, format_args!("switch-on: magic"));
+        self.line(indent + 2, format_args!("cases:"));
+        for variant in variants.iter().filter(|variant| !variant.fields.is_empty()) {
+            let case = match &variant.magic {
+                Some(Magic::Bytes(bytes)) => {
+                    let bytes = bytes
+                        .iter()
+                        .map(|byte| format!("{byte:#04x}"))
+                        .collect::<Vec<_>>();
+                    quote(&format!("[{}]", bytes.join(", ")))
+                }
+                Some(Magic::Value { value, .. }) => value.clone(),
+                None => continue,
+            };
+            self.line(
+                indent + 3,
+                format_args!("{case}: {}", variant_id(name, variant)),
+            );
+        }
+    }
+
+    fn field(&mut self, indent: usize, field: &Field) {
+        if let Some(magic) = &field.magic {
+            self.magic(indent, &format!("{}_magic", field_id(&field.name)), magic);
+        }
+
+        if field.layout == Layout::Unit {
+            return;
+        }
+
+        self.line(indent, format_args!("- id: {}", field_id(&field.name)));
+        let indent = indent + 1;
+        if let Layout::List { inner, len } = &field.layout {
+            self.layout(indent, inner, field.endian);
+            let count = field
+                .count
+                .as_ref()
+                .map(|count| quote(count))
+                .or_else(|| len.map(|len| format!("{len}")));
+            if let Some(count) = count {
+                self.line(indent, format_args!("repeat: expr"));
+                self.line(indent, format_args!("repeat-expr: {count}"));
+            } else {
+                self.line(
+                    indent,
+                    format_args!(
+                        "doc: {}",
+                        quote("The number of elements is passed as an argument")
+                    ),
+                );
+            }
+        } else {
+            self.layout(indent, &field.layout, field.endian);
+        }
+
+        if let Some(condition) = &field.condition {
+            self.line(indent, format_args!("if: {}", quote(condition)));
+        }
+    }
+
+    fn layout(&mut self, indent: usize, layout: &Layout, endian: Option<Endian>) {
+        let suffix = endian.map_or("", endian_suffix);
+        match layout {
+            Layout::Unit => {}
+            Layout::Int { size: 1, signed } => {
+                self.line(indent, format_args!("type: {}1", int_prefix(*signed)));
+            }
+            Layout::Int { size, signed } => {
+                self.line(
+                    indent,
+                    format_args!("type: {}{size}{suffix}", int_prefix(*signed)),
+                );
+            }
+            Layout::Float { size } => self.line(indent, format_args!("type: f{size}{suffix}")),
+            Layout::Bits(bits) => self.line(indent, format_args!("type: b{bits}")),
+            Layout::NullString { wide } => {
+                self.line(indent, format_args!("type: strz"));
+                let encoding = match (wide, endian) {
+                    (false, _) => "ASCII",
+                    (true, Some(Endian::Big)) => "UTF-16BE",
+                    (true, _) => "UTF-16LE",
+                };
+                self.line(indent, format_args!("encoding: {encoding}"));
+            }
+            Layout::List { .. } => {
+                self.line(
+                    indent,
+                    format_args!("doc: {}", quote("A nested list, which cannot be described")),
+                );
+            }
+            Layout::Named(name) => match self.types.get(name) {
+                Some(def) => {
+                    if let Some((repr, _)) = as_enum(def) {
+                        self.layout(indent, &repr, endian.or(def.endian));
+                        self.line(indent, format_args!("enum: {}", kaitai_id(name)));
+                    } else {
+                        self.line(indent, format_args!("type: {}", kaitai_id(name)));
+                    }
+                }
+                None => self.line(indent, format_args!("type: {}", kaitai_id(name))),
+            },
+            Layout::Unknown(description) => {
+                self.line(indent, format_args!("doc: {}", quote(description)));
+            }
+        }
+    }
+}
+
+enum Entry<'a> {
+    Magic(&'a str, &'a Magic),
+    Field(&'a Field),
+    Switch(&'a str, &'a Magic, &'a [Variant]),
+}
+
+fn int_prefix(signed: bool) -> char {
+    if signed {
+        's'
+    } else {
+        'u'
+    }
+}
+
+fn endian_suffix(endian: Endian) -> &'static str {
+    match endian {
+        Endian::Big => "be",
+        Endian::Little => "le",
+    }
+}
+
+fn variant_id(name: &str, variant: &Variant) -> String {
+    format!("{}_{}", kaitai_id(name), kaitai_id(&variant.name))
+}
+
+/// Converts a field name into a Kaitai identifier, which must start with a
+/// letter, so tuple fields like `0` become `field_0`.
+fn field_id(name: &str) -> String {
+    let id = kaitai_id(name);
+    if id.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{id}")
+    } else {
+        id
+    }
+}
+
+/// Converts a Rust name into a lower snake case Kaitai identifier, so
+/// `Wrapper<u8>` becomes `wrapper_u8`.
+fn kaitai_id(name: &str) -> String {
+    let mut id = String::with_capacity(name.len());
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                id.push('_');
+            }
+            id.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !id.is_empty() && !id.ends_with('_') {
+                id.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    while id.ends_with('_') {
+        id.pop();
+    }
+    id
+}
+
+/// Quotes a string for YAML.
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,577 @@
+//! Types for describing the binary layout of types, and for exporting it to
+//! other tools with the [`schema`](crate::docs::attribute#schema) directive.
+//!
+//! A [`Schema`] describes where each field of a type is on the wire: its
+//! declared type, layout, byte order, magic, count, and condition.
+//! Descriptions are collected into [`Types`], which can be inspected at
+//! runtime, used to find the [size](Types::size_of) of fixed-size types,
+//! exported with [`to_kaitai`] or [`to_binary_template`], or used to read
+//! [`Value`](crate::Value)s with [`value::read`](crate::value::read).
+//!
+//! Expressions such as counts and conditions are recorded as the Rust source
+//! text of the directive which uses them, so exported schemas may need
+//! adjusting when an expression uses anything other than simple arithmetic on
+//! earlier fields. Fields which are read with a custom parser or `map` are
+//! described as [`Layout::Unknown`].
+
+mod kaitai;
+mod template;
+
+pub use kaitai::to_kaitai;
+pub use template::to_binary_template;
+
+use crate::{Endian, NullString, NullWideString};
+#[cfg(not(feature = "std"))]
+use alloc::format;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU16,
+        NonZeroU32, NonZeroU64, NonZeroU8,
+    },
+};
+
+/// A type whose binary layout can be described.
+///
+/// This trait can be derived for structs and enums with the
+/// [`schema`](crate::docs::attribute#schema) directive.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::schema::{Body, Field, Layout, Schema, TypeDef, Types};
+///
+/// struct Point(i32, i32);
+///
+/// impl Schema for Point {
+///     fn layout(types: &mut Types) -> Layout {
+///         types.define::<Self>(|types| TypeDef {
+///             endian: None,
+///             magic: None,
+///             body: Body::Struct(vec![
+///                 Field::new("x", types.layout_of::<i32>()),
+///                 Field::new("y", types.layout_of::<i32>()),
+///             ]),
+///         })
+///     }
+/// }
+///
+/// let mut types = Types::new();
+/// assert_eq!(types.layout_of::<Point>(), Layout::Named("Point".into()));
+/// assert!(matches!(types.get("Point").unwrap().body, Body::Struct(ref fields) if fields.len() == 2));
+/// ```
+pub trait Schema {
+    /// Describes the layout of this type, adding the definitions of any named
+    /// types it uses to `types`.
+    fn layout(types: &mut Types) -> Layout;
+}
+
+/// The layout of a value on the wire.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Layout {
+    /// Nothing is read or written.
+    Unit,
+
+    /// An integer.
+    Int {
+        /// The size of the integer in bytes.
+        size: usize,
+        /// Whether the integer is signed.
+        signed: bool,
+    },
+
+    /// An IEEE 754 floating point number.
+    Float {
+        /// The size of the number in bytes.
+        size: usize,
+    },
+
+    /// An integer packed into a number of bits.
+    Bits(u32),
+
+    /// A null-terminated string.
+    NullString {
+        /// Whether the string is made of 16-bit code units instead of bytes.
+        wide: bool,
+    },
+
+    /// A sequence of values with the same layout.
+    List {
+        /// The layout of each element.
+        inner: Box<Layout>,
+        /// The number of elements, if it is fixed.
+        len: Option<usize>,
+    },
+
+    /// A type defined in [`Types`].
+    Named(String),
+
+    /// A value whose layout cannot be described, such as one read with a
+    /// custom parser. This contains a description of the value.
+    Unknown(String),
+}
+
+/// A magic number.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Magic {
+    /// A byte string.
+    Bytes(Vec<u8>),
+
+    /// A numeric value.
+    Value {
+        /// The layout of the value.
+        layout: Layout,
+        /// The value, as written in the source.
+        value: String,
+    },
+}
+
+/// A named field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Field {
+    /// The name of the field, or its index for a tuple field.
+    pub name: String,
+
+    /// The layout of the field.
+    pub layout: Layout,
+
+    /// The type of the field as it is declared in Rust, if it is known.
+    pub ty: Option<String>,
+
+    /// The byte order of the field, if it overrides the byte order of its
+    /// parent.
+    pub endian: Option<Endian>,
+
+    /// The magic number before the field.
+    pub magic: Option<Magic>,
+
+    /// The expression which gives the number of elements of the field.
+    pub count: Option<String>,
+
+    /// The expression which decides whether the field is present.
+    pub condition: Option<String>,
+}
+
+impl Field {
+    /// Creates a field with the given name and layout.
+    pub fn new(name: impl Into<String>, layout: Layout) -> Self {
+        Self {
+            name: name.into(),
+            layout,
+            ty: None,
+            endian: None,
+            magic: None,
+            count: None,
+            condition: None,
+        }
+    }
+}
+
+/// A variant of an enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Variant {
+    /// The name of the variant.
+    pub name: String,
+
+    /// The magic number which selects the variant.
+    pub magic: Option<Magic>,
+
+    /// The discriminant of a unit variant in an enum with a `repr`.
+    pub value: Option<i128>,
+
+    /// The fields of the variant.
+    pub fields: Vec<Field>,
+}
+
+/// The contents of a named type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Body {
+    /// A struct with the given fields.
+    Struct(Vec<Field>),
+
+    /// An enum, which is read as the first of its variants to succeed.
+    Enum {
+        /// The layout of the discriminant of an enum with a `repr`.
+        repr: Option<Layout>,
+        /// The variants of the enum.
+        variants: Vec<Variant>,
+    },
+}
+
+/// The definition of a named type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeDef {
+    /// The byte order of the type, if it is fixed.
+    pub endian: Option<Endian>,
+
+    /// The magic number at the start of the type.
+    pub magic: Option<Magic>,
+
+    /// The contents of the type.
+    pub body: Body,
+}
+
+/// A collection of named type definitions.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Types {
+    defs: Vec<(String, TypeDef)>,
+}
+
+impl Types {
+    /// Creates an empty collection.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Describes the layout of `T`, adding the definitions of any named types
+    /// it uses.
+    pub fn layout_of<T: Schema + ?Sized>(&mut self) -> Layout {
+        T::layout(self)
+    }
+
+    /// Adds a definition for `T` and returns a layout which refers to it.
+    ///
+    /// The name of the definition is the name of `T` without module paths.
+    /// `define` is only called if `T` has not already been defined, so
+    /// recursive types can call `define` for themselves.
+    pub fn define<T: ?Sized>(&mut self, define: impl FnOnce(&mut Self) -> TypeDef) -> Layout {
+        let name = short_type_name(core::any::type_name::<T>());
+        if self.get(&name).is_none() {
+            // A placeholder is added first so that a recursive type finds
+            // itself instead of being defined again
+            let index = self.defs.len();
+            self.defs.push((
+                name.clone(),
+                TypeDef {
+                    endian: None,
+                    magic: None,
+                    body: Body::Struct(Vec::new()),
+                },
+            ));
+            self.defs[index].1 = define(self);
+        }
+        Layout::Named(name)
+    }
+
+    /// Adds a definition with the given name, replacing any existing
+    /// definition with that name, and returns a layout which refers to it.
+    ///
+    /// This is used to describe types at runtime, such as when reading a
+    /// format whose structure is not known in advance with
+    /// [`value::read`](crate::value::read).
+    pub fn insert(&mut self, name: impl Into<String>, def: TypeDef) -> Layout {
+        let name = name.into();
+        if let Some((_, existing)) = self.defs.iter_mut().find(|(def_name, _)| *def_name == name) {
+            *existing = def;
+        } else {
+            self.defs.push((name.clone(), def));
+        }
+        Layout::Named(name)
+    }
+
+    /// Returns the definition of the named type.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&TypeDef> {
+        self.defs
+            .iter()
+            .find(|(def_name, _)| def_name == name)
+            .map(|(_, def)| def)
+    }
+
+    /// Returns an iterator over the names and definitions of all types, in
+    /// the order they were first defined.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &TypeDef)> {
+        self.defs.iter().map(|(name, def)| (name.as_str(), def))
+    }
+
+    /// Returns the number of bytes which a value with the given layout always
+    /// takes, or `None` if its size is not fixed.
+    ///
+    /// A type only has a fixed size if every field has a fixed size and no
+    /// field has a count or condition. An enum only has a fixed size if every
+    /// variant has the same size, including its magic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{BinRead, schema::Types};
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(magic = b"PT", schema)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: [u16; 2],
+    /// }
+    ///
+    /// let mut types = Types::new();
+    /// let point = types.layout_of::<Point>();
+    /// assert_eq!(types.size_of(&point), Some(10));
+    /// let points = types.layout_of::<Vec<Point>>();
+    /// assert_eq!(types.size_of(&points), None);
+    /// ```
+    #[must_use]
+    pub fn size_of(&self, layout: &Layout) -> Option<u64> {
+        self.size(layout, 0)
+    }
+
+    fn size(&self, layout: &Layout, depth: usize) -> Option<u64> {
+        // A type which contains itself is never fixed-size, since it must
+        // stop containing itself at some point
+        if depth > MAX_SIZE_DEPTH {
+            return None;
+        }
+
+        match layout {
+            Layout::Unit => Some(0),
+            Layout::Int { size, .. } | Layout::Float { size } => u64::try_from(*size).ok(),
+            Layout::Bits(_) | Layout::NullString { .. } | Layout::Unknown(_) => None,
+            Layout::List { inner, len } => {
+                let len = u64::try_from((*len)?).ok()?;
+                self.size(inner, depth + 1)?.checked_mul(len)
+            }
+            Layout::Named(name) => {
+                let def = self.get(name)?;
+                let magic = self.magic_size(def.magic.as_ref(), depth)?;
+                let body = match &def.body {
+                    Body::Struct(fields) => self.fields_size(fields, depth)?,
+                    Body::Enum { repr, variants } => {
+                        let repr = match repr {
+                            Some(repr) => self.size(repr, depth + 1)?,
+                            None => 0,
+                        };
+                        let mut sizes = variants.iter().map(|variant| {
+                            self.magic_size(variant.magic.as_ref(), depth)?
+                                .checked_add(self.fields_size(&variant.fields, depth)?)
+                        });
+                        let first = sizes.next().unwrap_or(Some(0))?;
+                        if !sizes.all(|size| size == Some(first)) {
+                            return None;
+                        }
+                        repr.checked_add(first)?
+                    }
+                };
+                magic.checked_add(body)
+            }
+        }
+    }
+
+    fn fields_size(&self, fields: &[Field], depth: usize) -> Option<u64> {
+        fields.iter().try_fold(0_u64, |total, field| {
+            if field.count.is_some() || field.condition.is_some() {
+                return None;
+            }
+            let magic = self.magic_size(field.magic.as_ref(), depth)?;
+            let size = self.size(&field.layout, depth + 1)?;
+            total.checked_add(magic)?.checked_add(size)
+        })
+    }
+
+    fn magic_size(&self, magic: Option<&Magic>, depth: usize) -> Option<u64> {
+        match magic {
+            None => Some(0),
+            Some(Magic::Bytes(bytes)) => u64::try_from(bytes.len()).ok(),
+            Some(Magic::Value { layout, .. }) => self.size(layout, depth + 1),
+        }
+    }
+}
+
+/// The greatest depth of nested types which [`Types::size_of`] looks through.
+const MAX_SIZE_DEPTH: usize = 64;
+
+/// Returns the layout and values of a type which exporters describe as an
+/// enumeration, which is a unit enum with either a `repr` or numeric magics.
+fn as_enum(def: &TypeDef) -> Option<(Layout, Vec<(String, &str)>)> {
+    let Body::Enum { repr, variants } = &def.body else {
+        return None;
+    };
+
+    if def.magic.is_some() || variants.iter().any(|variant| !variant.fields.is_empty()) {
+        return None;
+    }
+
+    if let Some(repr) = repr {
+        let values = variants
+            .iter()
+            .map(|variant| Some((format!("{}", variant.value?), variant.name.as_str())))
+            .collect::<Option<Vec<_>>>()?;
+        return Some((repr.clone(), values));
+    }
+
+    let Some(Magic::Value { layout, .. }) = switch_magic(variants) else {
+        return None;
+    };
+    let values = variants
+        .iter()
+        .map(|variant| match &variant.magic {
+            Some(Magic::Value { value, .. }) => Some((value.clone(), variant.name.as_str())),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((layout.clone(), values))
+}
+
+/// Returns the magic of the first variant if every variant has a magic of the
+/// same type, so exporters can select the variant by switching on the magic.
+fn switch_magic(variants: &[Variant]) -> Option<&Magic> {
+    let first = variants.first()?.magic.as_ref()?;
+    variants
+        .iter()
+        .all(|variant| match (&variant.magic, first) {
+            (Some(Magic::Bytes(a)), Magic::Bytes(b)) => a.len() == b.len(),
+            (Some(Magic::Value { layout: a, .. }), Magic::Value { layout: b, .. }) => a == b,
+            _ => false,
+        })
+        .then_some(first)
+}
+
+/// Removes the module paths from a type name, so `a::B<c::D>` becomes `B<D>`.
+fn short_type_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    for c in name.chars() {
+        if c == ':' {
+            out.truncate(segment_start);
+        } else {
+            out.push(c);
+            if !(c.is_alphanumeric() || c == '_') {
+                segment_start = out.len();
+            }
+        }
+    }
+    out
+}
+
+macro_rules! int_schema {
+    ($signed:literal, $($ty:ty),+) => {
+        $(
+            impl Schema for $ty {
+                fn layout(_: &mut Types) -> Layout {
+                    Layout::Int {
+                        size: core::mem::size_of::<$ty>(),
+                        signed: $signed,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+int_schema!(false, u8, u16, u32, u64, u128);
+int_schema!(true, i8, i16, i32, i64, i128);
+int_schema!(
+    false,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128
+);
+int_schema!(
+    true,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128
+);
+
+impl Schema for f32 {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::Float { size: 4 }
+    }
+}
+
+impl Schema for f64 {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::Float { size: 8 }
+    }
+}
+
+impl Schema for () {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::Unit
+    }
+}
+
+impl<T: ?Sized> Schema for PhantomData<T> {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::Unit
+    }
+}
+
+impl<T: Schema, const N: usize> Schema for [T; N] {
+    fn layout(types: &mut Types) -> Layout {
+        Layout::List {
+            inner: Box::new(T::layout(types)),
+            len: Some(N),
+        }
+    }
+}
+
+impl<T: Schema> Schema for Vec<T> {
+    fn layout(types: &mut Types) -> Layout {
+        Layout::List {
+            inner: Box::new(T::layout(types)),
+            len: None,
+        }
+    }
+}
+
+impl<T: Schema + ?Sized> Schema for Box<T> {
+    fn layout(types: &mut Types) -> Layout {
+        T::layout(types)
+    }
+}
+
+impl<T: Schema> Schema for Option<T> {
+    fn layout(types: &mut Types) -> Layout {
+        T::layout(types)
+    }
+}
+
+macro_rules! schema_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<$type1: Schema, $($types: Schema),*> Schema for ($type1, $($types),*) {
+            fn layout(types: &mut Types) -> Layout {
+                types.define::<Self>(|types| {
+                    let layouts = [$type1::layout(types), $($types::layout(types)),*];
+                    TypeDef {
+                        endian: None,
+                        magic: None,
+                        body: Body::Struct(
+                            layouts
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, layout)| Field::new(format!("{index}"), layout))
+                                .collect(),
+                        ),
+                    }
+                })
+            }
+        }
+
+        schema_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+schema_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
+
+impl Schema for NullString {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::NullString { wide: false }
+    }
+}
+
+impl Schema for NullWideString {
+    fn layout(_: &mut Types) -> Layout {
+        Layout::NullString { wide: true }
+    }
+}
@@ -0,0 +1,365 @@
+use super::{as_enum, switch_magic, Body, Field, Layout, Magic, Schema, TypeDef, Types, Variant};
+use crate::Endian;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// Describes the layout of `T` as an
+/// [010 Editor](https://www.sweetscape.com/010editor/) binary template.
+///
+/// Every named type used by `T` is defined as a `struct`, or as an `enum` for
+/// unit enums with a `repr` or numeric magics, before the type which uses it.
+/// The template ends by declaring a `T` named `file`. A type with a fixed
+/// byte order is declared with that byte order selected, and the previous
+/// byte order is restored after it. Magics are checked with
+/// `Warning`, so a file with the wrong magic can still be inspected. Data
+/// enums whose variants all have a magic of the same type read the variant
+/// which matches the magic. Other data enums are described by a comment only,
+/// since a template cannot try each variant in turn.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, schema};
+///
+/// #[derive(BinRead)]
+/// #[br(big, magic = b"PT", schema)]
+/// struct Points {
+///     count: u16,
+///     #[br(count = count)]
+///     points: Vec<Point>,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(schema)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(schema::to_binary_template::<Points>(), "\
+/// typedef struct {
+///     int x;
+///     int y;
+/// } Point;
+///
+/// typedef struct {
+///     uchar magic[2] <format=hex>;
+///     if (magic[0] != 0x50 || magic[1] != 0x54) Warning(\"Bad magic\");
+///     ushort count;
+///     Point points[count];
+/// } Points;
+///
+/// BigEndian();
+/// Points file;
+/// ");
+/// ```
+#[must_use]
+pub fn to_binary_template<T: Schema + ?Sized>() -> String {
+    let mut types = Types::new();
+    let root = types.layout_of::<T>();
+
+    let mut out = Writer {
+        types: &types,
+        out: String::new(),
+    };
+
+    // Types are defined after the types which first use them, so they are
+    // declared in reverse to come before their uses
+    let defs = types.iter().collect::<Vec<_>>();
+    for (name, def) in defs.into_iter().rev() {
+        out.type_def(name, def);
+        out.line(0, format_args!(""));
+    }
+
+    let endian = match &root {
+        Layout::Named(name) => types.get(name).and_then(|def| def.endian),
+        _ => None,
+    };
+    if let Some(endian) = endian {
+        out.line(0, format_args!("{}();", set_endian(endian)));
+    }
+    out.field(0, &Field::new("file", root), endian);
+    out.out
+}
+
+struct Writer<'a> {
+    types: &'a Types,
+    out: String,
+}
+
+impl Writer<'_> {
+    fn line(&mut self, indent: usize, args: fmt::Arguments<'_>) {
+        // Writing to a `String` cannot fail
+        let _ = writeln!(self.out, "{:1$}{args}", "", indent * 4);
+    }
+
+    fn type_def(&mut self, name: &str, def: &TypeDef) {
+        if let Some((repr, values)) = as_enum(def) {
+            let repr = match self.scalar_type(&repr) {
+                Some(repr) => format!(" <{repr}>"),
+                None => String::new(),
+            };
+            self.line(0, format_args!("typedef enum{repr} {{"));
+            let last = values.len().saturating_sub(1);
+            for (index, (value, variant)) in values.iter().enumerate() {
+                let comma = if index == last { "" } else { "," };
+                self.line(
+                    1,
+                    format_args!("{}_{} = {value}{comma}", ident(name), ident(variant)),
+                );
+            }
+            self.line(0, format_args!("}} {};", ident(name)));
+            return;
+        }
+
+        if let Body::Enum { variants, .. } = &def.body {
+            for variant in variants.iter().filter(|variant| !variant.fields.is_empty()) {
+                self.line(0, format_args!("typedef struct {{"));
+                for field in &variant.fields {
+                    self.field(1, field, def.endian);
+                }
+                self.line(0, format_args!("}} {};", variant_type_id(name, variant)));
+                self.line(0, format_args!(""));
+            }
+        }
+
+        self.line(0, format_args!("typedef struct {{"));
+        if let Some(magic) = &def.magic {
+            self.magic(1, "magic", magic);
+        }
+        match &def.body {
+            Body::Struct(fields) => {
+                for field in fields {
+                    self.field(1, field, def.endian);
+                }
+            }
+            Body::Enum { variants, .. } => {
+                if let Some(magic) = switch_magic(variants) {
+                    self.switch(name, magic, variants);
+                } else {
+                    let names = variants
+                        .iter()
+                        .map(|variant| variant.name.as_str())
+                        .collect::<Vec<_>>();
+                    self.line(1, format_args!("// One of: {}", names.join(", ")));
+                }
+            }
+        }
+        self.line(0, format_args!("}} {};", ident(name)));
+    }
+
+    fn magic(&mut self, indent: usize, id: &str, magic: &Magic) {
+        match magic {
+            Magic::Bytes(bytes) => {
+                self.line(
+                    indent,
+                    format_args!("uchar {id}[{}] <format=hex>;", bytes.len()),
+                );
+                let checks = bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, byte)| format!("{id}[{index}] != {byte:#04x}"))
+                    .collect::<Vec<_>>();
+                self.line(
+                    indent,
+                    format_args!("if ({}) Warning(\"Bad {id}\");", checks.join(" || ")),
+                );
+            }
+            Magic::Value { layout, value } => {
+                let ty = self.scalar_type(layout).unwrap_or("uchar");
+                self.line(indent, format_args!("{ty} {id} <format=hex>;"));
+                self.line(
+                    indent,
+                    format_args!("if ({id} != {value}) Warning(\"Bad {id}\");"),
+                );
+            }
+        }
+    }
+
+    /// Reads the magic of an enum and then the variant which it selects.
+    fn switch(&mut self, name: &str, magic: &Magic, variants: &[Variant]) {
+        match magic {
+            Magic::Bytes(bytes) => {
+                self.line(
+                    1,
+                    format_args!("uchar magic[{}] <format=hex>;", bytes.len()),
+                );
+            }
+            Magic::Value { layout, .. } => {
+                let ty = self.scalar_type(layout).unwrap_or("uchar");
+                self.line(1, format_args!("{ty} magic <format=hex>;"));
+            }
+        }
+
+        let mut keyword = "if";
+        for variant in variants.iter().filter(|variant| !variant.fields.is_empty()) {
+            let condition = match &variant.magic {
+                Some(Magic::Bytes(bytes)) => bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, byte)| format!("magic[{index}] == {byte:#04x}"))
+                    .collect::<Vec<_>>()
+                    .join(" && "),
+                Some(Magic::Value { value, .. }) => format!("magic == {value}"),
+                None => continue,
+            };
+            self.line(
+                1,
+                format_args!(
+                    "{keyword} ({condition}) {} body;",
+                    variant_type_id(name, variant)
+                ),
+            );
+            keyword = "else if";
+        }
+    }
+
+    /// Declares a field. `current` is the byte order which is known to be
+    /// selected, which is the byte order of the type containing the field if
+    /// it has one.
+    fn field(&mut self, indent: usize, field: &Field, current: Option<Endian>) {
+        let id = field_id(&field.name);
+        if let Some(magic) = &field.magic {
+            self.magic(indent, &format!("{id}_magic"), magic);
+        }
+
+        let (layout, count) = match &field.layout {
+            Layout::List { inner, len } => (
+                &**inner,
+                Some(
+                    field
+                        .count
+                        .clone()
+                        .or_else(|| len.map(|len| format!("{len}"))),
+                ),
+            ),
+            layout => (layout, None),
+        };
+
+        let declaration = match (self.declaration_type(layout), count) {
+            (Err(description), _) => {
+                self.line(indent, format_args!("// {id}: {description}"));
+                return;
+            }
+            (Ok(None), _) => return,
+            (Ok(Some(ty)), None) => match layout {
+                Layout::Bits(bits) => format!("{ty} {id} : {bits};"),
+                _ => format!("{ty} {id};"),
+            },
+            (Ok(Some(ty)), Some(Some(count))) => format!("{ty} {id}[{count}];"),
+            (Ok(Some(_)), Some(None)) => {
+                self.line(
+                    indent,
+                    format_args!("// {id}: the number of elements is passed as an argument"),
+                );
+                return;
+            }
+        };
+        let declaration = match &field.condition {
+            Some(condition) => format!("if ({condition}) {declaration}"),
+            None => declaration,
+        };
+
+        // Every type is declared with the byte order it needs selected, and
+        // the previous byte order is restored afterwards
+        let endian = field.endian.or_else(|| match layout {
+            Layout::Named(name) => self.types.get(name).and_then(|def| def.endian),
+            _ => None,
+        });
+        match endian.filter(|endian| Some(*endian) != current) {
+            Some(endian) => {
+                let saved = format!("{id}_big_endian");
+                self.line(indent, format_args!("local int {saved} = IsBigEndian();"));
+                self.line(indent, format_args!("{}();", set_endian(endian)));
+                self.line(indent, format_args!("{declaration}"));
+                self.line(
+                    indent,
+                    format_args!("if ({saved}) BigEndian(); else LittleEndian();"),
+                );
+            }
+            None => self.line(indent, format_args!("{declaration}")),
+        }
+    }
+
+    /// Returns the type to declare a value with, `None` if the value has no
+    /// data, or a description of why the value cannot be declared.
+    fn declaration_type(&self, layout: &Layout) -> Result<Option<String>, String> {
+        match layout {
+            Layout::Unit => Ok(None),
+            Layout::Int { size: 16, .. } => Err(String::from("128-bit integers are not supported")),
+            Layout::Bits(_) => Ok(Some(String::from("uint"))),
+            Layout::NullString { wide: false } => Ok(Some(String::from("string"))),
+            Layout::NullString { wide: true } => Ok(Some(String::from("wstring"))),
+            Layout::List { .. } => Err(String::from("a nested list, which cannot be described")),
+            Layout::Named(name) => Ok(Some(ident(name))),
+            Layout::Unknown(description) => Err(description.clone()),
+            layout => self
+                .scalar_type(layout)
+                .map(|ty| Some(String::from(ty)))
+                .ok_or_else(|| String::from("an unsupported number type")),
+        }
+    }
+
+    fn scalar_type(&self, layout: &Layout) -> Option<&'static str> {
+        Some(match layout {
+            Layout::Int { size: 1, signed } => pick(*signed, "char", "uchar"),
+            Layout::Int { size: 2, signed } => pick(*signed, "short", "ushort"),
+            Layout::Int { size: 4, signed } => pick(*signed, "int", "uint"),
+            Layout::Int { size: 8, signed } => pick(*signed, "int64", "uint64"),
+            Layout::Float { size: 4 } => "float",
+            Layout::Float { size: 8 } => "double",
+            Layout::Named(name) => {
+                let (repr, _) = as_enum(self.types.get(name)?)?;
+                return self.scalar_type(&repr);
+            }
+            _ => return None,
+        })
+    }
+}
+
+fn set_endian(endian: Endian) -> &'static str {
+    match endian {
+        Endian::Big => "BigEndian",
+        Endian::Little => "LittleEndian",
+    }
+}
+
+fn pick(signed: bool, if_signed: &'static str, if_unsigned: &'static str) -> &'static str {
+    if signed {
+        if_signed
+    } else {
+        if_unsigned
+    }
+}
+
+fn variant_type_id(name: &str, variant: &Variant) -> String {
+    format!("{}_{}", ident(name), ident(&variant.name))
+}
+
+/// Converts a field name into an identifier, which must not start with a
+/// digit, so tuple fields like `0` become `field_0`.
+fn field_id(name: &str) -> String {
+    let id = ident(name);
+    if id.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("field_{id}")
+    } else {
+        id
+    }
+}
+
+/// Converts a Rust name into a C identifier, so `Wrapper<u8>` becomes
+/// `Wrapper_u8`.
+fn ident(name: &str) -> String {
+    let mut id = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            id.push(c);
+        } else if !id.is_empty() && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    while id.ends_with('_') {
+        id.pop();
+    }
+    id
+}
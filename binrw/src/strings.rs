@@ -2,12 +2,18 @@
 
 use crate::{
     alloc::string::{FromUtf16Error, FromUtf8Error},
+    encoding::{Encoding, Utf8},
     io::{Read, Seek, Write},
-    BinRead, BinResult, BinWrite, Endian,
+    BinRead, BinResult, BinWrite, Endian, Error, NamedArgs, ReadSequential, WriteSequential,
 };
+use alloc::ffi::CString;
 #[cfg(not(feature = "std"))]
 use alloc::{string::String, vec, vec::Vec};
-use core::fmt::{self, Write as _};
+use core::{
+    ffi::CStr,
+    fmt::{self, Write as _},
+    marker::PhantomData,
+};
 
 /// A null-terminated 8-bit string.
 ///
@@ -34,28 +40,72 @@ pub struct NullString(
     pub Vec<u8>,
 );
 
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`NullString`].
+///
+/// Before these arguments were added, `NullString` took `()` as its arguments.
+/// Code which passed `()` explicitly should omit the arguments or pass
+/// `NullStringArgs::default()` instead, and generic code which requires
+/// `BinRead<Args<'a> = ()>` or `BinWrite<Args<'a> = ()>` no longer accepts
+/// `NullString`.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor, NullString};
+/// #[binrw]
+/// #[brw(little)]
+/// struct Entry {
+///     #[brw(args { max_len: 8 })]
+///     name: NullString,
+/// }
+///
+/// # assert_eq!(Entry::read(&mut Cursor::new(b"name\0")).unwrap().name.0, b"name");
+/// # assert!(Entry::read(&mut Cursor::new(b"long name\0")).is_err());
+/// # assert!(Entry { name: "long name".into() }.write(&mut Cursor::new(vec![])).is_err());
+/// ```
+#[derive(Clone, NamedArgs)]
+pub struct NullStringArgs {
+    /// The maximum number of bytes in the string, not including the
+    /// terminator.
+    #[named_args(default = usize::MAX)]
+    pub max_len: usize,
+}
+
+impl Default for NullStringArgs {
+    fn default() -> Self {
+        Self {
+            max_len: usize::MAX,
+        }
+    }
+}
+
+/// Reads a null-terminated 8-bit string.
+///
+/// If no null terminator is found within
+/// [`max_len`](NullStringArgs::max_len) bytes, or before the string would
+/// exceed an active [memory limit](crate::memory::limit), an
+/// [`Error::AssertFail`] is returned with the position of the start of the
+/// string.
 impl BinRead for NullString {
-    type Args<'a> = ();
+    type Args<'a> = NullStringArgs;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         endian: Endian,
-        (): Self::Args<'_>,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        let mut values = vec![];
-
-        loop {
-            let val = <u8>::read_options(reader, endian, ())?;
-            if val == 0 {
-                return Ok(Self(values));
-            }
-            values.push(val);
-        }
+        read_until_null(reader, endian, args.max_len).map(Self)
     }
 }
 
+/// Writes a null-terminated 8-bit string.
+///
+/// If the string is longer than [`max_len`](NullStringArgs::max_len) bytes, an
+/// [`Error::AssertFail`] is returned with the position of the start of the
+/// string and nothing is written.
 impl BinWrite for NullString {
-    type Args<'a> = ();
+    type Args<'a> = NullStringArgs;
 
     fn write_options<W: Write + Seek>(
         &self,
@@ -63,8 +113,18 @@ impl BinWrite for NullString {
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        self.0.write_options(writer, endian, args)?;
-        0u8.write_options(writer, endian, args)?;
+        if self.0.len() > args.max_len {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!(
+                    "string is longer than the maximum length of {}",
+                    args.max_len
+                ),
+            });
+        }
+
+        self.0.write_options(writer, endian, ())?;
+        0u8.write_options(writer, endian, ())?;
 
         Ok(())
     }
@@ -127,7 +187,9 @@ impl fmt::Display for NullString {
 /// A null-terminated 16-bit string.
 ///
 /// The null terminator must also be 16-bits, and is consumed and not included
-/// in the value.
+/// in the value. The code units are not checked, so strings which are not
+/// valid UTF-16, such as those with unpaired surrogates, are read and written
+/// unchanged.
 ///
 /// ```
 /// use binrw::{BinRead, BinReaderExt, NullWideString, io::Cursor};
@@ -156,28 +218,125 @@ pub struct NullWideString(
     pub Vec<u16>,
 );
 
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`NullWideString`].
+///
+/// Before these arguments were added, `NullWideString` took `()` as its
+/// arguments. Code which passed `()` explicitly should omit the arguments or
+/// pass `NullWideStringArgs::default()` instead, and generic code which
+/// requires `BinRead<Args<'a> = ()>` or `BinWrite<Args<'a> = ()>` no longer
+/// accepts `NullWideString`.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor, Endian, NullWideString};
+/// #[binrw]
+/// #[brw(big)]
+/// struct Entry {
+///     id: u16,
+///     // Always little-endian, regardless of the rest of the entry
+///     #[brw(args { endian: Some(Endian::Little) })]
+///     name: NullWideString,
+///     // Uses the byte order given by its byte order mark, if there is one
+///     #[brw(args { bom: true })]
+///     title: NullWideString,
+/// }
+///
+/// let entry = Entry::read(&mut Cursor::new(b"\0\x01h\0i\0\0\0\xff\xfeo\0k\0\0\0")).unwrap();
+/// assert_eq!(entry.name.to_string(), "hi");
+/// assert_eq!(entry.title.to_string(), "ok");
+///
+/// let mut out = Cursor::new(vec![]);
+/// entry.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), b"\0\x01h\0i\0\0\0\xfe\xff\0o\0k\0\0");
+/// ```
+#[derive(Clone, Copy, Debug, Default, NamedArgs)]
+pub struct NullWideStringArgs {
+    /// The byte order of the string, or `None` to use the byte order of the
+    /// surrounding data.
+    #[named_args(default = None)]
+    pub endian: Option<Endian>,
+
+    /// Whether the string starts with a byte order mark.
+    ///
+    /// When reading, a byte order mark at the start of the string overrides
+    /// the byte order and is not included in the value. If there is no byte
+    /// order mark, the string is read using the byte order given by
+    /// [`endian`](Self::endian). When writing, a byte order mark is written
+    /// before the string.
+    ///
+    /// To write a string back with the byte order mark it was read with, use
+    /// [`BomWideString`] instead.
+    #[named_args(default = false)]
+    pub bom: bool,
+}
+
+/// Reads a null-terminated 16-bit string.
+///
+/// If no null terminator is found before the string would exceed an active
+/// [memory limit](crate::memory::limit), an [`Error::AssertFail`] is returned
+/// with the position of the start of the string.
 impl BinRead for NullWideString {
-    type Args<'a> = ();
+    type Args<'a> = NullWideStringArgs;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         endian: Endian,
-        (): Self::Args<'_>,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
-        let mut values = vec![];
+        read_wide(reader, args.endian.unwrap_or(endian), args.bom).map(|(values, _)| Self(values))
+    }
+}
 
-        loop {
-            let val = <u16>::read_options(reader, endian, ())?;
-            if val == 0 {
-                return Ok(Self(values));
-            }
-            values.push(val);
+/// Reads a null-terminated 16-bit string, returning its code units and the
+/// byte order given by its byte order mark, if `bom` is true and it has one.
+fn read_wide<R: Read + Seek>(
+    reader: &mut R,
+    mut endian: Endian,
+    bom: bool,
+) -> BinResult<(Vec<u16>, Option<Endian>)> {
+    let pos = reader.stream_position()?;
+    let remaining = crate::memory::remaining();
+    let mut bom_endian = None;
+    let mut first = None;
+
+    if bom {
+        let bytes = <[u8; 2]>::read_options(reader, endian, ())?;
+        if let Ok(bom) = Endian::from_utf16_bom_bytes(bytes) {
+            endian = bom;
+            bom_endian = Some(bom);
+        } else {
+            first = Some(match endian {
+                Endian::Big => u16::from_be_bytes(bytes),
+                Endian::Little => u16::from_le_bytes(bytes),
+            });
         }
     }
+
+    let mut values = vec![];
+    loop {
+        let val = match first.take() {
+            Some(val) => val,
+            None => <u16>::read_options(reader, endian, ())?,
+        };
+        if val == 0 {
+            crate::memory::reserve(core::mem::size_of_val(values.as_slice()))?;
+            return Ok((values, bom_endian));
+        } else if values.len() == remaining / core::mem::size_of::<u16>() {
+            return Err(Error::AssertFail {
+                pos,
+                message: alloc::format!(
+                    "string is longer than the remaining memory limit of {remaining} bytes"
+                ),
+            });
+        }
+        values.push(val);
+    }
 }
 
 impl BinWrite for NullWideString {
-    type Args<'a> = ();
+    type Args<'a> = NullWideStringArgs;
 
     fn write_options<W: Write + Seek>(
         &self,
@@ -185,8 +344,12 @@ impl BinWrite for NullWideString {
         endian: Endian,
         args: Self::Args<'_>,
     ) -> BinResult<()> {
-        self.0.write_options(writer, endian, args)?;
-        0u16.write_options(writer, endian, args)?;
+        let endian = args.endian.unwrap_or(endian);
+        if args.bom {
+            writer.write_all(&endian.into_utf16_bom_bytes())?;
+        }
+        self.0.write_options(writer, endian, ())?;
+        0u16.write_options(writer, endian, ())?;
 
         Ok(())
     }
@@ -246,6 +409,477 @@ impl fmt::Debug for NullWideString {
     }
 }
 
+/// A null-terminated 16-bit string which may start with a byte order mark, and
+/// is written back with the same byte order mark.
+///
+/// When reading, a byte order mark at the start of the string gives its byte
+/// order and is not included in the string. A string without one uses the byte
+/// order of the surrounding data, and is also written without one.
+///
+/// ```
+/// use binrw::{prelude::*, io::Cursor, BomWideString, Endian};
+///
+/// let data = b"\xff\xfeh\0i\0\0\0";
+/// let s = BomWideString::read_be(&mut Cursor::new(data)).unwrap();
+/// assert_eq!(s.string.to_string(), "hi");
+/// assert_eq!(s.bom, Some(Endian::Little));
+///
+/// let mut out = Cursor::new(vec![]);
+/// s.write_be(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), data);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BomWideString {
+    /// The string, without its byte order mark.
+    pub string: NullWideString,
+    /// The byte order given by the byte order mark, or `None` if the string
+    /// has no byte order mark.
+    pub bom: Option<Endian>,
+}
+
+impl BinRead for BomWideString {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let (values, bom) = read_wide(reader, endian, true)?;
+        Ok(Self {
+            string: NullWideString(values),
+            bom,
+        })
+    }
+}
+
+impl BinWrite for BomWideString {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.string.write_options(
+            writer,
+            self.bom.unwrap_or(endian),
+            NullWideStringArgs {
+                endian: None,
+                bom: self.bom.is_some(),
+            },
+        )
+    }
+}
+
+impl From<NullWideString> for BomWideString {
+    fn from(string: NullWideString) -> Self {
+        Self { string, bom: None }
+    }
+}
+
+/// A fixed-length 8-bit string, padded to `N` bytes.
+///
+/// Exactly `N` bytes are read, and any trailing pad bytes are not included in
+/// the value. The pad byte is NUL by default and can be changed with
+/// [`FixedStringArgs`]. When writing, the string is truncated or padded to `N`
+/// bytes.
+///
+/// ```
+/// use binrw::{prelude::*, io::Cursor, FixedString};
+///
+/// #[binrw]
+/// struct Header {
+///     name: FixedString<8>,
+///     #[brw(args { pad: b' ' })]
+///     title: FixedString<6>,
+/// }
+///
+/// let header = Header::read_le(&mut Cursor::new(b"rom\0\0\0\0\0ZELDA ")).unwrap();
+/// assert_eq!(header.name.to_string(), "rom");
+/// assert_eq!(header.title.to_string(), "ZELDA");
+///
+/// let mut out = Cursor::new(vec![]);
+/// header.write_le(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), b"rom\0\0\0\0\0ZELDA ");
+/// ```
+#[derive(Clone, Eq, PartialEq, Default)]
+pub struct FixedString<const N: usize>(
+    /// The raw byte string, without padding.
+    pub Vec<u8>,
+);
+
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`FixedString`].
+#[derive(Clone, Copy, Debug, Default, NamedArgs)]
+pub struct FixedStringArgs {
+    /// The byte used to pad the string to its full length.
+    #[named_args(default = 0)]
+    pub pad: u8,
+}
+
+impl<const N: usize> BinRead for FixedString<N> {
+    type Args<'a> = FixedStringArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        crate::memory::reserve(N)?;
+        let mut values = vec![0; N];
+        reader.read_exact(&mut values)?;
+        let len = values
+            .iter()
+            .rposition(|&byte| byte != args.pad)
+            .map_or(0, |index| index + 1);
+        values.truncate(len);
+        Ok(Self(values))
+    }
+}
+
+impl<const N: usize> BinWrite for FixedString<N> {
+    type Args<'a> = FixedStringArgs;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let len = self.0.len().min(N);
+        writer.write_all(&self.0[..len])?;
+        for _ in len..N {
+            writer.write_all(&[args.pad])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> From<&str> for FixedString<N> {
+    fn from(s: &str) -> Self {
+        Self(s.as_bytes().to_vec())
+    }
+}
+
+impl<const N: usize> From<String> for FixedString<N> {
+    fn from(s: String) -> Self {
+        Self(s.into_bytes())
+    }
+}
+
+impl<const N: usize> From<FixedString<N>> for Vec<u8> {
+    fn from(s: FixedString<N>) -> Self {
+        s.0
+    }
+}
+
+impl<const N: usize> TryFrom<FixedString<N>> for String {
+    type Error = FromUtf8Error;
+
+    fn try_from(value: FixedString<N>) -> Result<Self, Self::Error> {
+        String::from_utf8(value.0)
+    }
+}
+
+impl<const N: usize> core::ops::Deref for FixedString<N> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> core::ops::DerefMut for FixedString<N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FixedString<{N}>(\"")?;
+        display_utf8(&self.0, f, str::escape_debug)?;
+        write!(f, "\")")
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        display_utf8(&self.0, f, str::chars)
+    }
+}
+
+/// A string which starts with its length in bytes, read as an `L`.
+///
+/// The length prefix uses the byte order of the surrounding data. The string
+/// is decoded using the encoding given in [`PascalStringArgs`], which is UTF-8
+/// by default.
+///
+/// ```
+/// use binrw::{prelude::*, encoding::Latin1, io::Cursor, PascalString};
+///
+/// #[binrw]
+/// #[brw(big)]
+/// struct Entry {
+///     name: PascalString<u8>,
+///     #[brw(args { encoding: &Latin1 })]
+///     title: PascalString<u16>,
+/// }
+///
+/// let entry = Entry::read(&mut Cursor::new(b"\x04name\0\x04caf\xe9")).unwrap();
+/// assert_eq!(entry.name.as_str(), "name");
+/// assert_eq!(entry.title.as_str(), "café");
+///
+/// let mut out = Cursor::new(vec![]);
+/// entry.write(&mut out).unwrap();
+/// assert_eq!(out.into_inner(), b"\x04name\0\x04caf\xe9");
+/// ```
+#[derive(Clone, Eq, PartialEq, Default)]
+pub struct PascalString<L> {
+    /// The decoded string.
+    pub value: String,
+    prefix: PhantomData<L>,
+}
+
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`PascalString`].
+#[derive(Clone, Copy, NamedArgs)]
+pub struct PascalStringArgs<'a> {
+    /// The encoding of the string.
+    #[named_args(default = &Utf8)]
+    pub encoding: &'a dyn Encoding,
+}
+
+impl Default for PascalStringArgs<'_> {
+    fn default() -> Self {
+        Self { encoding: &Utf8 }
+    }
+}
+
+impl<L> PascalString<L> {
+    /// Creates a new string.
+    #[must_use]
+    pub fn new(value: String) -> Self {
+        Self {
+            value,
+            prefix: PhantomData,
+        }
+    }
+}
+
+impl<L> BinRead for PascalString<L>
+where
+    L: for<'a> BinRead<Args<'a> = ()> + TryInto<usize>,
+{
+    type Args<'a> = PascalStringArgs<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        crate::encoding::read_prefixed::<L, _, _>(args.encoding)(reader, endian, ()).map(Self::new)
+    }
+}
+
+impl<L> BinWrite for PascalString<L>
+where
+    L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+{
+    type Args<'a> = PascalStringArgs<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        crate::encoding::write_prefixed::<L, _, _, _>(args.encoding)(
+            &self.value,
+            writer,
+            endian,
+            (),
+        )
+    }
+}
+
+impl<L> From<&str> for PascalString<L> {
+    fn from(s: &str) -> Self {
+        Self::new(s.into())
+    }
+}
+
+impl<L> From<String> for PascalString<L> {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<L> From<PascalString<L>> for String {
+    fn from(s: PascalString<L>) -> Self {
+        s.value
+    }
+}
+
+impl<L> core::ops::Deref for PascalString<L> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<L> core::ops::DerefMut for PascalString<L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<L> fmt::Debug for PascalString<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PascalString({:?})", self.value)
+    }
+}
+
+impl<L> fmt::Display for PascalString<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+/// Named arguments for the [`BinRead::read_options()`] implementation of
+/// [`CString`].
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// # use std::ffi::CString;
+/// #[derive(BinRead)]
+/// struct Entry {
+///     #[br(args { max_len: 8 })]
+///     name: CString,
+/// }
+///
+/// # assert_eq!(
+/// #     Entry::read_le(&mut Cursor::new(b"name\0")).unwrap().name.as_bytes(),
+/// #     b"name"
+/// # );
+/// # assert!(Entry::read_le(&mut Cursor::new(b"long name\0")).is_err());
+/// ```
+#[derive(Clone, NamedArgs)]
+pub struct CStringArgs {
+    /// The maximum number of bytes in the string, not including the
+    /// terminator.
+    #[named_args(default = usize::MAX)]
+    pub max_len: usize,
+}
+
+impl Default for CStringArgs {
+    fn default() -> Self {
+        Self {
+            max_len: usize::MAX,
+        }
+    }
+}
+
+/// Reads a null-terminated 8-bit string.
+///
+/// The null terminator is consumed and not included in the value. If no null
+/// terminator is found within [`max_len`](CStringArgs::max_len) bytes, or
+/// before the string would exceed an active
+/// [memory limit](crate::memory::limit), an [`Error::AssertFail`] is returned
+/// with the position of the start of the string.
+impl BinRead for CString {
+    type Args<'a> = CStringArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let values = read_until_null(reader, endian, args.max_len)?;
+        // `values` cannot contain a null byte since reading stops at the first
+        // one
+        Ok(CString::new(values).unwrap())
+    }
+}
+
+/// Writes the string followed by a null terminator.
+impl BinWrite for CStr {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.to_bytes_with_nul().write_options(writer, endian, args)
+    }
+}
+
+/// Writes the string followed by a null terminator.
+impl BinWrite for CString {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.as_c_str().write_options(writer, endian, args)
+    }
+}
+
+impl ReadSequential for NullString {}
+impl WriteSequential for NullString {}
+impl ReadSequential for NullWideString {}
+impl WriteSequential for NullWideString {}
+impl<const N: usize> ReadSequential for FixedString<N> {}
+impl<const N: usize> WriteSequential for FixedString<N> {}
+impl<L> ReadSequential for PascalString<L> where L: for<'a> BinRead<Args<'a> = ()> + TryInto<usize> {}
+impl<L> WriteSequential for PascalString<L> where L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>
+{}
+impl ReadSequential for CString {}
+impl WriteSequential for CString {}
+impl WriteSequential for CStr {}
+
+fn read_until_null<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    max_len: usize,
+) -> BinResult<Vec<u8>> {
+    let pos = reader.stream_position()?;
+    let remaining = crate::memory::remaining();
+    let mut values = vec![];
+
+    loop {
+        let val = <u8>::read_options(reader, endian, ())?;
+        if val == 0 {
+            crate::memory::reserve(values.len())?;
+            return Ok(values);
+        } else if values.len() == max_len {
+            return Err(Error::AssertFail {
+                pos,
+                message: alloc::format!("string is longer than the maximum length of {max_len}"),
+            });
+        } else if values.len() == remaining {
+            return Err(Error::AssertFail {
+                pos,
+                message: alloc::format!(
+                    "string is longer than the remaining memory limit of {remaining} bytes"
+                ),
+            });
+        }
+        values.push(val);
+    }
+}
+
 fn display_utf16<Transformer: Fn(char) -> O, O: Iterator<Item = char>>(
     input: &[u16],
     f: &mut fmt::Formatter<'_>,
@@ -0,0 +1,268 @@
+//! Assertions for testing that types read back what they write.
+//!
+//! A type which implements both [`BinRead`] and [`BinWrite`] should usually
+//! round-trip in both directions: writing a value and reading it back gives an
+//! equal value, and reading some bytes and writing them back gives the same
+//! bytes. [`assert_roundtrip`] and [`assert_roundtrip_bytes`] check each
+//! direction, and panic with a description of where the two sides differ if
+//! they do not.
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{binrw, testing, Endian};
+//!
+//! #[binrw]
+//! #[brw(big, magic = b"PT")]
+//! #[derive(Debug, PartialEq)]
+//! struct Point {
+//!     x: i16,
+//!     y: i16,
+//! }
+//!
+//! testing::assert_roundtrip(&Point { x: 1, y: -1 }, Endian::Big);
+//! testing::assert_roundtrip_bytes::<Point>(b"PT\0\x01\xff\xff", Endian::Big);
+//! ```
+
+use crate::{
+    io::{Cursor, Seek},
+    BinRead, BinWrite, Endian,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::{Debug, Write};
+
+/// Asserts that writing `value` and reading it back gives an equal value.
+///
+/// # Panics
+///
+/// Panics if writing or reading fails, if reading does not consume every
+/// written byte, or if the value which is read back is not equal to `value`.
+/// The message lists the lines of the [`Debug`] output of the two values which
+/// differ, and the written bytes.
+#[track_caller]
+pub fn assert_roundtrip<T>(value: &T, endian: Endian)
+where
+    T: BinRead + BinWrite + Debug + PartialEq,
+    for<'a> <T as BinRead>::Args<'a>: Default,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    assert_roundtrip_args(value, endian, Default::default(), Default::default());
+}
+
+/// Asserts that writing `value` with `write_args` and reading it back with
+/// `read_args` gives an equal value.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`assert_roundtrip`].
+#[track_caller]
+pub fn assert_roundtrip_args<'a, T>(
+    value: &T,
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'a>,
+) where
+    T: BinRead + BinWrite + Debug + PartialEq,
+{
+    let mut writer = Cursor::new(Vec::new());
+    if let Err(err) = value.write_options(&mut writer, endian, write_args) {
+        panic!("failed to write {value:?}: {err}");
+    }
+    let bytes = writer.into_inner();
+
+    let mut reader = Cursor::new(bytes.as_slice());
+    let read = match T::read_options(&mut reader, endian, read_args) {
+        Ok(read) => read,
+        Err(err) => panic!(
+            "failed to read back {value:?}: {err}\nwritten bytes:\n{}",
+            hex(&bytes, None)
+        ),
+    };
+
+    let end = stream_position(&mut reader);
+    assert!(
+        end == bytes.len(),
+        "reading back only consumed {end} of {} written bytes:\n{}value: {value:#?}",
+        bytes.len(),
+        hex(&bytes, Some(end))
+    );
+
+    assert!(
+        read == *value,
+        "value read back differs from value written:\n{}written bytes:\n{}",
+        debug_diff(value, &read),
+        hex(&bytes, None)
+    );
+}
+
+/// Asserts that reading `bytes` as a `T` and writing it back gives the same
+/// bytes.
+///
+/// # Panics
+///
+/// Panics if reading or writing fails, or if the written bytes differ from
+/// `bytes`, including when reading did not consume every byte. The message
+/// shows the first byte which differs in each sequence, and the value which
+/// was read.
+#[track_caller]
+pub fn assert_roundtrip_bytes<T>(bytes: &[u8], endian: Endian)
+where
+    T: BinRead + BinWrite + Debug,
+    for<'a> <T as BinRead>::Args<'a>: Default,
+    for<'a> <T as BinWrite>::Args<'a>: Default,
+{
+    assert_roundtrip_bytes_args::<T>(bytes, endian, Default::default(), Default::default());
+}
+
+/// Asserts that reading `bytes` as a `T` with `read_args` and writing it back
+/// with `write_args` gives the same bytes.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`assert_roundtrip_bytes`].
+#[track_caller]
+pub fn assert_roundtrip_bytes_args<'a, T>(
+    bytes: &[u8],
+    endian: Endian,
+    read_args: <T as BinRead>::Args<'a>,
+    write_args: <T as BinWrite>::Args<'a>,
+) where
+    T: BinRead + BinWrite + Debug,
+{
+    let value = match T::read_options(&mut Cursor::new(bytes), endian, read_args) {
+        Ok(value) => value,
+        Err(err) => panic!("failed to read:\n{}error: {err}", hex(bytes, None)),
+    };
+
+    let mut writer = Cursor::new(Vec::new());
+    if let Err(err) = value.write_options(&mut writer, endian, write_args) {
+        panic!("failed to write back {value:?}: {err}");
+    }
+    let written = writer.into_inner();
+
+    if written != bytes {
+        let diff = bytes
+            .iter()
+            .zip(&written)
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| bytes.len().min(written.len()));
+        panic!(
+            "bytes written back differ from bytes read, starting at offset {diff:#x}:\n\
+             read {}:\n{}written {}:\n{}value: {value:#?}",
+            bytes.len(),
+            hex(bytes, Some(diff)),
+            written.len(),
+            hex(&written, Some(diff)),
+        );
+    }
+}
+
+fn stream_position<S: Seek>(stream: &mut S) -> usize {
+    stream
+        .stream_position()
+        .ok()
+        .and_then(|pos| usize::try_from(pos).ok())
+        .unwrap_or_default()
+}
+
+/// The number of lines of context shown around a difference.
+const CONTEXT_LINES: usize = 2;
+
+/// The greatest number of lines shown when there is no difference to show
+/// the context of.
+const MAX_LINES: usize = 16;
+
+/// Formats a hex dump of `bytes`, 16 bytes per line. If `mark` is given, the
+/// byte at that offset is marked and only the lines around it are shown.
+fn hex(bytes: &[u8], mark: Option<usize>) -> String {
+    let lines = bytes
+        .chunks(16)
+        .enumerate()
+        .filter(|(index, _)| match mark {
+            Some(mark) => index + CONTEXT_LINES >= mark / 16 && *index <= mark / 16 + CONTEXT_LINES,
+            None => *index < MAX_LINES,
+        });
+
+    let mut out = String::new();
+    for (index, line) in lines {
+        let start = index * 16;
+        let _ = write!(out, "  {start:08x}:");
+        for (offset, byte) in line.iter().enumerate() {
+            let sep = if mark == Some(start + offset) {
+                '>'
+            } else {
+                ' '
+            };
+            let _ = write!(out, "{sep}{byte:02x}");
+        }
+        out.push('\n');
+    }
+
+    if mark.is_none() && bytes.len() > MAX_LINES * 16 {
+        let _ = writeln!(out, "  ... {} more bytes", bytes.len() - MAX_LINES * 16);
+    }
+    if bytes.is_empty() || mark == Some(bytes.len()) {
+        let _ = writeln!(out, "  {:08x}: (end)", bytes.len());
+    }
+    out
+}
+
+/// The greatest number of pairs of lines which are compared to find the
+/// smallest difference between two values. Larger values are compared line by
+/// line instead.
+const MAX_DIFF_CELLS: usize = 1 << 20;
+
+/// Formats the lines of the [`Debug`] output of two values, marking the
+/// lines which only the expected value has with `-` and the lines which only
+/// the actual value has with `+`.
+fn debug_diff<T: Debug>(expected: &T, actual: &T) -> String {
+    let expected = format!("{expected:#?}");
+    let actual = format!("{actual:#?}");
+    let a = expected.lines().collect::<Vec<_>>();
+    let b = actual.lines().collect::<Vec<_>>();
+
+    // The length of the longest common subsequence of the lines after each
+    // pair of positions, so the diff keeps as many lines in common as it can
+    let width = b.len() + 1;
+    let common = if a.len().saturating_mul(b.len()) <= MAX_DIFF_CELLS {
+        let mut common = vec![0_usize; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                common[i * width + j] = if a[i] == b[j] {
+                    common[(i + 1) * width + j + 1] + 1
+                } else {
+                    common[(i + 1) * width + j].max(common[i * width + j + 1])
+                };
+            }
+        }
+        Some(common)
+    } else {
+        None
+    };
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            let _ = writeln!(out, "  {}", a[i]);
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let take_expected = j == b.len()
+            || i < a.len()
+                && common.as_ref().map_or(true, |common| {
+                    common[(i + 1) * width + j] >= common[i * width + j + 1]
+                });
+        if take_expected {
+            let _ = writeln!(out, "- {}", a[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(out, "+ {}", b[j]);
+            j += 1;
+        }
+    }
+    out
+}
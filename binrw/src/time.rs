@@ -0,0 +1,434 @@
+//! Type definitions for reading and writing time values.
+//!
+//! [`Duration`] is always supported. Timestamps are supported for
+//! `chrono::DateTime<Utc>` with the `chrono` feature, and for
+//! `time::OffsetDateTime` with the `time` feature, using the encodings in
+//! [`TimestampFormat`].
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{args, prelude::*, io::Cursor};
+//! use binrw::time::{IntWidth, TimeUnit};
+//! use core::time::Duration;
+//!
+//! #[binrw]
+//! # #[derive(Debug, PartialEq)]
+//! #[brw(little)]
+//! struct Sample {
+//!     #[brw(args { width: IntWidth::U16, unit: TimeUnit::Millis })]
+//!     elapsed: Duration,
+//! }
+//!
+//! let sample = Sample::read(&mut Cursor::new(b"\xe8\x03")).unwrap();
+//! assert_eq!(sample.elapsed, Duration::from_secs(1));
+//! ```
+
+use crate::{
+    io::{self, Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error, NamedArgs,
+};
+use core::time::Duration;
+
+/// The width of an integer used to store a value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntWidth {
+    /// An 8-bit unsigned integer.
+    U8,
+    /// A 16-bit unsigned integer.
+    U16,
+    /// A 32-bit unsigned integer.
+    U32,
+    /// A 64-bit unsigned integer.
+    U64,
+}
+
+impl IntWidth {
+    fn read<R: Read + Seek>(self, reader: &mut R, endian: Endian) -> BinResult<u64> {
+        Ok(match self {
+            Self::U8 => u8::read_options(reader, endian, ())?.into(),
+            Self::U16 => u16::read_options(reader, endian, ())?.into(),
+            Self::U32 => u32::read_options(reader, endian, ())?.into(),
+            Self::U64 => u64::read_options(reader, endian, ())?,
+        })
+    }
+
+    fn write<W: Write + Seek>(self, value: u128, writer: &mut W, endian: Endian) -> BinResult<()> {
+        match self {
+            Self::U8 => u8::try_from(value)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::U16 => u16::try_from(value)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::U32 => u32::try_from(value)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::U64 => u64::try_from(value)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+        }
+    }
+}
+
+/// The unit of time represented by one tick of a stored integer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeUnit {
+    /// Seconds.
+    Seconds,
+    /// Milliseconds.
+    Millis,
+    /// Microseconds.
+    Micros,
+    /// Nanoseconds.
+    Nanos,
+}
+
+impl TimeUnit {
+    fn duration(self, ticks: u64) -> Duration {
+        match self {
+            Self::Seconds => Duration::from_secs(ticks),
+            Self::Millis => Duration::from_millis(ticks),
+            Self::Micros => Duration::from_micros(ticks),
+            Self::Nanos => Duration::from_nanos(ticks),
+        }
+    }
+
+    fn ticks(self, duration: Duration) -> u128 {
+        match self {
+            Self::Seconds => duration.as_secs().into(),
+            Self::Millis => duration.as_millis(),
+            Self::Micros => duration.as_micros(),
+            Self::Nanos => duration.as_nanos(),
+        }
+    }
+}
+
+/// Named arguments for the [`BinRead::read_options()`] and
+/// [`BinWrite::write_options()`] implementations of [`Duration`].
+///
+/// When writing, any part of the duration smaller than one `unit` is
+/// truncated. If the number of ticks does not fit in `width`, an error is
+/// returned.
+#[derive(Clone, Copy, NamedArgs)]
+pub struct DurationArgs {
+    /// The width of the stored integer.
+    pub width: IntWidth,
+
+    /// The unit of time represented by one tick of the stored integer.
+    pub unit: TimeUnit,
+}
+
+impl BinRead for Duration {
+    type Args<'a> = DurationArgs;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ticks = args.width.read(reader, endian)?;
+        Ok(args.unit.duration(ticks))
+    }
+}
+
+impl BinWrite for Duration {
+    type Args<'a> = DurationArgs;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        args.width.write(args.unit.ticks(*self), writer, endian)
+    }
+}
+
+impl crate::ReadSequential for Duration {}
+impl crate::WriteSequential for Duration {}
+
+/// The encoding of a stored timestamp.
+///
+/// This is the argument type for the [`BinRead`] and [`BinWrite`]
+/// implementations of timestamp types. All integers use the byte order of the
+/// data.
+///
+/// When writing, any part of the timestamp smaller than the precision of the
+/// format is truncated towards the past. If the timestamp cannot be
+/// represented by the format, an error is returned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimestampFormat {
+    /// An unsigned 32-bit number of seconds since the Unix epoch.
+    UnixSeconds32,
+    /// A signed 64-bit number of seconds since the Unix epoch.
+    UnixSeconds64,
+    /// An unsigned 32-bit number of milliseconds since the Unix epoch.
+    UnixMillis32,
+    /// A signed 64-bit number of milliseconds since the Unix epoch.
+    UnixMillis64,
+    /// A Windows `FILETIME`: an unsigned 64-bit number of 100-nanosecond
+    /// intervals since 1601-01-01.
+    FileTime,
+    /// An MS-DOS time and date: a 16-bit time followed by a 16-bit date, with
+    /// a precision of two seconds and a range of 1980 through 2107. The stored
+    /// value has no time zone and is treated as UTC.
+    DosDateTime,
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl TimestampFormat {
+    /// Reads a timestamp as a number of nanoseconds since the Unix epoch.
+    fn read<R: Read + Seek>(self, reader: &mut R, endian: Endian) -> BinResult<i128> {
+        Ok(match self {
+            Self::UnixSeconds32 => {
+                i128::from(u32::read_options(reader, endian, ())?) * NANOS_PER_SEC
+            }
+            Self::UnixSeconds64 => {
+                i128::from(i64::read_options(reader, endian, ())?) * NANOS_PER_SEC
+            }
+            Self::UnixMillis32 => {
+                i128::from(u32::read_options(reader, endian, ())?) * NANOS_PER_MILLI
+            }
+            Self::UnixMillis64 => {
+                i128::from(i64::read_options(reader, endian, ())?) * NANOS_PER_MILLI
+            }
+            Self::FileTime => {
+                let ticks = i128::from(u64::read_options(reader, endian, ())?);
+                (ticks - FILETIME_UNIX_EPOCH) * NANOS_PER_FILETIME_TICK
+            }
+            Self::DosDateTime => {
+                let pos = reader.stream_position()?;
+                let time = u16::read_options(reader, endian, ())?;
+                let date = u16::read_options(reader, endian, ())?;
+                let (hour, minute, second) = (time >> 11, (time >> 5) & 0x3f, (time & 0x1f) * 2);
+                let (year, month, day) = (1980 + (date >> 9), (date >> 5) & 0xf, date & 0x1f);
+                // The date is checked against the length of its month so that
+                // an impossible date such as February 31 is not silently moved
+                // into the next month
+                if hour > 23
+                    || minute > 59
+                    || second > 59
+                    || !(1..=12).contains(&month)
+                    || day == 0
+                    || day > days_in_month(year, month)
+                {
+                    return Err(Error::AssertFail {
+                        pos,
+                        message: alloc::format!(
+                            "invalid MS-DOS date and time {year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}"
+                        ),
+                    });
+                }
+                let days = days_from_civil(year.into(), month.into(), day.into());
+                let seconds = days * SECS_PER_DAY
+                    + i64::from(hour) * 3600
+                    + i64::from(minute) * 60
+                    + i64::from(second);
+                i128::from(seconds) * NANOS_PER_SEC
+            }
+        })
+    }
+
+    /// Writes a timestamp given as a number of nanoseconds since the Unix
+    /// epoch.
+    fn write<W: Write + Seek>(self, nanos: i128, writer: &mut W, endian: Endian) -> BinResult<()> {
+        let seconds = nanos.div_euclid(NANOS_PER_SEC);
+        let millis = nanos.div_euclid(NANOS_PER_MILLI);
+        match self {
+            Self::UnixSeconds32 => u32::try_from(seconds)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::UnixSeconds64 => i64::try_from(seconds)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::UnixMillis32 => u32::try_from(millis)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::UnixMillis64 => i64::try_from(millis)
+                .map_err(|_| out_of_range())?
+                .write_options(writer, endian, ()),
+            Self::FileTime => {
+                u64::try_from(nanos.div_euclid(NANOS_PER_FILETIME_TICK) + FILETIME_UNIX_EPOCH)
+                    .map_err(|_| out_of_range())?
+                    .write_options(writer, endian, ())
+            }
+            Self::DosDateTime => {
+                let seconds = i64::try_from(seconds).map_err(|_| out_of_range())?;
+                let (year, month, day) = civil_from_days(seconds.div_euclid(SECS_PER_DAY));
+                let second_of_day = seconds.rem_euclid(SECS_PER_DAY);
+                let year = year - 1980;
+                if !(0..128).contains(&year) {
+                    return Err(out_of_range());
+                }
+                // Lint: All of the components were range checked or are
+                // bounded by the calendar calculations.
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let (time, date) = (
+                    ((second_of_day / 3600) << 11
+                        | ((second_of_day / 60 % 60) << 5)
+                        | (second_of_day % 60 / 2)) as u16,
+                    ((year << 9) | (i64::from(month) << 5) | i64::from(day)) as u16,
+                );
+                time.write_options(writer, endian, ())?;
+                date.write_options(writer, endian, ())
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+const NANOS_PER_SEC: i128 = 1_000_000_000;
+#[cfg(any(feature = "chrono", feature = "time"))]
+const NANOS_PER_MILLI: i128 = 1_000_000;
+#[cfg(any(feature = "chrono", feature = "time"))]
+const NANOS_PER_FILETIME_TICK: i128 = 100;
+#[cfg(any(feature = "chrono", feature = "time"))]
+const SECS_PER_DAY: i64 = 86_400;
+/// The number of `FILETIME` ticks between 1601-01-01 and the Unix epoch.
+#[cfg(any(feature = "chrono", feature = "time"))]
+const FILETIME_UNIX_EPOCH: i128 = 116_444_736_000_000_000;
+
+/// Returns the number of days in the given month of the proleptic Gregorian
+/// calendar.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn days_in_month(year: u16, month: u16) -> u16 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between the Unix epoch and the given date in the
+/// proleptic Gregorian calendar.
+// https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the year, month, and day of the date which is the given number of
+/// days after the Unix epoch in the proleptic Gregorian calendar.
+// https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    // Lint: `month` is in 1..=12 and `day` is in 1..=31.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u8, day as u8)
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "chrono")))]
+impl BinRead for ::chrono::DateTime<::chrono::Utc> {
+    type Args<'a> = TimestampFormat;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        format: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        use ::chrono::TimeZone;
+
+        let nanos = format.read(reader, endian)?;
+        // Lint: The remainder is always in 0..NANOS_PER_SEC.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let subsec_nanos = nanos.rem_euclid(NANOS_PER_SEC) as u32;
+        i64::try_from(nanos.div_euclid(NANOS_PER_SEC))
+            .ok()
+            .and_then(|seconds| ::chrono::Utc.timestamp_opt(seconds, subsec_nanos).single())
+            .ok_or_else(unrepresentable)
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "chrono")))]
+impl BinWrite for ::chrono::DateTime<::chrono::Utc> {
+    type Args<'a> = TimestampFormat;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        format: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let nanos = i128::from(self.timestamp()) * NANOS_PER_SEC
+            + i128::from(self.timestamp_subsec_nanos());
+        format.write(nanos, writer, endian)
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "time")))]
+impl BinRead for ::time::OffsetDateTime {
+    type Args<'a> = TimestampFormat;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        format: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let nanos = format.read(reader, endian)?;
+        Self::from_unix_timestamp_nanos(nanos).map_err(|_| unrepresentable())
+    }
+}
+
+/// The offset of the value is ignored; the stored timestamp is always UTC.
+#[cfg(feature = "time")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "time")))]
+impl BinWrite for ::time::OffsetDateTime {
+    type Args<'a> = TimestampFormat;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        format: Self::Args<'_>,
+    ) -> BinResult<()> {
+        format.write(self.unix_timestamp_nanos(), writer, endian)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl crate::ReadSequential for ::chrono::DateTime<::chrono::Utc> {}
+#[cfg(feature = "chrono")]
+impl crate::WriteSequential for ::chrono::DateTime<::chrono::Utc> {}
+#[cfg(feature = "time")]
+impl crate::ReadSequential for ::time::OffsetDateTime {}
+#[cfg(feature = "time")]
+impl crate::WriteSequential for ::time::OffsetDateTime {}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn unrepresentable() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "timestamp out of range",
+    ))
+}
+
+fn out_of_range() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "value out of range for integer width",
+    ))
+}
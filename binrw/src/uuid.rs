@@ -0,0 +1,96 @@
+//! Type definitions for reading and writing [UUIDs](::uuid::Uuid).
+//!
+//! UUIDs are stored in one of two layouts, selected by [`UuidLayout`]. The
+//! byte order of the rest of the data does not affect either layout.
+//!
+//! # Examples
+//!
+//! ```
+//! # use binrw::{prelude::*, io::Cursor};
+//! use binrw::uuid::UuidLayout;
+//! use uuid::Uuid;
+//!
+//! #[derive(BinRead)]
+//! struct Header {
+//!     id: Uuid,
+//!     #[br(args_raw = UuidLayout::Microsoft)]
+//!     class_id: Uuid,
+//! }
+//!
+//! let mut data = Vec::new();
+//! data.extend_from_slice(b"\x67\xe5\x50\x44\x10\xb1\x42\x6f\x92\x47\xbb\x68\x0e\x5f\xe0\xc8");
+//! data.extend_from_slice(b"\x44\x50\xe5\x67\xb1\x10\x6f\x42\x92\x47\xbb\x68\x0e\x5f\xe0\xc8");
+//!
+//! let header = Header::read_le(&mut Cursor::new(data)).unwrap();
+//! let expected = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+//! assert_eq!(header.id, expected);
+//! assert_eq!(header.class_id, expected);
+//! ```
+
+use crate::{
+    io::{Read, Seek, Write},
+    meta::{EndianKind, ReadEndian, WriteEndian},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use ::uuid::Uuid;
+
+/// The layout of a stored [`Uuid`].
+///
+/// This is the argument type for the [`BinRead`] and [`BinWrite`]
+/// implementations of [`Uuid`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum UuidLayout {
+    /// The layout from [RFC 4122](https://www.rfc-editor.org/rfc/rfc4122),
+    /// where all fields are big-endian. This is the same as the order of the
+    /// bytes in the string form of the UUID.
+    #[default]
+    Rfc4122,
+    /// The mixed-endian layout used by Microsoft GUIDs, where the first three
+    /// fields are little-endian and the remaining eight bytes are stored in
+    /// order.
+    Microsoft,
+}
+
+impl BinRead for Uuid {
+    type Args<'a> = UuidLayout;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        layout: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let bytes = <[u8; 16]>::read_options(reader, endian, ())?;
+        Ok(match layout {
+            UuidLayout::Rfc4122 => Self::from_bytes(bytes),
+            UuidLayout::Microsoft => Self::from_bytes_le(bytes),
+        })
+    }
+}
+
+impl BinWrite for Uuid {
+    type Args<'a> = UuidLayout;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        layout: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match layout {
+            UuidLayout::Rfc4122 => self.as_bytes().write_options(writer, endian, ()),
+            UuidLayout::Microsoft => self.to_bytes_le().write_options(writer, endian, ()),
+        }
+    }
+}
+
+impl ReadEndian for Uuid {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl WriteEndian for Uuid {
+    const ENDIAN: EndianKind = EndianKind::None;
+}
+
+impl crate::ReadSequential for Uuid {}
+
+impl crate::WriteSequential for Uuid {}
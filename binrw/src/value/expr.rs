@@ -0,0 +1,260 @@
+//! An evaluator for the count and condition expressions of a schema.
+//!
+//! Expressions use a subset of Rust syntax: integer literals, `true` and
+//! `false`, the names of earlier fields and their subfields like `a.b`,
+//! parentheses, the unary `-` and `!` operators, and the binary arithmetic,
+//! bitwise, comparison, and logical operators with Rust precedence. Casts like
+//! `len as usize` are accepted and have no effect. Values are 128-bit integers,
+//! and booleans are `1` for true and `0` for false.
+
+use super::FieldValue;
+use alloc::{format, string::String, vec::Vec};
+
+/// Evaluates an expression using the values of the given fields.
+pub(super) fn eval(expr: &str, scope: &[FieldValue]) -> Result<i128, String> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        index: 0,
+        scope,
+    };
+    let value = parser.binary(0)?;
+    match parser.tokens.get(parser.index) {
+        None => Ok(value),
+        Some(token) => Err(format!("unexpected `{token}` in `{expr}`")),
+    }
+}
+
+/// Returns the names of the fields which an expression uses, not including
+/// the names of their subfields.
+pub(super) fn names(expr: &str) -> Vec<&str> {
+    let Ok(tokens) = tokenize(expr) else {
+        return Vec::new();
+    };
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(index, token)| {
+            let prev = index.checked_sub(1).map(|index| tokens[index]);
+            let is_subfield = prev == Some(".")
+                && index.checked_sub(2).map(|index| tokens[index]) != Some("self");
+            token.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && !matches!(**token, "true" | "false" | "self" | "as")
+                && prev != Some("as")
+                && !is_subfield
+        })
+        .map(|(_, token)| *token)
+        .collect()
+}
+
+/// Parses an integer literal, which may have a `0x`, `0o`, or `0b` prefix,
+/// `_` separators, and a type suffix.
+// Lint: Literals above `i128::MAX` are bit patterns, as in Rust.
+#[allow(clippy::cast_possible_wrap)]
+pub(super) fn parse_int(literal: &str) -> Option<i128> {
+    let literal = literal.replace('_', "");
+    let (digits, radix) = match literal.get(..2) {
+        Some("0x") => (&literal[2..], 16),
+        Some("0o") => (&literal[2..], 8),
+        Some("0b") => (&literal[2..], 2),
+        _ => (literal.as_str(), 10),
+    };
+    let end = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    let (digits, suffix) = digits.split_at(end);
+    if !matches!(
+        suffix,
+        "" | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+    ) {
+        return None;
+    }
+    i128::from_str_radix(digits, radix)
+        .ok()
+        .or_else(|| u128::from_str_radix(digits, radix).ok().map(|v| v as i128))
+}
+
+fn tokenize(expr: &str) -> Result<Vec<&str>, String> {
+    const OPERATORS: [&str; 23] = [
+        "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "+", "-", "*", "/", "%", "&", "|", "^",
+        "!", "<", ">", "(", ")", ".", ",",
+    ];
+
+    let mut tokens = Vec::new();
+    let mut rest = expr.trim_start();
+    while !rest.is_empty() {
+        let len = if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len())
+        } else if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) {
+            op.len()
+        } else {
+            return Err(format!("unexpected character in `{expr}`"));
+        };
+        let (token, tail) = rest.split_at(len);
+        tokens.push(token);
+        rest = tail.trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [&'a str],
+    index: usize,
+    scope: &'a [FieldValue],
+}
+
+/// The binary operators, from lowest to highest precedence.
+const PRECEDENCE: [&[&str]; 10] = [
+    &["||"],
+    &["&&"],
+    &["==", "!=", "<", "<=", ">", ">="],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+    &[],
+];
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Result<&'a str, String> {
+        let token = self
+            .peek()
+            .ok_or_else(|| String::from("unexpected end of expression"))?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<i128, String> {
+        if level == PRECEDENCE.len() - 1 {
+            return self.unary();
+        }
+
+        let mut lhs = self.binary(level + 1)?;
+        while let Some(op) = self.peek().filter(|op| PRECEDENCE[level].contains(op)) {
+            self.index += 1;
+            let rhs = self.binary(level + 1)?;
+            lhs = apply(op, lhs, rhs).ok_or_else(|| format!("overflow in `{op}`"))?;
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<i128, String> {
+        let value = match self.peek() {
+            Some("-") => {
+                self.index += 1;
+                self.unary()?
+                    .checked_neg()
+                    .ok_or_else(|| String::from("overflow in `-`"))?
+            }
+            Some("!") => {
+                self.index += 1;
+                i128::from(self.unary()? == 0)
+            }
+            _ => self.primary()?,
+        };
+
+        // Casts do not change the value, since every value is an `i128`
+        while self.peek() == Some("as") {
+            self.index += 1;
+            self.next()?;
+        }
+
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> Result<i128, String> {
+        let token = self.next()?;
+        if token == "(" {
+            let value = self.binary(0)?;
+            return match self.next()? {
+                ")" => Ok(value),
+                token => Err(format!("expected `)`, found `{token}`")),
+            };
+        }
+
+        match token {
+            "true" => return Ok(1),
+            "false" => return Ok(0),
+            _ => {}
+        }
+
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            return parse_int(token).ok_or_else(|| format!("invalid number `{token}`"));
+        }
+
+        if !token.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+            return Err(format!("unexpected `{token}`"));
+        }
+
+        // A leading `self` refers to the fields in scope
+        let mut path = Vec::new();
+        if token != "self" {
+            path.push(token);
+        }
+        while self.peek() == Some(".") {
+            self.index += 1;
+            path.push(self.next()?);
+        }
+
+        let (first, rest) = path
+            .split_first()
+            .ok_or_else(|| String::from("expected a field name after `self`"))?;
+        let mut value = self
+            .scope
+            .iter()
+            .rev()
+            .find(|field| field.name == *first)
+            .map(|field| &field.value)
+            .ok_or_else(|| format!("unknown field `{first}`"))?;
+        for name in rest {
+            value = value
+                .get(name)
+                .ok_or_else(|| format!("unknown field `{name}`"))?;
+        }
+        value
+            .as_int()
+            .ok_or_else(|| format!("`{}` is not a number", path.join(".")))
+    }
+}
+
+fn apply(op: &str, lhs: i128, rhs: i128) -> Option<i128> {
+    Some(match op {
+        "||" => i128::from(lhs != 0 || rhs != 0),
+        "&&" => i128::from(lhs != 0 && rhs != 0),
+        "==" => i128::from(lhs == rhs),
+        "!=" => i128::from(lhs != rhs),
+        "<" => i128::from(lhs < rhs),
+        "<=" => i128::from(lhs <= rhs),
+        ">" => i128::from(lhs > rhs),
+        ">=" => i128::from(lhs >= rhs),
+        "|" => lhs | rhs,
+        "^" => lhs ^ rhs,
+        "&" => lhs & rhs,
+        "<<" => lhs.checked_shl(u32::try_from(rhs).ok()?)?,
+        ">>" => lhs.checked_shr(u32::try_from(rhs).ok()?)?,
+        "+" => lhs.checked_add(rhs)?,
+        "-" => lhs.checked_sub(rhs)?,
+        "*" => lhs.checked_mul(rhs)?,
+        "/" => lhs.checked_div(rhs)?,
+        "%" => lhs.checked_rem(rhs)?,
+        _ => return None,
+    })
+}
@@ -0,0 +1,315 @@
+use super::{expr, int_value, FieldValue, Value};
+use crate::{
+    schema::{Body, Field, Layout, Magic, Types, Variant},
+    Endian,
+};
+use alloc::{string::String, vec::Vec};
+
+/// Generates bytes which match the given layout, choosing every value which
+/// is not fixed by the layout from `entropy`.
+///
+/// This is meant for fuzzing: a fuzzer which passes its input as `entropy`
+/// produces inputs with the right magics, list lengths which match their
+/// counts, and fields which are present when their conditions are true, so
+/// that parsing gets past the first magic check and exercises the rest of the
+/// parser. The same `entropy` always generates the same bytes.
+///
+/// Choices are taken from the start of `entropy`, and are zero once it is
+/// used up. Integers which are used in the count of a later field are kept
+/// below 16, and at most 64 KiB is generated, so a list which would make the
+/// output longer is cut short. Lists with no count, bit fields, and
+/// [`Layout::Unknown`] values are filled directly from `entropy`, so inputs
+/// are only valid for layouts without them.
+///
+/// # Examples
+///
+/// A fuzz target for a type using
+/// [`cargo fuzz`](https://github.com/rust-fuzz/cargo-fuzz):
+///
+/// ```
+/// use binrw::{io::Cursor, schema::Types, value, BinRead, Endian};
+///
+/// #[derive(BinRead)]
+/// #[br(little, magic = b"LIST", schema)]
+/// struct List {
+///     len: u32,
+///     #[br(count = len)]
+///     items: Vec<u16>,
+/// }
+///
+/// # let fuzz_target = |data: &[u8]| {
+/// // fuzz_target!(|data: &[u8]| {
+/// let mut types = Types::new();
+/// let layout = types.layout_of::<List>();
+/// let input = value::generate(Endian::Little, &types, &layout, data);
+/// let list = List::read(&mut Cursor::new(input)).unwrap();
+/// assert_eq!(list.items.len(), list.len as usize);
+/// // });
+/// # };
+/// # fuzz_target(&[3, 1, 2, 3, 4, 5, 6]);
+/// # fuzz_target(&[]);
+/// ```
+#[must_use]
+pub fn generate(endian: Endian, types: &Types, layout: &Layout, entropy: &[u8]) -> Vec<u8> {
+    let mut generator = Generator {
+        types,
+        entropy,
+        out: Vec::new(),
+        depth: 0,
+    };
+    generator.value(endian, layout, None, false);
+    generator.out
+}
+
+/// The greatest number of bytes which [`generate`] generates.
+const MAX_LEN: usize = 1 << 16;
+
+/// The greatest depth of nested values which [`generate`] generates, so that
+/// a type which contains itself cannot overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// The values of integers which are used in counts are less than this.
+const MAX_COUNT: u8 = 16;
+
+/// The greatest length of a list with no count, or of a string.
+const MAX_UNCOUNTED_LEN: u8 = 16;
+
+struct Generator<'a> {
+    types: &'a Types,
+    entropy: &'a [u8],
+    out: Vec<u8>,
+    depth: usize,
+}
+
+impl Generator<'_> {
+    fn byte(&mut self) -> u8 {
+        match self.entropy.split_first() {
+            Some((byte, rest)) => {
+                self.entropy = rest;
+                *byte
+            }
+            None => 0,
+        }
+    }
+
+    /// Takes enough bytes for an integer with the given number of bytes.
+    fn bits(&mut self, size: usize) -> u128 {
+        (0..size.min(16)).fold(0, |value, index| {
+            value | u128::from(self.byte()) << (index * 8)
+        })
+    }
+
+    fn is_full(&self) -> bool {
+        self.out.len() >= MAX_LEN
+    }
+
+    fn value(
+        &mut self,
+        endian: Endian,
+        layout: &Layout,
+        count: Option<usize>,
+        small: bool,
+    ) -> Value {
+        if self.depth >= MAX_DEPTH {
+            return Value::None;
+        }
+
+        self.depth += 1;
+        let value = self.layout(endian, layout, count, small);
+        self.depth -= 1;
+        value
+    }
+
+    fn layout(
+        &mut self,
+        endian: Endian,
+        layout: &Layout,
+        count: Option<usize>,
+        small: bool,
+    ) -> Value {
+        match layout {
+            Layout::Unit => Value::None,
+            Layout::Int { size, signed } => {
+                let value = if small {
+                    u128::from(self.byte() % MAX_COUNT)
+                } else {
+                    self.bits(*size)
+                };
+                self.int(endian, *size, value);
+                int_value(value, *size, *signed)
+            }
+            Layout::Float { size } => {
+                let value = self.bits(*size);
+                self.int(endian, *size, value);
+                Value::None
+            }
+            Layout::Bits(_) | Layout::Unknown(_) => {
+                // The size of these is not known, so some bytes are taken
+                // directly from the entropy
+                let len = self.byte() % MAX_UNCOUNTED_LEN;
+                for _ in 0..len {
+                    let byte = self.byte();
+                    self.out.push(byte);
+                }
+                Value::None
+            }
+            Layout::NullString { wide } => {
+                let len = self.byte() % MAX_UNCOUNTED_LEN;
+                let unit_len = if *wide { 2 } else { 1 };
+                let mut text = String::new();
+                for _ in 0..len {
+                    // Printable ASCII, so that it is never a terminator
+                    let c = 0x20 + self.byte() % 0x5f;
+                    text.push(char::from(c));
+                    self.int(endian, unit_len, u128::from(c));
+                }
+                self.int(endian, unit_len, 0);
+                Value::String(text)
+            }
+            Layout::List { inner, len } => {
+                let len = count
+                    .or(*len)
+                    .unwrap_or_else(|| usize::from(self.byte() % MAX_UNCOUNTED_LEN));
+                let mut values = Vec::new();
+                for _ in 0..len {
+                    if self.is_full() {
+                        break;
+                    }
+                    values.push(self.value(endian, inner, None, false));
+                }
+                Value::List(values)
+            }
+            Layout::Named(name) => {
+                let Some(def) = self.types.get(name) else {
+                    return Value::None;
+                };
+                let endian = def.endian.unwrap_or(endian);
+                if let Some(magic) = &def.magic {
+                    self.magic(endian, magic);
+                }
+                match &def.body {
+                    Body::Struct(fields) => Value::Struct(self.fields(endian, fields)),
+                    Body::Enum {
+                        repr: Some(repr),
+                        variants,
+                    } => self.repr_variant(endian, repr, variants),
+                    Body::Enum {
+                        repr: None,
+                        variants,
+                    } => self.variant(endian, variants),
+                }
+            }
+        }
+    }
+
+    fn fields(&mut self, endian: Endian, fields: &[Field]) -> Vec<FieldValue> {
+        let mut values = Vec::<FieldValue>::with_capacity(fields.len());
+        for (index, field) in fields.iter().enumerate() {
+            let present = field.condition.as_ref().map_or(true, |condition| {
+                expr::eval(condition, &values).map_or(false, |value| value != 0)
+            });
+            let endian = field.endian.unwrap_or(endian);
+            if present {
+                if let Some(magic) = &field.magic {
+                    self.magic(endian, magic);
+                }
+            }
+            let offset = self.out.len() as u64;
+            let value = if present {
+                let count = field.count.as_ref().map(|count| {
+                    expr::eval(count, &values)
+                        .ok()
+                        .and_then(|count| usize::try_from(count).ok())
+                        .unwrap_or(0)
+                });
+                let small = fields[index + 1..].iter().any(|later| {
+                    later.count.as_ref().map_or(false, |count| {
+                        expr::names(count).contains(&field.name.as_str())
+                    })
+                });
+                self.value(endian, &field.layout, count, small)
+            } else {
+                Value::None
+            };
+            values.push(FieldValue {
+                name: field.name.clone(),
+                offset,
+                value,
+            });
+        }
+        values
+    }
+
+    fn repr_variant(&mut self, endian: Endian, repr: &Layout, variants: &[Variant]) -> Value {
+        let variants = variants
+            .iter()
+            .filter(|variant| variant.value.is_some())
+            .collect::<Vec<_>>();
+        let Some(variant) = self.choose(&variants) else {
+            return Value::None;
+        };
+        if let (Layout::Int { size, .. }, Some(value)) = (repr, variant.value) {
+            self.int(endian, *size, u128::from_le_bytes(value.to_le_bytes()));
+        }
+        Value::Variant {
+            name: variant.name.clone(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn variant(&mut self, endian: Endian, variants: &[Variant]) -> Value {
+        let variants = variants.iter().collect::<Vec<_>>();
+        let Some(variant) = self.choose(&variants) else {
+            return Value::None;
+        };
+        if let Some(magic) = &variant.magic {
+            self.magic(endian, magic);
+        }
+        Value::Variant {
+            name: variant.name.clone(),
+            fields: self.fields(endian, &variant.fields),
+        }
+    }
+
+    fn choose<'v>(&mut self, variants: &[&'v Variant]) -> Option<&'v Variant> {
+        if variants.is_empty() {
+            return None;
+        }
+        let index = usize::from(self.byte()) % variants.len();
+        Some(variants[index])
+    }
+
+    fn magic(&mut self, endian: Endian, magic: &Magic) {
+        match magic {
+            Magic::Bytes(bytes) => self.out.extend_from_slice(bytes),
+            Magic::Value { layout, value } => match layout {
+                Layout::Int { size, .. } => {
+                    if let Some(value) = expr::parse_int(value) {
+                        self.int(endian, *size, u128::from_le_bytes(value.to_le_bytes()));
+                    }
+                }
+                Layout::Float { size: 4 } => {
+                    if let Ok(value) = value.parse::<f32>() {
+                        self.int(endian, 4, value.to_bits().into());
+                    }
+                }
+                Layout::Float { size: 8 } => {
+                    if let Ok(value) = value.parse::<f64>() {
+                        self.int(endian, 8, value.to_bits().into());
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Writes the low `size` bytes of an integer.
+    fn int(&mut self, endian: Endian, size: usize, value: u128) {
+        let bytes = value.to_le_bytes();
+        let bytes = &bytes[..size.min(16)];
+        match endian {
+            Endian::Big => self.out.extend(bytes.iter().rev()),
+            Endian::Little => self.out.extend_from_slice(bytes),
+        }
+    }
+}
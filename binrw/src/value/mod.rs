@@ -0,0 +1,526 @@
+//! Dynamically typed values, and an interpreter which reads them using a
+//! [schema](crate::schema) built at runtime.
+//!
+//! When reverse engineering a format, it is often easier to describe the
+//! parts which are understood so far as a [`Types`] collection, and to read
+//! files against it, than to define Rust types for them up front. [`read`]
+//! reads a [`Value`] using such a description, recording the position of each
+//! field so that the remaining unknown bytes are easy to find. [`generate`]
+//! does the reverse, generating valid input for a description, which is
+//! useful for fuzzing.
+//!
+//! Counts and conditions are evaluated as simple integer expressions over the
+//! earlier fields of the same struct or variant, like `len * 2` or
+//! `flags & 1 != 0`. Bit fields and [`Layout::Unknown`] values cannot be read.
+//!
+//! # Examples
+//!
+//! ```
+//! use binrw::{
+//!     io::Cursor,
+//!     schema::{Body, Field, Layout, Magic, TypeDef, Types},
+//!     value, Endian, Value,
+//! };
+//!
+//! let mut types = Types::new();
+//! let u16_layout = types.layout_of::<u16>();
+//! let u8_layout = types.layout_of::<u8>();
+//! let record = types.insert("Record", TypeDef {
+//!     endian: Some(Endian::Big),
+//!     magic: Some(Magic::Bytes(b"RC".to_vec())),
+//!     body: Body::Struct(vec![
+//!         Field::new("len", u16_layout),
+//!         Field {
+//!             count: Some("len".into()),
+//!             ..Field::new("data", Layout::List {
+//!                 inner: Box::new(u8_layout),
+//!                 len: None,
+//!             })
+//!         },
+//!     ]),
+//! });
+//!
+//! let mut data = Cursor::new(b"RC\0\x02\xab\xcd");
+//! let value = value::read(&mut data, Endian::Little, &types, &record).unwrap();
+//! assert_eq!(value.get("len").and_then(Value::as_int), Some(2));
+//! assert_eq!(value.get("data"), Some(&Value::Bytes(vec![0xab, 0xcd])));
+//! ```
+
+mod expr;
+mod generate;
+
+pub use generate::generate;
+
+use crate::{
+    io::{Read, Seek, SeekFrom},
+    schema::{Body, Field, Layout, Magic, Types, Variant},
+    BinResult, Endian, Error,
+};
+#[cfg(not(feature = "std"))]
+use alloc::format;
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// A dynamically typed value read by [`read`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A value with no data, such as a field whose condition was false.
+    None,
+
+    /// A signed integer.
+    Int(i128),
+
+    /// An unsigned integer.
+    UInt(u128),
+
+    /// A floating point number.
+    Float(f64),
+
+    /// A list of bytes.
+    Bytes(Vec<u8>),
+
+    /// A string. Invalid characters are replaced with
+    /// [`U+FFFD`](char::REPLACEMENT_CHARACTER).
+    String(String),
+
+    /// A list of values.
+    List(Vec<Value>),
+
+    /// A struct.
+    Struct(Vec<FieldValue>),
+
+    /// A variant of an enum.
+    Variant {
+        /// The name of the variant.
+        name: String,
+        /// The fields of the variant.
+        fields: Vec<FieldValue>,
+    },
+}
+
+impl Value {
+    /// Returns the value as an integer, if it is an integer which fits in an
+    /// `i128`.
+    #[must_use]
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Self::Int(value) => Some(*value),
+            Self::UInt(value) => i128::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the fields of a struct or variant.
+    #[must_use]
+    pub fn fields(&self) -> Option<&[FieldValue]> {
+        match self {
+            Self::Struct(fields) | Self::Variant { fields, .. } => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the named field of a struct or variant.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.field(name).map(|field| &field.value)
+    }
+
+    /// Returns the named field of a struct or variant.
+    #[must_use]
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields()?.iter().find(|field| field.name == name)
+    }
+}
+
+/// A field of a [`Value::Struct`] or [`Value::Variant`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldValue {
+    /// The name of the field.
+    pub name: String,
+
+    /// The position of the value in the stream, after any magic.
+    pub offset: u64,
+
+    /// The value of the field.
+    pub value: Value,
+}
+
+/// Reads a value with the given layout, using the definitions in `types` for
+/// named types.
+///
+/// `endian` is the byte order used by types and fields which do not have a
+/// fixed byte order. Variants of enums without a `repr` are tried in order,
+/// and the first one which reads successfully is returned.
+///
+/// # Errors
+///
+/// If reading fails, an [`Error`](crate::Error) variant will be returned. If
+/// the layout contains a bit field, an unknown value, an unknown named type,
+/// a list with no count, or an expression which cannot be evaluated, an
+/// [`Error::Custom`] containing a `String` description is returned.
+pub fn read<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    types: &Types,
+    layout: &Layout,
+) -> BinResult<Value> {
+    Interpreter { types, depth: 0 }.value(reader, endian, layout, None)
+}
+
+/// The greatest depth of nested values which [`read`] reads, so that a type
+/// which contains itself cannot overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+struct Interpreter<'a> {
+    types: &'a Types,
+    depth: usize,
+}
+
+impl Interpreter<'_> {
+    fn value<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        layout: &Layout,
+        count: Option<usize>,
+    ) -> BinResult<Value> {
+        if self.depth >= MAX_DEPTH {
+            return Err(error(reader, "values are nested too deeply")?);
+        }
+
+        self.depth += 1;
+        let value = self.layout(reader, endian, layout, count);
+        self.depth -= 1;
+        value
+    }
+
+    fn layout<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        layout: &Layout,
+        count: Option<usize>,
+    ) -> BinResult<Value> {
+        match layout {
+            Layout::Unit => Ok(Value::None),
+            Layout::Int { size, signed } => read_int(reader, endian, *size, *signed),
+            Layout::Float { size } => read_float(reader, endian, *size),
+            Layout::Bits(_) => Err(error(reader, "bit fields are not supported")?),
+            Layout::NullString { wide } => read_null_string(reader, endian, *wide),
+            Layout::List { inner, len } => {
+                let Some(len) = count.or(*len) else {
+                    return Err(error(
+                        reader,
+                        "the number of elements of a list is not known",
+                    )?);
+                };
+                if **inner
+                    == (Layout::Int {
+                        size: 1,
+                        signed: false,
+                    })
+                {
+                    return read_bytes(reader, len);
+                }
+                let mut values = Vec::new();
+                for _ in 0..len {
+                    values.push(self.value(reader, endian, inner, None)?);
+                }
+                Ok(Value::List(values))
+            }
+            Layout::Named(name) => {
+                let Some(def) = self.types.get(name) else {
+                    return Err(error(reader, format!("unknown type `{name}`"))?);
+                };
+                let endian = def.endian.unwrap_or(endian);
+                if let Some(magic) = &def.magic {
+                    self.magic(reader, endian, magic)?;
+                }
+                match &def.body {
+                    Body::Struct(fields) => Ok(Value::Struct(self.fields(reader, endian, fields)?)),
+                    Body::Enum {
+                        repr: Some(repr),
+                        variants,
+                    } => self.repr_variant(reader, endian, repr, variants),
+                    Body::Enum {
+                        repr: None,
+                        variants,
+                    } => self.variant(reader, endian, variants),
+                }
+            }
+            Layout::Unknown(description) => Err(error(reader, description.clone())?),
+        }
+    }
+
+    fn fields<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        fields: &[Field],
+    ) -> BinResult<Vec<FieldValue>> {
+        let mut values = Vec::<FieldValue>::with_capacity(fields.len());
+        for field in fields {
+            let present = match &field.condition {
+                Some(condition) => eval(reader, condition, &values)? != 0,
+                None => true,
+            };
+            let endian = field.endian.unwrap_or(endian);
+            if present {
+                if let Some(magic) = &field.magic {
+                    self.magic(reader, endian, magic)?;
+                }
+            }
+            let count = match field.count.as_ref().filter(|_| present) {
+                Some(count) => Some(eval_count(reader, count, &values)?),
+                None => None,
+            };
+            let offset = reader.stream_position()?;
+            let value = if present {
+                self.value(reader, endian, &field.layout, count)?
+            } else {
+                Value::None
+            };
+            values.push(FieldValue {
+                name: field.name.clone(),
+                offset,
+                value,
+            });
+        }
+        Ok(values)
+    }
+
+    fn repr_variant<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        repr: &Layout,
+        variants: &[Variant],
+    ) -> BinResult<Value> {
+        let pos = reader.stream_position()?;
+        let value = self.value(reader, endian, repr, None)?.as_int();
+        variants
+            .iter()
+            .find(|variant| value.is_some() && variant.value == value)
+            .map(|variant| Value::Variant {
+                name: variant.name.clone(),
+                fields: Vec::new(),
+            })
+            .ok_or(Error::NoVariantMatch { pos })
+    }
+
+    fn variant<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        variants: &[Variant],
+    ) -> BinResult<Value> {
+        let pos = reader.stream_position()?;
+        for variant in variants {
+            let fields = variant
+                .magic
+                .as_ref()
+                .map_or(Ok(()), |magic| self.magic(reader, endian, magic))
+                .and_then(|()| self.fields(reader, endian, &variant.fields));
+            match fields {
+                Ok(fields) => {
+                    return Ok(Value::Variant {
+                        name: variant.name.clone(),
+                        fields,
+                    })
+                }
+                Err(_) => {
+                    reader.seek(SeekFrom::Start(pos))?;
+                }
+            }
+        }
+        Err(Error::NoVariantMatch { pos })
+    }
+
+    // Lint: Magics are compared exactly, as they are by `BinRead`.
+    #[allow(clippy::float_cmp)]
+    fn magic<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        endian: Endian,
+        magic: &Magic,
+    ) -> BinResult<()> {
+        let pos = reader.stream_position()?;
+        let found = match magic {
+            Magic::Bytes(bytes) => {
+                let found = read_bytes(reader, bytes.len())?;
+                if matches!(&found, Value::Bytes(found) if found == bytes) {
+                    return Ok(());
+                }
+                found
+            }
+            Magic::Value { layout, value } => {
+                let found = self.value(reader, endian, layout, None)?;
+                let matches = match &found {
+                    Value::Float(found) => value.parse::<f64>().ok().map(|value| value == *found),
+                    found => expr::parse_int(value).map(|value| found.as_int() == Some(value)),
+                };
+                match matches {
+                    Some(true) => return Ok(()),
+                    Some(false) => found,
+                    None => {
+                        return Err(Error::Custom {
+                            pos,
+                            err: Box::new(format!("invalid magic `{value}`")),
+                        })
+                    }
+                }
+            }
+        };
+
+        Err(Error::BadMagic {
+            pos,
+            found: Box::new(found),
+        })
+    }
+}
+
+/// Evaluates a count or condition expression.
+fn eval<R: Read + Seek>(reader: &mut R, expr: &str, scope: &[FieldValue]) -> BinResult<i128> {
+    match expr::eval(expr, scope) {
+        Ok(value) => Ok(value),
+        Err(message) => Err(error(reader, message)?),
+    }
+}
+
+/// Evaluates the expression which gives the number of elements of a list.
+fn eval_count<R: Read + Seek>(
+    reader: &mut R,
+    expr: &str,
+    scope: &[FieldValue],
+) -> BinResult<usize> {
+    let count = eval(reader, expr, scope)?;
+    match usize::try_from(count) {
+        Ok(count) => Ok(count),
+        Err(_) => Err(error(
+            reader,
+            format!("invalid count {count} from `{expr}`"),
+        )?),
+    }
+}
+
+/// Creates an error with a description at the current position.
+fn error<R: Seek>(reader: &mut R, message: impl Into<String>) -> BinResult<Error> {
+    Ok(Error::Custom {
+        pos: reader.stream_position()?,
+        err: Box::new(message.into()),
+    })
+}
+
+fn read_uint<R: Read + Seek>(reader: &mut R, endian: Endian, size: usize) -> BinResult<u128> {
+    let mut bytes = [0; 16];
+    let Some(bytes) = bytes.get_mut(..size) else {
+        return Err(error(
+            reader,
+            format!("{size}-byte integers are not supported"),
+        )?);
+    };
+    reader.read_exact(bytes)?;
+    let push = |value: u128, byte: &u8| value << 8 | u128::from(*byte);
+    Ok(match endian {
+        Endian::Big => bytes.iter().fold(0, push),
+        Endian::Little => bytes.iter().rev().fold(0, push),
+    })
+}
+
+// Lint: Only `size` bytes are read, so the value fits in the smaller type.
+#[allow(clippy::cast_possible_truncation)]
+fn read_float<R: Read + Seek>(reader: &mut R, endian: Endian, size: usize) -> BinResult<Value> {
+    match size {
+        4 => {
+            let bits = read_uint(reader, endian, 4)?;
+            Ok(Value::Float(f32::from_bits(bits as u32).into()))
+        }
+        8 => {
+            let bits = read_uint(reader, endian, 8)?;
+            Ok(Value::Float(f64::from_bits(bits as u64)))
+        }
+        _ => Err(error(
+            reader,
+            format!("{size}-byte floating point numbers are not supported"),
+        )?),
+    }
+}
+
+fn read_int<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    size: usize,
+    signed: bool,
+) -> BinResult<Value> {
+    if size == 0 {
+        return Ok(Value::UInt(0));
+    }
+
+    let value = read_uint(reader, endian, size)?;
+    Ok(int_value(value, size, signed))
+}
+
+/// Converts the bits of an integer with the given number of bytes into a
+/// value.
+// Lint: The sign bit is moved into the top bit, so wrapping is intended.
+#[allow(clippy::cast_possible_wrap)]
+fn int_value(value: u128, size: usize, signed: bool) -> Value {
+    match size {
+        1..=16 if signed => {
+            // Shifting the sign bit into the top bit and back extends it
+            let shift = 128 - size * 8;
+            Value::Int((value << shift) as i128 >> shift)
+        }
+        _ => Value::UInt(value),
+    }
+}
+
+fn read_bytes<R: Read + Seek>(reader: &mut R, len: usize) -> BinResult<Value> {
+    crate::memory::reserve(len)?;
+    let mut bytes = Vec::new();
+    reader
+        .by_ref()
+        .take(u64::try_from(len).unwrap_or(u64::MAX))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() < len {
+        reader.read_exact(&mut [0])?;
+    }
+    Ok(Value::Bytes(bytes))
+}
+
+fn read_null_string<R: Read + Seek>(
+    reader: &mut R,
+    endian: Endian,
+    wide: bool,
+) -> BinResult<Value> {
+    if wide {
+        let mut units = Vec::new();
+        loop {
+            let mut unit = [0; 2];
+            reader.read_exact(&mut unit)?;
+            let unit = match endian {
+                Endian::Big => u16::from_be_bytes(unit),
+                Endian::Little => u16::from_le_bytes(unit),
+            };
+            if unit == 0 {
+                break;
+            }
+            units.push(unit);
+        }
+        Ok(Value::String(
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect(),
+        ))
+    } else {
+        let mut bytes = Vec::new();
+        loop {
+            let mut byte = [0];
+            reader.read_exact(&mut byte)?;
+            if byte[0] == 0 {
+                break;
+            }
+            bytes.push(byte[0]);
+        }
+        Ok(Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+    }
+}
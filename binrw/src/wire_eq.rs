@@ -0,0 +1,28 @@
+//! Types for comparing serialised data with the
+//! [`wire_eq`](crate::docs::attribute#wire-comparison) directive.
+
+use core::{fmt, ops::Range};
+
+/// The first field which differs between two serialised values.
+///
+/// This is returned by the `wire_eq` functions generated by the
+/// [`wire_eq`](crate::docs::attribute#wire-comparison) directive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldDiff {
+    /// The name of the field, or its index for a tuple struct.
+    pub field: &'static str,
+    /// The range of bytes holding the field in the first stream.
+    pub a: Range<u64>,
+    /// The range of bytes holding the field in the second stream.
+    pub b: Range<u64>,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field `{}` differs: {:#x?} in the first stream, {:#x?} in the second stream",
+            self.field, self.a, self.b
+        )
+    }
+}
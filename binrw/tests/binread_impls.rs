@@ -1,5 +1,31 @@
+extern crate alloc;
+
 use binrw::{io::Cursor, BinRead};
 
+#[test]
+fn atomic() {
+    use core::sync::atomic::{AtomicI16, AtomicU32, AtomicU8, Ordering};
+
+    assert_eq!(
+        AtomicU8::read(&mut Cursor::new(b"\x01"))
+            .unwrap()
+            .load(Ordering::Relaxed),
+        1
+    );
+    assert_eq!(
+        AtomicI16::read_be(&mut Cursor::new(b"\xff\xfe"))
+            .unwrap()
+            .load(Ordering::Relaxed),
+        -2
+    );
+    assert_eq!(
+        AtomicU32::read_le(&mut Cursor::new(b"\x01\0\0\0"))
+            .unwrap()
+            .load(Ordering::Relaxed),
+        1
+    );
+}
+
 #[test]
 fn boxed() {
     assert_eq!(
@@ -75,6 +101,45 @@ fn clone_args() {
     TestCloneArray::read_le(&mut Cursor::new(b"")).unwrap();
 }
 
+#[test]
+fn collections() {
+    use std::collections::{BTreeSet, BinaryHeap, VecDeque};
+
+    let args = || binrw::VecArgs::builder().count(4).finalize();
+
+    assert_eq!(
+        VecDeque::<u8>::read_args(&mut Cursor::new(b"\x03\x01\x02\x01"), args()).unwrap(),
+        [3, 1, 2, 1]
+    );
+    assert_eq!(
+        BinaryHeap::<u8>::read_args(&mut Cursor::new(b"\x03\x01\x02\x01"), args())
+            .unwrap()
+            .into_sorted_vec(),
+        [1, 1, 2, 3]
+    );
+    assert_eq!(
+        BTreeSet::<u8>::read_args(&mut Cursor::new(b"\x03\x01\x02\x01"), args()).unwrap(),
+        BTreeSet::from([1, 2, 3])
+    );
+    assert!(matches!(
+        BTreeSet::<u8>::read_args(&mut Cursor::new(b"\x01"), args())
+            .expect_err("accepted bad data"),
+        binrw::Error::Io(..)
+    ));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_set() {
+    use std::collections::HashSet;
+
+    let args = binrw::VecArgs::builder().count(4).finalize();
+    assert_eq!(
+        HashSet::<u8>::read_args(&mut Cursor::new(b"\x03\x01\x02\x01"), args).unwrap(),
+        HashSet::from([1, 2, 3])
+    );
+}
+
 #[test]
 fn non_zero() {
     assert!(matches!(
@@ -92,6 +157,33 @@ fn phantom_data() {
     core::marker::PhantomData::<()>::read(&mut Cursor::new(b"")).unwrap();
 }
 
+#[test]
+fn shared() {
+    use alloc::{borrow::Cow, rc::Rc, sync::Arc};
+
+    assert_eq!(
+        Rc::<u8>::read(&mut Cursor::new(b"\x03")).unwrap(),
+        Rc::new(3)
+    );
+    assert_eq!(
+        Arc::<u16>::read_be(&mut Cursor::new(b"\0\x03")).unwrap(),
+        Arc::new(3)
+    );
+    assert_eq!(
+        &*Arc::<[u16]>::read_be_args(&mut Cursor::new(b"\0\x01\0\x02"), binrw::args! { count: 2 })
+            .unwrap(),
+        [1, 2]
+    );
+    assert_eq!(
+        &*Rc::<[u8]>::read_args(&mut Cursor::new(b"\x01\x02"), binrw::args! { count: 2 }).unwrap(),
+        [1, 2]
+    );
+    assert!(matches!(
+        Cow::<[u8]>::read_args(&mut Cursor::new(b"\x01"), binrw::args! { count: 1 }).unwrap(),
+        Cow::Owned(bytes) if bytes == [1]
+    ));
+}
+
 #[test]
 fn tuple() {
     assert_eq!(
@@ -100,6 +192,19 @@ fn tuple() {
     );
 }
 
+#[test]
+fn wrapping() {
+    assert_eq!(
+        core::num::Wrapping::<u16>::read_be(&mut Cursor::new(b"\0\x01")).unwrap(),
+        core::num::Wrapping(1)
+    );
+    #[cfg(has_saturating)]
+    assert_eq!(
+        core::num::Saturating::<u16>::read_le(&mut Cursor::new(b"\x01\0")).unwrap(),
+        core::num::Saturating(1)
+    );
+}
+
 #[test]
 fn vec_u8() {
     assert!(matches!(
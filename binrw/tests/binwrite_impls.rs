@@ -1,3 +1,5 @@
+extern crate alloc;
+
 use binrw::{BinWrite, Endian};
 
 macro_rules! compare {
@@ -14,6 +16,15 @@ macro_rules! compare {
     };
 }
 
+#[test]
+fn atomic() {
+    use core::sync::atomic::{AtomicI16, AtomicU32, AtomicU8};
+
+    compare!(AtomicU8::new(1), b"\x01");
+    compare!(AtomicI16::new(-2), Endian::Big, b"\xff\xfe");
+    compare!(AtomicU32::new(1), Endian::Little, b"\x01\0\0\0");
+}
+
 #[test]
 #[allow(unused_allocation)]
 fn boxed() {
@@ -60,6 +71,25 @@ fn clone_args() {
     );
 }
 
+#[test]
+fn collections() {
+    use std::collections::{BTreeSet, BinaryHeap, VecDeque};
+
+    compare!(VecDeque::from([1_u16, 2]), Endian::Big, b"\0\x01\0\x02");
+    compare!(BinaryHeap::from([1_u8]), b"\x01");
+    compare!(BTreeSet::from([2_u8, 1, 3]), b"\x01\x02\x03");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_set() {
+    compare!(
+        std::collections::HashSet::<u16>::from([1]),
+        Endian::Little,
+        b"\x01\0"
+    );
+}
+
 #[test]
 fn non_zero() {
     compare!(core::num::NonZeroU8::new(1).unwrap(), b"\x01");
@@ -86,6 +116,24 @@ fn phantom_data() {
     compare!(core::marker::PhantomData::<()>, b"");
 }
 
+#[test]
+fn shared() {
+    use alloc::{borrow::Cow, rc::Rc, sync::Arc};
+
+    compare!(Rc::<str>::from("rc"), b"rc");
+    compare!(Arc::<str>::from("arc"), b"arc");
+    compare!(Rc::<[u8]>::from(&b"\x01\x02"[..]), b"\x01\x02");
+    compare!(
+        Arc::<[u16]>::from(&[1, 2][..]),
+        Endian::Big,
+        b"\0\x01\0\x02"
+    );
+    compare!(Arc::new(3_u16), Endian::Little, b"\x03\0");
+    compare!(Cow::Borrowed("cow"), b"cow");
+    compare!(Cow::<[u8]>::Owned(vec![1, 2]), b"\x01\x02");
+    compare!(*"str", b"str");
+}
+
 #[test]
 fn tuple() {
     compare!((1_u8, 2_u8), b"\x01\x02");
@@ -99,3 +147,31 @@ fn vec_i8() {
     vec![-1_i8; 4].write(&mut output).unwrap();
     assert_eq!(output.into_inner(), b"\xff\xff\xff\xff");
 }
+
+#[test]
+fn vec_int() {
+    compare!(vec![1_u16, 0x203], Endian::Big, b"\0\x01\x02\x03");
+    compare!(vec![1_u16, 0x203], Endian::Little, b"\x01\0\x03\x02");
+    compare!(vec![-2_i32], Endian::Big, b"\xff\xff\xff\xfe");
+    compare!(vec![1_u64], Endian::Little, b"\x01\0\0\0\0\0\0\0");
+
+    // Lists longer than the byte swapping buffer are written in chunks
+    let input = (0..1000_u32).collect::<Vec<_>>();
+    let expected = input
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect::<Vec<_>>();
+    compare!(input, Endian::Big, expected);
+    let expected = input
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect::<Vec<_>>();
+    compare!(input, Endian::Little, expected);
+}
+
+#[test]
+fn wrapping() {
+    compare!(core::num::Wrapping(1_u16), Endian::Big, b"\0\x01");
+    #[cfg(has_saturating)]
+    compare!(core::num::Saturating(1_u16), Endian::Little, b"\x01\0");
+}
@@ -45,16 +45,16 @@ fn dbg() {
             format!(
                 concat!(
                     "[{file}:{offset_0} | pad_before 0x2]\n",
-                    "[{file}:{offset_0} | offset 0x4] value = 0x4\n",
+                    "[{file}:{offset_0} | offset 0x4..0x8] value = 0x4\n",
                     "[{file}:{offset_0} | pad_after 0x1]\n",
                     "[{file}:{offset_1} | align_before 0xa]\n",
-                    "[{file}:{offset_1} | offset 0xa] inner = Inner(\n",
+                    "[{file}:{offset_1} | offset 0xa..0xe] inner = Inner(\n",
                     "    0xeffed,\n",
                     ")\n",
                     "[{file}:{offset_1} | align_after 0x10]\n",
-                    "[{file}:{offset_2} | offset 0x10] last = 0x42\n",
+                    "[{file}:{offset_2} | offset 0x10..0x11] last = 0x42\n",
                     "[{file}:{offset_2} | pad_size_to 0x4]\n",
-                    "[{file}:{offset_3} | offset 0x14] terminator = 0x69\n",
+                    "[{file}:{offset_3} | offset 0x14..0x15] terminator = 0x69\n",
                 ),
                 file = core::file!(),
                 offset_0 = if cfg!(nightly) { 16 } else { 11 },
@@ -86,6 +86,7 @@ fn enum_field_args() {
 
 #[test]
 fn enum_non_copy_args() {
+    #[allow(dead_code)]
     #[derive(BinRead, Debug)]
     #[br(import(a: NonCopyArg))]
     enum Test {
@@ -99,6 +100,7 @@ fn enum_non_copy_args() {
         },
     }
 
+    #[allow(dead_code)]
     #[derive(Clone)]
     struct NonCopyArg(u8);
 }
@@ -180,6 +182,149 @@ fn enum_magic() {
     assert_eq!(result, Test::One { a: 515 });
 }
 
+#[test]
+fn enum_magic_alternatives() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    enum Test {
+        #[br(magic(b"II" | b"MM", name = order))]
+        Image {
+            #[br(is_little = order == *b"II")]
+            a: u16,
+        },
+        #[br(magic(0u8))]
+        Empty,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"II\x01\0")).unwrap(),
+        Test::Image { a: 1 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"MM\x01\0")).unwrap(),
+        Test::Image { a: 256 }
+    );
+    assert_eq!(Test::read(&mut Cursor::new(b"\0")).unwrap(), Test::Empty);
+}
+
+#[test]
+fn enum_fallback() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    enum Test {
+        // The fallback is tried last even though it comes first
+        #[br(fallback)]
+        Unknown {
+            tag: u8,
+            len: u8,
+            #[br(count = len)]
+            data: Vec<u8>,
+        },
+        #[br(magic(1u8))]
+        One { a: u16 },
+        #[br(magic(2u8))]
+        Two,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x02\x03")).unwrap(),
+        Test::One { a: 0x203 }
+    );
+    assert_eq!(Test::read(&mut Cursor::new(b"\x02")).unwrap(), Test::Two);
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x07\x02\x03\x04")).unwrap(),
+        Test::Unknown {
+            tag: 7,
+            len: 2,
+            data: vec![3, 4]
+        }
+    );
+    // A known variant which fails is also read by the fallback
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x00")).unwrap(),
+        Test::Unknown {
+            tag: 1,
+            len: 0,
+            data: vec![]
+        }
+    );
+    Test::read(&mut Cursor::new(b"\x07\x02\x03")).expect_err("accepted bad data");
+}
+
+#[test]
+fn enum_tag() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, import(kind: u8), tag = kind)]
+    enum Payload {
+        #[br(tag_value = 1)]
+        Point {
+            x: u16,
+            y: u16,
+        },
+        #[br(tag_value = 2)]
+        Empty,
+        #[br(tag_value = 3)]
+        Flag(#[br(assert(self_0 < 2))] u8),
+        Unknown(u8),
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Record {
+        kind: u8,
+        len: u8,
+        #[br(args(kind))]
+        payload: Payload,
+    }
+
+    assert_eq!(
+        Record::read(&mut Cursor::new(b"\x01\x04\0\x01\0\x02")).unwrap(),
+        Record {
+            kind: 1,
+            len: 4,
+            payload: Payload::Point { x: 1, y: 2 }
+        }
+    );
+    assert_eq!(
+        Record::read(&mut Cursor::new(b"\x02\0")).unwrap().payload,
+        Payload::Empty
+    );
+    assert_eq!(
+        Record::read(&mut Cursor::new(b"\x09\x01\x07"))
+            .unwrap()
+            .payload,
+        Payload::Unknown(7)
+    );
+
+    // A tagged variant which fails is not followed by the untagged variants
+    let mut data = Cursor::new(b"\x03\x05");
+    let error = Payload::read_args(&mut data, (3,)).expect_err("accepted bad data");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { .. }
+    ));
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn enum_tag_all_tagged() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, import(kind: u32), tag = kind)]
+    enum Payload {
+        #[br(tag_value = 0x10)]
+        A(u8),
+        #[br(tag_value = 0x20)]
+        B(u16),
+    }
+
+    assert_eq!(
+        Payload::read_args(&mut Cursor::new(b"\x01\x02"), (0x20,)).unwrap(),
+        Payload::B(0x201)
+    );
+    let error = Payload::read_args(&mut Cursor::new(b"\x01\x02"), (0x30,)).unwrap_err();
+    assert!(matches!(error, binrw::Error::NoVariantMatch { pos: 0 }));
+}
+
 #[test]
 fn enum_magic_holey() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -302,6 +447,16 @@ fn enum_rewind_on_variant_assert() {
     assert_eq!(expected, data.stream_position().unwrap());
 }
 
+#[test]
+fn enum_empty() {
+    #[derive(BinRead, Debug)]
+    enum Never {}
+
+    let error =
+        Never::read_le(&mut Cursor::new(b"\x01")).expect_err("read a value of an empty enum");
+    assert!(matches!(error, binrw::Error::NoVariantMatch { pos: 0 }));
+}
+
 #[test]
 fn enum_return_unexpected_error() {
     #[derive(BinRead, Debug)]
@@ -317,6 +472,88 @@ fn enum_return_unexpected_error() {
     assert!(matches!(error, binrw::Error::NoVariantMatch { .. }));
 }
 
+#[test]
+fn enum_visitor() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    struct Palette(u8);
+
+    #[binread]
+    #[br(little, visitor)]
+    enum Chunk {
+        #[br(magic(0u8))]
+        End,
+        #[br(magic(1u8))]
+        Palette(Palette),
+        #[br(magic(2u8))]
+        ImageData {
+            #[br(temp)]
+            len: u8,
+            #[br(count = len)]
+            data: Vec<u8>,
+        },
+        #[br(magic(3u8))]
+        Offset(i8, i8),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl ChunkVisitor for Recorder {
+        fn visit_end(&mut self) {
+            self.0.push("end".into());
+        }
+
+        fn visit_palette(&mut self, palette: &Palette) {
+            self.0.push(format!("palette {}", palette.0));
+        }
+
+        fn visit_image_data(&mut self, data: &Vec<u8>) {
+            self.0.push(format!("image data {data:?}"));
+        }
+
+        fn visit_offset(&mut self, x: &i8, y: &i8) {
+            self.0.push(format!("offset {x} {y}"));
+        }
+    }
+
+    let chunks = <Vec<Chunk>>::read_args(
+        &mut Cursor::new(b"\x01\x07\x02\x02\x08\x09\x03\x01\xff\0"),
+        binrw::VecArgs::builder().count(4).finalize(),
+    )
+    .unwrap();
+
+    let mut recorder = Recorder::default();
+    for chunk in &chunks {
+        chunk.accept(&mut recorder);
+    }
+    assert_eq!(
+        recorder.0,
+        ["palette 7", "image data [8, 9]", "offset 1 -1", "end"]
+    );
+
+    // The visitor should only be generated once when both traits are derived
+    #[binrw::binrw]
+    #[br(visitor)]
+    enum Mixed {
+        #[brw(magic(0u8))]
+        A(u8),
+    }
+
+    struct Counter(u8);
+
+    impl MixedVisitor for Counter {
+        fn visit_a(&mut self, a: &u8) {
+            self.0 += a;
+        }
+    }
+
+    let mut counter = Counter(1);
+    Mixed::read_le(&mut Cursor::new(b"\0\x02"))
+        .unwrap()
+        .accept(&mut counter);
+    assert_eq!(counter.0, 3);
+}
+
 #[test]
 fn mixed_enum() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -337,3 +574,42 @@ fn mixed_enum() {
     let result = Test::read(&mut Cursor::new(b"\x02\0\x03\0\x04")).unwrap();
     assert_eq!(result, Test::Two { a: 3, b: 4 });
 }
+
+#[test]
+fn enum_dump() {
+    #[derive(BinRead)]
+    #[br(big, dump)]
+    enum Chunk {
+        #[br(magic(0u8))]
+        End,
+        #[br(magic(1u8))]
+        Text { len: u8, r#char: Letter },
+        #[br(magic(2u8))]
+        Pair(u8, i8),
+    }
+
+    #[derive(BinRead)]
+    #[br(dump)]
+    struct Letter(#[br(map = |b: u8| char::from(b))] char);
+
+    #[derive(BinRead)]
+    #[br(repr = u8, dump)]
+    enum Kind {
+        A,
+        B,
+    }
+
+    let chunks = Vec::<Chunk>::read_args(
+        &mut Cursor::new(b"\x01\x02a\x02\x03\xff\0"),
+        binrw::args! { count: 3 },
+    )
+    .unwrap();
+    assert_eq!(
+        binrw::dump::to_text(&chunks),
+        "[\n    Text {\n        len: 2\n        char: Letter(\n            0: 'a'\n        )\n    }\n    Pair(\n        0: 3\n        1: -1\n    )\n    End\n]"
+    );
+    assert_eq!(
+        binrw::dump::to_text(&Kind::read(&mut Cursor::new(b"\x01")).unwrap()),
+        "B"
+    );
+}
@@ -2,6 +2,7 @@ mod binwrite_temp;
 mod r#enum;
 mod fn_helper;
 mod map_args;
+mod sequential;
 mod r#struct;
 mod struct_generic;
 mod struct_map;
@@ -0,0 +1,121 @@
+use binrw::{binrw, BinRead, NullString, ReadSequential, WriteSequential};
+
+#[test]
+fn sequential_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    struct Entry<T: 'static>
+    where
+        T: for<'a> BinRead<Args<'a> = ()> + for<'a> binrw::BinWrite<Args<'a> = ()>,
+    {
+        value: T,
+        name: NullString,
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little, magic = b"SQ")]
+    struct Test {
+        #[bw(calc = entries.len() as u8)]
+        len: u8,
+        #[br(count = len)]
+        entries: Vec<Entry<u16>>,
+        #[br(ignore)]
+        #[bw(ignore)]
+        skipped: u8,
+        trailer: Option<[u8; 2]>,
+    }
+
+    let data = b"SQ\x02\x01\x00a\0\x02\x00bc\0\xff\xfe";
+    let value = Test::read_sequential(&mut data.as_slice()).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            entries: vec![
+                Entry {
+                    value: 1,
+                    name: "a".into(),
+                },
+                Entry {
+                    value: 2,
+                    name: "bc".into(),
+                },
+            ],
+            skipped: 0,
+            trailer: Some([0xff, 0xfe]),
+        }
+    );
+
+    let mut out = Vec::new();
+    value.write_sequential(&mut out).unwrap();
+    assert_eq!(out, data);
+}
+
+#[test]
+fn sequential_error_position() {
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Test {
+        _a: u16,
+        #[br(magic = 0u8)]
+        _b: u8,
+    }
+
+    let error = Test::read_sequential(&mut b"\0\0\x01\0".as_slice()).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::BadMagic { pos: 2, .. }
+    ));
+}
+
+#[test]
+fn sequential_eof_default() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Rec {
+        a: u8,
+        #[br(if_eof_default)]
+        b: u16,
+    }
+
+    assert_eq!(
+        Rec::read_sequential(&mut b"\x01\x02\x03".as_slice()).unwrap(),
+        Rec { a: 1, b: 0x302 }
+    );
+    assert_eq!(
+        Rec::read_sequential(&mut b"\x01".as_slice()).unwrap(),
+        Rec { a: 1, b: 0 }
+    );
+}
+
+#[test]
+fn sequential_ignored_rewind_position() {
+    use binrw::{
+        io::{Read, Seek, SeekFrom},
+        BinResult, Endian,
+    };
+
+    // Reports where the stream is after trying to rewind
+    struct Rewind(u64);
+
+    impl BinRead for Rewind {
+        type Args<'a> = ();
+
+        fn read_options<R: Read + Seek>(reader: &mut R, _: Endian, (): ()) -> BinResult<Self> {
+            reader.read_exact(&mut [0; 2])?;
+            Ok(Self(reader.seek(SeekFrom::Start(0))?))
+        }
+    }
+
+    impl ReadSequential for Rewind {}
+
+    let mut data = b"\x01\x02\x03".as_slice();
+    assert_eq!(
+        Rewind::read_sequential_options(&mut data, Endian::Little, ())
+            .unwrap()
+            .0,
+        2
+    );
+    assert_eq!(data, b"\x03");
+}
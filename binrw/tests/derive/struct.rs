@@ -122,6 +122,117 @@ fn assert_formatted() {
     }
 }
 
+#[test]
+fn assert_formatted_captured() {
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    #[br(import(entry: usize))]
+    struct Test {
+        mode: u8,
+        #[br(assert(mode < 4 && b != 0, "bad mode {mode:#x} (b = {}) at entry {entry}", b))]
+        b: u8,
+    }
+
+    Test::read_le_args(&mut Cursor::new("\x01\x01"), (3,)).unwrap();
+    let error =
+        Test::read_le_args(&mut Cursor::new("\x05\x02"), (3,)).expect_err("accepted bad data");
+    match error {
+        binrw::Error::AssertFail { pos, message } => {
+            assert_eq!(pos, 0);
+            assert_eq!(message, "bad mode 0x5 (b = 2) at entry 3");
+        }
+        _ => panic!("bad error type"),
+    }
+}
+
+#[test]
+fn bit_order() {
+    use binrw::{binrw, bits::BitOrder, BinWrite};
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, bit_order = BitOrder::Lsb0)]
+    struct Test {
+        #[brw(bits = 3)]
+        a: u8,
+        #[brw(bits = 5)]
+        b: u8,
+        // Changing the bit order starts a new byte
+        #[brw(bits = 4, bit_order = BitOrder::Msb0)]
+        c: u8,
+        #[brw(bits = 12)]
+        d: u16,
+        last: u16,
+    }
+
+    let expected = Test {
+        a: 5,
+        b: 0b10011,
+        c: 0xa,
+        d: 0xabc,
+        last: 0x102,
+    };
+
+    let result = Test::read(&mut Cursor::new(b"\x9d\xa5\xbc\x0a\x01\x02")).unwrap();
+    assert_eq!(result, expected);
+
+    let mut output = Cursor::new(vec![]);
+    expected.write(&mut output).unwrap();
+    assert_eq!(output.into_inner(), b"\x9d\xa0\xbc\x0a\x01\x02");
+}
+
+#[test]
+fn bits() {
+    use binrw::{binrw, BinWrite};
+
+    #[derive(Debug, PartialEq)]
+    struct Mode(u8);
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    struct Test {
+        #[brw(bits = 3)]
+        a: u8,
+        #[brw(bits = 5)]
+        b: i8,
+        #[brw(bits = 1)]
+        c: bool,
+        #[br(bits = 2, map = Mode)]
+        #[bw(bits = 2, map = |mode: &Mode| mode.0)]
+        mode: Mode,
+        tail: u8,
+        #[brw(big, bits = 12)]
+        d: u16,
+        last: u8,
+    }
+
+    let expected = Test {
+        a: 5,
+        b: -3,
+        c: true,
+        mode: Mode(2),
+        tail: 0x42,
+        d: 0xabc,
+        last: 1,
+    };
+
+    // Unused bits at the end of each run are skipped when reading
+    let result = Test::read(&mut Cursor::new(b"\xed\xfd\x42\xab\xcf\x01")).unwrap();
+    assert_eq!(result, expected);
+
+    // and written as zero
+    let mut output = Cursor::new(vec![]);
+    expected.write(&mut output).unwrap();
+    assert_eq!(output.into_inner(), b"\xed\x05\x42\xab\xc0\x01");
+
+    let mut output = Cursor::new(vec![]);
+    let error = Test { a: 8, ..expected }
+        .write(&mut output)
+        .expect_err("accepted too large value");
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 0, .. }));
+}
+
 #[test]
 fn calc_temp_field() {
     #[binread]
@@ -146,6 +257,55 @@ fn calc_temp_field() {
     );
 }
 
+#[test]
+fn checksum() {
+    use binrw::checksum::{crc16, crc32};
+
+    #[binrw::binread]
+    #[derive(Debug, PartialEq)]
+    #[br(big)]
+    struct Chunk {
+        #[br(temp, checksum(crc16, over = header))]
+        header_crc: u16,
+        header: [u8; 4],
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[br(temp, checksum(crc32, over = len..=data))]
+        crc: u32,
+    }
+
+    let mut data = b"\0\0IHDR\x03abc\0\0\0\0".to_vec();
+    data[..2].copy_from_slice(&crc16(b"IHDR").to_be_bytes());
+    let crc = crc32(b"\x03abc").to_be_bytes();
+    data[10..].copy_from_slice(&crc);
+
+    let mut reader = Cursor::new(&data);
+    assert_eq!(
+        Chunk::read(&mut reader).unwrap(),
+        Chunk {
+            header: *b"IHDR",
+            len: 3,
+            data: b"abc".to_vec(),
+        }
+    );
+    assert_eq!(reader.position(), 14);
+
+    data[8] = b'x';
+    let error = Chunk::read(&mut Cursor::new(&data)).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 6, .. }
+    ));
+
+    data[2] = b'i';
+    let error = Chunk::read(&mut Cursor::new(&data)).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 2, .. }
+    ));
+}
+
 #[test]
 fn count_too_big() {
     #[derive(BinRead, Debug)]
@@ -185,6 +345,125 @@ fn count_no_useless_conversion_lint() {
     );
 }
 
+#[test]
+fn count_with_parser() {
+    use binrw::{helpers::count_with, Endian};
+
+    fn read_scaled<R: binrw::io::Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (scale,): (u16,),
+    ) -> BinResult<u16> {
+        u16::read_options(reader, endian, ()).map(|value| value * scale)
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Test {
+        len: u8,
+        #[br(parse_with = count_with(len.into(), read_scaled), args(10))]
+        data: Vec<u16>,
+        #[br(parse_with = count_with(2, |reader, endian, ()| u8::read_options(reader, endian, ()).map(|b| !b)))]
+        inverted: Vec<u8>,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x02\x01\0\x02\0\xfe\xfd")).unwrap(),
+        Test {
+            len: 2,
+            data: vec![10, 20],
+            inverted: vec![1, 2],
+        }
+    );
+}
+
+#[test]
+fn until_terminator() {
+    use binrw::helpers::{until_terminator, write_terminated};
+
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        #[br(parse_with = until_terminator(b"\xff\xff"))]
+        #[bw(write_with = write_terminated(b"\xff\xff"))]
+        data: Vec<u8>,
+        after: u8,
+    }
+
+    let data = b"\x01\xff\x02\xff\xff\x03";
+    let value = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            data: vec![1, 0xff, 2],
+            after: 3
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    binrw::BinWrite::write(&value, &mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    Test::read(&mut Cursor::new(b"\x01\x02\xff")).expect_err("accepted unterminated data");
+}
+
+#[test]
+fn default_endian() {
+    use binrw::Endian;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(default_big, endian_var = endian)]
+    struct Test {
+        a: u16,
+        #[br(little)]
+        b: u16,
+        #[br(calc = endian)]
+        endian: Endian,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Parent {
+        child: Test,
+        #[br(big)]
+        big_child: Test,
+    }
+
+    let data = b"\0\x01\x02\0";
+    assert_eq!(
+        Test::read(&mut Cursor::new(data)).unwrap(),
+        Test {
+            a: 1,
+            b: 2,
+            endian: Endian::Big
+        }
+    );
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(data)).unwrap(),
+        Test {
+            a: 256,
+            b: 2,
+            endian: Endian::Little
+        }
+    );
+    assert_eq!(
+        Parent::read(&mut Cursor::new(b"\x01\0\x02\0\0\x01\x02\0")).unwrap(),
+        Parent {
+            child: Test {
+                a: 1,
+                b: 2,
+                endian: Endian::Little
+            },
+            big_child: Test {
+                a: 1,
+                b: 2,
+                endian: Endian::Big
+            },
+        }
+    );
+}
+
 #[test]
 fn deref_now() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -375,6 +654,89 @@ fn all_default_imports() {
     assert_eq!(result, Test { a: 1 });
 }
 
+#[test]
+fn list_default_imports() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(base: u8, scale: u8 = 2, offset: u8 = base))]
+    struct Child {
+        #[br(map = |a: u8| a * scale + offset)]
+        a: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Parent {
+        #[br(args(1))]
+        a: Child,
+        #[br(args(1, 3))]
+        b: Child,
+        #[br(args(1, 3, 0))]
+        c: Child,
+    }
+
+    let result = Parent::read_le(&mut Cursor::new(b"\x01\x01\x01")).unwrap();
+    assert_eq!(
+        result,
+        Parent {
+            a: Child { a: 3 },
+            b: Child { a: 4 },
+            c: Child { a: 3 },
+        }
+    );
+
+    let result = Child::read_le_args(&mut Cursor::new(b"\x02"), (5,).into()).unwrap();
+    assert_eq!(result, Child { a: 9 });
+}
+
+#[test]
+fn list_all_default_imports() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(scale: u8 = 2))]
+    struct Test {
+        #[br(map = |a: u8| a * scale)]
+        a: u8,
+    }
+
+    let result = Test::read_le(&mut Cursor::new(b"\x01")).unwrap();
+    assert_eq!(result, Test { a: 2 });
+}
+
+#[test]
+fn list_imports_coerce() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(f: &dyn Fn(u8) -> u8))]
+    struct Child {
+        #[br(map = f)]
+        a: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(f: &dyn Fn(u8) -> u8, extra: u8 = 0))]
+    struct DefaultChild {
+        #[br(map = |a: u8| f(a) + extra)]
+        a: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Parent {
+        #[br(args(&|a| a + 1))]
+        a: Child,
+        #[br(args(&|a| a * 2))]
+        b: DefaultChild,
+        #[br(args(&|a| a * 2, 1))]
+        c: DefaultChild,
+    }
+
+    let result = Parent::read_le(&mut Cursor::new(b"\x01\x02\x03")).unwrap();
+    assert_eq!(
+        result,
+        Parent {
+            a: Child { a: 2 },
+            b: DefaultChild { a: 4 },
+            c: DefaultChild { a: 7 },
+        }
+    );
+}
+
 #[test]
 fn recursive_lifetime_imports() {
     #[derive(Default)]
@@ -473,6 +835,88 @@ fn if_alternate() {
     assert_eq!(result.a, 10);
 }
 
+#[test]
+fn if_eof_default() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Test {
+        a: u8,
+        #[br(if_eof_default)]
+        b: u16,
+        #[br(if_eof_default, or = 3)]
+        c: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01")).unwrap(),
+        Test { a: 1, b: 0, c: 3 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x02\0")).unwrap(),
+        Test { a: 1, b: 2, c: 3 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x02\0\x04")).unwrap(),
+        Test { a: 1, b: 2, c: 4 }
+    );
+
+    // A field which is only partly present is still an error
+    let error = Test::read(&mut Cursor::new(b"\x01\x02")).expect_err("accepted bad data");
+    assert!(error.is_eof());
+}
+
+#[test]
+fn since_until() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, import(version: u8), version = version)]
+    struct Test {
+        a: u8,
+        #[br(since(2))]
+        b: u8,
+        #[br(until(3))]
+        c: u8,
+        #[br(since = 2, until = 4, if(a != 0))]
+        d: u8,
+    }
+
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02"), (1,)).unwrap(),
+        Test {
+            a: 1,
+            b: 0,
+            c: 2,
+            d: 0
+        }
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02\x03\x04"), (2,)).unwrap(),
+        Test {
+            a: 1,
+            b: 2,
+            c: 3,
+            d: 4
+        }
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\0\x02"), (3,)).unwrap(),
+        Test {
+            a: 0,
+            b: 2,
+            c: 0,
+            d: 0
+        }
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02"), (4,)).unwrap(),
+        Test {
+            a: 1,
+            b: 2,
+            c: 0,
+            d: 0
+        }
+    );
+}
+
 #[test]
 fn ignore_and_default() {
     #[derive(Debug, Eq, PartialEq)]
@@ -530,6 +974,85 @@ fn magic_field() {
     assert_eq!(result, Test { b: b'B', d: b'D' });
 }
 
+#[test]
+fn magic_mask() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, magic(b"RIF?", mask = b"\xff\xff\xff\0"))]
+    struct Test {
+        #[br(magic = 0x1200u16, mask = 0xff00)]
+        a: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"RIFF\x12\x34\x01")).unwrap(),
+        Test { a: 1 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"RIFX\x12\0\x02")).unwrap(),
+        Test { a: 2 }
+    );
+    let error = Test::read(&mut Cursor::new(b"RIXF\x12\0\0")).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 0, .. }));
+    let error = Test::read(&mut Cursor::new(b"RIFF\x13\0\0")).expect_err("accepted bad data");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::BadMagic { pos: 4, .. }
+    ));
+}
+
+#[test]
+fn magic_alternatives() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, magic(b"V1" | b"V2", name = version))]
+    struct Test {
+        a: u8,
+        #[br(if(version == *b"V2"))]
+        b: Option<u8>,
+        #[br(magic(0u8 | 0xffu8))]
+        c: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"V1\x01\0\x03")).unwrap(),
+        Test {
+            a: 1,
+            b: None,
+            c: 3
+        }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"V2\x01\x02\xff\x03")).unwrap(),
+        Test {
+            a: 1,
+            b: Some(2),
+            c: 3
+        }
+    );
+    let error = Test::read(&mut Cursor::new(b"V3\x01\0\x03")).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 0, .. }));
+    let error = Test::read(&mut Cursor::new(b"V1\x01\x01\x03")).expect_err("accepted bad data");
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::BadMagic { pos: 3, .. }
+    ));
+}
+
+#[test]
+fn magic_alternatives_mask() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, magic(0x10u8 | 0x20u8, mask = 0xf0, name = kind))]
+    struct Test {
+        #[br(calc = kind & 0xf)]
+        minor: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x23")).unwrap(),
+        Test { minor: 3 }
+    );
+    Test::read(&mut Cursor::new(b"\x33")).expect_err("accepted bad data");
+}
+
 #[test]
 fn magic_const() {
     use binrw::meta::ReadMagic;
@@ -608,19 +1131,47 @@ fn map_stream_parse_with_args() {
 }
 
 #[test]
-fn named_args_trailing_commas() {
-    #[rustfmt::skip]
-    #[derive(BinRead, Debug, PartialEq)]
-    struct Test {
-        count: u8,
-        #[br(args {
-            count: count.into(),
-            inner: ItemBinReadArgs { count: 2, },
-        })]
-        items: Vec<Item>,
-    }
+fn until_eof_non_static_collection() {
+    use binrw::helpers::until_eof;
+    use core::marker::PhantomData;
 
-    #[rustfmt::skip]
+    #[derive(Debug, PartialEq)]
+    struct Bytes<'a>(Vec<u8>, PhantomData<&'a ()>);
+
+    impl<'a> FromIterator<u8> for Bytes<'a> {
+        fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+            Self(iter.into_iter().collect(), PhantomData)
+        }
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test<'a> {
+        #[br(parse_with = until_eof)]
+        a: Bytes<'a>,
+    }
+
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\x01\x02")).unwrap(),
+        Test {
+            a: Bytes(vec![1, 2], PhantomData)
+        }
+    );
+}
+
+#[test]
+fn named_args_trailing_commas() {
+    #[rustfmt::skip]
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        count: u8,
+        #[br(args {
+            count: count.into(),
+            inner: ItemBinReadArgs { count: 2, },
+        })]
+        items: Vec<Item>,
+    }
+
+    #[rustfmt::skip]
     #[derive(BinRead, Debug, PartialEq)]
     #[br(import { count: usize, })]
     struct Item(#[br(args { count, })] Vec<u8>);
@@ -705,6 +1256,43 @@ fn parse_with_default_args() {
     );
 }
 
+#[test]
+fn present_if() {
+    const HAS_A: u8 = 1;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        flags: u8,
+        #[br(present_if(flags, HAS_A))]
+        a: Option<u8>,
+        #[br(present_if(flags, 0b10))]
+        b: Option<u16>,
+        #[br(present_if(flags, 0b110))]
+        c: Option<u8>,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x02\0\x01")).unwrap(),
+        Test {
+            flags: 2,
+            a: None,
+            b: Some(1),
+            c: None,
+        }
+    );
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x07\x01\0\x02\x03")).unwrap(),
+        Test {
+            flags: 7,
+            a: Some(1),
+            b: Some(2),
+            c: Some(3),
+        }
+    );
+}
+
 #[test]
 fn args_type_hint_borrowck() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -772,6 +1360,40 @@ fn import_tuple() {
     assert_eq!(result.a.a, 3);
 }
 
+#[test]
+fn is_big_from_import() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        #[br(assert(order == *b"II" || order == *b"MM"))]
+        order: [u8; 2],
+        #[br(args(order == *b"MM"))]
+        body: Body,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(big: bool), is_big = big)]
+    struct Body {
+        a: u16,
+        b: u32,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"II\x01\0\x02\0\0\0")).unwrap(),
+        Test {
+            order: *b"II",
+            body: Body { a: 1, b: 2 }
+        }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"MM\0\x01\0\0\0\x02")).unwrap(),
+        Test {
+            order: *b"MM",
+            body: Body { a: 1, b: 2 }
+        }
+    );
+}
+
 #[test]
 fn mixed_attrs() {
     #[binread]
@@ -1003,6 +1625,42 @@ fn try_directive() {
     assert_eq!(result.a, Some([-1, 0]));
 }
 
+#[test]
+fn try_or() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        #[br(try, or = 0xffff, assert(a != 0))]
+        a: u16,
+        #[br(try, or = vec![1, 2], count = a & 3)]
+        b: Vec<u32>,
+        c: u8,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x01\0\0\0\x02\x03")).unwrap(),
+        Test {
+            a: 1,
+            b: vec![2],
+            c: 3
+        }
+    );
+
+    // The stream is restored to the start of the field which failed, so the
+    // bytes of the partly read list are read again by the next field
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x02\0\0\0\x02\x03")).unwrap(),
+        Test {
+            a: 2,
+            b: vec![1, 2],
+            c: 0
+        }
+    );
+
+    // A value which fails an assertion is not replaced
+    Test::read(&mut Cursor::new(b"\0\0\x03")).unwrap_err();
+}
+
 #[test]
 fn try_calc() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -1067,3 +1725,663 @@ fn no_clone_needed_for_parse_with() {
         }
     );
 }
+
+#[test]
+fn flexible_array_member() {
+    use binrw::{
+        binrw,
+        helpers::{until_size, written_size},
+        BinWrite, Endian,
+    };
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little, import(slot_size: u64))]
+    struct Record {
+        kind: u16,
+        #[br(parse_with = until_size(slot_size - 2))]
+        #[bw(assert(written_size(items, Endian::Little, ()).unwrap() + 2 == slot_size))]
+        items: Vec<u16>,
+    }
+
+    let record = Record::read_args(&mut Cursor::new(b"\x01\0\x02\0\x03\0\xff"), (6,)).unwrap();
+    assert_eq!(
+        record,
+        Record {
+            kind: 1,
+            items: vec![2, 3]
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    record.write_args(&mut out, (6,)).unwrap();
+    assert_eq!(out.into_inner(), b"\x01\0\x02\0\x03\0");
+
+    let error = Record::read_args(&mut Cursor::new(b"\x01\0\x02\0\x03\0"), (5,)).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 4, .. }
+    ));
+    assert_eq!(
+        Record::read_args(&mut Cursor::new(b"\x01\0"), (2,))
+            .unwrap()
+            .items,
+        []
+    );
+}
+
+#[test]
+fn sized_block() {
+    use binrw::helpers::sized_block;
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Header {
+        version: u8,
+        flags: u16,
+    }
+
+    #[binread]
+    #[derive(Debug)]
+    #[br(big)]
+    struct Chunk {
+        #[br(temp)]
+        len: u8,
+        #[br(parse_with = sized_block(len.into()))]
+        header: Header,
+        trailer: u8,
+    }
+
+    let mut reader = Cursor::new(b"\x05\x01\0\x02\xff\xff\x03");
+    let chunk = Chunk::read(&mut reader).unwrap();
+    assert_eq!(
+        chunk.header,
+        Header {
+            version: 1,
+            flags: 2
+        }
+    );
+    assert_eq!(chunk.trailer, 3);
+    assert_eq!(reader.position(), 7);
+
+    let error = Chunk::read(&mut Cursor::new(b"\x02\x01\0\x02\x03")).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::Io(error) if error.kind() == binrw::io::ErrorKind::UnexpectedEof)
+    );
+}
+
+#[test]
+fn decoded_block() {
+    use binrw::{
+        binrw,
+        helpers::{decoded_block, encoded_block},
+        BinWrite,
+    };
+
+    fn invert(data: &[u8]) -> binrw::io::Result<Vec<u8>> {
+        if data.is_empty() {
+            return Err(binrw::io::Error::new(
+                binrw::io::ErrorKind::InvalidData,
+                "empty block",
+            ));
+        }
+        Ok(data.iter().map(|byte| !byte).collect())
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Inner {
+        offset: u8,
+        #[br(seek_before = SeekFrom::Start(offset.into()), restore_position)]
+        #[bw(ignore)]
+        pointed: u8,
+        value: u16,
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Section {
+        len: u8,
+        #[br(parse_with = decoded_block(len.into(), invert))]
+        #[bw(write_with = encoded_block(invert))]
+        inner: Inner,
+        trailer: u8,
+    }
+
+    let data = b"\x03\xfe\xff\xfc\x04";
+    let mut reader = Cursor::new(data);
+    let section = Section::read(&mut reader).unwrap();
+    assert_eq!(
+        section.inner,
+        Inner {
+            offset: 1,
+            pointed: 0,
+            value: 3
+        }
+    );
+    assert_eq!(section.trailer, 4);
+    assert_eq!(reader.position(), 5);
+
+    let mut out = Cursor::new(Vec::new());
+    section.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    let error = Section::read(&mut Cursor::new(b"\x04\xfe\xff\xfc")).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::Io(error) if error.kind() == binrw::io::ErrorKind::UnexpectedEof)
+    );
+    let error = Section::read(&mut Cursor::new(b"\x00\x04")).unwrap_err();
+    assert!(
+        matches!(error.root_cause(), binrw::Error::Io(error) if error.kind() == binrw::io::ErrorKind::InvalidData)
+    );
+}
+
+#[test]
+fn impl_try_from() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, impl_try_from)]
+    struct Test {
+        a: u16,
+    }
+
+    assert_eq!(Test::try_from(&b"\0\x01"[..]).unwrap(), Test { a: 1 });
+    assert!(Test::try_from(&b"\0"[..]).is_err());
+
+    #[cfg(feature = "std")]
+    {
+        use std::io::Write;
+
+        let path = std::env::temp_dir().join(format!("binrw-impl-try-from-{}", std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(b"\0\x02\0\x03")
+            .unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        assert_eq!(Test::try_from(&mut file).unwrap(), Test { a: 2 });
+        assert_eq!(Test::try_from(&mut file).unwrap(), Test { a: 3 });
+        assert!(Test::try_from(&mut file).is_err());
+        drop(file);
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn error_type() {
+    use binrw::TryRead;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        TooBig(u8),
+        Other(bool),
+    }
+
+    impl From<binrw::Error> for TestError {
+        fn from(error: binrw::Error) -> Self {
+            TestError::Other(error.is_eof())
+        }
+    }
+
+    impl core::fmt::Display for TestError {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{self:?}")
+        }
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, impl_try_from, error = TestError)]
+    struct Test {
+        #[br(assert(a < 10, TestError::TooBig(a)))]
+        a: u8,
+        #[br(assert(b < 10, "too big"))]
+        b: u8,
+    }
+
+    assert_eq!(
+        Test::try_read(&mut Cursor::new(b"\x01\x02")).unwrap(),
+        Test { a: 1, b: 2 }
+    );
+    assert_eq!(
+        Test::try_read(&mut Cursor::new(b"\x0a\x02")).unwrap_err(),
+        TestError::TooBig(10)
+    );
+    assert_eq!(
+        Test::try_read(&mut Cursor::new(b"\x01\x0a")).unwrap_err(),
+        TestError::Other(false)
+    );
+    assert_eq!(
+        Test::try_read(&mut Cursor::new(b"\x01")).unwrap_err(),
+        TestError::Other(true)
+    );
+    assert_eq!(
+        Test::try_from(&b"\x0b\0"[..]).unwrap_err(),
+        TestError::TooBig(11)
+    );
+
+    // The `BinRead` implementation still returns `binrw::Error`
+    let error = Test::read(&mut Cursor::new(b"\x0a\x02")).unwrap_err();
+    assert_eq!(
+        error.custom_err::<TestError>(),
+        Some(&TestError::TooBig(10))
+    );
+}
+
+#[test]
+fn preserve_unknown() {
+    use binrw::{binrw, io::TakeSeekExt, BinWrite};
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, preserve_unknown)]
+    struct Header {
+        version: u16,
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        size: u8,
+        #[br(map_stream = |s| s.take_seek(u64::from(size)))]
+        header: Header,
+        trailer: u8,
+    }
+
+    let data = b"\x06\0\x02\xaa\xbb\xcc\xdd\xff";
+    let value = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            size: 6,
+            header: Header {
+                version: 2,
+                __binrw_unknown: vec![0xaa, 0xbb, 0xcc, 0xdd],
+            },
+            trailer: 0xff,
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
+
+#[test]
+fn preserve_unknown_size() {
+    use binrw::{binrw, BinWrite};
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, preserve_unknown(size))]
+    struct Chunk {
+        size: u16,
+        version: u16,
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, preserve_unknown = 3)]
+    struct Tuple(u8);
+
+    let data = b"\0\x06\0\x02\xaa\xbb\xff";
+    let mut input = Cursor::new(data);
+    let value = Chunk::read(&mut input).unwrap();
+    assert_eq!(
+        value,
+        Chunk {
+            size: 6,
+            version: 2,
+            __binrw_unknown: vec![0xaa, 0xbb],
+        }
+    );
+    assert_eq!(u8::read(&mut input).unwrap(), 0xff);
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), &data[..6]);
+
+    let value = Tuple::read(&mut Cursor::new(b"\x01\x02\x03\x04")).unwrap();
+    assert_eq!(value, Tuple(1, vec![2, 3]));
+
+    let error = Chunk::read(&mut Cursor::new(b"\0\x02\0\x02")).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 4, .. }
+    ));
+
+    let error = Chunk::read(&mut Cursor::new(b"\0\x08\0\x02\xaa")).unwrap_err();
+    assert!(matches!(error.root_cause(), binrw::Error::Io(..)));
+}
+
+#[test]
+fn borrowed_fields() {
+    use binrw::helpers::{args_iter, borrow_bytes, borrow_str};
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(source: &'a [u8]))]
+    struct Entry<'a> {
+        len: u8,
+        #[br(parse_with = borrow_str(source), args_raw = len.into())]
+        name: &'a str,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, import(source: &'a [u8]))]
+    struct Archive<'a> {
+        len: u16,
+        #[br(parse_with = args_iter(core::iter::repeat((source,)).take(len.into())))]
+        entries: Vec<Entry<'a>>,
+        #[br(parse_with = borrow_bytes(source), args_raw = 2)]
+        trailer: &'a [u8],
+    }
+
+    let source = b"\0\x02\x01a\x02bc\xff\xfe";
+    let archive = Archive::read_args(&mut Cursor::new(source), (source,)).unwrap();
+    assert_eq!(archive.entries[0].name, "a");
+    assert_eq!(archive.entries[1].name, "bc");
+    assert_eq!(archive.trailer, b"\xff\xfe");
+    assert!(core::ptr::eq(archive.trailer, &source[7..]));
+
+    let source = b"\x02\xff\xfe";
+    let error = Entry::read_le_args(&mut Cursor::new(source), (source,)).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 1, .. }
+    ));
+
+    let source = b"\x03ab";
+    let error = Entry::read_le_args(&mut Cursor::new(source), (source,)).unwrap_err();
+    assert!(matches!(error.root_cause(), binrw::Error::Io(..)));
+}
+
+#[test]
+fn wire_eq() {
+    use binrw::wire_eq::FieldDiff;
+
+    #[allow(dead_code)]
+    #[binread]
+    #[br(big, magic = b"HD", wire_eq)]
+    struct Header {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len)]
+        name: Vec<u8>,
+        version: u16,
+    }
+
+    let mut a = Cursor::new(b"HD\x02ab\0\x01");
+    let mut b = Cursor::new(b"\xffHD\x02ab\0\x01");
+    b.set_position(1);
+    assert_eq!(Header::wire_eq(&mut a, &mut b).unwrap(), None);
+    assert_eq!((a.position(), b.position()), (7, 8));
+
+    let mut a = Cursor::new(b"HD\x02ab\0\x01");
+    let mut b = Cursor::new(b"HD\x03abc\0\x01");
+    assert_eq!(
+        Header::wire_eq(&mut a, &mut b).unwrap(),
+        Some(FieldDiff {
+            field: "len",
+            a: 2..3,
+            b: 2..3,
+        })
+    );
+
+    let mut a = Cursor::new(b"HD\x02ab\0\x01");
+    let mut b = Cursor::new(b"HD\x02ab\0\x02");
+    let diff = Header::wire_eq(&mut a, &mut b).unwrap().unwrap();
+    assert_eq!(diff.field, "version");
+    assert_eq!(
+        diff.to_string(),
+        "field `version` differs: 0x5..0x7 in the first stream, 0x5..0x7 in the second stream"
+    );
+
+    #[allow(dead_code)]
+    #[derive(BinRead)]
+    #[br(wire_eq)]
+    struct Pair(u8, u16);
+
+    let diff = Pair::wire_eq_options(
+        &mut Cursor::new(b"\x01\0\x02"),
+        &mut Cursor::new(b"\x01\x02\0"),
+        binrw::Endian::Big,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(diff.field, "1");
+
+    let error = Pair::wire_eq_options(
+        &mut Cursor::new(b"\x01"),
+        &mut Cursor::new(b"\x01\x02\0"),
+        binrw::Endian::Big,
+    )
+    .unwrap_err();
+    assert!(matches!(error.root_cause(), binrw::Error::Io(..)));
+}
+
+#[test]
+fn dump() {
+    use binrw::dump;
+
+    #[derive(BinRead)]
+    #[br(big, dump)]
+    struct Flags(u8);
+
+    #[binread]
+    #[br(big, magic = b"HD", dump)]
+    struct Header {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len)]
+        items: Vec<u16>,
+        r#type: NullString,
+        #[br(if(items.is_empty()))]
+        flags: Option<Flags>,
+        ratio: f32,
+    }
+
+    let text = Header::read_dump(&mut Cursor::new(b"HD\x01\0\x02ab\0\x40\0\0\0")).unwrap();
+    assert_eq!(
+        text,
+        "Header {\n    items @ 0x3..0x5: [\n        2\n    ]\n    type @ 0x5..0x8: \"ab\"\n    flags @ 0x8..0x8: None\n    ratio @ 0x8..0xc: 2.0\n}"
+    );
+
+    let value = Header::read(&mut Cursor::new(b"HD\0\0\x01\0\0\0\0")).unwrap();
+    assert_eq!(
+        dump::to_text(&value),
+        "Header {\n    items: []\n    type: \"\"\n    flags: Some(Flags(\n        0: 1\n    ))\n    ratio: 0.0\n}"
+    );
+
+    #[derive(BinRead)]
+    #[br(dump)]
+    struct Empty;
+
+    assert_eq!(dump::to_text(&Empty), "Empty");
+    assert_eq!(Empty::read_dump(&mut Cursor::new(b"")).unwrap(), "Empty");
+}
+
+#[test]
+fn dyn_reader() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, dyn_reader, import(add: u8))]
+    struct Test<T: for<'a> BinRead<Args<'a> = ()> + 'static> {
+        #[br(map = |a: u8| a + add)]
+        a: u8,
+        #[br(count = a)]
+        b: Vec<T>,
+        c: Choice,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(dyn_reader)]
+    enum Choice {
+        #[br(magic = 1u8)]
+        A(u8),
+        #[br(magic = 2u8)]
+        B,
+    }
+
+    assert_eq!(
+        Test::<u16>::read_args(&mut Cursor::new(b"\x01\0\x02\0\x03\x01\x09"), (1,)).unwrap(),
+        Test {
+            a: 2,
+            b: vec![2, 3],
+            c: Choice::A(9),
+        }
+    );
+
+    // The reader can itself be a trait object
+    let mut data = Cursor::new(b"\0\x02\xff");
+    let mut reader: &mut dyn binrw::io::ReadSeek = &mut data;
+    let test = Test::<u16>::read_args(&mut reader, (0,)).unwrap();
+    assert_eq!(test.c, Choice::B);
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"\xff");
+}
+
+#[test]
+fn non_clone_args() {
+    struct Offset(u8);
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import { offset: &Offset })]
+    struct Named {
+        #[br(map = |v: u8| v + offset.0)]
+        value: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(offset: Offset = Offset(1)))]
+    struct Raw {
+        #[br(map = |v: u8| v + offset.0)]
+        value: u8,
+    }
+
+    let offset = Offset(2);
+    assert_eq!(
+        Named::read_le_args(&mut Cursor::new(b"\x01"), args! { offset: &offset }).unwrap(),
+        Named { value: 3 }
+    );
+    assert_eq!(
+        Raw::read_le(&mut Cursor::new(b"\x01")).unwrap(),
+        Raw { value: 2 }
+    );
+    assert_eq!(
+        Raw::read_le_args(&mut Cursor::new(b"\x01"), RawBinReadArgs(Offset(3))).unwrap(),
+        Raw { value: 4 }
+    );
+}
+
+#[test]
+fn import_raw_passthrough() {
+    struct Args {
+        count: usize,
+        offset: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import_raw(args: Args))]
+    struct Inner {
+        #[br(count = args.count, map = |v: Vec<u8>| v.into_iter().map(|v| v + args.offset).collect())]
+        values: Vec<u8>,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import_raw(args: Args))]
+    struct Outer {
+        #[br(args_raw = args)]
+        inner: Inner,
+    }
+
+    assert_eq!(
+        Outer::read_le_args(
+            &mut Cursor::new(b"\x01\x02\x03"),
+            Args {
+                count: 2,
+                offset: 1
+            }
+        )
+        .unwrap(),
+        Outer {
+            inner: Inner { values: vec![2, 3] }
+        }
+    );
+}
+
+#[test]
+fn pos_var() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, pos_var = pos, import(base: u64))]
+    struct Test {
+        a: u8,
+        #[br(map = |v: u8| u64::from(v) + pos + base)]
+        b: u64,
+        #[br(calc = pos, assert(pos == 2))]
+        c: u64,
+    }
+
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02"), (10,)).unwrap(),
+        Test { a: 1, b: 13, c: 2 }
+    );
+}
+
+#[test]
+fn top_level_assert_end_pos() {
+    #[binread]
+    #[derive(Debug, PartialEq)]
+    #[br(big, pos_var = pos, assert(pos == u64::from(size), "size {} but ended at {}", size, pos))]
+    struct Test {
+        size: u8,
+        #[br(temp)]
+        count: u8,
+        #[br(count = count)]
+        data: Vec<u8>,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x04\x02\x01\x02")).unwrap(),
+        Test {
+            size: 4,
+            data: vec![1, 2]
+        }
+    );
+    let error = Test::read(&mut Cursor::new(b"\x04\x01\x01")).expect_err("accepted bad data");
+    match error {
+        binrw::Error::AssertFail { message, .. } => {
+            assert_eq!(message, "size 4 but ended at 3");
+        }
+        _ => panic!("bad error type"),
+    }
+}
+
+#[test]
+fn seek_before_end() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Test {
+        // The footer is the last 4 bytes of the stream
+        #[br(seek_before = SeekFrom::End(-4), restore_position)]
+        directory_offset: u32,
+        first: u8,
+        #[br(seek_before = SeekFrom::End(i64::from(first) - 6))]
+        last: u8,
+    }
+
+    let mut data = Cursor::new(b"\x01\x02\x03\x04\x05\x00\x00\x00");
+    assert_eq!(
+        Test::read(&mut data).unwrap(),
+        Test {
+            directory_offset: 5,
+            first: 1,
+            last: 4,
+        }
+    );
+    assert_eq!(data.position(), 4);
+
+    // A failed seek restores the position from before the read
+    let mut data = Cursor::new(b"\0\0\0\0\0");
+    Test::read(&mut data).expect_err("accepted bad data");
+    assert_eq!(data.position(), 0);
+}
@@ -10,3 +10,20 @@ fn derive_generic() {
     let result = Test::<u8>::read_le(&mut Cursor::new(b"\0\x01\x02")).unwrap();
     assert_eq!(result.a, [0, 1, 2]);
 }
+
+#[test]
+fn generic_impl_try_from() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, impl_try_from)]
+    struct Test<T>
+    where
+        T: for<'a> BinRead<Args<'a> = ()>,
+    {
+        a: T,
+    }
+
+    assert_eq!(
+        Test::<u16>::try_from(&b"\x01\0"[..]).unwrap(),
+        Test { a: 1 }
+    );
+}
@@ -268,3 +268,29 @@ fn try_map_struct_closure() {
     let error = Test::read(&mut Cursor::new(b"\x01\0")).expect_err("accepted bad data");
     error.custom_err::<Oops>().unwrap();
 }
+
+#[test]
+fn try_map_enum() {
+    #[derive(BinRead)]
+    #[br(little)]
+    struct Raw {
+        kind: u8,
+        value: u16,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(try_map = |raw: Raw| match raw.kind {
+        0 => Ok(Self::Width(raw.value)),
+        1 => Ok(Self::Height(raw.value)),
+        _ => Err("bad kind"),
+    })]
+    enum Test {
+        Width(u16),
+        Height(u16),
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\x01\x02\0")).unwrap();
+    assert_eq!(result, Test::Height(2));
+    let error = Test::read(&mut Cursor::new(b"\x02\x02\0")).expect_err("accepted bad data");
+    error.custom_err::<&str>().unwrap();
+}
@@ -82,6 +82,45 @@ fn unit_enum_magic_bytes() {
     assert_eq!(Test::read(&mut Cursor::new(b"two0")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_magic_alternatives() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(big)]
+    enum Test {
+        #[br(magic(b"v1" | b"V1"))]
+        One,
+        #[br(magic(b"v2" | b"V2" | b"v?", mask = b"\xff\x7f"))]
+        Two,
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"V1")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"v1")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"v\xb2")).unwrap(), Test::Two);
+    Test::read(&mut Cursor::new(b"v3")).expect_err("accepted bad data");
+}
+
+#[test]
+fn unit_enum_magic_mask() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(big)]
+    enum Test {
+        #[br(magic(b"v1"))]
+        One,
+        #[br(magic(b"v?", mask = b"\xff\0"))]
+        Other,
+        #[br(magic(0x10u8, mask = 0xf0))]
+        Version,
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"v1")).unwrap(), Test::One);
+    assert_eq!(Test::read(&mut Cursor::new(b"v2")).unwrap(), Test::Other);
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x17")).unwrap(),
+        Test::Version
+    );
+    Test::read(&mut Cursor::new(b"\x27")).expect_err("accepted bad data");
+}
+
 #[test]
 fn unit_enum_magic_pre_assert() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -188,6 +227,68 @@ fn unit_enum_repr() {
     assert_eq!(Test::read(&mut Cursor::new(b"\0\x02")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_repr_custom() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Code(u16);
+
+    impl TryFrom<Code> for u8 {
+        type Error = &'static str;
+
+        fn try_from(code: Code) -> Result<Self, Self::Error> {
+            u8::try_from(code.0 >> 4).map_err(|_| "code out of range")
+        }
+    }
+
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(repr = Code, repr_err = |value: u8| format!("unknown kind {value:#x}"))]
+    #[repr(u8)]
+    enum Kind {
+        A = 1,
+        B = 0xff,
+    }
+
+    assert_eq!(Kind::read_be(&mut Cursor::new(b"\0\x10")).unwrap(), Kind::A);
+    assert_eq!(
+        Kind::read_be(&mut Cursor::new(b"\x0f\xf0")).unwrap(),
+        Kind::B
+    );
+
+    let error = Kind::read_be(&mut Cursor::new(b"\0\x20")).expect_err("accepted bad data");
+    assert_eq!(
+        error.custom_err::<String>().map(String::as_str),
+        Some("unknown kind 0x2")
+    );
+
+    let mut data = Cursor::new(b"\xff\xff");
+    let error = Kind::read_be(&mut data).expect_err("accepted bad data");
+    assert_eq!(error.custom_err(), Some(&"code out of range"));
+    assert_eq!(data.position(), 0);
+}
+
+#[test]
+fn unit_enum_repr_sparse() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(little, repr(u16))]
+    enum Test {
+        Low = 7,
+        High = 0xffff,
+        Mid = 0x1000,
+        Next,
+    }
+
+    for (data, expected) in [
+        (b"\x07\0", Test::Low),
+        (b"\xff\xff", Test::High),
+        (b"\0\x10", Test::Mid),
+        (b"\x01\x10", Test::Next),
+    ] {
+        assert_eq!(Test::read(&mut Cursor::new(data)).unwrap(), expected);
+    }
+    Test::read(&mut Cursor::new(b"\x08\0")).expect_err("accepted bad data");
+}
+
 #[test]
 fn unit_enum_rewind_on_eof() {
     #[derive(BinRead, Debug)]
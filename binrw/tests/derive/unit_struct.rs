@@ -1,4 +1,5 @@
-use binrw::{io::Cursor, BinRead};
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+use core::marker::PhantomData;
 
 #[test]
 fn unit_struct_magic() {
@@ -22,3 +23,38 @@ fn unit_struct_import_pre_assert() {
         .expect_err("accepted negative pre-assert");
     assert!(matches!(error, binrw::Error::AssertFail { .. }));
 }
+
+#[test]
+fn unit_struct_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, magic = b"END")]
+    struct End;
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    struct Marker<T: 'static>(PhantomData<T>);
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        marker: Marker<u32>,
+        empty: [u8; 0],
+        end: End,
+    }
+
+    let value = Test::read(&mut Cursor::new(b"END")).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            marker: Marker(PhantomData),
+            empty: [],
+            end: End,
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"END");
+}
@@ -80,3 +80,32 @@ fn top_level_assert_self_struct() {
         panic!("Assert error expected");
     }
 }
+
+#[test]
+fn assert_custom_err() {
+    #[derive(Debug, PartialEq)]
+    enum Oops {
+        TooLong { len: usize },
+    }
+
+    impl core::fmt::Display for Oops {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            core::fmt::Debug::fmt(self, f)
+        }
+    }
+
+    #[binwrite]
+    struct Test {
+        #[bw(assert(name.len() < 4, Oops::TooLong { len: name.len() }))]
+        name: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&Test { name: vec![1, 2] }).unwrap();
+    let err = x
+        .write_be(&Test {
+            name: vec![1, 2, 3, 4],
+        })
+        .expect_err("accepted bad data");
+    assert_eq!(err.custom_err::<Oops>(), Some(&Oops::TooLong { len: 4 }));
+}
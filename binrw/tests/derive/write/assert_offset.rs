@@ -0,0 +1,138 @@
+use binrw::{binwrite, io::Cursor, BinWrite, BinWriterExt};
+
+#[test]
+fn assert_offset() {
+    #[derive(BinWrite)]
+    #[bw(assert_offset = 8)]
+    struct Test {
+        a: u16,
+        #[bw(align_before = 4, assert_offset = 4)]
+        b: u32,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&Test { a: 1, b: 2 }).unwrap();
+    assert_eq!(x.into_inner(), b"\0\x01\0\0\0\0\0\x02");
+}
+
+#[test]
+fn assert_offset_fail() {
+    #[derive(BinWrite)]
+    struct Test {
+        a: u8,
+        #[bw(assert_offset = 4)]
+        b: u32,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x.write_be(&Test { a: 1, b: 2 }).unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 1, ref message } if message == "expected `b` to start at 0x4, but it is at 0x1"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn top_level_assert_offset_fail() {
+    #[binwrite]
+    #[bw(assert_offset = *len as u64)]
+    struct Test {
+        len: u8,
+        data: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&Test {
+        len: 3,
+        data: vec![1, 2],
+    })
+    .unwrap();
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x
+        .write_be(&Test {
+            len: 3,
+            data: vec![1, 2, 3],
+        })
+        .unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 4, ref message } if message == "expected end of `Test` at 0x3, but it is at 0x4"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn variant_assert_offset_fail() {
+    #[derive(BinWrite)]
+    enum Test {
+        #[bw(magic = 0u8, assert_offset = 2)]
+        A(u8),
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&Test::A(1)).unwrap();
+
+    let mut x = Cursor::new(vec![0]);
+    x.set_position(1);
+    let err = x.write_be(&Test::A(1)).unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 3, ref message } if message == "expected end of the variant at 0x2, but it is at 0x3"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn assert_offset_negative() {
+    #[derive(BinWrite)]
+    #[bw(import(offset: i64))]
+    struct Test {
+        #[bw(assert_offset = offset)]
+        a: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x.write_be_args(&Test { a: 1 }, (-1,)).unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 0, ref message } if message == "expected `a` to start at -1, which is not a stream position"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn assert_align() {
+    #[derive(BinWrite)]
+    #[bw(assert_align = 4)]
+    struct Test {
+        a: u8,
+        #[bw(pad_before = 1, assert_align = 2u8)]
+        b: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    x.write_be(&Test { a: 1, b: 2 }).unwrap();
+    assert_eq!(x.into_inner(), b"\x01\0\0\x02");
+
+    let mut x = Cursor::new(vec![0]);
+    x.set_position(1);
+    let err = x.write_be(&Test { a: 1, b: 2 }).unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 3, ref message } if message == "expected `b` to start at a multiple of 0x2, but it is at 0x3"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn assert_align_zero() {
+    #[derive(BinWrite)]
+    #[bw(assert_align = 0)]
+    struct Test {
+        a: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x.write_be(&Test { a: 1 }).unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 1, ref message } if message == "expected end of `Test` at a multiple of 0, which is not an alignment"),
+        "{err:?}"
+    );
+}
@@ -23,6 +23,87 @@ fn write_enum() {
     );
 }
 
+#[test]
+fn round_trip_unit_enum_custom_repr() {
+    // An unsigned LEB128 integer
+    #[derive(Debug)]
+    struct VarInt(u64);
+
+    impl BinRead for VarInt {
+        type Args<'a> = ();
+
+        fn read_options<R: binrw::io::Read + binrw::io::Seek>(
+            reader: &mut R,
+            endian: Endian,
+            (): Self::Args<'_>,
+        ) -> binrw::BinResult<Self> {
+            let mut value = 0;
+            for shift in (0..64).step_by(7) {
+                let byte = u8::read_options(reader, endian, ())?;
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            Ok(Self(value))
+        }
+    }
+
+    impl BinWrite for VarInt {
+        type Args<'a> = ();
+
+        fn write_options<W: binrw::io::Write + binrw::io::Seek>(
+            &self,
+            writer: &mut W,
+            endian: Endian,
+            (): Self::Args<'_>,
+        ) -> binrw::BinResult<()> {
+            let mut value = self.0;
+            loop {
+                let byte = (value & 0x7f) as u8;
+                value >>= 7;
+                if value == 0 {
+                    return byte.write_options(writer, endian, ());
+                }
+                (byte | 0x80).write_options(writer, endian, ())?;
+            }
+        }
+    }
+
+    impl TryFrom<VarInt> for u16 {
+        type Error = core::num::TryFromIntError;
+
+        fn try_from(value: VarInt) -> Result<Self, Self::Error> {
+            value.0.try_into()
+        }
+    }
+
+    impl From<u16> for VarInt {
+        fn from(value: u16) -> Self {
+            Self(value.into())
+        }
+    }
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(repr = VarInt)]
+    #[repr(u16)]
+    enum Test {
+        A = 1,
+        B = 300,
+    }
+
+    let data = b"\x01\xac\x02";
+    let test: [Test; 2] = Cursor::new(data).read_le().unwrap();
+    assert_eq!(test, [Test::A, Test::B]);
+
+    let mut x = Cursor::new(Vec::new());
+    test.write_options(&mut x, Endian::Little, ()).unwrap();
+    assert_eq!(x.into_inner(), data);
+
+    let error = Test::read_le(&mut Cursor::new(b"\xff\xff\xff\x01")).unwrap_err();
+    assert!(matches!(error, binrw::Error::Custom { pos: 0, .. }));
+}
+
 #[test]
 fn round_trip_unit_enum() {
     #[derive(BinRead, BinWrite)]
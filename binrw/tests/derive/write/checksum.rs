@@ -0,0 +1,65 @@
+use binrw::{
+    binrw, binwrite,
+    checksum::{crc16, crc32},
+    io::Cursor,
+    BinRead, BinWrite,
+};
+
+#[test]
+fn checksum() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Chunk {
+        #[br(temp)]
+        #[bw(calc = 0)]
+        #[brw(checksum(crc16, over = header))]
+        header_crc: u16,
+        header: [u8; 4],
+        #[br(temp)]
+        #[bw(calc = data.len() as u8)]
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[br(temp)]
+        #[bw(calc = 0)]
+        #[brw(checksum(crc32, over = len..=data))]
+        crc: u32,
+    }
+
+    let chunk = Chunk {
+        header: *b"IHDR",
+        data: b"abc".to_vec(),
+    };
+
+    let mut out = Cursor::new(Vec::new());
+    chunk.write(&mut out).unwrap();
+    assert_eq!(out.position(), 14);
+
+    let mut expected = b"\0\0IHDR\x03abc\0\0\0\0".to_vec();
+    expected[..2].copy_from_slice(&crc16(b"IHDR").to_be_bytes());
+    expected[10..].copy_from_slice(&crc32(b"\x03abc").to_be_bytes());
+    assert_eq!(out.get_ref(), &expected);
+
+    out.set_position(0);
+    assert_eq!(Chunk::read(&mut out).unwrap(), chunk);
+}
+
+#[test]
+fn checksum_placeholder_size() {
+    #[binwrite]
+    #[bw(little)]
+    struct Test {
+        data: u16,
+        #[bw(calc = 0, checksum(crc32, over = data))]
+        crc: u16,
+    }
+
+    let err = Test { data: 1 }
+        .write(&mut Cursor::new(Vec::new()))
+        .unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::AssertFail { pos: 2, ref message } if message == "checksum is 4 bytes but its placeholder is 2 bytes"),
+        "{err:?}"
+    );
+}
@@ -65,3 +65,22 @@ fn top_level_endian() {
 
     assert_eq!(x.into_inner(), [1, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0, 4]);
 }
+
+#[test]
+fn top_level_default_endian() {
+    #[derive(BinWrite)]
+    #[bw(default_big)]
+    struct Test {
+        x: u16,
+        #[bw(little)]
+        y: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { x: 1, y: 2 }.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [0, 1, 2, 0]);
+
+    let mut x = Cursor::new(Vec::new());
+    Test { x: 1, y: 2 }.write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [1, 0, 2, 0]);
+}
@@ -34,6 +34,32 @@ fn enum_round_trip() {
     assert_eq!(x.into_inner(), data);
 }
 
+#[test]
+fn enum_fallback_round_trip() {
+    #[binrw::binrw]
+    #[brw(big)]
+    enum Record {
+        #[brw(magic = 1u8)]
+        Point(u16, u16),
+        #[br(fallback)]
+        Unknown {
+            tag: u8,
+            #[bw(calc = data.len() as u8)]
+            len: u8,
+            #[br(count = len)]
+            data: Vec<u8>,
+        },
+    }
+
+    let data = b"\x01\0\x01\0\x02\x09\x03abc\x01\0\x03\0\x04";
+    let records: [Record; 3] = Cursor::new(data).read_be().unwrap();
+    assert!(matches!(&records[1], Record::Unknown { tag: 9, data } if data == b"abc"));
+
+    let mut x = Cursor::new(Vec::new());
+    records.write_options(&mut x, Endian::Big, ()).unwrap();
+    assert_eq!(x.into_inner(), data);
+}
+
 #[test]
 fn enum_one_way() {
     #[derive(BinWrite)]
@@ -71,3 +97,82 @@ fn enum_one_way() {
         b"BBB\xBB\xAA\0\0\0\x02CCCAAA\x03\x02\x01\0\xFF"
     );
 }
+
+#[test]
+fn enum_empty() {
+    #[derive(BinRead, BinWrite, Debug)]
+    enum Never {}
+
+    #[derive(BinRead, BinWrite, Debug)]
+    #[brw(big)]
+    struct Test {
+        a: u8,
+        #[br(if(false))]
+        #[bw(ignore)]
+        never: Option<Never>,
+        b: (),
+    }
+
+    let value = Test::read(&mut Cursor::new(b"\x01")).unwrap();
+    assert!(value.never.is_none());
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\x01");
+}
+
+#[test]
+fn enum_pre_assert() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big, import(ty: u8))]
+    enum Command {
+        #[brw(pre_assert(ty == 0))]
+        Move(u16, u16),
+        #[brw(pre_assert(ty == 1))]
+        Wait(u32),
+        #[brw(pre_assert(ty == 2))]
+        Stop,
+    }
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(import(ty: u8))]
+    enum Speed {
+        #[brw(magic = 0u8, pre_assert(ty != 2))]
+        Slow,
+        #[brw(magic = 1u8)]
+        Fast,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Command::Wait(1)
+        .write_options(&mut x, Endian::Big, (1,))
+        .unwrap();
+    Command::Stop
+        .write_options(&mut x, Endian::Big, (2,))
+        .unwrap();
+    assert_eq!(x.into_inner(), [0, 0, 0, 1]);
+
+    let mut x = Cursor::new(Vec::new());
+    let err = Command::Wait(1)
+        .write_options(&mut x, Endian::Big, (0,))
+        .expect_err("wrote a variant which cannot be read back");
+    assert!(matches!(err, binrw::Error::AssertFail { .. }));
+    let err = Command::Stop
+        .write_options(&mut x, Endian::Big, (1,))
+        .expect_err("wrote a variant which cannot be read back");
+    assert!(matches!(err, binrw::Error::AssertFail { .. }));
+    assert!(x.into_inner().is_empty());
+
+    let mut x = Cursor::new(Vec::new());
+    Speed::Slow
+        .write_options(&mut x, Endian::Big, (1,))
+        .unwrap();
+    Speed::Fast
+        .write_options(&mut x, Endian::Big, (2,))
+        .unwrap();
+    assert_eq!(x.into_inner(), [0, 1]);
+    let err = Speed::Slow
+        .write_options(&mut Cursor::new(Vec::new()), Endian::Big, (2,))
+        .expect_err("wrote a variant which cannot be read back");
+    assert!(matches!(err, binrw::Error::AssertFail { .. }));
+}
@@ -1,4 +1,4 @@
-use binrw::{io::Cursor, BinWrite, Endian};
+use binrw::{io::Cursor, BinRead, BinWrite, Endian};
 
 #[test]
 fn if_cond() {
@@ -35,3 +35,145 @@ fn if_cond() {
 
     assert_eq!(&x.into_inner(), &[3, 0, 4, 0, 0, 0, 5]);
 }
+
+#[test]
+fn present_if() {
+    const HAS_B: u8 = 2;
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        flags: u8,
+        #[bw(present_if(flags, 1))]
+        a: Option<u8>,
+        #[bw(present_if(flags, HAS_B))]
+        b: Option<u16>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        flags: 2,
+        a: None,
+        b: Some(1),
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(&x.into_inner(), &[2, 0, 1]);
+
+    let err = Test {
+        flags: 1,
+        a: Some(1),
+        b: Some(2),
+    }
+    .write(&mut Cursor::new(Vec::new()))
+    .unwrap_err();
+    assert!(matches!(err, binrw::Error::AssertFail { pos: 0, .. }));
+
+    let err = Test {
+        flags: 3,
+        a: Some(1),
+        b: None,
+    }
+    .write(&mut Cursor::new(Vec::new()))
+    .unwrap_err();
+    assert!(matches!(err, binrw::Error::AssertFail { pos: 0, .. }));
+}
+
+#[test]
+fn present_if_calc_flags() {
+    #[binrw::binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    struct Test {
+        #[br(temp)]
+        #[bw(calc = u8::from(a.is_some()) | u8::from(b.is_some()) << 1)]
+        flags: u8,
+        #[brw(present_if(flags, 1))]
+        a: Option<u8>,
+        #[brw(present_if(flags, 2))]
+        b: Option<u8>,
+    }
+
+    let value = Test {
+        a: None,
+        b: Some(5),
+    };
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), &[2, 5]);
+    x.set_position(0);
+    assert_eq!(Test::read(&mut x).unwrap(), value);
+}
+
+#[test]
+fn if_cond_else() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        has_values: u8,
+        #[bw(if(*has_values != 0), else = skip)]
+        a: Option<u8>,
+        #[bw(if(*has_values != 0), else = write_anyway)]
+        b: Option<u8>,
+        #[bw(if(*has_values != 0), else = error)]
+        c: Option<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        has_values: 0,
+        a: Some(1),
+        b: Some(2),
+        c: None,
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(&x.into_inner(), &[0, 2]);
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        has_values: 1,
+        a: Some(1),
+        b: None,
+        c: Some(3),
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(&x.into_inner(), &[1, 1, 3]);
+
+    for (has_values, c) in [(0, Some(3)), (1, None)] {
+        let err = Test {
+            has_values,
+            a: None,
+            b: None,
+            c,
+        }
+        .write(&mut Cursor::new(Vec::new()))
+        .unwrap_err();
+        assert!(matches!(err, binrw::Error::AssertFail { pos: 0, .. }));
+    }
+}
+
+#[test]
+fn since_until() {
+    #[derive(BinWrite)]
+    #[bw(big, import(version: u8), version = version)]
+    struct Test {
+        #[bw(since(2))]
+        a: u8,
+        #[bw(until(2))]
+        b: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 1, b: 2 }
+        .write_options(&mut x, Endian::Big, (1,))
+        .unwrap();
+    assert_eq!(&x.into_inner(), &[0, 2]);
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 1, b: 2 }
+        .write_options(&mut x, Endian::Big, (2,))
+        .unwrap();
+    assert_eq!(&x.into_inner(), &[1]);
+}
@@ -28,6 +28,74 @@ fn gat_list() {
     assert_eq!(out.into_inner(), b"\x01");
 }
 
+#[test]
+fn gat_list_default() {
+    #[derive(BinWrite)]
+    #[bw(little, import(borrowed: &u8, extra: u8 = 1))]
+    struct Test {
+        #[bw(map = |a| *a + *borrowed + extra)]
+        a: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Parent {
+        #[bw(args(&1))]
+        a: Test,
+        #[bw(args(&1, 2))]
+        b: Test,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Parent {
+        a: Test { a: 0 },
+        b: Test { a: 0 },
+    }
+    .write(&mut out)
+    .unwrap();
+
+    assert_eq!(out.into_inner(), b"\x02\x03");
+}
+
+#[test]
+fn gat_list_coerce() {
+    #[derive(BinWrite)]
+    #[bw(little, import(f: &dyn Fn(u8) -> u8))]
+    struct Test {
+        #[bw(map = |a| f(*a))]
+        a: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(little, import(f: &dyn Fn(u8) -> u8, extra: u8 = 0))]
+    struct DefaultTest {
+        #[bw(map = |a| f(*a) + extra)]
+        a: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Parent {
+        #[bw(args(&|a| a + 1))]
+        a: Test,
+        #[bw(args(&|a| a * 2))]
+        b: DefaultTest,
+        #[bw(args(&|a| a * 2, 1))]
+        c: DefaultTest,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Parent {
+        a: Test { a: 1 },
+        b: DefaultTest { a: 2 },
+        c: DefaultTest { a: 3 },
+    }
+    .write(&mut out)
+    .unwrap();
+
+    assert_eq!(out.into_inner(), b"\x02\x04\x07");
+}
+
 #[test]
 fn gat_named() {
     #[derive(BinWrite)]
@@ -1,4 +1,4 @@
-use binrw::{io::Cursor, BinRead, BinReaderExt, BinWrite};
+use binrw::{binrw, io::Cursor, BinRead, BinReaderExt, BinWrite};
 
 #[test]
 fn magic_round_trip() {
@@ -21,6 +21,54 @@ fn magic_round_trip() {
     assert_eq!(out.into_inner(), data);
 }
 
+#[test]
+fn magic_alternatives_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big, magic(b"V1" | b"V2", name = version))]
+    struct Test {
+        a: u8,
+        #[br(if(version == *b"V2"))]
+        b: Option<u8>,
+    }
+
+    let data = b"V2\x01\x02";
+    let test: Test = Cursor::new(data).read_be().unwrap();
+    assert_eq!(
+        test,
+        Test {
+            version: *b"V2",
+            a: 1,
+            b: Some(2)
+        }
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+
+    assert_eq!(out.into_inner(), data);
+}
+
+#[test]
+fn magic_alternatives_write_one_way() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    #[br(magic(1u8 | 2u8, name = kind))]
+    #[bw(magic = 3u8)]
+    struct Test {
+        x: u8,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"\x02\x05")).unwrap();
+    assert_eq!(test, Test { kind: 2, x: 5 });
+
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+
+    assert_eq!(out.into_inner(), b"\x03\x05");
+}
+
 #[test]
 fn magic_one_way() {
     #[derive(BinRead, BinWrite)]
@@ -39,3 +87,25 @@ fn magic_one_way() {
 
     assert_eq!(out.into_inner(), data);
 }
+
+#[test]
+fn magic_alternatives_variant_round_trip() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    enum Test {
+        #[brw(magic(1u8 | 2u8, name = kind))]
+        A { x: u8 },
+        #[brw(magic = 3u8)]
+        B,
+    }
+
+    let data = b"\x02\x05";
+    let test = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(test, Test::A { kind: 2, x: 5 });
+
+    let mut out = Cursor::new(Vec::new());
+    test.write(&mut out).unwrap();
+
+    assert_eq!(out.into_inner(), data);
+}
@@ -1,8 +1,10 @@
 mod args;
 mod assert;
+mod assert_offset;
 mod binread_temp;
 mod c_enum;
 mod calc;
+mod checksum;
 mod custom_writer;
 mod endian;
 mod r#enum;
@@ -73,3 +73,25 @@ fn padding_one_way() {
 
     assert_eq!(x.into_inner(), data);
 }
+
+#[test]
+fn seek_before_end() {
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Test {
+        data: [u8; 4],
+        #[bw(seek_before = binrw::io::SeekFrom::End(-i64::from(*len)), restore_position)]
+        len: u8,
+        tail: u8,
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    Test {
+        data: [1, 2, 3, 4],
+        len: 2,
+        tail: 5,
+    }
+    .write(&mut data)
+    .unwrap();
+    assert_eq!(data.into_inner(), [1, 2, 2, 4, 5]);
+}
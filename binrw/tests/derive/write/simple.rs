@@ -83,3 +83,45 @@ fn round_trip_2() {
 
     assert_eq!(x.into_inner() == bytes, y.into_inner() == bytes_conj);
 }
+
+// Tracing spans record the position of each object
+#[cfg(not(feature = "tracing"))]
+#[test]
+fn simple_write_no_seek() {
+    use binrw::io::{Seek, SeekFrom, Write};
+
+    struct NoSeek(Vec<u8>);
+
+    impl Write for NoSeek {
+        fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+            Write::write(&mut self.0, buf)
+        }
+
+        fn flush(&mut self) -> binrw::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for NoSeek {
+        fn seek(&mut self, _: SeekFrom) -> binrw::io::Result<u64> {
+            panic!("unexpected seek");
+        }
+    }
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Outer {
+        a: Test,
+        #[bw(pad_before = 0)]
+        b: [Test; 2],
+    }
+
+    let mut x = NoSeek(Vec::new());
+    Outer {
+        a: Test { x: 1, y: 2, z: 3 },
+        b: [Test { x: 4, y: 5, z: 6 }, Test { x: 7, y: 8, z: 9 }],
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.0.len(), 21);
+}
@@ -57,3 +57,20 @@ fn writer_var() {
 
     assert_eq!(out.into_inner(), b"\x01\x02\x03\x04\x0a");
 }
+
+#[test]
+fn pos_var() {
+    #[binwrite]
+    #[bw(little, pos_var = pos)]
+    struct Test {
+        a: u8,
+        #[bw(map = |v: &u8| u64::from(*v) + pos)]
+        b: u8,
+        #[bw(calc = u8::try_from(pos).unwrap())]
+        c: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 1, b: 2 }.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [1, 3, 0, 0, 0, 0, 0, 0, 0, 9]);
+}
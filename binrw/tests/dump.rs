@@ -0,0 +1,56 @@
+use binrw::io::Cursor;
+use binrw::{
+    dump::{to_text, Dump, Dumper},
+    BinRead, FilePtr8, NullWideString, PosValue,
+};
+use core::marker::PhantomData;
+
+#[test]
+fn dump_scalars() {
+    assert_eq!(to_text(&-1i8), "-1");
+    assert_eq!(to_text(&1.0f64), "1.0");
+    assert_eq!(to_text(&'\n'), "'\\n'");
+    assert_eq!(to_text("a\"b"), "\"a\\\"b\"");
+    assert_eq!(to_text(&true), "true");
+    assert_eq!(to_text(&()), "()");
+    assert_eq!(to_text(&PhantomData::<u8>), "PhantomData");
+    assert_eq!(to_text(&None::<u8>), "None");
+    assert_eq!(to_text(&Some(Box::new(1u8))), "Some(1)");
+}
+
+#[test]
+fn dump_nested() {
+    assert_eq!(to_text(&Vec::<u8>::new()), "[]");
+    assert_eq!(
+        to_text(&[(1u8, [2u8]), (3, [4])]),
+        "[\n    (\n        1\n        [\n            2\n        ]\n    )\n    (\n        3\n        [\n            4\n        ]\n    )\n]"
+    );
+}
+
+#[test]
+fn dump_binrw_types() {
+    #[derive(BinRead)]
+    struct Test {
+        ptr: FilePtr8<PosValue<u8>>,
+        name: NullWideString,
+    }
+
+    let value = Test::read_le(&mut Cursor::new(b"\x05a\0\0\0\x07")).unwrap();
+    assert_eq!(
+        to_text(&(&value.ptr, &value.name)),
+        "(\n    FilePtr {\n        ptr: 5\n        value: PosValue {\n            val: 7\n            pos: 5\n        }\n    }\n    \"a\"\n)"
+    );
+}
+
+#[test]
+fn dump_custom() {
+    struct Custom;
+
+    impl Dump for Custom {
+        fn dump(&self, dumper: &mut Dumper<'_>) {
+            dumper.tuple("Custom").entries(["a", "b"]).finish();
+        }
+    }
+
+    assert_eq!(to_text(&Custom), "Custom(\n    \"a\"\n    \"b\"\n)");
+}
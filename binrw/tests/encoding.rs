@@ -0,0 +1,221 @@
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use binrw::{
+    encoding::{self, Encoding, Latin1, Lossy, Utf16Be, Utf16Le, Utf8, Windows1252},
+    io::Cursor,
+    BinRead, BinWrite, Endian,
+};
+
+#[test]
+fn encodings() {
+    assert_eq!(Utf8.decode(b"caf\xc3\xa9").unwrap(), "café");
+    assert!(Utf8.decode(b"\xff").is_none());
+    assert_eq!(Utf16Le.decode(b"h\0i\0").unwrap(), "hi");
+    assert_eq!(Utf16Be.decode(b"\0h\0i").unwrap(), "hi");
+    assert!(Utf16Le.decode(b"h\0i").is_none());
+    assert!(Utf16Le.decode(b"\0\xd8").is_none());
+    assert_eq!(Latin1.decode(b"caf\xe9").unwrap(), "café");
+
+    assert_eq!(Utf16Be.encode("hi").unwrap(), b"\0h\0i");
+    assert_eq!(Latin1.encode("café").unwrap(), b"caf\xe9");
+    assert!(Latin1.encode("€").is_none());
+}
+
+#[test]
+fn windows_1252() {
+    assert_eq!(
+        Windows1252.decode(b"\x93caf\xe9\x94 \x80").unwrap(),
+        "“café” €"
+    );
+    assert_eq!(Windows1252.decode(b"\x81").unwrap(), "\u{81}");
+    assert_eq!(
+        Windows1252.encode("“café” €").unwrap(),
+        b"\x93caf\xe9\x94 \x80"
+    );
+    assert!(Windows1252.encode("\u{80}").is_none());
+    assert!(Windows1252.encode("あ").is_none());
+}
+
+#[test]
+fn lossy() {
+    assert_eq!(Lossy(Utf8).decode(b"a\xffb").unwrap(), "a\u{fffd}b");
+    assert_eq!(
+        Lossy(Utf16Le).decode(b"h\0\0\xd8i").unwrap(),
+        "h\u{fffd}\u{fffd}"
+    );
+    assert_eq!(Lossy(Latin1).encode("a€b").unwrap(), b"a?b");
+    assert_eq!(Lossy(Utf16Be).encode("hi").unwrap(), b"\0h\0i");
+    assert_eq!(Lossy(Latin1).name(), "Latin-1");
+
+    let read = encoding::read_null_terminated(Lossy(Utf8));
+    let mut data = Cursor::new(&b"caf\xe9\0"[..]);
+    assert_eq!(read(&mut data, Endian::Little, ()).unwrap(), "caf\u{fffd}");
+
+    let write = encoding::write_sized(Lossy(Windows1252));
+    let mut out = Cursor::new(Vec::new());
+    write("€ ≈ 1", &mut out, Endian::Little, 6).unwrap();
+    assert_eq!(out.into_inner(), b"\x80 ? 1\0");
+}
+
+#[test]
+fn null_terminated() {
+    let read = encoding::read_null_terminated(Utf16Le);
+    let mut data = Cursor::new(&b"h\0i\0\0\0after"[..]);
+    assert_eq!(read(&mut data, Endian::Little, ()).unwrap(), "hi");
+    assert_eq!(data.position(), 6);
+
+    let mut data = Cursor::new(&b"\0\0\0\xd8\0\0"[..]);
+    data.set_position(2);
+    let error = read(&mut data, Endian::Little, ()).unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 2, .. }));
+
+    let write = encoding::write_null_terminated(Latin1);
+    let mut out = Cursor::new(Vec::new());
+    write("café", &mut out, Endian::Little, ()).unwrap();
+    assert_eq!(out.into_inner(), b"caf\xe9\0");
+
+    let mut out = Cursor::new(Vec::new());
+    assert!(matches!(
+        write("€", &mut out, Endian::Little, ()).unwrap_err(),
+        binrw::Error::AssertFail { pos: 0, .. }
+    ));
+}
+
+#[test]
+fn sized() {
+    let read = encoding::read_sized(Utf16Be);
+    let mut data = Cursor::new(&b"\0h\0i\0\0\0\0after"[..]);
+    assert_eq!(read(&mut data, Endian::Little, 8).unwrap(), "hi");
+    assert_eq!(data.position(), 8);
+
+    assert!(matches!(
+        read(&mut Cursor::new(&b"\0h"[..]), Endian::Little, 4).unwrap_err(),
+        binrw::Error::Io(..)
+    ));
+
+    let write = encoding::write_sized(Utf16Be);
+    let mut out = Cursor::new(Vec::new());
+    write("hi", &mut out, Endian::Little, 6).unwrap();
+    assert_eq!(out.into_inner(), b"\0h\0i\0\0");
+
+    let mut out = Cursor::new(Vec::new());
+    assert!(matches!(
+        write("hi", &mut out, Endian::Little, 3).unwrap_err(),
+        binrw::Error::AssertFail { .. }
+    ));
+}
+
+#[test]
+fn prefixed() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        #[br(parse_with = encoding::read_prefixed::<u16, _, _>(Latin1))]
+        #[bw(write_with = encoding::write_prefixed::<u16, _, _, _>(Latin1))]
+        name: String,
+    }
+
+    let data = b"\0\x04caf\xe9";
+    let value = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(value.name, "café");
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    let write = encoding::write_prefixed::<u8, _, _, _>(Utf8);
+    let mut out = Cursor::new(Vec::new());
+    assert!(matches!(
+        write(&"a".repeat(256), &mut out, Endian::Big, ()).unwrap_err(),
+        binrw::Error::AssertFail { .. }
+    ));
+}
+
+#[test]
+fn wide() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        #[br(parse_with = encoding::read_wide_sized, args_raw = 6)]
+        #[bw(write_with = encoding::write_wide_sized, args_raw = 6)]
+        name: Vec<u16>,
+        #[br(parse_with = encoding::read_wide_prefixed::<u8, _>)]
+        #[bw(write_with = encoding::write_wide_prefixed::<u8, _, _>)]
+        title: Vec<u16>,
+    }
+
+    let data = b"\xdc\0\0h\0\0\x04\xd8\0\0i";
+    let value = Test::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(value.name, [0xdc00, 0x68]);
+    assert_eq!(value.title, [0xd800, 0x69]);
+
+    let mut out = Cursor::new(Vec::new());
+    value.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+
+    assert!(matches!(
+        encoding::read_wide_prefixed::<u8, _>(&mut Cursor::new(b"\x03abc"), Endian::Little, ())
+            .unwrap_err(),
+        binrw::Error::AssertFail { pos: 1, .. }
+    ));
+    assert!(encoding::read_wide_prefixed::<u64, _>(
+        &mut Cursor::new([0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+        Endian::Little,
+        ()
+    )
+    .is_err());
+
+    let mut out = Cursor::new(Vec::new());
+    assert!(matches!(
+        encoding::write_wide_sized(&[1u16, 2][..], &mut out, Endian::Little, 3).unwrap_err(),
+        binrw::Error::AssertFail { .. }
+    ));
+}
+
+#[test]
+fn terminated() {
+    let read = encoding::read_terminated(Utf16Le, b"\0\0\0\0");
+    let mut data = Cursor::new(&b"a\0\0\0b\0\0\0\0\0after"[..]);
+    assert_eq!(read(&mut data, Endian::Little, ()).unwrap(), "a\0b");
+    assert_eq!(data.position(), 10);
+
+    let read = encoding::read_terminated(Utf16Le, b"\0\0");
+    let mut data = Cursor::new(&b"\0\x01\0\0"[..]);
+    assert_eq!(read(&mut data, Endian::Little, ()).unwrap(), "\u{100}");
+
+    let mut data = Cursor::new(&b"a\0b\0"[..]);
+    assert!(matches!(
+        read(&mut data, Endian::Little, ()).unwrap_err(),
+        binrw::Error::Io(..)
+    ));
+
+    let write = encoding::write_terminated(Utf8, [0xff, 0xff]);
+    let mut out = Cursor::new(Vec::new());
+    write("hi", &mut out, Endian::Little, ()).unwrap();
+    assert_eq!(out.into_inner(), b"hi\xff\xff");
+}
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn encoding_rs() {
+    use encoding_rs::{SHIFT_JIS, UTF_16BE};
+
+    // The inherent methods of `encoding_rs::Encoding` take precedence over
+    // the trait methods
+
+    assert_eq!(Encoding::name(&SHIFT_JIS), "Shift_JIS");
+    assert_eq!(Encoding::decode(&SHIFT_JIS, b"\x82\xa0a").unwrap(), "あa");
+    assert!(Encoding::decode(&SHIFT_JIS, b"\x82").is_none());
+    assert_eq!(Lossy(SHIFT_JIS).decode(b"a\x82").unwrap(), "a\u{fffd}");
+    assert_eq!(Encoding::encode(&SHIFT_JIS, "あa").unwrap(), b"\x82\xa0a");
+    assert!(Encoding::encode(&SHIFT_JIS, "€").is_none());
+    assert_eq!(Lossy(SHIFT_JIS).encode("a€").unwrap(), b"a?");
+
+    assert_eq!(Encoding::unit_size(&UTF_16BE), 2);
+    assert_eq!(Encoding::encode(&UTF_16BE, "hi").unwrap(), b"\0h\0i");
+
+    let mut data = Cursor::new(b"\0h\0i\0\0");
+    let value = encoding::read_null_terminated(UTF_16BE)(&mut data, Endian::Little, ()).unwrap();
+    assert_eq!(value, "hi");
+}
@@ -130,6 +130,67 @@ fn display() {
     assert!(err.contains("Oops"));
 }
 
+#[test]
+fn enum_errors_display_nested() {
+    let err = Error::EnumErrors {
+        pos: 0x10,
+        variant_errors: vec![
+            (
+                "Outer",
+                Error::EnumErrors {
+                    pos: 0x12,
+                    variant_errors: vec![
+                        ("InnerA", Error::NoVariantMatch { pos: 0x12 }),
+                        (
+                            "InnerB",
+                            Error::AssertFail {
+                                pos: 0x13,
+                                message: "Oops".into(),
+                            },
+                        ),
+                    ],
+                },
+            ),
+            ("Other", Error::NoVariantMatch { pos: 0x10 }),
+        ],
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "no variants matched at 0x10:\n  \
+        Outer: no variants matched at 0x12:\n    \
+        InnerA: no variants matched at 0x12\n    \
+        InnerB: Oops at 0x13\n  \
+        Other: no variants matched at 0x10"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn enum_errors_display_fields() {
+    use binrw::{io::Cursor, BinRead};
+
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    #[br(little, return_all_errors)]
+    enum Test {
+        #[br(magic(0u8))]
+        A,
+        B {
+            b: u32,
+        },
+    }
+
+    let err = Test::read(&mut Cursor::new(b"\x01")).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "no variants matched at 0x0:\n  \
+        A: bad magic at 0x0: 1\n  \
+        B: failed to fill whole buffer\n    \
+        While parsing field 'b' in Test::B at 0x0"
+    );
+}
+
 #[test]
 fn enum_is_eol() {
     use binrw::{io::Cursor, BinRead};
@@ -151,6 +212,58 @@ fn enum_is_eol() {
         .is_eof());
 }
 
+#[test]
+fn field_backtrace() {
+    use binrw::{error::BacktraceFrame, io::Cursor, BinRead};
+
+    #[derive(BinRead, Debug)]
+    struct Inner {
+        _a: u8,
+        _b: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    struct Middle(u8, Inner);
+
+    #[derive(BinRead, Debug)]
+    struct Outer {
+        _header: u16,
+        _middle: Middle,
+    }
+
+    let error =
+        Outer::read_le(&mut Cursor::new(b"\0\0\x05\x07\x01")).expect_err("accepted bad data");
+    let display = error.to_string();
+    let Error::Backtrace(bt) = error else {
+        panic!("expected backtrace");
+    };
+
+    let path = bt
+        .frames
+        .iter()
+        .map(|frame| match frame {
+            BacktraceFrame::Field {
+                type_name,
+                variant_name: None,
+                field_name,
+                pos,
+                ..
+            } => (*type_name, *field_name, *pos),
+            frame => panic!("unexpected frame {frame:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        path,
+        [
+            ("Inner", "_b", 3),
+            ("Middle", "1", 2),
+            ("Outer", "_middle", 0)
+        ]
+    );
+    assert!(display.contains("While parsing field '1' in Middle at 0x2"));
+}
+
 #[test]
 fn is_eof() {
     use binrw::{io::Cursor, BinRead};
@@ -295,6 +408,7 @@ fn no_seek_unit_enum() {
 
 #[test]
 fn parse_backtrace_with_empty_comment_lines() {
+    #[allow(dead_code)]
     #[derive(binrw::BinRead)]
     pub struct Test {
         /// Blank next line has no whitespace…
@@ -305,6 +419,69 @@ fn parse_backtrace_with_empty_comment_lines() {
     }
 }
 
+#[test]
+fn position() {
+    use binrw::{io::Cursor, BinRead};
+
+    #[derive(BinRead, Debug)]
+    struct Inner {
+        #[br(assert(_a == 1))]
+        _a: u8,
+        _b: u16,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Outer {
+        _header: u16,
+        _inner: Inner,
+    }
+
+    let error = Outer::read_le(&mut Cursor::new(b"\0\0\x02")).expect_err("accepted bad data");
+    assert_eq!(error.position(), Some(2));
+
+    // I/O errors use the start of the innermost object
+    let error = Outer::read_le(&mut Cursor::new(b"\0\0\x01\0")).expect_err("accepted bad data");
+    assert!(error.is_eof());
+    assert_eq!(error.position(), Some(2));
+
+    let error = Error::Io(binrw::io::Error::new(binrw::io::ErrorKind::Other, "Oops"));
+    assert_eq!(error.position(), None);
+}
+
+#[test]
+fn snippet() {
+    let data = (0..48).collect::<Vec<u8>>();
+    let error = Error::AssertFail {
+        pos: 0x13,
+        message: "bad value".into(),
+    };
+    assert_eq!(
+        error.snippet(&data).to_string(),
+        "bad value at 0x13\n\
+        \n\
+        00000000  00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f\n\
+        00000010  10 11 12 13 14 15 16 17 18 19 1a 1b 1c 1d 1e 1f\n                   \
+        ^^\n\
+        00000020  20 21 22 23 24 25 26 27 28 29 2a 2b 2c 2d 2e 2f"
+    );
+
+    // The end of the data is marked for errors at EOF
+    let error = Error::NoVariantMatch { pos: 3 };
+    assert_eq!(
+        error.snippet(&data[..3]).to_string(),
+        "no variants matched at 0x3\n\n00000000  00 01 02\n                   ^^"
+    );
+
+    let error = Error::NoVariantMatch { pos: 4 };
+    assert_eq!(
+        error.snippet(&data[..3]).to_string(),
+        "no variants matched at 0x4"
+    );
+
+    let error = Error::Io(binrw::io::Error::new(binrw::io::ErrorKind::Other, "Oops"));
+    assert_eq!(error.snippet(&data).to_string(), "Oops");
+}
+
 #[test]
 fn show_backtrace() {
     use alloc::borrow::Cow;
@@ -0,0 +1,86 @@
+use binrw::io::{AdaptExt, Cursor, Read, Seek, SeekFrom, Write, Xor};
+
+#[test]
+fn adapt() {
+    let mut data = Cursor::new(b"\0\x21\x23\x21\x23\xff".to_vec());
+    data.seek(SeekFrom::Start(1)).unwrap();
+    let mut stream = data.adapt().take(4).xor([0x20, 0x22]);
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"\x01\x01\x01\x01");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+
+    stream.seek(SeekFrom::Start(2)).unwrap();
+    let mut buf = [0; 2];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, *b"\x01\x01", "seeking misaligned the key");
+
+    let data = stream.into_inner().into_inner().into_inner();
+    assert_eq!(data.position(), 4);
+
+    let mut mapped = data.adapt().map(|s| s.take(1));
+    let mut buf = Vec::new();
+    mapped.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"\x23");
+}
+
+#[test]
+fn xor() {
+    let mut out = Xor::new(Cursor::new(Vec::new()), b"ab");
+    out.write_all(b"abc").unwrap();
+    assert_eq!(out.get_ref().get_ref(), b"\0\0\x02");
+
+    out.seek(SeekFrom::Start(1)).unwrap();
+    out.write_all(b"b").unwrap();
+    assert_eq!(out.get_ref().get_ref(), b"\0\0\x02");
+
+    let mut data = Cursor::new(b"\0\0\0\x04".to_vec());
+    data.seek(SeekFrom::Start(2)).unwrap();
+    let mut stream = Xor::new(data, [1, 2, 3]);
+    stream.seek(SeekFrom::Start(0)).unwrap();
+    let mut buf = [0; 4];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [2, 3, 1, 6], "key is not aligned before the start");
+
+    let mut unchanged = Xor::new(Cursor::new(b"data"), []);
+    let mut buf = Vec::new();
+    unchanged.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"data");
+}
+
+#[test]
+fn transform() {
+    // A keystream which can only be generated in order, and re-keys from the
+    // start when the position jumps
+    fn keystream() -> impl FnMut(u64, &mut [u8]) {
+        let mut next = (0, 0u8);
+        move |pos, buf| {
+            if pos != next.0 {
+                next = (0, 0);
+                while next.0 != pos {
+                    next = (next.0 + 1, next.1.wrapping_mul(5).wrapping_add(3));
+                }
+            }
+            for byte in buf {
+                *byte ^= next.1;
+                next = (next.0 + 1, next.1.wrapping_mul(5).wrapping_add(3));
+            }
+        }
+    }
+
+    let mut out = Cursor::new(Vec::new()).adapt().transform(keystream());
+    out.write_all(b"hello").unwrap();
+    assert_ne!(out.get_ref().get_ref().get_ref(), b"hello");
+
+    let data = out.into_inner().into_inner().into_inner();
+    let mut stream = Cursor::new(data).adapt().transform(keystream());
+    let mut buf = [0; 5];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+
+    stream.seek(SeekFrom::Start(3)).unwrap();
+    let mut buf = [0; 2];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"lo", "seeking did not re-key");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+}
@@ -0,0 +1,84 @@
+#![allow(clippy::seek_from_current)]
+use binrw::io::{CachedSeek, Cursor, Read, Seek, SeekFrom, Write};
+
+/// A stream which counts the calls to `seek`, including the calls made by
+/// `stream_position`.
+struct Counter {
+    inner: Cursor<Vec<u8>>,
+    seeks: usize,
+}
+
+impl Read for Counter {
+    fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> binrw::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl Seek for Counter {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+fn counter(data: &[u8]) -> CachedSeek<Counter> {
+    CachedSeek::new(Counter {
+        inner: Cursor::new(data.to_vec()),
+        seeks: 0,
+    })
+}
+
+#[test]
+fn cached_seek_read() {
+    let mut stream = counter(b"hello world");
+    let mut buf = [0; 5];
+
+    assert_eq!(stream.stream_position().unwrap(), 0);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::Current(0)).unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::Start(5)).unwrap(), 5);
+    assert_eq!(stream.get_ref().seeks, 1);
+
+    assert_eq!(stream.seek(SeekFrom::Current(1)).unwrap(), 6);
+    assert_eq!(stream.read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf, b"world");
+    assert_eq!(stream.seek(SeekFrom::End(-5)).unwrap(), 6);
+    assert_eq!(stream.stream_position().unwrap(), 6);
+    assert_eq!(stream.get_ref().seeks, 3);
+
+    stream.seek(SeekFrom::Current(-7)).unwrap_err();
+    assert_eq!(stream.get_ref().seeks, 3);
+    stream.read_exact(&mut [0; 6]).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 11);
+    assert_eq!(stream.get_ref().seeks, 4);
+}
+
+#[test]
+fn cached_seek_write() {
+    let mut stream = counter(b"");
+
+    stream.write_all(b"abc").unwrap();
+    assert_eq!(stream.write(b"de").unwrap(), 2);
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    assert_eq!(stream.get_ref().seeks, 1);
+
+    stream.get_mut().inner.set_position(1);
+    assert_eq!(stream.stream_position().unwrap(), 1);
+    stream.write_all(b"x").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Start(2)).unwrap(), 2);
+    assert_eq!(stream.get_ref().seeks, 2);
+    stream.flush().unwrap();
+    assert_eq!(stream.into_inner().inner.into_inner(), b"axcde");
+}
@@ -0,0 +1,51 @@
+use binrw::{
+    io::{limit_reads, Cursor, Read},
+    BinRead,
+};
+
+#[test]
+fn limit_reads_counts_rereads() {
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    enum Record {
+        #[br(magic = 1u8)]
+        One,
+        #[br(magic = 2u8)]
+        Two(u32),
+    }
+
+    let data = b"\x00\0\0\0\0";
+    let result = limit_reads(Cursor::new(data), 1024, Record::read);
+    assert!(matches!(result, Err(binrw::Error::EnumErrors { .. })));
+
+    // Each variant reads the magic byte again, so the second attempt exceeds
+    // a limit of one byte
+    let result = limit_reads(Cursor::new(data), 1, Record::read);
+    assert!(matches!(
+        result,
+        Err(binrw::Error::LimitExceeded { pos: 0, limit: 1 })
+    ));
+
+    let result = limit_reads(Cursor::new(b"\x02\0\0\0\x05"), 6, |reader| {
+        let value = Record::read(reader)?;
+        assert_eq!(reader.remaining(), 0);
+        Ok(value)
+    });
+    assert!(matches!(result, Ok(Record::Two(5))));
+}
+
+#[test]
+fn limit_reads_partial() {
+    let result = limit_reads(Cursor::new(b"\x01\x02\x03\x04"), 2, |reader| {
+        let mut buf = Vec::new();
+        let error = reader.read_to_end(&mut buf).unwrap_err();
+        assert_eq!(error.kind(), binrw::io::ErrorKind::InvalidData);
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(reader.get_ref().position(), 2);
+        u8::read(reader)
+    });
+    assert!(matches!(
+        result,
+        Err(binrw::Error::LimitExceeded { pos: 2, limit: 2 })
+    ));
+}
@@ -1,6 +1,11 @@
+mod adapt;
 #[cfg(feature = "std")]
 mod bufreader;
+mod cached_seek;
+mod limit;
 #[cfg(not(feature = "std"))]
 mod no_std;
 mod seek;
+mod segment;
 mod take_seek;
+mod transaction;
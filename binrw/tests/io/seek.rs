@@ -1,5 +1,5 @@
 #![allow(clippy::seek_to_start_instead_of_rewind)]
-use binrw::io::{NoSeek, Read, Seek, SeekFrom, Write};
+use binrw::io::{ForwardSeek, NoSeek, Read, Seek, SeekFrom, Write};
 
 #[test]
 fn read() {
@@ -41,6 +41,31 @@ fn read() {
     assert_eq!(stream.into_inner(), &[]);
 }
 
+#[test]
+fn read_unread_byte() {
+    let mut stream = NoSeek::new(b"abcd".as_slice());
+    let mut byte = [0];
+    let mut buf = [0; 2];
+
+    assert_eq!(stream.read(&mut byte).unwrap(), 1);
+    assert_eq!(stream.seek(SeekFrom::Current(-1)).unwrap(), 0);
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"ab");
+    assert_eq!(stream.stream_position().unwrap(), 2);
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+
+    assert_eq!(stream.read(&mut byte).unwrap(), 1);
+    assert_eq!(stream.seek(SeekFrom::Current(-1)).unwrap(), 2);
+    let mut rest = String::new();
+    stream.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "cd");
+    assert_eq!(stream.stream_position().unwrap(), 4);
+
+    assert_eq!(stream.read(&mut byte).unwrap(), 0);
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn read_vectored() {
@@ -114,3 +139,29 @@ fn write_vectored() {
     assert_eq!(stream.get_ref(), b"aaaaaaaaaa");
     assert_eq!(stream.stream_position().unwrap(), 10);
 }
+
+#[test]
+fn forward_seek() {
+    let mut stream = ForwardSeek::new(b"helloworld".as_slice());
+    let mut buf = [0; 2];
+
+    assert_eq!(stream.stream_position().unwrap(), 0);
+    assert_eq!(stream.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert_eq!(stream.seek(SeekFrom::Start(1)).unwrap(), 1);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"el");
+    assert_eq!(stream.seek(SeekFrom::Current(2)).unwrap(), 5);
+    assert_eq!(stream.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf, b"wo");
+    assert_eq!(stream.stream_position().unwrap(), 7);
+
+    stream.seek(SeekFrom::Start(6)).unwrap_err();
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    stream.seek(SeekFrom::End(0)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 7);
+
+    let error = stream.seek(SeekFrom::Current(5)).unwrap_err();
+    assert_eq!(error.kind(), binrw::io::ErrorKind::UnexpectedEof);
+    assert_eq!(stream.stream_position().unwrap(), 10);
+    assert_eq!(stream.into_inner(), &[]);
+}
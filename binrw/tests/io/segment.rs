@@ -0,0 +1,73 @@
+#![allow(clippy::seek_to_start_instead_of_rewind)]
+use binrw::io::{Seek, SeekFrom, SegmentWriter, Write};
+
+#[test]
+fn write_outside_segment() {
+    let mut stream = SegmentWriter::new(Vec::new());
+    assert_eq!(stream.stream_position().unwrap(), 0);
+    stream.write_all(b"hello").unwrap();
+    assert_eq!(stream.get_ref(), b"hello");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::Start(5)).unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::End(0)).unwrap(), 5);
+    stream.seek(SeekFrom::Start(0)).unwrap_err();
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    stream.seek(SeekFrom::Current(1)).unwrap_err();
+    stream.end_segment().unwrap_err();
+}
+
+#[test]
+fn patch_segment() {
+    let mut stream = SegmentWriter::new(Vec::new());
+    stream.write_all(b"ab").unwrap();
+
+    stream.begin_segment();
+    assert!(stream.in_segment());
+    stream.write_all(b"??cdef").unwrap();
+    assert_eq!(stream.get_ref(), b"ab");
+    assert_eq!(stream.stream_position().unwrap(), 8);
+
+    stream.seek(SeekFrom::Start(1)).unwrap_err();
+    assert_eq!(stream.seek(SeekFrom::Start(2)).unwrap(), 2);
+    stream.write_all(b"XY").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Current(-1)).unwrap(), 3);
+    stream.write_all(b"ZZZZZZ").unwrap();
+    assert_eq!(stream.stream_position().unwrap(), 9);
+    stream.seek(SeekFrom::End(1)).unwrap_err();
+
+    stream.seek(SeekFrom::Start(4)).unwrap();
+    stream.end_segment().unwrap_err();
+    stream.seek(SeekFrom::End(0)).unwrap();
+    stream.end_segment().unwrap();
+    assert!(!stream.in_segment());
+    assert_eq!(stream.get_ref(), b"abXZZZZZZ");
+
+    stream.write_all(b"!").unwrap();
+    assert_eq!(stream.into_inner().unwrap(), b"abXZZZZZZ!");
+}
+
+#[test]
+fn nested_segments() {
+    let mut stream = SegmentWriter::new(Vec::new());
+    stream.begin_segment();
+    stream.write_all(b"1").unwrap();
+    stream.begin_segment();
+    stream.write_all(b"2").unwrap();
+    stream.end_segment().unwrap();
+    assert!(stream.get_ref().is_empty());
+    stream.seek(SeekFrom::Start(0)).unwrap();
+    stream.write_all(b"0").unwrap();
+    stream.seek(SeekFrom::End(0)).unwrap();
+    stream.end_segment().unwrap();
+    assert_eq!(stream.get_ref(), b"02");
+}
+
+#[test]
+fn into_inner_flushes_segment() {
+    let mut stream = SegmentWriter::new(Vec::new());
+    stream.begin_segment();
+    stream.write_all(b"data").unwrap();
+    stream.flush().unwrap();
+    assert!(stream.get_ref().is_empty());
+    assert_eq!(stream.into_inner().unwrap(), b"data");
+}
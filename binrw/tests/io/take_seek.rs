@@ -131,3 +131,23 @@ fn take_seek_ref() {
     assert_eq!(data.take_seek(5).read(&mut buf).unwrap(), 1);
     assert_eq!(&buf, b"dworl");
 }
+
+#[test]
+fn take_seek_clamps_to_end() {
+    let data = &mut Cursor::new(b"hello world");
+    data.seek(SeekFrom::Start(2)).unwrap();
+    let mut take = data.take_seek(4);
+
+    assert_eq!(take.seek(SeekFrom::Start(10)).unwrap(), 6);
+    assert_eq!(take.limit(), 0);
+    assert_eq!(take.seek(SeekFrom::Start(3)).unwrap(), 3);
+    assert_eq!(take.seek(SeekFrom::Current(5)).unwrap(), 6);
+    assert_eq!(take.seek(SeekFrom::Current(i64::MAX)).unwrap(), 6);
+    assert_eq!(take.seek(SeekFrom::End(2)).unwrap(), 6);
+    assert_eq!(take.seek(SeekFrom::Current(-2)).unwrap(), 4);
+
+    let mut buf = [0; 4];
+    assert_eq!(take.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"o ");
+    assert_eq!(take.into_inner().position(), 6);
+}
@@ -0,0 +1,49 @@
+use binrw::io::{Cursor, Seek, SeekFrom, TransactionalWriter, Write};
+
+#[test]
+fn commit() {
+    let mut out = Cursor::new(b"0123456789".to_vec());
+    out.set_position(2);
+
+    let mut writer = TransactionalWriter::new(&mut out);
+    writer.write_all(b"ab").unwrap();
+    writer.seek(SeekFrom::Current(2)).unwrap();
+    writer.write_all(b"c").unwrap();
+    writer.seek(SeekFrom::Start(3)).unwrap();
+    writer.write_all(b"d").unwrap();
+    assert_eq!(writer.seek(SeekFrom::End(1)).unwrap(), 11);
+    writer.write_all(b"e").unwrap();
+    assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 12);
+    assert_eq!(writer.get_ref().get_ref(), b"0123456789");
+    writer.commit().unwrap();
+
+    assert_eq!(out.position(), 12);
+    assert_eq!(out.into_inner(), b"01ad45c789\0e");
+}
+
+#[test]
+fn rollback() {
+    let mut out = Cursor::new(b"0123".to_vec());
+    out.set_position(1);
+
+    let mut writer = TransactionalWriter::new(&mut out);
+    writer.write_all(b"ab").unwrap();
+    writer.seek(SeekFrom::End(0)).unwrap();
+    writer.write_all(b"c").unwrap();
+    drop(writer);
+    assert_eq!(out.position(), 1);
+
+    let mut writer = TransactionalWriter::new(&mut out);
+    writer.write_all(b"x").unwrap();
+    writer.rollback();
+
+    assert_eq!(out.position(), 1);
+    assert_eq!(out.into_inner(), b"0123");
+}
+
+#[test]
+fn bad_seek() {
+    let mut writer = TransactionalWriter::new(Cursor::new(Vec::new()));
+    writer.seek(SeekFrom::Current(-1)).unwrap_err();
+    assert_eq!(writer.stream_position().unwrap(), 0);
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "std")]
+
+use binrw::{
+    io::{Cursor, Read, Seek},
+    memo, BinRead, BinResult, Endian,
+};
+use core::cell::Cell;
+
+std::thread_local! {
+    static READS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Counted(u8);
+
+impl BinRead for Counted {
+    type Args<'a> = (u8,);
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (add,): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        READS.with(|reads| reads.set(reads.get() + 1));
+        u8::read_options(reader, endian, ()).map(|value| Self(value + add))
+    }
+}
+
+fn read_at(reader: &mut Cursor<&[u8]>, pos: u64, add: u8) -> BinResult<Counted> {
+    reader.set_position(pos);
+    memo::read(reader, Endian::Little, (add,))
+}
+
+fn reads() -> usize {
+    READS.with(|reads| reads.replace(0))
+}
+
+#[test]
+fn memo() {
+    let mut reader = Cursor::new(&b"\x01\x02"[..]);
+
+    memo::scope(|| {
+        assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+        assert_eq!(read_at(&mut reader, 1, 0).unwrap(), Counted(2));
+        assert_eq!(reads(), 2);
+
+        assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+        assert_eq!(reader.position(), 1);
+        assert_eq!(reads(), 0);
+
+        assert_eq!(read_at(&mut reader, 0, 1).unwrap(), Counted(2));
+        assert_eq!(reads(), 1);
+
+        memo::scope(|| {
+            assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+            assert_eq!(reads(), 1);
+        });
+
+        assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+        assert_eq!(reads(), 0);
+    });
+
+    assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+    assert_eq!(read_at(&mut reader, 0, 0).unwrap(), Counted(1));
+    assert_eq!(reads(), 2);
+}
+
+#[test]
+fn memo_error() {
+    let mut reader = Cursor::new(&b""[..]);
+
+    memo::scope(|| {
+        read_at(&mut reader, 0, 0).unwrap_err();
+        read_at(&mut reader, 0, 0).unwrap_err();
+        assert_eq!(reads(), 2);
+    });
+}
+
+#[test]
+fn memo_hash_collision() {
+    // Every value of these arguments has the same hash
+    #[derive(Clone, PartialEq, Eq)]
+    struct Add(u8);
+
+    impl core::hash::Hash for Add {
+        fn hash<H: core::hash::Hasher>(&self, _: &mut H) {}
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Value(u8);
+
+    impl BinRead for Value {
+        type Args<'a> = Add;
+
+        fn read_options<R: Read + Seek>(
+            reader: &mut R,
+            endian: Endian,
+            Add(add): Self::Args<'_>,
+        ) -> BinResult<Self> {
+            u8::read_options(reader, endian, ()).map(|value| Self(value + add))
+        }
+    }
+
+    let mut reader = Cursor::new(&b"\x01"[..]);
+    memo::scope(|| {
+        for add in [0, 1, 0, 1] {
+            reader.set_position(0);
+            let value = memo::read::<_, Value>(&mut reader, Endian::Little, Add(add)).unwrap();
+            assert_eq!(value, Value(1 + add));
+        }
+    });
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "std")]
+
+use binrw::{
+    binread,
+    helpers::{read_to_end, until_eof, until_exclusive},
+    io::Cursor,
+    memory, BinRead, NullString, NullWideString,
+};
+
+#[binread]
+#[br(big)]
+struct Nested {
+    #[br(temp)]
+    len: u8,
+    #[br(count = len, args { inner: binrw::args! { count: 2 } })]
+    data: Vec<Vec<u16>>,
+    name: NullString,
+    #[br(parse_with = until_exclusive(|&byte| byte == 0))]
+    terminated: Vec<u8>,
+    #[br(parse_with = until_eof)]
+    rest: Vec<u32>,
+}
+
+const NESTED: &[u8] = b"\x02\0\x01\0\x02\0\x03\0\x04abc\0\x01\x02\0\0\0\0\x05";
+
+#[test]
+fn measure() {
+    let (value, used) = memory::measure(|| Nested::read(&mut Cursor::new(NESTED)));
+    let value = value.unwrap();
+    assert_eq!(value.data, [[1, 2], [3, 4]]);
+    assert_eq!(value.name.0, b"abc");
+    assert_eq!(value.terminated, [1, 2]);
+    assert_eq!(value.rest, [5]);
+    let vec_size = core::mem::size_of::<Vec<u16>>();
+    assert_eq!(used, 2 * vec_size + 2 * 4 + 3 + 2 + 4);
+
+    let (_, used) = memory::measure(|| u32::read_be(&mut Cursor::new(b"\0\0\0\0")));
+    assert_eq!(used, 0);
+}
+
+#[test]
+fn limit() {
+    let input = b"\xff\xff\xff\xff";
+    let read_huge = || {
+        Vec::<u64>::read_le_args(
+            &mut Cursor::new(input),
+            binrw::args! { count: usize::MAX / 8 },
+        )
+    };
+
+    let (result, used) = memory::limit(1024, read_huge);
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err, binrw::Error::Io(err) if err.kind() == binrw::io::ErrorKind::InvalidData)
+    );
+    assert_eq!(used, 0);
+
+    let (result, _) = memory::limit(16, || Nested::read(&mut Cursor::new(NESTED)));
+    assert!(result.is_err());
+
+    let (result, _) = memory::limit(1024, || Nested::read(&mut Cursor::new(NESTED)));
+    assert!(result.is_ok());
+}
+
+#[test]
+fn unterminated_string() {
+    let mut data = Cursor::new([b'a'; 64]);
+    data.set_position(2);
+    let (result, used) = memory::limit(16, || NullString::read(&mut data));
+    assert!(matches!(
+        result.unwrap_err(),
+        binrw::Error::AssertFail { pos: 2, .. }
+    ));
+    assert_eq!(used, 0);
+    assert_eq!(data.position(), 19);
+}
+
+#[test]
+fn unterminated_wide_string() {
+    let mut data = Cursor::new([b'a'; 64]);
+    data.set_position(2);
+    let (result, used) = memory::limit(16, || NullWideString::read_le(&mut data));
+    assert!(matches!(
+        result.unwrap_err(),
+        binrw::Error::AssertFail { pos: 2, .. }
+    ));
+    assert_eq!(used, 0);
+    assert_eq!(data.position(), 20);
+
+    let (result, used) = memory::limit(16, || {
+        NullWideString::read_le(&mut Cursor::new(b"a\0b\0\0\0"))
+    });
+    assert_eq!(result.unwrap().to_string(), "ab");
+    assert_eq!(used, 4);
+}
+
+#[test]
+fn trailing_data() {
+    let mut data = Cursor::new([1; 64]);
+    data.set_position(4);
+    let (result, used) = memory::limit(60, || read_to_end(&mut data, binrw::Endian::Little, ()));
+    assert_eq!(result.unwrap(), [1; 60]);
+    assert_eq!(used, 60);
+
+    data.set_position(4);
+    let (result, used) = memory::limit(59, || {
+        until_eof::<_, u8, _, Vec<u8>>(&mut data, binrw::Endian::Little, ())
+    });
+    assert!(
+        matches!(result.unwrap_err(), binrw::Error::Io(err) if err.kind() == binrw::io::ErrorKind::InvalidData)
+    );
+    assert_eq!(used, 0);
+}
+
+#[test]
+fn nested_limit() {
+    let (inner, outer_used) = memory::limit(10, || {
+        let (result, inner_used) = memory::limit(1024, || {
+            Vec::<u8>::read_args(&mut Cursor::new([0; 20]), binrw::args! { count: 8 })
+        });
+        assert!(result.is_ok());
+        assert_eq!(inner_used, 8);
+
+        memory::limit(1024, || {
+            Vec::<u8>::read_args(&mut Cursor::new([0; 20]), binrw::args! { count: 4 })
+        })
+        .0
+    });
+    assert!(inner.is_err());
+    assert_eq!(outer_used, 8);
+
+    let (_, used) = memory::measure(|| ());
+    assert_eq!(used, 0);
+}
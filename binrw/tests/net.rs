@@ -0,0 +1,73 @@
+#![cfg(feature = "std")]
+
+use binrw::{io::Cursor, net::IpVersion, BinRead, BinWrite, Endian};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+const V6_BYTES: &[u8] = b"\x20\x01\x0d\xb8\0\0\0\0\0\0\0\0\0\0\0\x01";
+const V6_ADDR: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+
+#[test]
+fn ip_addr() {
+    assert_eq!(
+        Ipv4Addr::read(&mut Cursor::new(b"\x7f\0\0\x01")).unwrap(),
+        Ipv4Addr::LOCALHOST
+    );
+    assert_eq!(Ipv6Addr::read(&mut Cursor::new(V6_BYTES)).unwrap(), V6_ADDR);
+    assert_eq!(
+        IpAddr::read_le_args(&mut Cursor::new(b"\x7f\0\0\x01"), IpVersion::V4).unwrap(),
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    );
+    assert_eq!(
+        IpAddr::read_le_args(&mut Cursor::new(V6_BYTES), IpVersion::V6).unwrap(),
+        IpAddr::V6(V6_ADDR)
+    );
+    assert!(
+        IpAddr::read_le_args(&mut Cursor::new(b"\x7f\0\0\x01"), IpVersion::V6)
+            .unwrap_err()
+            .is_eof()
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    Ipv4Addr::LOCALHOST.write(&mut out).unwrap();
+    V6_ADDR.write(&mut out).unwrap();
+    IpAddr::V4(Ipv4Addr::BROADCAST)
+        .write_options(&mut out, Endian::Little, IpVersion::V4)
+        .unwrap();
+    assert_eq!(
+        out.into_inner(),
+        [b"\x7f\0\0\x01", V6_BYTES, b"\xff\xff\xff\xff"].concat()
+    );
+
+    IpAddr::V4(Ipv4Addr::LOCALHOST)
+        .write_options(&mut Cursor::new(Vec::new()), Endian::Little, IpVersion::V6)
+        .unwrap_err();
+}
+
+#[test]
+fn socket_addr() {
+    let v4 = SocketAddrV4::new(Ipv4Addr::LOCALHOST, 80);
+    let v6 = SocketAddrV6::new(V6_ADDR, 443, 0, 0);
+
+    assert_eq!(
+        SocketAddrV4::read_be(&mut Cursor::new(b"\x7f\0\0\x01\0\x50")).unwrap(),
+        v4
+    );
+    assert_eq!(
+        SocketAddrV4::read_le(&mut Cursor::new(b"\x7f\0\0\x01\x50\0")).unwrap(),
+        v4
+    );
+    assert_eq!(
+        SocketAddrV6::read_be(&mut Cursor::new([V6_BYTES, b"\x01\xbb"].concat())).unwrap(),
+        v6
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    v4.write_be(&mut out).unwrap();
+    SocketAddrV6::new(V6_ADDR, 443, 1, 2)
+        .write_le(&mut out)
+        .unwrap();
+    assert_eq!(
+        out.into_inner(),
+        [b"\x7f\0\0\x01\0\x50", V6_BYTES, b"\xbb\x01"].concat()
+    );
+}
@@ -0,0 +1,80 @@
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+use binrw::{binread, io::Cursor, BinRead, BinWrite, Partial};
+
+#[derive(BinRead, BinWrite, Debug, PartialEq)]
+#[brw(big)]
+struct Entry {
+    #[br(assert(id != 0))]
+    id: u8,
+    len: u16,
+}
+
+#[test]
+fn partial_complete() {
+    let mut data = Cursor::new(b"\x01\0\x10\x02\0\x20\xff");
+    let entries = <Partial<Vec<Entry>>>::read_be_args(
+        &mut data,
+        binrw::VecArgs::builder().count(2).finalize(),
+    )
+    .unwrap();
+    assert!(entries.is_complete());
+    assert_eq!(entries.len(), 2);
+    assert_eq!(data.position(), 6);
+    assert_eq!(
+        entries.into_result().unwrap(),
+        [Entry { id: 1, len: 0x10 }, Entry { id: 2, len: 0x20 }]
+    );
+}
+
+#[test]
+fn partial_error() {
+    #[binread]
+    #[derive(Debug)]
+    #[br(big)]
+    struct Table {
+        #[br(temp)]
+        count: u8,
+        #[br(count = count)]
+        entries: Partial<Vec<Entry>>,
+        trailer: u8,
+    }
+
+    // The reader is left at the start of the element that failed
+    let table = Table::read(&mut Cursor::new(b"\x03\x01\0\x10\0\xff\xff")).unwrap();
+    assert!(!table.entries.is_complete());
+    assert_eq!(*table.entries, [Entry { id: 1, len: 0x10 }]);
+    assert_eq!(
+        table
+            .entries
+            .error
+            .as_ref()
+            .and_then(binrw::Error::position),
+        Some(4)
+    );
+    assert_eq!(table.trailer, 0);
+
+    // Running out of data is also recorded instead of failing
+    let mut data = Cursor::new(b"\x01\0\x10\x02\0");
+    let entries = <Partial<Vec<Entry>>>::read_be_args(
+        &mut data,
+        binrw::VecArgs::builder().count(1000).finalize(),
+    )
+    .unwrap();
+    assert_eq!(entries.value, [Entry { id: 1, len: 0x10 }]);
+    assert!(entries.error.as_ref().unwrap().is_eof());
+    assert_eq!(data.position(), 3);
+    assert!(entries.into_result().is_err());
+}
+
+#[test]
+fn partial_write() {
+    let entries = Partial {
+        value: vec![Entry { id: 1, len: 0x10 }],
+        error: None,
+    };
+    let mut out = Cursor::new(Vec::new());
+    entries.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\x01\0\x10");
+}
@@ -1,7 +1,7 @@
 extern crate alloc;
 
 use alloc::format;
-use binrw::{io::Cursor, BinRead, BinReaderExt, PosValue};
+use binrw::{binread, io::Cursor, BinRead, BinReaderExt, PosValue, RelPos};
 
 #[test]
 fn pos_value() {
@@ -24,3 +24,40 @@ fn pos_value() {
     assert_eq!(*clone, *val.b);
     assert_eq!(clone.pos, val.b.pos);
 }
+
+#[test]
+fn rel_pos() {
+    #[binread]
+    struct MyType {
+        #[br(temp)]
+        start: PosValue<()>,
+        a: u16,
+        #[br(args { anchor: start.pos })]
+        b: RelPos<u8>,
+        c: RelPos<u8>,
+    }
+
+    let mut data = Cursor::new(b"\0\xFF\xFE\xFD\xFC");
+    data.set_position(1);
+    let mut val = data.read_be::<MyType>().unwrap();
+    assert_eq!(val.a, 0xFFFE);
+    assert_eq!(val.b.pos, 2);
+    assert_eq!(*val.b, 0xFD);
+    assert_eq!(val.b, 0xFDu8);
+    assert_eq!(val.c.pos, 4);
+    assert_eq!(*val.c, 0xFC);
+
+    *val.b = 1u8;
+    assert_eq!(*val.b, 1);
+    assert_eq!(format!("{:?}", val.b), "1");
+    let clone = val.b.clone();
+    assert_eq!(*clone, *val.b);
+    assert_eq!(clone.pos, val.b.pos);
+
+    let error = RelPos::<u8>::read_be_args(
+        &mut Cursor::new(b"\0"),
+        binrw::RelPosArgs::builder().anchor(1).finalize(),
+    )
+    .expect_err("accepted bad anchor");
+    assert!(matches!(error, binrw::Error::Io(..)));
+}
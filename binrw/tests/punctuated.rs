@@ -1,17 +1,17 @@
 extern crate alloc;
 
-use alloc::format;
-use binrw::{io::Cursor, punctuated::Punctuated, BinRead, BinReaderExt};
+use alloc::{format, vec, vec::Vec};
+use binrw::{io::Cursor, punctuated::Punctuated, BinRead, BinReaderExt, BinWrite};
 
-#[derive(BinRead, Clone, Copy, Debug)]
-#[br(magic = 1u8)]
+#[derive(BinRead, BinWrite, Clone, Copy, Debug)]
+#[brw(magic = 1u8)]
 struct One;
 
-#[derive(BinRead, Clone, Copy, Debug)]
-#[br(magic = 2u8)]
+#[derive(BinRead, BinWrite, Clone, Copy, Debug)]
+#[brw(magic = 2u8)]
 struct Two;
 
-#[derive(BinRead)]
+#[derive(BinRead, BinWrite)]
 struct PunctuatedTest {
     count: u8,
 
@@ -20,7 +20,7 @@ struct PunctuatedTest {
     list: Punctuated<One, Two>,
 }
 
-#[derive(BinRead)]
+#[derive(BinRead, BinWrite)]
 struct PunctuatedTestTrailing {
     count: u8,
 
@@ -73,6 +73,30 @@ fn punctuated_trailing() {
     y[0] = y[1];
 }
 
+#[test]
+fn punctuated_write() {
+    let value = PunctuatedTest::read_be(&mut Cursor::new(TEST_DATA)).unwrap();
+    let mut out = Cursor::new(Vec::new());
+    value.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), TEST_DATA);
+
+    let value = PunctuatedTestTrailing::read_be(&mut Cursor::new(TEST_DATA_TRAILING)).unwrap();
+    let mut out = Cursor::new(Vec::new());
+    value.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), TEST_DATA_TRAILING);
+
+    let list = Punctuated::new(vec![1u16, 2], vec![b',']);
+    let mut out = Cursor::new(Vec::new());
+    list.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\0\x01,\0\x02");
+
+    let list = Punctuated::new(vec![1u16], vec![b',', b',']);
+    assert!(matches!(
+        list.write_be(&mut Cursor::new(Vec::new())).unwrap_err(),
+        binrw::Error::AssertFail { pos: 0, .. }
+    ));
+}
+
 // TODO: move to UI tests?
 // #[test]
 // #[should_panic]
@@ -0,0 +1,227 @@
+use binrw::{
+    schema::{to_binary_template, to_kaitai, Body, Field, Layout, Magic, Types},
+    BinRead, Endian, NullString,
+};
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(little, schema)]
+struct Header {
+    #[br(magic = 0x1234u16)]
+    version: u8,
+    #[br(big)]
+    len: u32,
+    #[br(calc = len * 2)]
+    _double: u32,
+    #[br(if(version > 1))]
+    name: Option<NullString>,
+    #[br(map = |v: u8| v != 0)]
+    flag: bool,
+    kind: Kind,
+    tags: [u8; 2],
+}
+
+#[derive(BinRead)]
+#[br(repr = u8, schema)]
+enum Kind {
+    A = 1,
+    B = 2,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(schema)]
+enum Chunk {
+    #[br(magic = b"DA")]
+    Data(u8, u16),
+    #[br(magic = b"EN")]
+    End,
+}
+
+#[test]
+fn schema_layout() {
+    let mut types = Types::new();
+    assert_eq!(types.layout_of::<Header>(), Layout::Named("Header".into()));
+
+    let header = types.get("Header").unwrap();
+    assert_eq!(header.endian, Some(Endian::Little));
+    let Body::Struct(fields) = &header.body else {
+        panic!("expected a struct");
+    };
+    let names = fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["version", "len", "name", "flag", "kind", "tags"]);
+    assert_eq!(
+        fields[0],
+        Field {
+            magic: Some(Magic::Value {
+                layout: Layout::Int {
+                    size: 2,
+                    signed: false
+                },
+                value: "0x1234".into(),
+            }),
+            ty: Some("u8".into()),
+            ..Field::new(
+                "version",
+                Layout::Int {
+                    size: 1,
+                    signed: false
+                }
+            )
+        }
+    );
+    assert_eq!(fields[1].endian, Some(Endian::Big));
+    assert_eq!(fields[2].ty.as_deref(), Some("Option<NullString>"));
+    assert_eq!(fields[2].condition.as_deref(), Some("version > 1"));
+    assert!(matches!(fields[3].layout, Layout::Unknown(_)));
+    assert_eq!(fields[4].layout, Layout::Named("Kind".into()));
+
+    let Body::Enum { repr, variants } = &types.get("Kind").unwrap().body else {
+        panic!("expected an enum");
+    };
+    assert_eq!(
+        *repr,
+        Some(Layout::Int {
+            size: 1,
+            signed: false
+        })
+    );
+    assert_eq!(variants[1].value, Some(2));
+}
+
+#[test]
+fn schema_size() {
+    #[allow(dead_code)]
+    #[derive(BinRead)]
+    #[br(schema)]
+    struct Fixed {
+        #[br(magic = 1u16)]
+        kind: Kind,
+        pair: (u8, [i32; 2]),
+        _marker: core::marker::PhantomData<u8>,
+    }
+
+    let mut types = Types::new();
+    let fixed = types.layout_of::<Fixed>();
+    assert_eq!(types.size_of(&fixed), Some(12));
+    let Body::Struct(fields) = &types.get("Fixed").unwrap().body else {
+        panic!("expected a struct");
+    };
+    assert_eq!(fields[1].ty.as_deref(), Some("(u8, [i32; 2])"));
+
+    // Conditions, counts, strings, and variants of different sizes are not
+    // fixed-size
+    let header = types.layout_of::<Header>();
+    assert_eq!(types.size_of(&header), None);
+    let chunk = types.layout_of::<Chunk>();
+    assert_eq!(types.size_of(&chunk), None);
+    let kind = types.layout_of::<Kind>();
+    assert_eq!(types.size_of(&kind), Some(1));
+}
+
+#[test]
+fn schema_kaitai() {
+    assert_eq!(
+        to_kaitai::<Header>("header"),
+        "\
+meta:
+  id: header
+  endian: le
+seq:
+  - id: version_magic
+    type: u2
+    valid: 0x1234
+  - id: version
+    type: u1
+  - id: len
+    type: u4be
+  - id: name
+    type: strz
+    encoding: ASCII
+    if: 'version > 1'
+  - id: flag
+    doc: 'read with a map function'
+  - id: kind
+    type: u1
+    enum: kind
+  - id: tags
+    type: u1
+    repeat: expr
+    repeat-expr: 2
+enums:
+  kind:
+    1: a
+    2: b
+"
+    );
+
+    assert_eq!(
+        to_kaitai::<Chunk>("chunk"),
+        "\
+meta:
+  id: chunk
+seq:
+  - id: magic
+    size: 2
+  - id: body
+    type:
+      switch-on: magic
+      cases:
+        '[0x44, 0x41]': chunk_data
+types:
+  chunk_data:
+    seq:
+      - id: field_0
+        type: u1
+      - id: field_1
+        type: u2
+"
+    );
+}
+
+#[test]
+fn schema_binary_template() {
+    assert_eq!(
+        to_binary_template::<Header>(),
+        "\
+typedef enum <uchar> {
+    Kind_A = 1,
+    Kind_B = 2
+} Kind;
+
+typedef struct {
+    ushort version_magic <format=hex>;
+    if (version_magic != 0x1234) Warning(\"Bad version_magic\");
+    uchar version;
+    local int len_big_endian = IsBigEndian();
+    BigEndian();
+    uint len;
+    if (len_big_endian) BigEndian(); else LittleEndian();
+    if (version > 1) string name;
+    // flag: read with a map function
+    Kind kind;
+    uchar tags[2];
+} Header;
+
+LittleEndian();
+Header file;
+"
+    );
+
+    assert_eq!(
+        to_binary_template::<Chunk>(),
+        "\
+typedef struct {
+    uchar field_0;
+    ushort field_1;
+} Chunk_Data;
+
+typedef struct {
+    uchar magic[2] <format=hex>;
+    if (magic[0] == 0x44 && magic[1] == 0x41) Chunk_Data body;
+} Chunk;
+
+Chunk file;
+"
+    );
+}
@@ -138,6 +138,38 @@ fn null_string_round_trip() {
     assert_eq!(&s2.to_string(), data);
 }
 
+#[test]
+fn null_string_max_len() {
+    use binrw::{
+        io::{Cursor, Seek, SeekFrom},
+        BinRead, BinWrite, NullString, NullStringArgs,
+    };
+
+    let mut data = Cursor::new(b"\0abcd\0");
+    data.seek(SeekFrom::Start(1)).unwrap();
+    let error = NullString::read_le_args(&mut data, NullStringArgs { max_len: 3 }).unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 1, .. }));
+
+    let mut data = Cursor::new(b"abc\0");
+    assert_eq!(
+        NullString::read_le_args(&mut data, NullStringArgs { max_len: 3 })
+            .unwrap()
+            .0,
+        b"abc"
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    out.seek(SeekFrom::Start(1)).unwrap();
+    let error = NullString::from("abcd")
+        .write_le_args(&mut out, NullStringArgs { max_len: 3 })
+        .unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 1, .. }));
+    NullString::from("abc")
+        .write_le_args(&mut out, NullStringArgs { max_len: 3 })
+        .unwrap();
+    assert_eq!(out.into_inner(), b"\0abc\0");
+}
+
 #[test]
 fn null_wide_string_round_trip() {
     use binrw::{io::Cursor, BinReaderExt, BinWriterExt, NullWideString};
@@ -152,3 +184,201 @@ fn null_wide_string_round_trip() {
 
     assert_eq!(&s2.to_string(), data);
 }
+
+#[test]
+fn bom_wide_string() {
+    use binrw::{io::Cursor, BinRead, BinWrite, BomWideString, Endian, NullWideString};
+
+    let s = BomWideString::read_be(&mut Cursor::new(b"\xff\xfeh\0i\0\0\0")).unwrap();
+    assert_eq!(s.string, NullWideString::from("hi"));
+    assert_eq!(s.bom, Some(Endian::Little));
+    let mut out = Cursor::new(Vec::new());
+    s.write_be(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"\xff\xfeh\0i\0\0\0");
+
+    let s = BomWideString::read_be(&mut Cursor::new(b"\0h\0i\0\0")).unwrap();
+    assert_eq!(s.string, NullWideString::from("hi"));
+    assert_eq!(s.bom, None);
+    let mut out = Cursor::new(Vec::new());
+    s.write_le(&mut out).unwrap();
+    assert_eq!(out.into_inner(), b"h\0i\0\0\0");
+}
+
+#[test]
+fn null_wide_string_args() {
+    use binrw::{io::Cursor, BinRead, BinWrite, Endian, NullWideString, NullWideStringArgs};
+
+    let little = NullWideStringArgs {
+        endian: Some(Endian::Little),
+        bom: false,
+    };
+    let bom = NullWideStringArgs {
+        endian: None,
+        bom: true,
+    };
+
+    assert_eq!(
+        NullWideString::read_be_args(&mut Cursor::new(b"h\0i\0\0\0"), little)
+            .unwrap()
+            .to_string(),
+        "hi"
+    );
+
+    for data in [
+        &b"\xff\xfeh\0i\0\0\0"[..],
+        b"\xfe\xff\0h\0i\0\0",
+        b"\0h\0i\0\0",
+    ] {
+        assert_eq!(
+            NullWideString::read_be_args(&mut Cursor::new(data), bom)
+                .unwrap()
+                .to_string(),
+            "hi"
+        );
+    }
+
+    let mut data = Cursor::new(b"\0\0\0\0");
+    assert!(NullWideString::read_be_args(&mut data, bom)
+        .unwrap()
+        .is_empty());
+    assert_eq!(data.position(), 2);
+
+    let s = NullWideString::from("hi");
+    let mut out = Cursor::new(Vec::new());
+    s.write_be_args(&mut out, little).unwrap();
+    s.write_be_args(&mut out, bom).unwrap();
+    s.write_le_args(&mut out, bom).unwrap();
+    assert_eq!(
+        out.into_inner(),
+        b"h\0i\0\0\0\xfe\xff\0h\0i\0\0\xff\xfeh\0i\0\0\0"
+    );
+}
+
+#[test]
+fn c_strings() {
+    use binrw::{
+        io::{Cursor, Seek, SeekFrom},
+        BinRead, BinReaderExt, BinWrite, CStringArgs,
+    };
+    use std::ffi::{CStr, CString};
+
+    let mut data = Cursor::new(b"c string\0after");
+    assert_eq!(data.read_le::<CString>().unwrap().as_bytes(), b"c string",);
+    assert_eq!(data.position(), 9);
+
+    let mut data = Cursor::new(b"\0abcd\0");
+    data.seek(SeekFrom::Start(1)).unwrap();
+    let error = CString::read_le_args(&mut data, CStringArgs { max_len: 3 }).unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 1, .. }));
+
+    let mut data = Cursor::new(b"abc\0");
+    assert_eq!(
+        CString::read_le_args(&mut data, CStringArgs { max_len: 3 })
+            .unwrap()
+            .as_bytes(),
+        b"abc"
+    );
+
+    let mut data = Cursor::new(b"abc");
+    assert!(matches!(
+        data.read_le::<CString>().unwrap_err(),
+        binrw::Error::Io(..)
+    ));
+
+    let mut out = Cursor::new(Vec::new());
+    CString::new("round trip")
+        .unwrap()
+        .write_le(&mut out)
+        .unwrap();
+    CStr::from_bytes_with_nul(b"cstr\0")
+        .unwrap()
+        .write_le(&mut out)
+        .unwrap();
+    assert_eq!(out.into_inner(), b"round trip\0cstr\0");
+}
+
+#[test]
+fn fixed_strings() {
+    use binrw::{io::Cursor, BinRead, BinReaderExt, BinWrite, FixedString, FixedStringArgs};
+
+    let mut data = Cursor::new(b"name\0\0\0\0after");
+    let s = data.read_le::<FixedString<8>>().unwrap();
+    assert_eq!(s.to_string(), "name");
+    assert_eq!(data.position(), 8);
+    assert_eq!(format!("{s:?}"), "FixedString<8>(\"name\")");
+
+    let spaces = FixedStringArgs { pad: b' ' };
+    assert_eq!(
+        FixedString::<6>::read_le_args(&mut Cursor::new(b"a\0b   "), spaces)
+            .unwrap()
+            .as_slice(),
+        b"a\0b"
+    );
+    assert!(
+        FixedString::<4>::read_le_args(&mut Cursor::new(b"    "), spaces)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(Cursor::new(b"abc").read_le::<FixedString<4>>().is_err());
+
+    let mut out = Cursor::new(Vec::new());
+    FixedString::<4>::from("ab").write_le(&mut out).unwrap();
+    FixedString::<4>::from("abcdef")
+        .write_le_args(&mut out, spaces)
+        .unwrap();
+    FixedString::<4>::from("ab")
+        .write_le_args(&mut out, spaces)
+        .unwrap();
+    assert_eq!(out.into_inner(), b"ab\0\0abcdab  ");
+}
+
+#[test]
+fn pascal_strings() {
+    use binrw::{
+        encoding::{Latin1, Utf16Le},
+        io::Cursor,
+        BinRead, BinReaderExt, BinWrite, PascalString, PascalStringArgs,
+    };
+
+    let mut data = Cursor::new(b"\0\x05hello!");
+    let s = data.read_be::<PascalString<u16>>().unwrap();
+    assert_eq!(s.as_str(), "hello");
+    assert_eq!(data.position(), 7);
+    assert_eq!(format!("{s:?}"), "PascalString(\"hello\")");
+
+    let s = PascalString::<u32>::read_le_args(
+        &mut Cursor::new(b"\x04\0\0\0h\0i\0"),
+        PascalStringArgs { encoding: &Utf16Le },
+    )
+    .unwrap();
+    assert_eq!(s.to_string(), "hi");
+
+    assert!(matches!(
+        Cursor::new(b"\x01\xff")
+            .read_le::<PascalString<u8>>()
+            .unwrap_err(),
+        binrw::Error::AssertFail { pos: 1, .. }
+    ));
+    assert!(Cursor::new(b"\x04abc")
+        .read_le::<PascalString<u8>>()
+        .is_err());
+    assert!(Cursor::new(b"\xff\xff\xff\xffabc")
+        .read_le::<PascalString<u32>>()
+        .is_err());
+    assert!(Cursor::new([0xff; 8])
+        .read_le::<PascalString<u64>>()
+        .is_err());
+
+    let mut out = Cursor::new(Vec::new());
+    PascalString::<u16>::from("café")
+        .write_be(&mut out)
+        .unwrap();
+    PascalString::<u16>::from("café")
+        .write_le_args(&mut out, PascalStringArgs { encoding: &Latin1 })
+        .unwrap();
+    assert_eq!(out.into_inner(), b"\0\x05caf\xc3\xa9\x04\0caf\xe9");
+
+    assert!(PascalString::<u8>::from("a".repeat(256))
+        .write_le(&mut Cursor::new(Vec::new()))
+        .is_err());
+}
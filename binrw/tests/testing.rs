@@ -0,0 +1,72 @@
+use binrw::{binrw, testing, Endian};
+
+#[binrw]
+#[derive(Debug, PartialEq)]
+struct Lossy {
+    // Reading keeps only the low bit, so other values do not round-trip
+    #[br(map = |v: u8| v & 1)]
+    flag: u8,
+    #[br(count = 2)]
+    data: Vec<u8>,
+}
+
+#[test]
+fn roundtrip_ok() {
+    testing::assert_roundtrip(
+        &Lossy {
+            flag: 1,
+            data: vec![2, 3],
+        },
+        Endian::Little,
+    );
+    testing::assert_roundtrip_bytes::<Lossy>(b"\x01\x02\x03", Endian::Big);
+    testing::assert_roundtrip_bytes_args::<Vec<u16>>(
+        b"\x01\x02\x03\x04",
+        Endian::Big,
+        binrw::VecArgs::builder().count(2).finalize(),
+        (),
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "value read back differs from value written:\n  Lossy {\n-     flag: 2,\n+     flag: 0,\n"
+)]
+fn roundtrip_value_differs() {
+    testing::assert_roundtrip(
+        &Lossy {
+            flag: 2,
+            data: vec![2, 3],
+        },
+        Endian::Little,
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "reading back only consumed 3 of 4 written bytes:\n  00000000: 01 02 03>04\n"
+)]
+fn roundtrip_trailing_bytes() {
+    testing::assert_roundtrip(
+        &Lossy {
+            flag: 1,
+            data: vec![2, 3, 4],
+        },
+        Endian::Little,
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "bytes written back differ from bytes read, starting at offset 0x0:\n\
+                           read 3:\n  00000000:>03 02 03\nwritten 3:\n  00000000:>01 02 03\n"
+)]
+fn roundtrip_bytes_differ() {
+    testing::assert_roundtrip_bytes::<Lossy>(b"\x03\x02\x03", Endian::Little);
+}
+
+#[test]
+#[should_panic(expected = "failed to read:\n  00000000: 01\nerror:")]
+fn roundtrip_bytes_read_error() {
+    testing::assert_roundtrip_bytes::<Lossy>(b"\x01", Endian::Little);
+}
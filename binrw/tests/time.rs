@@ -0,0 +1,223 @@
+use binrw::{
+    args,
+    io::Cursor,
+    time::{DurationArgs, IntWidth, TimeUnit},
+    BinRead, BinWrite, Endian,
+};
+use core::time::Duration;
+
+#[test]
+fn duration_read() {
+    let args = |width, unit| -> DurationArgs {
+        args! { width, unit }
+    };
+
+    assert_eq!(
+        Duration::read_be_args(
+            &mut Cursor::new(b"\x05"),
+            args(IntWidth::U8, TimeUnit::Seconds)
+        )
+        .unwrap(),
+        Duration::from_secs(5)
+    );
+    assert_eq!(
+        Duration::read_le_args(
+            &mut Cursor::new(b"\xe8\x03"),
+            args(IntWidth::U16, TimeUnit::Millis)
+        )
+        .unwrap(),
+        Duration::from_secs(1)
+    );
+    assert_eq!(
+        Duration::read_be_args(
+            &mut Cursor::new(b"\0\0\0\x2a"),
+            args(IntWidth::U32, TimeUnit::Micros)
+        )
+        .unwrap(),
+        Duration::from_micros(42)
+    );
+    assert_eq!(
+        Duration::read_le_args(
+            &mut Cursor::new(b"\x01\0\0\0\0\0\0\0"),
+            args(IntWidth::U64, TimeUnit::Nanos)
+        )
+        .unwrap(),
+        Duration::from_nanos(1)
+    );
+    assert!(Duration::read_le_args(
+        &mut Cursor::new(b"\x01"),
+        args(IntWidth::U16, TimeUnit::Nanos)
+    )
+    .unwrap_err()
+    .is_eof());
+}
+
+#[test]
+fn duration_write() {
+    let args = |width, unit| -> DurationArgs {
+        args! { width, unit }
+    };
+    let write = |value: Duration, endian, args| {
+        let mut out = Cursor::new(Vec::new());
+        value
+            .write_options(&mut out, endian, args)
+            .map(|_| out.into_inner())
+    };
+
+    assert_eq!(
+        write(
+            Duration::from_millis(1500),
+            Endian::Little,
+            args(IntWidth::U16, TimeUnit::Millis)
+        )
+        .unwrap(),
+        b"\xdc\x05"
+    );
+    assert_eq!(
+        write(
+            Duration::from_millis(1500),
+            Endian::Big,
+            args(IntWidth::U32, TimeUnit::Seconds)
+        )
+        .unwrap(),
+        b"\0\0\0\x01"
+    );
+    assert!(write(
+        Duration::from_secs(1),
+        Endian::Big,
+        args(IntWidth::U8, TimeUnit::Millis)
+    )
+    .is_err());
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+const TIMESTAMPS: &[(binrw::time::TimestampFormat, Endian, &[u8])] = {
+    use binrw::time::TimestampFormat;
+    &[
+        (
+            TimestampFormat::UnixSeconds32,
+            Endian::Little,
+            b"\0\xf1\x53\x65",
+        ),
+        (
+            TimestampFormat::UnixSeconds64,
+            Endian::Big,
+            b"\0\0\0\0\x65\x53\xf1\0",
+        ),
+        (
+            TimestampFormat::UnixMillis64,
+            Endian::Big,
+            b"\0\0\x01\x8b\xcf\xe5\x68\0",
+        ),
+        (
+            TimestampFormat::FileTime,
+            Endian::Little,
+            b"\0\0\x6d\xc6\x47\x17\xda\x01",
+        ),
+        (
+            TimestampFormat::DosDateTime,
+            Endian::Little,
+            b"\xaa\xb1\x6e\x57",
+        ),
+    ]
+};
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_date_time() {
+    use binrw::time::TimestampFormat;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    let expected = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+    for (format, endian, bytes) in TIMESTAMPS {
+        assert_eq!(
+            DateTime::<Utc>::read_options(&mut Cursor::new(bytes), *endian, *format).unwrap(),
+            expected,
+            "{format:?}"
+        );
+
+        let mut out = Cursor::new(Vec::new());
+        (expected + chrono::Duration::nanoseconds(1))
+            .write_options(&mut out, *endian, *format)
+            .unwrap();
+        assert_eq!(&out.into_inner(), bytes, "{format:?}");
+    }
+
+    assert!(DateTime::<Utc>::read_options(
+        &mut Cursor::new(b"\0\0\0\0"),
+        Endian::Little,
+        TimestampFormat::DosDateTime
+    )
+    .is_err());
+    assert!(Utc
+        .timestamp_opt(0, 0)
+        .unwrap()
+        .write_options(
+            &mut Cursor::new(Vec::new()),
+            Endian::Little,
+            TimestampFormat::DosDateTime
+        )
+        .is_err());
+    assert!((expected - chrono::Duration::days(365 * 60))
+        .write_options(
+            &mut Cursor::new(Vec::new()),
+            Endian::Little,
+            TimestampFormat::UnixSeconds32
+        )
+        .is_err());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_offset_date_time() {
+    use binrw::time::TimestampFormat;
+    use time::{OffsetDateTime, UtcOffset};
+
+    let expected = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+    for (format, endian, bytes) in TIMESTAMPS {
+        assert_eq!(
+            OffsetDateTime::read_options(&mut Cursor::new(bytes), *endian, *format).unwrap(),
+            expected,
+            "{format:?}"
+        );
+
+        let mut out = Cursor::new(Vec::new());
+        (expected + time::Duration::nanoseconds(1))
+            .to_offset(UtcOffset::from_hms(2, 0, 0).unwrap())
+            .write_options(&mut out, *endian, *format)
+            .unwrap();
+        assert_eq!(&out.into_inner(), bytes, "{format:?}");
+    }
+
+    assert!(OffsetDateTime::read_options(
+        &mut Cursor::new(b"\0\0\0\0"),
+        Endian::Little,
+        TimestampFormat::DosDateTime
+    )
+    .is_err());
+
+    // February 31, 2021 and February 29, 2021 do not exist, and must not be
+    // moved into March
+    for date in [b"\x5f\x52", b"\x5d\x52"] {
+        let mut data = Cursor::new([b"\xff\0\0".as_slice(), date].concat());
+        data.set_position(1);
+        let err =
+            OffsetDateTime::read_options(&mut data, Endian::Little, TimestampFormat::DosDateTime)
+                .unwrap_err();
+        assert!(
+            matches!(err, binrw::Error::AssertFail { pos: 1, .. }),
+            "{err:?}"
+        );
+    }
+
+    // February 29, 2020
+    assert_eq!(
+        OffsetDateTime::read_options(
+            &mut Cursor::new(b"\0\0\x5d\x50"),
+            Endian::Little,
+            TimestampFormat::DosDateTime
+        )
+        .unwrap(),
+        OffsetDateTime::from_unix_timestamp(1_582_934_400).unwrap()
+    );
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "tracing")]
+
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<String> {
+        core::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(format!("span {}", span.metadata().name()));
+        span.record(&mut fields);
+        let mut log = self.0.lock().unwrap();
+        log.push(fields.0);
+        Id::from_u64(log.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::from("event"));
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn spans_and_events() {
+    #[binrw]
+    #[brw(little)]
+    struct Inner {
+        #[br(assert(a != 0))]
+        a: u8,
+    }
+
+    #[binrw]
+    #[brw(little)]
+    struct Outer {
+        a: u8,
+        inner: Inner,
+    }
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        Outer::read(&mut Cursor::new(b"\x01\x02")).unwrap();
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span binrw::read ty="Outer" pos=0"#,
+                r#"event message=field read field="a" pos=0 len=1"#,
+                r#"span binrw::read ty="Inner" pos=1"#,
+                r#"event message=field read field="a" pos=1 len=1"#,
+                r#"event message=field read field="inner" pos=1 len=1"#,
+            ]
+        );
+
+        assert!(Outer::read(&mut Cursor::new(b"\x01\x00")).is_err());
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span binrw::read ty="Outer" pos=0"#,
+                r#"event message=field read field="a" pos=0 len=1"#,
+                r#"span binrw::read ty="Inner" pos=1"#,
+                r#"event message=field read field="a" pos=1 len=1"#,
+                "event message=assertion failed pos=1 error=assertion failed: `a != 0` at 0x1",
+                "event message=read failed pos=Some(1) error=assertion failed: `a != 0` at 0x1",
+                "event message=read failed pos=Some(1) error=assertion failed: `a != 0` at 0x1",
+            ]
+        );
+
+        Outer {
+            a: 1,
+            inner: Inner { a: 2 },
+        }
+        .write(&mut Cursor::new(Vec::new()))
+        .unwrap();
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span binrw::write ty="Outer" pos=0"#,
+                r#"event message=field written field="a" pos=0 len=1"#,
+                r#"span binrw::write ty="Inner" pos=1"#,
+                r#"event message=field written field="a" pos=1 len=1"#,
+                r#"event message=field written field="inner" pos=1 len=1"#,
+            ]
+        );
+
+        Outer {
+            a: 1,
+            inner: Inner { a: 2 },
+        }
+        .write(&mut Cursor::new(&mut [0_u8; 1][..]))
+        .unwrap_err();
+        assert_eq!(
+            recorder.take(),
+            [
+                r#"span binrw::write ty="Outer" pos=0"#,
+                r#"event message=field written field="a" pos=0 len=1"#,
+                r#"span binrw::write ty="Inner" pos=1"#,
+                "event message=write failed pos=None error=failed to write whole buffer",
+                "event message=write failed pos=None error=failed to write whole buffer",
+            ]
+        );
+    });
+}
@@ -0,0 +1,9 @@
+use binrw::BinRead;
+
+#[derive(BinRead)]
+#[br(preserve_unknown)]
+struct Foo {
+    a: u8,
+}
+
+fn main() {}
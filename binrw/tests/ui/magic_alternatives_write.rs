@@ -0,0 +1,19 @@
+use binrw::{binrw, BinWrite};
+
+#[derive(BinWrite)]
+#[bw(magic(b"V1" | b"V2", name = version))]
+struct Derived {
+    a: u8,
+}
+
+#[binrw]
+#[brw(magic(b"V1" | b"V2"))]
+struct Unnamed {
+    a: u8,
+}
+
+#[binrw]
+#[brw(magic(b"V1" | b"V2", name = version))]
+struct Tuple(u8);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use binrw::binwrite;
+
+#[binwrite]
+#[bw(pos_var = pos, assert(pos == 0))]
+struct Foo {
+    a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use binrw::BinRead;
+
+#[derive(BinRead)]
+#[br(strict, import(count: u8, extra: u8))]
+struct Foo {
+    #[br(count = count)]
+    items: Vec<u8>,
+}
+
+fn main() {}
@@ -1,9 +0,0 @@
-use binrw::{BinRead, binread};
-
-#[derive(BinRead)]
-enum Foo {}
-
-#[binread]
-enum Bar {}
-
-fn main() {}
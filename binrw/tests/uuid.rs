@@ -0,0 +1,27 @@
+#![cfg(feature = "uuid")]
+
+use binrw::{io::Cursor, uuid::UuidLayout, BinRead, BinWrite, Endian};
+use uuid::Uuid;
+
+const RFC_BYTES: &[u8] = b"\x67\xe5\x50\x44\x10\xb1\x42\x6f\x92\x47\xbb\x68\x0e\x5f\xe0\xc8";
+const MS_BYTES: &[u8] = b"\x44\x50\xe5\x67\xb1\x10\x6f\x42\x92\x47\xbb\x68\x0e\x5f\xe0\xc8";
+const UUID: Uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+#[test]
+fn uuid_read() {
+    assert_eq!(Uuid::read(&mut Cursor::new(RFC_BYTES)).unwrap(), UUID);
+    assert_eq!(Uuid::read_le(&mut Cursor::new(RFC_BYTES)).unwrap(), UUID);
+    assert_eq!(
+        Uuid::read_be_args(&mut Cursor::new(MS_BYTES), UuidLayout::Microsoft).unwrap(),
+        UUID
+    );
+}
+
+#[test]
+fn uuid_write() {
+    let mut out = Cursor::new(Vec::new());
+    UUID.write(&mut out).unwrap();
+    UUID.write_options(&mut out, Endian::Big, UuidLayout::Microsoft)
+        .unwrap();
+    assert_eq!(out.into_inner(), [RFC_BYTES, MS_BYTES].concat());
+}
@@ -0,0 +1,225 @@
+use binrw::{
+    io::Cursor,
+    schema::{Body, Field, Layout, Magic, TypeDef, Types, Variant},
+    value::{self, FieldValue},
+    BinRead, Endian, Error, NullString, Value,
+};
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(big, magic = b"HD", schema)]
+struct Header {
+    version: u8,
+    #[br(little)]
+    len: i16,
+    #[br(if(version > 1 && len != 0))]
+    name: Option<NullString>,
+    #[br(count = len as usize * 2)]
+    data: Vec<u8>,
+    kind: Kind,
+    chunks: [Chunk; 2],
+}
+
+#[derive(BinRead)]
+#[br(repr = u8, schema)]
+enum Kind {
+    A = 1,
+    B = 2,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead)]
+#[br(schema)]
+enum Chunk {
+    #[br(magic = 1u8)]
+    Point(f32, u16),
+    #[br(magic = 2u8)]
+    End,
+}
+
+fn field(name: &str, offset: u64, value: Value) -> FieldValue {
+    FieldValue {
+        name: name.into(),
+        offset,
+        value,
+    }
+}
+
+#[test]
+fn value_read_derived_schema() {
+    let mut types = Types::new();
+    let header = types.layout_of::<Header>();
+
+    let mut data = Cursor::new(b"HD\x02\x01\0ab\0\xaa\xbb\x01\x01\x3f\x80\0\0\0\x07\x02");
+    let value = value::read(&mut data, Endian::Little, &types, &header).unwrap();
+    assert_eq!(
+        value,
+        Value::Struct(vec![
+            field("version", 2, Value::UInt(2)),
+            field("len", 3, Value::Int(1)),
+            field("name", 5, Value::String("ab".into())),
+            field("data", 8, Value::Bytes(vec![0xaa, 0xbb])),
+            field(
+                "kind",
+                10,
+                Value::Variant {
+                    name: "A".into(),
+                    fields: vec![]
+                }
+            ),
+            field(
+                "chunks",
+                11,
+                Value::List(vec![
+                    Value::Variant {
+                        name: "Point".into(),
+                        fields: vec![
+                            field("0", 12, Value::Float(1.0)),
+                            field("1", 16, Value::UInt(7)),
+                        ]
+                    },
+                    Value::Variant {
+                        name: "End".into(),
+                        fields: vec![]
+                    },
+                ])
+            ),
+        ])
+    );
+    assert_eq!(data.position(), 19);
+}
+
+#[test]
+fn value_read_runtime_schema() {
+    let mut types = Types::new();
+    let u8_layout = types.layout_of::<u8>();
+    let u32_layout = types.layout_of::<u32>();
+    let entry = types.insert(
+        "Entry",
+        TypeDef {
+            endian: None,
+            magic: None,
+            body: Body::Struct(vec![
+                Field::new("flags", u8_layout.clone()),
+                Field {
+                    condition: Some("(flags & 0x80) != 0".into()),
+                    endian: Some(Endian::Big),
+                    ..Field::new("extra", u32_layout.clone())
+                },
+                Field {
+                    magic: Some(Magic::Value {
+                        layout: u8_layout.clone(),
+                        value: "0xee".into(),
+                    }),
+                    count: Some("flags & 0x0f".into()),
+                    ..Field::new(
+                        "items",
+                        Layout::List {
+                            inner: Box::new(u32_layout.clone()),
+                            len: None,
+                        },
+                    )
+                },
+            ]),
+        },
+    );
+
+    let mut data = Cursor::new(b"\x81\0\0\x01\0\xee\x02\0\0\0\x01\xee\x05\0\0\0");
+    let entries = Layout::List {
+        inner: Box::new(entry.clone()),
+        len: Some(2),
+    };
+    let value = value::read(&mut data, Endian::Little, &types, &entries).unwrap();
+    let Value::List(entries) = value else {
+        panic!("expected a list");
+    };
+    assert_eq!(entries[0].get("extra"), Some(&Value::UInt(0x100)));
+    assert_eq!(
+        entries[0].get("items"),
+        Some(&Value::List(vec![Value::UInt(2)]))
+    );
+    assert_eq!(entries[1].get("extra"), Some(&Value::None));
+    assert_eq!(entries[1].field("items").map(|f| f.offset), Some(12));
+    assert_eq!(
+        entries[1].get("items"),
+        Some(&Value::List(vec![Value::UInt(5)]))
+    );
+
+    // A wrong magic is reported at its position
+    let mut data = Cursor::new(b"\x01\xef\0\0\0\0");
+    let err = value::read(&mut data, Endian::Little, &types, &entry).unwrap_err();
+    assert!(matches!(err, Error::BadMagic { pos: 1, .. }), "{err:?}");
+
+    // Unreadable layouts and bad expressions are described
+    types.insert(
+        "Broken",
+        TypeDef {
+            endian: None,
+            magic: None,
+            body: Body::Enum {
+                repr: None,
+                variants: vec![Variant {
+                    name: "Only".into(),
+                    magic: None,
+                    value: None,
+                    fields: vec![Field {
+                        count: Some("missing + 1".into()),
+                        ..Field::new(
+                            "list",
+                            Layout::List {
+                                inner: Box::new(u8_layout),
+                                len: None,
+                            },
+                        )
+                    }],
+                }],
+            },
+        },
+    );
+    let err = value::read(
+        &mut Cursor::new(b""),
+        Endian::Little,
+        &types,
+        &Layout::Named("Broken".into()),
+    )
+    .unwrap_err();
+    assert!(matches!(err, Error::NoVariantMatch { pos: 0 }), "{err:?}");
+
+    let err = value::read(
+        &mut Cursor::new(b""),
+        Endian::Little,
+        &types,
+        &Layout::Bits(3),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.custom_err::<String>().map(String::as_str),
+        Some("bit fields are not supported")
+    );
+}
+
+#[test]
+fn value_generate() {
+    let mut types = Types::new();
+    let header = types.layout_of::<Header>();
+
+    for seed in 0..64_u8 {
+        let entropy = (0..64_u8)
+            .map(|i| i.wrapping_mul(seed).wrapping_add(seed))
+            .collect::<Vec<_>>();
+        let input = value::generate(Endian::Little, &types, &header, &entropy);
+        assert_eq!(
+            input,
+            value::generate(Endian::Little, &types, &header, &entropy)
+        );
+
+        let parsed = Header::read(&mut Cursor::new(&input)).unwrap();
+        assert_eq!(parsed.data.len(), usize::try_from(parsed.len).unwrap() * 2);
+        assert_eq!(parsed.name.is_some(), parsed.version > 1 && parsed.len != 0);
+        let value = value::read(&mut Cursor::new(&input), Endian::Little, &types, &header).unwrap();
+        assert_eq!(
+            value.get("len").and_then(Value::as_int),
+            Some(parsed.len.into())
+        );
+    }
+}
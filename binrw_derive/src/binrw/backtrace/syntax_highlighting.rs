@@ -199,9 +199,15 @@ fn visit_expr_attributes(field: &StructField, visitor: &mut Visitor) {
         let Condition {
             condition,
             alternate,
+            flag,
         } = condition;
 
-        visit!(condition);
+        if let Some((flags, mask)) = flag {
+            visit!(flags);
+            visit!(mask);
+        } else {
+            visit!(condition);
+        }
         if let Some(alternate) = alternate {
             visit!(alternate);
         }
@@ -0,0 +1,183 @@
+use super::{
+    get_args_lifetime,
+    sanitization::{
+        BINREAD_TRAIT, BIN_RESULT, DUMPER, DUMPER_VAR, DUMP_TRAIT, DUMP_WITH_SPANS, ENDIAN_ENUM,
+        READ_ENDIAN, READ_TRAIT, REQUIRED_ARG_TRAIT, SEEK_FROM, SEEK_TRAIT, STRING, WIRE_SPANS_FN,
+    },
+};
+use crate::binrw::parser::{EnumVariant, Input, Struct};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{ext::IdentExt, spanned::Spanned, DeriveInput, Ident, Type};
+
+/// Generates an implementation of `Dump`, and for structs, the `read_dump`
+/// functions.
+pub(crate) fn generate(input: &Input, derive_input: &DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let mut types = Vec::new();
+    let body = match input {
+        Input::Struct(st) => {
+            let arm = dump_fields(&quote! { Self }, name, st, &mut types);
+            quote! { match self { #arm } }
+        }
+        Input::UnitStruct(_) => dump_unit(name),
+        Input::Enum(en) if en.variants.is_empty() => quote! { match *self {} },
+        Input::Enum(en) => {
+            let arms = en
+                .variants
+                .iter()
+                .map(|variant| match variant {
+                    EnumVariant::Variant { ident, options } => {
+                        dump_fields(&quote! { Self::#ident }, ident, options, &mut types)
+                    }
+                    EnumVariant::Unit(field) => {
+                        let ident = &field.ident;
+                        let unit = dump_unit(ident);
+                        quote! { Self::#ident => { #unit } }
+                    }
+                })
+                .collect::<Vec<_>>();
+            quote! { match self { #(#arms)* } }
+        }
+        Input::UnitOnlyEnum(en) => {
+            let arms = en.fields.iter().map(|field| {
+                let ident = &field.ident;
+                let unit = dump_unit(ident);
+                quote! { Self::#ident => { #unit } }
+            });
+            quote! { match self { #(#arms)* } }
+        }
+    };
+
+    let bounds = types
+        .iter()
+        .map(|ty| quote_spanned! {ty.span()=> #ty: #DUMP_TRAIT });
+    let dump_where_clause = match where_clause {
+        Some(where_clause) if !where_clause.predicates.empty_or_trailing() => {
+            quote! { #where_clause, #(#bounds,)* }
+        }
+        Some(where_clause) => quote! { #where_clause #(#bounds,)* },
+        None => quote! { where #(#bounds,)* },
+    };
+
+    let read_fns = match input {
+        Input::Struct(_) | Input::UnitStruct(_) => Some(generate_read_fns(derive_input)),
+        Input::Enum(_) | Input::UnitOnlyEnum(_) => None,
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #DUMP_TRAIT for #name #ty_generics #dump_where_clause {
+            fn dump(&self, #DUMPER_VAR: &mut #DUMPER<'_>) {
+                #body
+            }
+        }
+
+        #read_fns
+    }
+}
+
+/// Generates a match arm which dumps the fields of a struct or variant, and
+/// adds the type of each field to `types`.
+fn dump_fields<'a>(
+    path: &TokenStream,
+    name: &Ident,
+    st: &'a Struct,
+    types: &mut Vec<&'a Type>,
+) -> TokenStream {
+    let pattern = st.fields_pattern();
+    let name = name.to_string();
+    let is_tuple = st.is_tuple();
+    let fields = st
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !field.is_temp(st.for_write))
+        .map(|(index, field)| {
+            types.push(&field.ty);
+            let ident = &field.ident;
+            // Tuple fields use their index, like the positions recorded for
+            // `read_dump`
+            let field_name = if is_tuple {
+                index.to_string()
+            } else {
+                ident.unraw().to_string()
+            };
+            quote! { .field(#field_name, #ident) }
+        });
+    let block = if is_tuple {
+        quote! { tuple }
+    } else {
+        quote! { record }
+    };
+
+    quote! {
+        #path #pattern => {
+            #DUMPER::#block(#DUMPER_VAR, #name) #(#fields)* .finish();
+        }
+    }
+}
+
+fn dump_unit(name: &Ident) -> TokenStream {
+    let name = name.to_string();
+    quote! { #DUMPER::value(#DUMPER_VAR, #name); }
+}
+
+fn generate_read_fns(derive_input: &DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+    let vis = &derive_input.vis;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let args_lifetime = get_args_lifetime(Span::call_site());
+
+    let read_dump_doc = format!(
+        "Reads a [`{name}`] and renders it in the [dump format](binrw::dump), \
+        annotating each field with the range of bytes it was read from. Uses \
+        default arguments and the byte order of the struct.\n\n\
+        # Errors\n\n\
+        If reading fails, the error is returned."
+    );
+    let read_dump_options_doc = format!(
+        "Reads a [`{name}`] and renders it in the [dump format](binrw::dump), \
+        annotating each field with the range of bytes it was read from. Uses \
+        default arguments and the given byte order.\n\n\
+        # Errors\n\n\
+        If reading fails, the error is returned."
+    );
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #read_dump_doc]
+            #vis fn read_dump<R>(reader: &mut R) -> #BIN_RESULT<#STRING>
+            where
+                R: #READ_TRAIT + #SEEK_TRAIT,
+                // The bound is higher-ranked so that it is not rejected as a
+                // trivially false bound for a type without a fixed byte order
+                for<#args_lifetime> Self: #READ_ENDIAN + #DUMP_TRAIT,
+                for<#args_lifetime> <Self as #BINREAD_TRAIT>::Args<#args_lifetime>: #REQUIRED_ARG_TRAIT,
+            {
+                Self::read_dump_options(reader, #ENDIAN_ENUM::Little)
+            }
+
+            #[doc = #read_dump_options_doc]
+            #vis fn read_dump_options<R>(reader: &mut R, endian: #ENDIAN_ENUM) -> #BIN_RESULT<#STRING>
+            where
+                R: #READ_TRAIT + #SEEK_TRAIT,
+                for<#args_lifetime> Self: #DUMP_TRAIT,
+                for<#args_lifetime> <Self as #BINREAD_TRAIT>::Args<#args_lifetime>: #REQUIRED_ARG_TRAIT,
+            {
+                let pos = #SEEK_TRAIT::stream_position(reader)?;
+                let spans = Self::#WIRE_SPANS_FN(reader, endian, #REQUIRED_ARG_TRAIT::args())?;
+                #SEEK_TRAIT::seek(reader, #SEEK_FROM::Start(pos))?;
+                let value = <Self as #BINREAD_TRAIT>::read_options(
+                    reader,
+                    endian,
+                    #REQUIRED_ARG_TRAIT::args(),
+                )?;
+                Ok(#DUMP_WITH_SPANS(&value, &spans))
+            }
+        }
+    }
+}
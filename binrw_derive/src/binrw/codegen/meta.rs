@@ -10,17 +10,23 @@ pub(crate) fn generate<const WRITE: bool>(
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
-    let magic = input.magic().as_ref().map(|magic| {
-        let magic_meta = if WRITE { WRITE_MAGIC } else { READ_MAGIC };
-        let ty = TokenStream::from(magic.kind());
-        let val = magic.deref_value();
-        quote! {
-            impl #impl_generics #magic_meta for #name #ty_generics #where_clause {
-                type MagicType = #ty;
-                const MAGIC: Self::MagicType = #val;
+    // Which of several alternative magics is written depends on the value, so
+    // there is no constant for it
+    let magic = input
+        .magic()
+        .as_ref()
+        .filter(|magic| !WRITE || !magic.has_alternatives())
+        .map(|magic| {
+            let magic_meta = if WRITE { WRITE_MAGIC } else { READ_MAGIC };
+            let ty = TokenStream::from(magic.kind());
+            let val = magic.deref_value();
+            quote! {
+                impl #impl_generics #magic_meta for #name #ty_generics #where_clause {
+                    type MagicType = #ty;
+                    const MAGIC: Self::MagicType = #val;
+                }
             }
-        }
-    });
+        });
 
     let endian_meta = if WRITE { WRITE_ENDIAN } else { READ_ENDIAN };
 
@@ -38,7 +44,7 @@ pub(crate) fn generate<const WRITE: bool>(
                 quote! { <(#repr) as #endian_meta>::ENDIAN }
             }),
         },
-        CondEndian::Fixed(endian) => Some(quote! {
+        CondEndian::Fixed(endian) | CondEndian::Default(endian) => Some(quote! {
             #META_ENDIAN_KIND::Endian(#endian)
         }),
         CondEndian::Cond(..) => Some(quote! {
@@ -1,23 +1,30 @@
+mod dump;
 mod meta;
 mod read_options;
 pub(crate) mod sanitization;
+mod schema;
+mod sequential;
+mod visitor;
+mod wire_eq;
 mod write_options;
 
 use crate::{
     binrw::parser::{
         Assert, AssertionError, CondEndian, Imports, Input, ParseResult, PassedArgs, StructField,
     },
+    meta_types::IdentTypeMaybeDefault,
     named_args::{arg_type_name, derive_from_imports},
     util::{quote_spanned_any, IdentStr},
 };
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Literal, Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use sanitization::{
-    ARGS, ARGS_LIFETIME, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, BINREAD_TRAIT, BINWRITE_TRAIT,
-    BIN_ERROR, BIN_RESULT, ENDIAN_ENUM, OPT, POS, READER, READ_TRAIT, SEEK_TRAIT, TEMP, WRITER,
-    WRITE_TRAIT,
+    ARGS, ARGS_LIFETIME, ARGS_LIST, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, BINREAD_TRAIT,
+    BINWRITE_TRAIT, BIN_ERROR, BIN_RESULT, CURSOR, ENDIAN_ENUM, FILE, INTO_ERROR, OPT, POS, READER,
+    READ_DYN_FN, READ_ENDIAN, READ_FILE, READ_SEEK_TRAIT, READ_TRAIT, REQUIRED_ARG_TRAIT,
+    SEEK_TRAIT, SPAN, TEMP, TRACE_POS, TRACING, TRY_READ, WRITER, WRITE_TRAIT,
 };
-use syn::{spanned::Spanned, DeriveInput, Ident, Type};
+use syn::{ext::IdentExt, spanned::Spanned, DeriveInput, Ident, Type};
 
 pub(crate) fn generate_impl<const WRITE: bool>(
     derive_input: &DeriveInput,
@@ -42,10 +49,149 @@ pub(crate) fn generate_impl<const WRITE: bool>(
         ParseResult::Err(_) => None,
     };
 
+    let sequential = match binrw_input {
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) => {
+            sequential::generate::<WRITE>(binrw_input, derive_input)
+        }
+        ParseResult::Err(_) => None,
+    };
+
+    // The visitor is only generated by the `BinRead` side so that `#[binrw]`
+    // does not emit it twice
+    let visitor = match binrw_input {
+        ParseResult::Ok(Input::Enum(en)) | ParseResult::Partial(Input::Enum(en), _)
+            if !WRITE && en.visitor.is_some() =>
+        {
+            Some(visitor::generate(en, derive_input))
+        }
+        _ => None,
+    };
+
+    // Like the visitor, these are only generated by the `BinRead` side
+    let wire_eq = match binrw_input {
+        ParseResult::Ok(input @ (Input::Struct(st) | Input::UnitStruct(st)))
+            if !WRITE && (st.wire_eq.is_some() || st.dump.is_some()) =>
+        {
+            let spans_fn = wire_eq::generate_spans_fn(input, st, derive_input);
+            let wire_eq = st
+                .wire_eq
+                .is_some()
+                .then(|| wire_eq::generate(derive_input));
+            Some(quote! { #spans_fn #wire_eq })
+        }
+        _ => None,
+    };
+
+    let dump = match binrw_input {
+        ParseResult::Ok(binrw_input) if !WRITE && binrw_input.dump() => {
+            Some(dump::generate(binrw_input, derive_input))
+        }
+        _ => None,
+    };
+
+    let schema = match binrw_input {
+        ParseResult::Ok(binrw_input) if !WRITE && binrw_input.schema() => {
+            Some(schema::generate(binrw_input, derive_input))
+        }
+        _ => None,
+    };
+
+    let try_from_impls = match binrw_input {
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _)
+            if !WRITE && binrw_input.impl_try_from() =>
+        {
+            Some(generate_try_from_impls(
+                derive_input,
+                binrw_input.error_type(),
+            ))
+        }
+        _ => None,
+    };
+
+    let try_read = match binrw_input {
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) if !WRITE => {
+            binrw_input
+                .error_type()
+                .map(|error_type| generate_try_read_impl(derive_input, error_type))
+        }
+        _ => None,
+    };
+
     quote! {
         #trait_impl
         #meta_impls
+        #sequential
         #arg_type_declaration
+        #visitor
+        #wire_eq
+        #dump
+        #schema
+        #try_from_impls
+        #try_read
+    }
+}
+
+fn generate_try_read_impl(derive_input: &DeriveInput, error_type: &TokenStream) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #TRY_READ for #name #ty_generics #where_clause {
+            type Error = #error_type;
+        }
+    }
+}
+
+fn generate_try_from_impls(
+    derive_input: &DeriveInput,
+    error_type: Option<&TokenStream>,
+) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let args_lifetime = get_args_lifetime(Span::call_site());
+    let bounds = quote! {
+        Self: #BINREAD_TRAIT + #READ_ENDIAN,
+        for<#args_lifetime> <Self as #BINREAD_TRAIT>::Args<#args_lifetime>: #REQUIRED_ARG_TRAIT,
+    };
+    let where_clause = match where_clause {
+        Some(where_clause) if !where_clause.predicates.empty_or_trailing() => {
+            quote! { #where_clause, #bounds }
+        }
+        Some(where_clause) => quote! { #where_clause #bounds },
+        None => quote! { where #bounds },
+    };
+
+    let (error_type, map_err) = match error_type {
+        Some(error_type) => (error_type.clone(), quote! { .map_err(#INTO_ERROR) }),
+        None => (BIN_ERROR.to_token_stream(), TokenStream::new()),
+    };
+
+    // `TryFrom` cannot be implemented for every reader type since it would
+    // conflict with the blanket implementation for `Into`
+    let file_impl = cfg!(feature = "std").then(|| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::TryFrom<&mut #FILE> for #name #ty_generics #where_clause {
+                type Error = #error_type;
+
+                fn try_from(file: &mut #FILE) -> ::core::result::Result<Self, Self::Error> {
+                    #READ_FILE(file)#map_err
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::TryFrom<&[u8]> for #name #ty_generics #where_clause {
+            type Error = #error_type;
+
+            fn try_from(bytes: &[u8]) -> ::core::result::Result<Self, Self::Error> {
+                <Self as #BINREAD_TRAIT>::read(&mut #CURSOR::new(bytes))#map_err
+            }
+        }
+
+        #file_impl
     }
 }
 
@@ -96,8 +242,32 @@ fn generate_imports(
 
     match imports {
         Imports::None => (quote! { () }, None),
-        Imports::List(_, types) => {
-            let types = types.iter().map(|ty| ExpandLifetimes.fold_type(ty.clone()));
+        Imports::List(args) if imports.has_list_defaults() => {
+            let name = arg_type_name(type_name, is_write);
+            let lifetime = args
+                .iter()
+                .any(|arg| has_elided_lifetime(&arg.ty))
+                .then(|| get_args_lifetime(type_name.span()));
+            let types = args
+                .iter()
+                .map(|arg| ExpandLifetimes.fold_type(arg.ty.clone()))
+                .collect::<Vec<_>>();
+            let generics = lifetime.map(|lifetime| quote! { <#lifetime> });
+            let defs = generate_list_defaults(
+                type_name,
+                is_write,
+                &name,
+                ty_vis,
+                generics.as_ref(),
+                args,
+                &types,
+            );
+            (quote! { #name #generics }, Some(defs))
+        }
+        Imports::List(args) => {
+            let types = args
+                .iter()
+                .map(|arg| ExpandLifetimes.fold_type(arg.ty.clone()));
             (quote! { (#(#types,)*) }, None)
         }
         Imports::Raw(_, ty) => (
@@ -136,6 +306,84 @@ fn generate_imports(
     }
 }
 
+/// Generates the arguments type for an `import(...)` list with default
+/// values, which can be converted from a tuple of any number of the leading
+/// arguments so long as every argument without a default is included.
+fn generate_list_defaults(
+    type_name: &Ident,
+    is_write: bool,
+    name: &Ident,
+    ty_vis: &syn::Visibility,
+    generics: Option<&TokenStream>,
+    args: &[IdentTypeMaybeDefault],
+    types: &[Type],
+) -> TokenStream {
+    let required = args.iter().take_while(|arg| arg.default.is_none()).count();
+    let idents = args.iter().map(|arg| &arg.ident).collect::<Vec<_>>();
+
+    let from_impls = (required..=args.len()).map(|passed| {
+        let (passed_idents, default_idents) = idents.split_at(passed);
+        let passed_types = &types[..passed];
+        let defaults = args[passed..].iter().map(|arg| &arg.default);
+        let passed_len = Literal::usize_unsuffixed(passed);
+        quote! {
+            impl #generics ::core::convert::From<(#(#passed_types,)*)> for #name #generics {
+                fn from((#(#passed_idents,)*): (#(#passed_types,)*)) -> Self {
+                    #(let #default_idents = #defaults;)*
+                    Self(#(#idents),*)
+                }
+            }
+
+            impl #generics #ARGS_LIST<#passed_len> for #name #generics {
+                type List = (#(#passed_types,)*);
+
+                fn from_list(list: Self::List) -> Self {
+                    <Self as ::core::convert::From<Self::List>>::from(list)
+                }
+            }
+        }
+    });
+
+    let default_impl = (required == 0).then(|| {
+        quote! {
+            impl #generics ::core::default::Default for #name #generics {
+                fn default() -> Self {
+                    Self::from(())
+                }
+            }
+        }
+    });
+
+    let (impl_name, impl_fn) = if is_write {
+        ("BinWrite", "write_options")
+    } else {
+        ("BinRead", "read_options")
+    };
+    let docs = format!(
+        "Arguments for the [`{impl_name}::{impl_fn}`](::binrw::{impl_name}::{impl_fn}) implementation of [`{type_name}`].",
+    );
+
+    let indexes = (0..types.len()).map(syn::Index::from);
+
+    // See the named arguments builder for why `Clone` is implemented this way
+    quote! {
+        #[doc = #docs]
+        #ty_vis struct #name #generics (#(pub #types),*);
+
+        impl #generics ::core::clone::Clone for #name #generics
+        where
+            #(for<'__binrw_generated_clone> #types: ::core::clone::Clone,)*
+        {
+            fn clone(&self) -> Self {
+                Self(#(::core::clone::Clone::clone(&self.#indexes)),*)
+            }
+        }
+
+        #(#from_impls)*
+        #default_impl
+    }
+}
+
 fn generate_trait_impl<const WRITE: bool>(
     binrw_input: &ParseResult<Input>,
     derive_input: &DeriveInput,
@@ -182,6 +430,34 @@ fn generate_trait_impl<const WRITE: bool>(
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
+    // With `dyn_reader`, the body is moved to a separate function which is
+    // only ever instantiated with a trait object reader, so every reader type
+    // shares one copy of it
+    let (fn_impl, dyn_impl) = match binrw_input {
+        ParseResult::Ok(binrw_input) if !WRITE && binrw_input.dyn_reader() => (
+            quote! {
+                let mut #READER: &mut (dyn #READ_SEEK_TRAIT + '_) = #READER;
+                Self::#READ_DYN_FN(&mut #READER, #OPT, #ARGS)
+            },
+            Some(quote! {
+                #[automatically_derived]
+                #[allow(non_snake_case, unknown_lints)]
+                #[allow(clippy::redundant_closure_call)]
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #[inline(never)]
+                    fn #READ_DYN_FN<R: #READ_TRAIT + #SEEK_TRAIT>(
+                        #READER: &mut R,
+                        #OPT: #ENDIAN_ENUM,
+                        #ARGS: <Self as #BINREAD_TRAIT>::Args<'_>,
+                    ) -> #BIN_RESULT<Self> {
+                        #fn_impl
+                    }
+                }
+            }),
+        ),
+        _ => (fn_impl, None),
+    };
+
     let args_lifetime = get_args_lifetime(Span::call_site());
     quote! {
         #[automatically_derived]
@@ -194,6 +470,8 @@ fn generate_trait_impl<const WRITE: bool>(
                 #fn_impl
             }
         }
+
+        #dyn_impl
     }
 }
 
@@ -232,11 +510,18 @@ fn get_destructured_imports(
 ) -> Option<TokenStream> {
     match imports {
         Imports::None => None,
-        Imports::List(idents, _) => {
-            if idents.is_empty() {
+        Imports::List(args) if imports.has_list_defaults() => type_name.map(|type_name| {
+            let args_ty_name = arg_type_name(type_name, is_write);
+            let idents = args.iter().map(|x| &x.ident);
+            quote! {
+                #args_ty_name(#(mut #idents),*)
+            }
+        }),
+        Imports::List(args) => {
+            if args.is_empty() {
                 None
             } else {
-                let idents = idents.iter();
+                let idents = args.iter().map(|x| &x.ident);
                 Some(quote! {
                     (#(mut #idents,)*)
                 })
@@ -259,7 +544,7 @@ fn get_destructured_imports(
 
 fn get_endian(endian: &CondEndian) -> TokenStream {
     match endian {
-        CondEndian::Inherited => OPT.to_token_stream(),
+        CondEndian::Inherited | CondEndian::Default(_) => OPT.to_token_stream(),
         CondEndian::Fixed(endian) => endian.to_token_stream(),
         CondEndian::Cond(endian, condition) => {
             let (true_cond, false_cond) = (endian, endian.flipped());
@@ -306,6 +591,95 @@ fn get_passed_args(field: &StructField, stream: &TokenStream) -> Option<TokenStr
     }
 }
 
+/// Returns the number of arguments in an `args(...)` list, whose tuple must be
+/// converted to the arguments type of the field through `ArgsList`.
+fn get_passed_args_list_len(field: &StructField) -> Option<Literal> {
+    match &field.args {
+        PassedArgs::List(list) => Some(Literal::usize_unsuffixed(list.len())),
+        _ => None,
+    }
+}
+
+fn get_span(direction: &str, name: &Ident) -> Option<TokenStream> {
+    cfg!(feature = "tracing").then(|| {
+        let name = name.to_string();
+        quote! {
+            let #SPAN = #TRACING::debug_span!(#direction, ty = #name, pos = #POS).entered();
+        }
+    })
+}
+
+/// Wraps the code which reads or writes a field so that a trace event with
+/// the position and length of the field is emitted afterwards, if the
+/// `tracing` feature is enabled. `code` is an expression of type `value_ty`
+/// whose value is kept if `value_ty` is given, and statements otherwise.
+fn wrap_field_trace(
+    stream: &TokenStream,
+    ident: &Ident,
+    message: &str,
+    code: TokenStream,
+    value_ty: Option<&Type>,
+) -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return code;
+    }
+
+    let name = ident.unraw().to_string();
+    // The position is only queried if the event would be recorded, since
+    // querying it flushes a buffered writer
+    let start = quote! {
+        let #TRACE_POS = if #TRACING::enabled!(#TRACING::Level::TRACE) {
+            ::core::option::Option::Some(#SEEK_TRAIT::stream_position(#stream)?)
+        } else {
+            ::core::option::Option::None
+        };
+    };
+    let end = quote! {
+        if let ::core::option::Option::Some(#TRACE_POS) = #TRACE_POS {
+            #TRACING::trace!(
+                field = #name,
+                pos = #TRACE_POS,
+                len = #SEEK_TRAIT::stream_position(#stream)?.saturating_sub(#TRACE_POS),
+                #message
+            );
+        }
+    };
+
+    if let Some(value_ty) = value_ty {
+        // The type is given so that inference of the value is not lost by
+        // binding it to a variable
+        quote! {{
+            #start
+            let #TEMP: #value_ty = #code;
+            #end
+            #TEMP
+        }}
+    } else {
+        quote! {
+            #start
+            #code
+            #end
+        }
+    }
+}
+
+/// Emits a debug event with the position and cause of the error if reading or
+/// writing a type fails, if the `tracing` feature is enabled.
+fn get_trace_err(message: &str) -> Option<TokenStream> {
+    cfg!(feature = "tracing").then(|| {
+        quote! {
+            .map_err(|error: #BIN_ERROR| {
+                #TRACING::debug!(
+                    pos = ?error.position(),
+                    error = %error.root_cause(),
+                    #message
+                );
+                error
+            })
+        }
+    })
+}
+
 fn get_try_calc(pos: IdentStr, ty: &Type, calc: &TokenStream) -> TokenStream {
     let map_err = get_map_err(pos, calc.span());
     quote_spanned! {ty.span()=> {
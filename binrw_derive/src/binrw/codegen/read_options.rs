@@ -2,17 +2,17 @@ mod r#enum;
 mod map;
 mod r#struct;
 
-use super::{get_assertions, get_destructured_imports};
+use super::{get_assertions, get_destructured_imports, get_span, get_trace_err};
 use crate::{
     binrw::{
         codegen::{
             get_endian,
             sanitization::{
-                ARGS, ASSERT_MAGIC, MAP_READER_TYPE_HINT, OPT, POS, READER, RESTORE_POSITION,
-                SEEK_TRAIT,
+                ARGS, ASSERT_MAGIC, ASSERT_MAGIC_ANY, ASSERT_MAGIC_ANY_MASKED, ASSERT_MAGIC_MASKED,
+                MAP_READER_TYPE_HINT, OPT, POS, READER, RESTORE_POSITION, SEEK_TRAIT,
             },
         },
-        parser::{Input, Magic, Map},
+        parser::{Input, Magic, Map, Struct},
     },
     util::quote_spanned_any,
 };
@@ -50,6 +50,8 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
     };
 
     let reader_var = input.stream_ident_or(READER);
+    let span = get_span("binrw::read", &derive_input.ident);
+    let trace_err = get_trace_err("read failed");
 
     let rewind = (needs_rewind || input.magic().is_some()).then(|| {
         quote! {
@@ -57,12 +59,33 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
         }
     });
 
+    let pos = (rewind.is_some() || POS.is_used_in(&inner) || span.is_some()).then(|| {
+        quote! {
+            let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+        }
+    });
+
+    quote! {
+        let #reader_var = #READER;
+        #pos
+        #span
+        (|| {
+            #inner
+        })()#rewind #trace_err
+    }
+}
+
+pub(super) fn generate_wire_spans(input: &Input, st: &Struct, name: &Ident) -> TokenStream {
+    let inner = r#struct::generate_wire_spans(input, Some(name), st);
+    let reader_var = input.stream_ident_or(READER);
+
     quote! {
         let #reader_var = #READER;
         let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
         (|| {
             #inner
-        })()#rewind
+        })()
+        .or_else(#RESTORE_POSITION::<binrw::Error, _, _>(#reader_var, #POS))
     }
 }
 
@@ -100,10 +123,15 @@ impl<'input> PreludeGenerator<'input> {
 
     fn add_endian(mut self) -> Self {
         let endian = get_endian(self.input.endian());
+        let endian_var = self
+            .input
+            .endian_var()
+            .map(|var| quote! { let #var = #OPT; });
         let head = self.out;
         self.out = quote! {
             #head
             let #OPT = #endian;
+            #endian_var
         };
         self
     }
@@ -143,7 +171,7 @@ impl<'input> PreludeGenerator<'input> {
                 #head
                 let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
             };
-        };
+        }
 
         self
     }
@@ -155,9 +183,26 @@ fn get_magic(
     endian_var: impl ToTokens,
 ) -> Option<TokenStream> {
     magic.as_ref().map(|magic| {
-        let magic = magic.deref_value();
-        quote! {
-            #ASSERT_MAGIC(#reader_var, #magic, #endian_var)?;
+        if magic.is_any() {
+            let values = magic.deref_values();
+            let name = magic.name().map(|name| quote! { let #name = });
+            let read = if let Some(mask) = magic.deref_mask() {
+                quote! { #ASSERT_MAGIC_ANY_MASKED(#reader_var, &[#(#values),*], #mask, #endian_var)? }
+            } else {
+                quote! { #ASSERT_MAGIC_ANY(#reader_var, &[#(#values),*], #endian_var)? }
+            };
+            return quote! { #name #read; };
+        }
+
+        let value = magic.deref_value();
+        if let Some(mask) = magic.deref_mask() {
+            quote! {
+                #ASSERT_MAGIC_MASKED(#reader_var, #value, #mask, #endian_var)?;
+            }
+        } else {
+            quote! {
+                #ASSERT_MAGIC(#reader_var, #value, #endian_var)?;
+            }
         }
     })
 }
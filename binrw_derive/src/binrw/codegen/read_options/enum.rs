@@ -2,16 +2,22 @@ use super::{
     r#struct::{generate_unit_struct, StructGenerator},
     PreludeGenerator,
 };
-use crate::binrw::{
-    codegen::sanitization::{
-        BACKTRACE_FRAME, BIN_ERROR, ERROR_BASKET, OPT, POS, READER, READ_METHOD,
-        RESTORE_POSITION_VARIANT, TEMP, WITH_CONTEXT,
+use crate::{
+    binrw::{
+        codegen::{
+            get_map_err,
+            sanitization::{
+                make_ident, BACKTRACE_FRAME, BIN_ERROR, ERROR_BASKET, MAGIC_MASK, OPT, POS, READER,
+                READ_METHOD, RESTORE_POSITION_VARIANT, TAG, TEMP, WITH_CONTEXT,
+            },
+        },
+        parser::{Enum, EnumErrorMode, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
     },
-    parser::{Enum, EnumErrorMode, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
+    util::quote_spanned_any,
 };
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Ident};
 
 pub(super) fn generate_unit_enum(
     input: &Input,
@@ -25,7 +31,7 @@ pub(super) fn generate_unit_enum(
         .finish();
 
     let read = match en.map.as_repr() {
-        Some(repr) => generate_unit_enum_repr(&input.stream_ident_or(READER), repr, &en.fields),
+        Some(repr) => generate_unit_enum_repr(&input.stream_ident_or(READER), name, repr, en),
         None => generate_unit_enum_magic(&input.stream_ident_or(READER), &en.fields),
     };
 
@@ -37,27 +43,40 @@ pub(super) fn generate_unit_enum(
 
 fn generate_unit_enum_repr(
     reader_var: &TokenStream,
+    name: Option<&Ident>,
     repr: &TokenStream,
-    variants: &[UnitEnumField],
+    en: &UnitOnlyEnum,
 ) -> TokenStream {
-    let clauses = variants.iter().map(|variant| {
-        let ident = &variant.ident;
-        let pre_assertions = variant
-            .pre_assertions
-            .iter()
-            .map(|assert| &assert.condition);
+    // Items nested in a function cannot refer to `Self`, but a unit-only enum
+    // cannot be generic, so its name can be used instead
+    let name = name.map_or_else(|| quote! { Self }, ToTokens::into_token_stream);
 
+    // A `repr` which is not a primitive integer is converted to the type of
+    // the discriminants so they can still be matched as constants
+    let discriminant = en.repr_discriminant();
+    let read = if let Some(discriminant) = &discriminant {
+        let map_err = get_map_err(POS, repr.span());
         quote! {
-            if #TEMP == Self::#ident as #repr #(&& (#pre_assertions))* {
-                Ok(Self::#ident)
-            }
+            let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
+            let #TEMP = <#repr as core::convert::TryInto<#discriminant>>::try_into(#TEMP)#map_err?;
         }
-    });
+    } else {
+        quote! {
+            let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
+        }
+    };
+    let discriminant = discriminant.as_ref().unwrap_or(repr);
 
-    quote! {
-        let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
-        #(#clauses else)* {
-            Err(#WITH_CONTEXT(
+    let unknown = if let Some(repr_err) = &en.repr_err {
+        quote_spanned_any! { repr_err.span()=>
+            #BIN_ERROR::Custom {
+                pos: #POS,
+                err: Box::new((#repr_err)(#TEMP)) as _,
+            }
+        }
+    } else {
+        quote! {
+            #WITH_CONTEXT(
                 #BIN_ERROR::NoVariantMatch {
                     pos: #POS,
                 },
@@ -65,7 +84,51 @@ fn generate_unit_enum_repr(
                     extern crate alloc;
                     alloc::format!("Unexpected value for enum: {:?}", #TEMP).into()
                 })
-            ))
+            )
+        }
+    };
+
+    // Each discriminant is given a name so that all the variants can be
+    // matched in a single `match`, which the compiler can turn into a lookup
+    // table, instead of a chain of comparisons
+    let (values, arms) = en
+        .fields
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            let value = make_ident(ident, "repr");
+            let guard = (!variant.pre_assertions.is_empty()).then(|| {
+                let pre_assertions = variant
+                    .pre_assertions
+                    .iter()
+                    .map(|assert| &assert.condition);
+                quote! { if #((#pre_assertions))&&* }
+            });
+
+            (
+                quote! {
+                    const #value: #discriminant = #name::#ident as #discriminant;
+                },
+                quote! {
+                    #value #guard => Ok(Self::#ident),
+                },
+            )
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    quote! {
+        #[allow(non_upper_case_globals)]
+        {
+            #(#values)*
+
+            #read
+            // Two variants with the same value cannot both be read, so this
+            // is an error instead of silently reading the first one
+            #[deny(unreachable_patterns)]
+            match #TEMP {
+                #(#arms)*
+                _ => Err(#unknown),
+            }
         }
     }
 }
@@ -102,13 +165,23 @@ fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]
             let ident = &field.ident;
 
             if let Some(magic) = &field.magic {
-                let magic = magic.match_value();
-                let condition = if field.pre_assertions.is_empty() {
-                    quote! { #magic }
+                let pre_assertions = field.pre_assertions.iter().map(|assert| &assert.condition);
+                let condition = if let Some(mask) = magic.deref_mask() {
+                    // A masked magic cannot be a pattern, so it is matched
+                    // with a guard instead
+                    let deref = (!magic.add_ref().is_empty()).then(|| quote! { * });
+                    let matches = magic.deref_values().map(|value| {
+                        quote! { #MAGIC_MASK::masked_eq(#deref #TEMP, #value, #mask) }
+                    });
+                    quote! {
+                        #TEMP if (#(#matches)||*) #(&& (#pre_assertions))*
+                    }
+                } else if field.pre_assertions.is_empty() {
+                    let magic = magic.match_values();
+                    quote! { #(#magic)|* }
                 } else {
-                    let pre_assertions =
-                        field.pre_assertions.iter().map(|assert| &assert.condition);
-                    quote! { #magic if true #(&& (#pre_assertions))* }
+                    let magic = magic.match_values();
+                    quote! { #(#magic)|* if true #(&& (#pre_assertions))* }
                 };
 
                 quote! { #condition => Ok(Self::#ident) }
@@ -180,9 +253,38 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
         .reset_position_after_magic()
         .finish();
 
+    // An enum without variants can never be read
+    if en.variants.is_empty() {
+        return quote! {
+            #prelude
+            Err(#BIN_ERROR::NoVariantMatch {
+                pos: #POS
+            })
+        };
+    }
+
     let reader_var = input.stream_ident_or(READER);
 
-    let try_each_variant = en.variants.iter().map(|variant| {
+    let read_tagged = generate_tagged_variants(en, &reader_var);
+
+    let untagged = en
+        .variants_in_read_order()
+        .filter(|variant| variant.tag_value().is_none())
+        .collect::<Vec<_>>();
+
+    // When every variant is tagged, a tag which matches none of them is the
+    // only way for reading to fail
+    if read_tagged.is_some() && untagged.is_empty() {
+        return quote! {
+            #prelude
+            #read_tagged
+            Err(#BIN_ERROR::NoVariantMatch {
+                pos: #POS
+            })
+        };
+    }
+
+    let try_each_variant = untagged.into_iter().map(|variant| {
         let body = generate_variant_impl(en, variant);
 
         let handle_error = if return_all_errors {
@@ -210,21 +312,44 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
 
     quote! {
         #prelude
+        #read_tagged
         #create_error_basket
         #(#try_each_variant)*
         #return_error
     }
 }
 
+/// Generates the reading of the variants with a `tag_value`. A variant is read
+/// only when the tag is equal to its value, and its error is returned directly
+/// since no other variant can match.
+fn generate_tagged_variants(en: &Enum, reader_var: &TokenStream) -> Option<TokenStream> {
+    let tag = en.tag.as_ref()?;
+    let tagged = en.variants.iter().filter_map(|variant| {
+        let tag_value = variant.tag_value()?;
+        let body = generate_variant_impl(en, variant);
+        Some(quote! {
+            if #TAG == (#tag_value) {
+                return (|| {
+                    #body
+                })().or_else(|error| {
+                    Err(#RESTORE_POSITION_VARIANT(#reader_var, #POS, error)?)
+                });
+            }
+        })
+    });
+
+    Some(quote! {
+        let #TAG = #tag;
+        #(#tagged)*
+    })
+}
+
 fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
     let input = Input::Struct(variant.clone().into());
 
     match variant {
         EnumVariant::Variant { ident, options } => StructGenerator::new(&input, options)
-            .read_fields(
-                None,
-                Some(&format!("{}::{}", en.ident.as_ref().unwrap(), &ident)),
-            )
+            .read_fields(None, Some((en.ident.as_ref().unwrap(), ident)))
             .initialize_value_with_assertions(Some(ident), &en.assertions)
             .return_value()
             .finish(),
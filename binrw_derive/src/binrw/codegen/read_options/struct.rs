@@ -5,13 +5,17 @@ use crate::binrw::parser::Assert;
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            get_assertions, get_endian, get_map_err, get_passed_args, get_passed_args_list_len,
+            get_try_calc,
             sanitization::{
-                make_ident, ARGS_TYPE_HINT, BACKTRACE_FRAME, BINREAD_TRAIT, COERCE_FN,
-                DBG_EPRINTLN, MAP_ARGS_TYPE_HINT, MAP_READER_TYPE_HINT, OPT, PARSE_FN_TYPE_HINT,
-                POS, READER, READ_FUNCTION, READ_METHOD, REQUIRED_ARG_TRAIT, SAVED_POSITION,
-                SEEK_FROM, SEEK_TRAIT, TEMP, THIS, WITH_CONTEXT,
+                make_ident, ARGS_LIST, ARGS_LIST_TYPE_HINT, ARGS_TYPE_HINT, AT_EOF,
+                BACKTRACE_FRAME, BEFORE_POS, BINREAD_TRAIT, BITS_VAR, BIT_ORDER, BIT_READER,
+                COERCE_FN, DBG_EPRINTLN, MAP_ARGS_LIST_TYPE_HINT, MAP_ARGS_TYPE_HINT,
+                MAP_READER_TYPE_HINT, OPT, PARSE_FN_TYPE_HINT, POS, READER, READ_FUNCTION,
+                READ_METHOD, REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, TEMP, THIS,
+                VERIFY_CHECKSUM, WIRE_SPANS, WIRE_SPANS_VAR, WITH_CONTEXT,
             },
+            wrap_field_trace,
         },
         parser::{ErrContext, FieldMode, Input, Map, Struct, StructField},
     },
@@ -20,7 +24,7 @@ use crate::{
 use alloc::borrow::Cow;
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{spanned::Spanned, Ident};
+use syn::{ext::IdentExt, spanned::Spanned, Ident};
 
 pub(super) fn generate_unit_struct(
     input: &Input,
@@ -43,9 +47,24 @@ pub(super) fn generate_struct(input: &Input, name: Option<&Ident>, st: &Struct)
         .finish()
 }
 
+pub(super) fn generate_wire_spans(input: &Input, name: Option<&Ident>, st: &Struct) -> TokenStream {
+    let out = StructGenerator::new(input, st)
+        .record_spans()
+        .read_fields(name, None)
+        .initialize_value_with_assertions(None, &[])
+        .finish();
+
+    quote! {
+        let mut #WIRE_SPANS_VAR = <#WIRE_SPANS as ::core::default::Default>::default();
+        #out
+        Ok(#WIRE_SPANS_VAR)
+    }
+}
+
 pub(super) struct StructGenerator<'input> {
     input: &'input Input,
     st: &'input Struct,
+    record_spans: bool,
     out: TokenStream,
 }
 
@@ -54,10 +73,17 @@ impl<'input> StructGenerator<'input> {
         Self {
             input,
             st,
+            record_spans: false,
             out: TokenStream::new(),
         }
     }
 
+    /// Records the position of each field for `wire_eq`.
+    fn record_spans(mut self) -> Self {
+        self.record_spans = true;
+        self
+    }
+
     pub(super) fn finish(self) -> TokenStream {
         self.out
     }
@@ -96,21 +122,125 @@ impl<'input> StructGenerator<'input> {
         self
     }
 
-    pub(super) fn read_fields(mut self, name: Option<&Ident>, variant_name: Option<&str>) -> Self {
+    pub(super) fn read_fields(
+        mut self,
+        name: Option<&Ident>,
+        variant: Option<(&Ident, &Ident)>,
+    ) -> Self {
         let prelude = get_prelude(self.input, name);
-        let read_fields = self
-            .st
-            .fields
-            .iter()
-            .map(|field| generate_field(self.input, field, name, variant_name));
+        let reader_var = self.input.stream_ident_or(READER);
+        let read_fields = self.st.fields.iter().enumerate().map(|(index, field)| {
+            let read_field = generate_field(self.input, field, name, variant);
+            let (start, end) = self.checksum_positions(field, &reader_var);
+            let (span_start, span_end) = self.span_positions(index, field, &reader_var);
+            let pos = self.st.pos_var.as_ref().map(|pos_var| {
+                quote! {
+                    #[allow(unused_variables)]
+                    let #pos_var = #SEEK_TRAIT::stream_position(#reader_var)?;
+                }
+            });
+            // Each run of bit fields starts at a new byte
+            let bits_start = self.st.starts_bit_fields(index).then(|| {
+                quote! {
+                    let mut #BITS_VAR = #BIT_READER::new();
+                }
+            });
+            quote! {
+                #bits_start
+                #pos
+                #span_start
+                #start
+                #read_field
+                #end
+                #span_end
+            }
+        });
+        let checksums = self.st.fields.iter().filter_map(|field| {
+            let checksum = field.checksum.as_ref()?;
+            let function = &checksum.function;
+            let start = make_ident(&checksum.first, "checksum_start");
+            let end = make_ident(&checksum.last, "checksum_end");
+            let stored = &field.ident;
+            Some(quote! {
+                #VERIFY_CHECKSUM(#reader_var, #start, #end, #function, &#stored)?;
+            })
+        });
+        // The position variable holds the end position for the struct-level
+        // assertions
+        let end_pos = self.st.pos_var.as_ref().map(|pos_var| {
+            quote! {
+                #[allow(unused_variables)]
+                let #pos_var = #SEEK_TRAIT::stream_position(#reader_var)?;
+            }
+        });
         self.out = quote! {
             #prelude
             #(#read_fields)*
+            #(#checksums)*
+            #end_pos
         };
 
         self
     }
 
+    // Checksums are verified after all fields have been read so that the
+    // stored value can come before or after the data it covers, so the
+    // positions of the fields at the ends of each range are saved until then.
+    fn checksum_positions(
+        &self,
+        field: &StructField,
+        reader_var: &TokenStream,
+    ) -> (Option<TokenStream>, Option<TokenStream>) {
+        let mut checksums = self
+            .st
+            .fields
+            .iter()
+            .filter_map(|field| field.checksum.as_ref());
+        let save = |kind| {
+            let ident = make_ident(&field.ident, kind);
+            quote! {
+                let #ident = #SEEK_TRAIT::stream_position(#reader_var)?;
+            }
+        };
+
+        (
+            checksums
+                .clone()
+                .any(|checksum| checksum.first == field.ident)
+                .then(|| save("checksum_start")),
+            checksums
+                .any(|checksum| checksum.last == field.ident)
+                .then(|| save("checksum_end")),
+        )
+    }
+
+    fn span_positions(
+        &self,
+        index: usize,
+        field: &StructField,
+        reader_var: &TokenStream,
+    ) -> (Option<TokenStream>, Option<TokenStream>) {
+        if !self.record_spans {
+            return (None, None);
+        }
+
+        let start = make_ident(&field.ident, "wire_start");
+        let name = if field.generated_ident {
+            index.to_string()
+        } else {
+            field.ident.unraw().to_string()
+        };
+
+        (
+            Some(quote! {
+                let #start = #SEEK_TRAIT::stream_position(#reader_var)?;
+            }),
+            Some(quote! {
+                #WIRE_SPANS_VAR.push(#name, #start, #SEEK_TRAIT::stream_position(#reader_var)?);
+            }),
+        )
+    }
+
     fn init_value(mut self, variant_ident: Option<&Ident>) -> Self {
         let out_names = self.st.iter_permanent_idents();
         let return_type = get_return_type(variant_ident);
@@ -145,7 +275,7 @@ fn generate_field(
     input: &Input,
     field: &StructField,
     name: Option<&Ident>,
-    variant_name: Option<&str>,
+    variant: Option<(&Ident, &Ident)>,
 ) -> TokenStream {
     // temp + ignore == just don't bother
     if field.is_temp(false) && matches!(field.field_mode, FieldMode::Default) {
@@ -155,9 +285,11 @@ fn generate_field(
     FieldGenerator::new(input, field)
         .read_value()
         .wrap_map_stream()
-        .try_conversion(name, variant_name)
+        .try_conversion(name, variant)
         .map_value()
         .wrap_debug()
+        .wrap_trace()
+        .wrap_eof_default()
         .wrap_seek()
         .wrap_condition()
         .assign_to_var()
@@ -245,8 +377,13 @@ impl<'field> FieldGenerator<'field> {
                 let #SAVED_POSITION = #SEEK_TRAIT::stream_position(#reader_var)?;
                 let #TEMP = #head;
                 #DBG_EPRINTLN!(
-                    "[{}:{} | offset {:#x}] {} = {:#x?}",
-                    ::core::file!(), #at, #SAVED_POSITION, ::core::stringify!(#ident), &#TEMP
+                    "[{}:{} | offset {:#x}..{:#x}] {} = {:#x?}",
+                    ::core::file!(),
+                    #at,
+                    #SAVED_POSITION,
+                    #SEEK_TRAIT::stream_position(#reader_var)?,
+                    ::core::stringify!(#ident),
+                    &#TEMP
                 );
                 #dbg_pad_size_to
                 #dbg_pad_after
@@ -258,6 +395,17 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn wrap_trace(mut self) -> Self {
+        self.out = wrap_field_trace(
+            &self.outer_reader_var,
+            &self.field.ident,
+            "field read",
+            self.out,
+            Some(&self.field.ty),
+        );
+        self
+    }
+
     fn append_assertions(mut self) -> Self {
         let assertions = get_assertions(&self.field.assertions);
         let head = self.out;
@@ -358,6 +506,10 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn prefix_read_function(mut self) -> Self {
+        if self.field.bits.is_some() {
+            return self;
+        }
+
         let read_function = match &self.field.field_mode {
             FieldMode::Function(parser) => {
                 quote_spanned_any! { parser.span()=>
@@ -385,23 +537,31 @@ impl<'field> FieldGenerator<'field> {
             let args = get_passed_args(self.field, &self.outer_reader_var);
             let ty = &self.field.ty;
 
-            if let FieldMode::Function(_) = &self.field.field_mode {
-                quote_spanned! {ty.span()=>
+            // An `args(...)` list goes through `ARGS_LIST` so that it can
+            // also be converted to a type with default arguments
+            match (
+                &self.field.field_mode,
+                &self.field.map,
+                get_passed_args_list_len(self.field),
+            ) {
+                (FieldMode::Function(_), _, None) => quote_spanned! {ty.span()=>
                     let #args_var = #ARGS_TYPE_HINT::<_, #ty, _, _>(&#READ_FUNCTION, #args);
-                }
-            } else {
-                match &self.field.map {
-                    Map::Map(_) | Map::Try(_) | Map::Repr(_) => {
-                        quote_spanned! {ty.span()=>
-                            let #args_var = #MAP_ARGS_TYPE_HINT(&#map_func, #args);
-                        }
-                    }
-                    Map::None => {
-                        quote_spanned! {ty.span()=>
-                            let #args_var: <#ty as #BINREAD_TRAIT>::Args<'_> = #args;
-                        }
-                    }
-                }
+                },
+                (FieldMode::Function(_), _, Some(len)) => quote_spanned! {ty.span()=>
+                    let #args_var = #ARGS_LIST_TYPE_HINT::<_, #ty, _, _, #len>(&#READ_FUNCTION, #args);
+                },
+                (_, Map::Map(_) | Map::Try(_) | Map::Repr(_), None) => quote_spanned! {ty.span()=>
+                    let #args_var = #MAP_ARGS_TYPE_HINT(&#map_func, #args);
+                },
+                (_, Map::Map(_) | Map::Try(_) | Map::Repr(_), Some(len)) => quote_spanned! {ty.span()=>
+                    let #args_var = #MAP_ARGS_LIST_TYPE_HINT::<_, _, _, _, #len>(&#map_func, #args);
+                },
+                (_, Map::None, None) => quote_spanned! {ty.span()=>
+                    let #args_var: <#ty as #BINREAD_TRAIT>::Args<'_> = #args;
+                },
+                (_, Map::None, Some(len)) => quote_spanned! {ty.span()=>
+                    let #args_var = <<#ty as #BINREAD_TRAIT>::Args<'_> as #ARGS_LIST<#len>>::from_list(#args);
+                },
             }
         });
 
@@ -440,6 +600,18 @@ impl<'field> FieldGenerator<'field> {
             FieldMode::Default => quote! { <_>::default() },
             FieldMode::Calc(calc) => quote! { #calc },
             FieldMode::TryCalc(calc) => get_try_calc(POS, &self.field.ty, calc),
+            FieldMode::Normal if self.field.bits.is_some() => {
+                let reader_var = &self.reader_var;
+                let endian_var = &self.endian_var;
+                let len = self.field.bits.as_deref();
+                let order = self.field.bit_order.as_ref().map_or_else(
+                    || quote! { #BIT_ORDER::from(#endian_var) },
+                    ToTokens::to_token_stream,
+                );
+                quote! {
+                    #BITS_VAR.read(#reader_var, #order, #len)
+                }
+            }
             read_mode @ (FieldMode::Normal | FieldMode::Function(_)) => {
                 let args_arg = self.args_var.as_ref().map_or_else(
                     || quote_spanned! {self.field.ty.span()=> <_ as #REQUIRED_ARG_TRAIT>::args() },
@@ -476,18 +648,40 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
-    fn try_conversion(mut self, name: Option<&Ident>, variant_name: Option<&str>) -> Self {
+    fn try_conversion(mut self, name: Option<&Ident>, variant: Option<(&Ident, &Ident)>) -> Self {
         if !self.field.generated_value() {
             let result = self.out;
             self.out = if self.field.do_try.is_some() {
-                quote! { #result.unwrap_or_default() }
+                let outer_reader_var = &self.outer_reader_var;
+                let fallback = self
+                    .field
+                    .try_or
+                    .as_ref()
+                    .map_or_else(|| quote! { <_>::default() }, ToTokens::to_token_stream);
+                // Without a map, the value is the field itself, so its type is
+                // given to keep inference working through any wrapping code
+                let ty = matches!(self.field.map, Map::None).then(|| {
+                    let ty = &self.field.ty;
+                    quote! { : #ty }
+                });
+                quote! {{
+                    let #SAVED_POSITION = #SEEK_TRAIT::stream_position(#outer_reader_var)?;
+                    let #TEMP #ty = match #result {
+                        Ok(#TEMP) => #TEMP,
+                        Err(_) => {
+                            #SEEK_TRAIT::seek(#outer_reader_var, #SEEK_FROM::Start(#SAVED_POSITION))?;
+                            #fallback
+                        }
+                    };
+                    #TEMP
+                }}
             } else {
                 let span = match &self.field.field_mode {
                     FieldMode::Function(f) => f.span(),
                     _ => result.span(),
                 };
 
-                let map_err = get_err_context(self.field, name, variant_name);
+                let map_err = get_err_context(self.field, name, variant);
                 quote_spanned! {span=> #result #map_err ? }
             };
         }
@@ -495,6 +689,27 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn wrap_eof_default(mut self) -> Self {
+        if self.field.eof_default.is_some() {
+            let outer_reader_var = &self.outer_reader_var;
+            let value = self.out;
+            let fallback = self
+                .field
+                .try_or
+                .as_ref()
+                .map_or_else(|| quote! { <_>::default() }, ToTokens::to_token_stream);
+            self.out = quote! {
+                if #AT_EOF(#outer_reader_var)? {
+                    #fallback
+                } else {
+                    #value
+                }
+            };
+        }
+
+        self
+    }
+
     fn wrap_condition(mut self) -> Self {
         if let Some(cond) = &self.field.if_cond {
             let condition = &cond.condition;
@@ -543,7 +758,7 @@ impl<'field> FieldGenerator<'field> {
 fn get_err_context(
     field: &StructField,
     name: Option<&Ident>,
-    variant_name: Option<&str>,
+    variant: Option<(&Ident, &Ident)>,
 ) -> TokenStream {
     let backtrace = if let Some(ErrContext::Context(expr)) = &field.err_context {
         quote_spanned! {field.ident.span()=>
@@ -562,8 +777,8 @@ fn get_err_context(
         #[cfg(not(feature = "verbose-backtrace"))]
         let code = quote!(None);
 
-        let message = if let Some(ErrContext::Format(fmt, exprs)) = &field.err_context {
-            if exprs.is_empty() {
+        if let Some(ErrContext::Format(fmt, exprs)) = &field.err_context {
+            let message = if exprs.is_empty() {
                 quote! { (#fmt) }
             } else {
                 quote! {
@@ -572,22 +787,45 @@ fn get_err_context(
                         alloc::format!(#fmt, #(#exprs),*)
                     }
                 }
+            };
+
+            quote_spanned! {field.ident.span()=>
+                #BACKTRACE_FRAME::Full {
+                    message: #message.into(),
+                    line: ::core::line!(),
+                    file: ::core::file!(),
+                    code: #code,
+                }
             }
         } else {
-            format!(
-                "While parsing field '{}' in {}",
-                field.ident,
-                name.map_or_else(|| variant_name.unwrap().into(), ToString::to_string)
-            )
-            .into_token_stream()
-        };
+            let (type_name, variant_name) = match (name, variant) {
+                (Some(name), _) => (name.to_string(), quote! { None }),
+                (None, Some((name, variant))) => {
+                    let variant = variant.to_string();
+                    (name.to_string(), quote! { Some(#variant) })
+                }
+                (None, None) => unreachable!("fields always have a parent type"),
+            };
+            let field_name = if field.generated_ident {
+                field
+                    .ident
+                    .to_string()
+                    .trim_start_matches("self_")
+                    .to_owned()
+            } else {
+                field.ident.unraw().to_string()
+            };
 
-        quote_spanned! {field.ident.span()=>
-            #BACKTRACE_FRAME::Full {
-                message: #message.into(),
-                line: ::core::line!(),
-                file: ::core::file!(),
-                code: #code,
+            quote_spanned! {field.ident.span()=>
+                #BACKTRACE_FRAME::Field {
+                    type_name: #type_name,
+                    variant_name: #variant_name,
+                    field_name: #field_name,
+                    pos: #POS,
+                    line: ::core::line!(),
+                    file: ::core::file!(),
+                    code: #code,
+                }
             }
         }
     };
@@ -610,7 +848,7 @@ fn generate_seek_after(reader_var: &TokenStream, field: &StructField) -> TokenSt
     let pad_size_to = field.pad_size_to.as_ref().map(|pad| {
         quote! {{
             let pad = (#pad) as i64;
-            let size = (#SEEK_TRAIT::stream_position(#reader_var)? - #POS) as i64;
+            let size = (#SEEK_TRAIT::stream_position(#reader_var)? - #BEFORE_POS) as i64;
             if size < pad {
                 #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(pad - size))?;
             }
@@ -648,7 +886,7 @@ fn generate_seek_before(reader_var: &TokenStream, field: &StructField) -> TokenS
         .map(|value| map_align(reader_var, value));
     let pad_size_to_before = field.pad_size_to.as_ref().map(|_| {
         quote! {
-            let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+            let #BEFORE_POS = #SEEK_TRAIT::stream_position(#reader_var)?;
         }
     });
 
@@ -693,14 +931,20 @@ fn map_align(reader_var: &TokenStream, align: &TokenStream) -> TokenStream {
     quote! {{
         let align = (#align) as i64;
         let pos = #SEEK_TRAIT::stream_position(#reader_var)? as i64;
-        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current((align - (pos % align)) % align))?;
+        let pad = (align - (pos % align)) % align;
+        if pad != 0 {
+            #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(pad))?;
+        }
     }}
 }
 
 fn map_pad(reader_var: &TokenStream, pad: &TokenStream) -> TokenStream {
-    quote! {
-        #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current((#pad) as i64))?;
-    }
+    quote! {{
+        let pad = (#pad) as i64;
+        if pad != 0 {
+            #SEEK_TRAIT::seek(#reader_var, #SEEK_FROM::Current(pad))?;
+        }
+    }}
 }
 
 fn wrap_save_restore(reader_var: &TokenStream, value: TokenStream) -> TokenStream {
@@ -30,6 +30,12 @@ ident_str! {
     pub(crate) WRITE_TRAIT = from_crate!(io::Write);
     pub(crate) SEEK_TRAIT = from_crate!(io::Seek);
     pub(crate) SEEK_FROM = from_crate!(io::SeekFrom);
+    pub(crate) CURSOR = from_crate!(io::Cursor);
+    pub(crate) FILE = from_crate!(__private::File);
+    pub(crate) READ_FILE = from_crate!(__private::read_file);
+    pub(crate) READ_SEEK_TRAIT = from_crate!(io::ReadSeek);
+    pub(crate) READ_DYN_FN = "__binrw_generated_read_dyn";
+    pub(crate) INTO_ERROR = from_crate!(__private::into_error);
     pub(crate) BIN_RESULT = from_crate!(BinResult);
     pub(crate) ENDIAN_ENUM = from_crate!(Endian);
     pub(crate) READ_METHOD = from_read_trait!(read_options);
@@ -39,12 +45,53 @@ ident_str! {
     pub(crate) OPT = "__binrw_generated_var_endian";
     pub(crate) ARGS = "__binrw_generated_var_arguments";
     pub(crate) SAVED_POSITION = "__binrw_generated_saved_position";
+    pub(crate) CHECKSUM_STREAM = "__binrw_generated_checksum_stream";
+    pub(crate) WIRE_SPANS_VAR = "__binrw_generated_wire_spans";
+    pub(crate) WIRE_SPANS_FN = "__binrw_generated_wire_spans_fn";
+    pub(crate) DUMPER_VAR = "__binrw_generated_var_dumper";
+    pub(crate) TYPES_VAR = "__binrw_generated_var_types";
+    pub(crate) BITS_VAR = "__binrw_generated_var_bits";
     pub(crate) ASSERT_MAGIC = from_crate!(__private::magic);
+    pub(crate) AT_EOF = from_crate!(__private::at_eof);
+    pub(crate) ASSERT_MAGIC_MASKED = from_crate!(__private::magic_masked);
+    pub(crate) ASSERT_MAGIC_ANY = from_crate!(__private::magic_any);
+    pub(crate) ASSERT_MAGIC_ANY_MASKED = from_crate!(__private::magic_any_masked);
+    pub(crate) MAGIC_MASK = from_crate!(__private::MagicMask);
     pub(crate) ASSERT = from_crate!(__private::assert);
+    pub(crate) ASSERT_OFFSET = from_crate!(__private::assert_offset);
+    pub(crate) ASSERT_ALIGN = from_crate!(__private::assert_align);
     pub(crate) ASSERT_ERROR_FN = from_crate!(__private::AssertErrorFn);
+    pub(crate) FLAG_PRESENT = from_crate!(__private::flag_present);
+    pub(crate) VERIFY_CHECKSUM = from_crate!(__private::verify_checksum);
+    pub(crate) CHECKSUM_WRITER = from_crate!(__private::ChecksumWriter);
+    pub(crate) WRITE_CHECKSUM = from_crate!(__private::write_checksum);
+    pub(crate) WIRE_SPANS = from_crate!(__private::WireSpans);
+    pub(crate) WIRE_DIFF = from_crate!(__private::wire_diff);
+    pub(crate) FIELD_DIFF = from_crate!(wire_eq::FieldDiff);
+    pub(crate) DUMP_TRAIT = from_crate!(dump::Dump);
+    pub(crate) DUMPER = from_crate!(dump::Dumper);
+    pub(crate) DUMP_WITH_SPANS = from_crate!(__private::dump_with_spans);
+    pub(crate) SCHEMA_TRAIT = from_crate!(schema::Schema);
+    pub(crate) SCHEMA_TYPES = from_crate!(schema::Types);
+    pub(crate) SCHEMA_TYPE_DEF = from_crate!(schema::TypeDef);
+    pub(crate) SCHEMA_BODY = from_crate!(schema::Body);
+    pub(crate) SCHEMA_FIELD = from_crate!(schema::Field);
+    pub(crate) SCHEMA_VARIANT = from_crate!(schema::Variant);
+    pub(crate) SCHEMA_LAYOUT = from_crate!(schema::Layout);
+    pub(crate) SCHEMA_MAGIC = from_crate!(schema::Magic);
+    pub(crate) STRING = from_crate!(__private::String);
+    pub(crate) VEC = from_crate!(__private::Vec);
+    pub(crate) READ_UNKNOWN = from_crate!(__private::read_unknown);
+    pub(crate) BIT_ORDER = from_crate!(bits::BitOrder);
+    pub(crate) BIT_READER = from_crate!(bits::BitReader);
+    pub(crate) BIT_WRITER = from_crate!(bits::BitWriter);
+    pub(crate) TRACING = from_crate!(__private::tracing);
     pub(crate) COERCE_FN = from_crate!(__private::coerce_fn);
     pub(crate) ARGS_TYPE_HINT = from_crate!(__private::parse_function_args_type_hint);
     pub(crate) MAP_ARGS_TYPE_HINT = from_crate!(__private::map_args_type_hint);
+    pub(crate) ARGS_LIST = from_crate!(__private::ArgsList);
+    pub(crate) ARGS_LIST_TYPE_HINT = from_crate!(__private::parse_function_args_list_type_hint);
+    pub(crate) MAP_ARGS_LIST_TYPE_HINT = from_crate!(__private::map_args_list_type_hint);
     pub(crate) REQUIRED_ARG_TRAIT = from_crate!(__private::Required);
     pub(crate) MAP_READER_TYPE_HINT = from_crate!(__private::map_reader_type_hint);
     pub(crate) MAP_WRITER_TYPE_HINT = from_crate!(__private::map_writer_type_hint);
@@ -53,6 +100,9 @@ ident_str! {
     pub(crate) WRITE_ARGS_TYPE_HINT = from_crate!(__private::write_function_args_type_hint);
     pub(crate) WRITE_MAP_ARGS_TYPE_HINT = from_crate!(__private::write_map_args_type_hint);
     pub(crate) WRITE_TRY_MAP_ARGS_TYPE_HINT = from_crate!(__private::write_try_map_args_type_hint);
+    pub(crate) WRITE_ARGS_LIST_TYPE_HINT = from_crate!(__private::write_function_args_list_type_hint);
+    pub(crate) WRITE_MAP_ARGS_LIST_TYPE_HINT = from_crate!(__private::write_map_args_list_type_hint);
+    pub(crate) WRITE_TRY_MAP_ARGS_LIST_TYPE_HINT = from_crate!(__private::write_try_map_args_list_type_hint);
     pub(crate) WRITE_MAP_INPUT_TYPE_HINT = from_crate!(__private::write_map_fn_input_type_hint);
     pub(crate) WRITE_FN_MAP_OUTPUT_TYPE_HINT = from_crate!(__private::write_fn_map_output_type_hint);
     pub(crate) WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT = from_crate!(__private::write_fn_try_map_output_type_hint);
@@ -63,17 +113,24 @@ ident_str! {
     pub(crate) META_ENDIAN_KIND = from_crate!(meta::EndianKind);
     pub(crate) READ_ENDIAN = from_crate!(meta::ReadEndian);
     pub(crate) READ_MAGIC = from_crate!(meta::ReadMagic);
+    pub(crate) READ_SEQUENTIAL = from_crate!(ReadSequential);
+    pub(crate) TRY_READ = from_crate!(TryRead);
     pub(crate) WRITE_ENDIAN = from_crate!(meta::WriteEndian);
     pub(crate) WRITE_MAGIC = from_crate!(meta::WriteMagic);
+    pub(crate) WRITE_SEQUENTIAL = from_crate!(WriteSequential);
     pub(crate) WITH_CONTEXT = from_crate!(error::ContextExt::with_context);
     pub(crate) BACKTRACE_FRAME = from_crate!(error::BacktraceFrame);
     pub(crate) TEMP = "__binrw_temp";
+    pub(crate) SPAN = "__binrw_generated_span";
     pub(crate) THIS = "__binrw_this";
     pub(crate) POS = "__binrw_generated_position_temp";
     pub(crate) ERROR_BASKET = "__binrw_generated_error_basket";
+    pub(crate) TAG = "__binrw_generated_tag";
     pub(crate) READ_FUNCTION = "__binrw_generated_read_function";
     pub(crate) WRITE_FUNCTION = "__binrw_generated_write_function";
     pub(crate) BEFORE_POS = "__binrw_generated_before_pos";
+    pub(crate) TRACE_POS = "__binrw_generated_trace_pos";
+    pub(crate) UNKNOWN_FIELD = "__binrw_unknown";
     pub(crate) DBG_EPRINTLN = from_crate!(__private::eprintln);
 }
 
@@ -0,0 +1,248 @@
+use super::sanitization::{
+    SCHEMA_BODY, SCHEMA_FIELD, SCHEMA_LAYOUT, SCHEMA_MAGIC, SCHEMA_TRAIT, SCHEMA_TYPES,
+    SCHEMA_TYPE_DEF, SCHEMA_VARIANT, STRING, TYPES_VAR, VEC,
+};
+use crate::binrw::parser::{
+    CondEndian, EnumVariant, FieldMode, Input, Magic, Map, Struct, StructField,
+};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{ext::IdentExt, spanned::Spanned, DeriveInput, Ident, Lit, Type};
+
+/// Generates an implementation of `Schema`.
+pub(crate) fn generate(input: &Input, derive_input: &DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let mut types = Vec::new();
+    let (magic, body) = match input {
+        Input::Struct(st) | Input::UnitStruct(st) => {
+            let fields = describe_fields(st, &mut types);
+            (&st.magic, quote! { #SCHEMA_BODY::Struct(#fields) })
+        }
+        Input::Enum(en) => {
+            let variants = en.variants_in_read_order().map(|variant| match variant {
+                EnumVariant::Variant { ident, options } => {
+                    let fields = describe_fields(options, &mut types);
+                    describe_variant(ident, &options.magic, &quote! { None }, &fields)
+                }
+                EnumVariant::Unit(field) => {
+                    let fields = quote! { #VEC::new() };
+                    describe_variant(&field.ident, &field.magic, &quote! { None }, &fields)
+                }
+            });
+            let variants = quote! { #VEC::from([#(#variants),*]) };
+            (
+                &en.magic,
+                quote! { #SCHEMA_BODY::Enum { repr: None, variants: #variants } },
+            )
+        }
+        Input::UnitOnlyEnum(en) => {
+            let repr = en.map.as_repr();
+            let discriminant = en.repr_discriminant();
+            let variants = en.fields.iter().map(|field| {
+                let ident = &field.ident;
+                let value = if let Some(repr) = repr {
+                    let discriminant = discriminant.as_ref().unwrap_or(repr);
+                    quote! { Some(Self::#ident as #discriminant as i128) }
+                } else {
+                    quote! { None }
+                };
+                describe_variant(ident, &field.magic, &value, &quote! { #VEC::new() })
+            });
+            let variants = quote! { #VEC::from([#(#variants),*]) };
+            let repr = if let Some(repr) = repr {
+                types.push(repr.clone());
+                quote! { Some(<#repr as #SCHEMA_TRAIT>::layout(#TYPES_VAR)) }
+            } else {
+                quote! { None }
+            };
+            (
+                &en.magic,
+                quote! { #SCHEMA_BODY::Enum { repr: #repr, variants: #variants } },
+            )
+        }
+    };
+    let endian = describe_endian(input.endian());
+    let magic = describe_magic(magic);
+
+    let bounds = types
+        .iter()
+        .map(|ty| quote_spanned! {ty.span()=> #ty: #SCHEMA_TRAIT });
+    let schema_where_clause = match where_clause {
+        Some(where_clause) if !where_clause.predicates.empty_or_trailing() => {
+            quote! { #where_clause, #(#bounds,)* }
+        }
+        Some(where_clause) => quote! { #where_clause #(#bounds,)* },
+        None => quote! { where #(#bounds,)* },
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #SCHEMA_TRAIT for #name #ty_generics #schema_where_clause {
+            fn layout(#TYPES_VAR: &mut #SCHEMA_TYPES) -> #SCHEMA_LAYOUT {
+                #SCHEMA_TYPES::define::<Self>(#TYPES_VAR, |#TYPES_VAR| #SCHEMA_TYPE_DEF {
+                    endian: #endian,
+                    magic: #magic,
+                    body: #body,
+                })
+            }
+        }
+    }
+}
+
+fn describe_variant(
+    ident: &Ident,
+    magic: &Magic,
+    value: &TokenStream,
+    fields: &TokenStream,
+) -> TokenStream {
+    let name = ident.unraw().to_string();
+    let magic = describe_magic(magic);
+    quote! {
+        #SCHEMA_VARIANT {
+            name: #STRING::from(#name),
+            magic: #magic,
+            value: #value,
+            fields: #fields,
+        }
+    }
+}
+
+/// Describes the fields of a struct or variant which are read from the
+/// stream, and adds the type of each field whose layout comes from its type
+/// to `types`.
+fn describe_fields(st: &Struct, types: &mut Vec<TokenStream>) -> TokenStream {
+    let is_tuple = st.is_tuple();
+    let fields = st
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| !field.generated_value())
+        .map(|(index, field)| {
+            let name = if is_tuple {
+                index.to_string()
+            } else {
+                field.ident.unraw().to_string()
+            };
+            let layout = describe_layout(field, types);
+            let ty = type_text(&field.ty);
+            let endian = describe_endian(&field.endian);
+            let magic = describe_magic(&field.magic);
+            let count = describe_expr(field.count.as_ref());
+            let condition = describe_expr(field.if_cond.as_ref().map(|cond| match &cond.flag {
+                Some((flags, mask)) => quote! { (#flags & #mask) != 0 },
+                None => cond.condition.clone(),
+            }));
+
+            quote! {
+                #SCHEMA_FIELD {
+                    name: #STRING::from(#name),
+                    layout: #layout,
+                    ty: Some(#STRING::from(#ty)),
+                    endian: #endian,
+                    magic: #magic,
+                    count: #count,
+                    condition: #condition,
+                }
+            }
+        });
+
+    quote! { #VEC::from([#(#fields),*]) }
+}
+
+fn describe_layout(field: &StructField, types: &mut Vec<TokenStream>) -> TokenStream {
+    if let Some(bits) = &field.bits {
+        let bits = **bits;
+        return quote! { #SCHEMA_LAYOUT::Bits(#bits) };
+    }
+
+    let ty = match (&field.field_mode, &field.map) {
+        (FieldMode::Function(_), _) => {
+            return quote! { #SCHEMA_LAYOUT::Unknown(#STRING::from("read with a custom parser")) };
+        }
+        (_, Map::Map(_) | Map::Try(_)) => {
+            return quote! { #SCHEMA_LAYOUT::Unknown(#STRING::from("read with a map function")) };
+        }
+        (_, Map::Repr(ty)) => ty.clone(),
+        _ => field.ty.to_token_stream(),
+    };
+
+    let layout = quote! { <#ty as #SCHEMA_TRAIT>::layout(#TYPES_VAR) };
+    types.push(ty);
+    layout
+}
+
+fn describe_endian(endian: &CondEndian) -> TokenStream {
+    match endian {
+        CondEndian::Fixed(endian) => quote! { Some(#endian) },
+        CondEndian::Inherited | CondEndian::Default(_) | CondEndian::Cond(..) => quote! { None },
+    }
+}
+
+fn describe_magic(magic: &Magic) -> TokenStream {
+    let Some(magic) = magic else {
+        return quote! { None };
+    };
+
+    let value = magic.match_value();
+    match syn::parse2::<Lit>(value.clone()) {
+        Ok(Lit::ByteStr(_)) => quote! { Some(#SCHEMA_MAGIC::Bytes(#VEC::from(*#value))) },
+        Ok(lit) => {
+            let text = match &lit {
+                Lit::Byte(byte) => byte.value().to_string(),
+                Lit::Int(int) => strip_suffix(&int.to_string(), int.suffix()),
+                Lit::Float(float) => strip_suffix(&float.to_string(), float.suffix()),
+                _ => value.to_string(),
+            };
+            let ty = TokenStream::from(magic.kind());
+            quote! {
+                Some(#SCHEMA_MAGIC::Value {
+                    layout: <#ty as #SCHEMA_TRAIT>::layout(#TYPES_VAR),
+                    value: #STRING::from(#text),
+                })
+            }
+        }
+        Err(_) => quote! { None },
+    }
+}
+
+fn strip_suffix(text: &str, suffix: &str) -> String {
+    text.strip_suffix(suffix).unwrap_or(text).to_owned()
+}
+
+/// Records the source text of a type, with the spaces which token streams add
+/// around punctuation removed.
+fn type_text(ty: &Type) -> String {
+    let text = ty.to_token_stream().to_string();
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let next_is_punct = chars
+            .peek()
+            .map_or(false, |next| "<>:,;&[]()*".contains(*next));
+        if c == ' ' && (next_is_punct || out.ends_with(|prev| " <>:&[(*".contains(prev))) {
+            continue;
+        }
+        out.push(c);
+        if c == ',' || c == ';' {
+            out.push(' ');
+        }
+    }
+    out
+}
+
+/// Records the source text of an expression, with the spaces which token
+/// streams add around path and field separators removed.
+fn describe_expr(expr: Option<impl ToTokens>) -> TokenStream {
+    if let Some(expr) = expr {
+        let text = expr
+            .to_token_stream()
+            .to_string()
+            .replace(" . ", ".")
+            .replace(" :: ", "::");
+        quote! { Some(#STRING::from(#text)) }
+    } else {
+        quote! { None }
+    }
+}
@@ -0,0 +1,90 @@
+use super::{
+    get_args_lifetime,
+    sanitization::{READ_SEQUENTIAL, WRITE_SEQUENTIAL},
+};
+use crate::binrw::parser::{FieldMode, Input, Map, StructField};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// Generates an implementation of `ReadSequential` or `WriteSequential` for a
+/// struct which never seeks, or nothing if it might.
+pub(crate) fn generate<const WRITE: bool>(
+    input: &Input,
+    derive_input: &syn::DeriveInput,
+) -> Option<TokenStream> {
+    let (Input::Struct(st) | Input::UnitStruct(st)) = input else {
+        return None;
+    };
+
+    if !matches!(st.map, Map::None)
+        || st.map_stream.is_some()
+        || st.stream_ident.is_some()
+        || st.preserve_unknown.is_some()
+    {
+        return None;
+    }
+
+    let mut types = Vec::with_capacity(st.fields.len());
+    for field in &st.fields {
+        if may_seek(field) {
+            return None;
+        }
+
+        let uses_type = match field.field_mode {
+            FieldMode::Normal => true,
+            FieldMode::Calc(_) | FieldMode::TryCalc(_) => WRITE,
+            FieldMode::Default => false,
+            FieldMode::Function(_) => return None,
+        };
+
+        if uses_type {
+            types.push(&field.ty);
+        }
+    }
+
+    let trait_name = if WRITE {
+        WRITE_SEQUENTIAL
+    } else {
+        READ_SEQUENTIAL
+    };
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    // The bounds are higher-ranked so that a field type which does not
+    // implement the trait makes the implementation unusable instead of
+    // causing an error
+    let args_lifetime = get_args_lifetime(Span::call_site());
+    let bounds = quote! {
+        #(for<#args_lifetime> #types: #trait_name,)*
+    };
+    let where_clause = match where_clause {
+        Some(where_clause) if !where_clause.predicates.empty_or_trailing() => {
+            quote! { #where_clause, #bounds }
+        }
+        Some(where_clause) => quote! { #where_clause #bounds },
+        None => quote! { where #bounds },
+    };
+
+    Some(quote! {
+        #[automatically_derived]
+        impl #impl_generics #trait_name for #name #ty_generics #where_clause {}
+    })
+}
+
+/// Returns true if the field uses any directive which may seek or read or
+/// write a type other than the field type.
+fn may_seek(field: &StructField) -> bool {
+    !matches!(field.map, Map::None)
+        || field.map_stream.is_some()
+        || field.offset.is_some()
+        || field.if_mismatch.is_some()
+        || field.restore_position.is_some()
+        || field.do_try.is_some()
+        || field.checksum.is_some()
+        || field.pad_before.is_some()
+        || field.pad_after.is_some()
+        || field.align_before.is_some()
+        || field.align_after.is_some()
+        || field.seek_before.is_some()
+        || field.pad_size_to.is_some()
+}
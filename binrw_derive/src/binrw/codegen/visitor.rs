@@ -0,0 +1,119 @@
+use crate::binrw::parser::{Enum, EnumVariant};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+pub(crate) fn generate(en: &Enum, derive_input: &DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+    let vis = &derive_input.vis;
+    let trait_name = format_ident!("{}Visitor", name);
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    let (methods, arms) = en
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = variant.ident();
+            let snake_name = to_snake_case(&ident.to_string());
+            let method = format_ident!("visit_{}", snake_name);
+            let (pattern, params, values) = match variant {
+                EnumVariant::Variant { options, .. } => {
+                    let fields = options
+                        .fields
+                        .iter()
+                        .filter(|field| !field.is_temp(options.for_write))
+                        .collect::<Vec<_>>();
+                    let params = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field)| {
+                            let param = if !options.is_tuple() {
+                                field.ident.clone()
+                            } else if fields.len() == 1 {
+                                format_ident!("{}", snake_name)
+                            } else {
+                                format_ident!("field_{}", index)
+                            };
+                            let ty = &field.ty;
+                            quote! { #param: &#ty }
+                        })
+                        .collect::<Vec<_>>();
+                    let values = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+                    (Some(options.fields_pattern()), params, values)
+                }
+                EnumVariant::Unit(_) => (None, Vec::new(), Vec::new()),
+            };
+
+            let doc = format!("Visits a [`{name}::{ident}`] variant.");
+            let method_def = quote! {
+                #[doc = #doc]
+                fn #method(&mut self, #(#params),*);
+            };
+            let arm = quote! {
+                Self::#ident #pattern => visitor.#method(#(#values),*),
+            };
+            (method_def, arm)
+        })
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let trait_doc = format!(
+        "A visitor with one method for each variant of [`{name}`].\n\nUse [`{name}::accept`] to call the method for a value."
+    );
+    let accept_doc = format!("Calls the [`{trait_name}`] method for the variant of this value.");
+
+    quote! {
+        #[doc = #trait_doc]
+        // Field types are used as written, even when they are a `Vec`
+        #[allow(clippy::ptr_arg)]
+        #vis trait #trait_name #impl_generics #where_clause {
+            #(#methods)*
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #accept_doc]
+            #vis fn accept<V: #trait_name #ty_generics + ?Sized>(&self, visitor: &mut V) {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Converts a variant name to snake case, keeping acronyms together, so `RGBPalette` becomes `rgb_palette`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = index.checked_sub(1).map(|index| chars[index]);
+            let next = chars.get(index + 1);
+            let starts_word = prev.map_or(false, |prev| {
+                prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next.map_or(false, |next| next.is_lowercase()))
+            });
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("Palette"), "palette");
+        assert_eq!(to_snake_case("ImageData"), "image_data");
+        assert_eq!(to_snake_case("RGBPalette"), "rgb_palette");
+        assert_eq!(to_snake_case("Already_Snake"), "already_snake");
+    }
+}
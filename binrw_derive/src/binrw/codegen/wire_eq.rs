@@ -0,0 +1,96 @@
+use super::{
+    get_args_lifetime, read_options,
+    sanitization::{
+        ARGS, BINREAD_TRAIT, BIN_RESULT, ENDIAN_ENUM, FIELD_DIFF, OPT, READER, READ_ENDIAN,
+        READ_TRAIT, REQUIRED_ARG_TRAIT, SEEK_TRAIT, WIRE_DIFF, WIRE_SPANS, WIRE_SPANS_FN,
+    },
+};
+use crate::binrw::parser::{Input, Struct};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::DeriveInput;
+
+pub(crate) fn generate(derive_input: &DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+    let vis = &derive_input.vis;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let args_lifetime = get_args_lifetime(Span::call_site());
+
+    let wire_eq_doc = format!(
+        "Compares two serialised [`{name}`] values field by field, assuming \
+        little-endian byte order if the byte order is not specified.\n\n\
+        Returns the first field whose bytes differ, or `None` if every field \
+        is the same.\n\n\
+        # Errors\n\n\
+        If reading either value fails, the error is returned."
+    );
+    let wire_eq_options_doc = format!(
+        "Compares two serialised [`{name}`] values field by field, using the \
+        given byte order.\n\n\
+        Returns the first field whose bytes differ, or `None` if every field \
+        is the same.\n\n\
+        # Errors\n\n\
+        If reading either value fails, the error is returned."
+    );
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = #wire_eq_doc]
+            #vis fn wire_eq<A, B>(a: &mut A, b: &mut B) -> #BIN_RESULT<Option<#FIELD_DIFF>>
+            where
+                A: #READ_TRAIT + #SEEK_TRAIT,
+                B: #READ_TRAIT + #SEEK_TRAIT,
+                // The bound is higher-ranked so that it is not rejected as a
+                // trivially false bound for a type without a fixed byte order
+                for<#args_lifetime> Self: #READ_ENDIAN,
+                for<#args_lifetime> <Self as #BINREAD_TRAIT>::Args<#args_lifetime>: #REQUIRED_ARG_TRAIT,
+            {
+                Self::wire_eq_options(a, b, #ENDIAN_ENUM::Little)
+            }
+
+            #[doc = #wire_eq_options_doc]
+            #vis fn wire_eq_options<A, B>(
+                a: &mut A,
+                b: &mut B,
+                endian: #ENDIAN_ENUM,
+            ) -> #BIN_RESULT<Option<#FIELD_DIFF>>
+            where
+                A: #READ_TRAIT + #SEEK_TRAIT,
+                B: #READ_TRAIT + #SEEK_TRAIT,
+                for<#args_lifetime> <Self as #BINREAD_TRAIT>::Args<#args_lifetime>: #REQUIRED_ARG_TRAIT,
+            {
+                let a_spans = Self::#WIRE_SPANS_FN(a, endian, #REQUIRED_ARG_TRAIT::args())?;
+                let b_spans = Self::#WIRE_SPANS_FN(b, endian, #REQUIRED_ARG_TRAIT::args())?;
+                #WIRE_DIFF(a, &a_spans, b, &b_spans)
+            }
+        }
+    }
+}
+
+/// Generates the function which reads a struct and records the position of
+/// each field, which is used by both `wire_eq` and `dump`.
+pub(crate) fn generate_spans_fn(
+    input: &Input,
+    st: &Struct,
+    derive_input: &DeriveInput,
+) -> TokenStream {
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let spans = read_options::generate_wire_spans(input, st, name);
+
+    quote! {
+        #[automatically_derived]
+        #[allow(non_snake_case, unknown_lints)]
+        #[allow(clippy::redundant_closure_call)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn #WIRE_SPANS_FN<R: #READ_TRAIT + #SEEK_TRAIT>(
+                #READER: &mut R,
+                #OPT: #ENDIAN_ENUM,
+                #ARGS: <Self as #BINREAD_TRAIT>::Args<'_>,
+            ) -> #BIN_RESULT<#WIRE_SPANS> {
+                #spans
+            }
+        }
+    }
+}
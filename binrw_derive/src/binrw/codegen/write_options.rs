@@ -3,9 +3,9 @@ mod prelude;
 mod r#struct;
 mod struct_field;
 
-use super::get_map_err;
+use super::{get_map_err, get_span, get_trace_err};
 use crate::binrw::{
-    codegen::sanitization::{OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD},
+    codegen::sanitization::{BIN_RESULT, OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD},
     parser::{Input, Map},
 };
 use proc_macro2::TokenStream;
@@ -19,6 +19,10 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
     let inner = match input.map() {
         Map::None => match input {
             Input::UnitStruct(s) | Input::Struct(s) => generate_struct(input, name, s),
+            // No value of an enum without variants exists to be written.
+            // Since a reference to an uninhabited type is not itself
+            // uninhabited, the match has to be on the value
+            Input::Enum(e) if e.variants.is_empty() => return quote! { match *self {} },
             Input::Enum(e) => generate_data_enum(input, name, e),
             Input::UnitOnlyEnum(e) => generate_unit_enum(input, name, e),
         },
@@ -30,13 +34,35 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
     };
 
     let writer_var = input.stream_ident_or(WRITER);
+    let span = get_span("binrw::write", &derive_input.ident);
+
+    // Querying the position flushes a buffered writer, so it is only done if
+    // something needs it
+    let pos = (POS.is_used_in(&inner) || span.is_some()).then(|| {
+        quote! {
+            let #POS = #SEEK_TRAIT::stream_position(#writer_var)?;
+        }
+    });
+
+    let inner = if let Some(trace_err) = get_trace_err("write failed") {
+        quote! {
+            (|| -> #BIN_RESULT<()> {
+                #inner
+                Ok(())
+            })()#trace_err
+        }
+    } else {
+        quote! {
+            #inner
+            Ok(())
+        }
+    };
 
     quote! {
         let #writer_var = #WRITER;
-        let #POS = #SEEK_TRAIT::stream_position(#writer_var)?;
+        #pos
+        #span
         #inner
-
-        Ok(())
     }
 }
 
@@ -1,10 +1,14 @@
 use super::{prelude::PreludeGenerator, r#struct::StructGenerator};
 use crate::binrw::{
-    codegen::sanitization::{OPT, WRITER, WRITE_METHOD},
+    codegen::{
+        get_assertions, get_map_err,
+        sanitization::{OPT, SAVED_POSITION, SEEK_TRAIT, WRITER, WRITE_METHOD},
+    },
     parser::{Enum, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
 };
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
+use syn::spanned::Spanned;
 
 pub(crate) fn generate_unit_enum(
     input: &Input,
@@ -13,14 +17,25 @@ pub(crate) fn generate_unit_enum(
 ) -> TokenStream {
     let writer_var = input.stream_ident_or(WRITER);
     let write = match en.map.as_repr() {
-        Some(repr) => generate_unit_enum_repr(&writer_var, repr, &en.fields),
+        Some(repr) => generate_unit_enum_repr(
+            &writer_var,
+            repr,
+            en.repr_discriminant().as_ref(),
+            &en.fields,
+        ),
         None => generate_unit_enum_magic(&writer_var, &en.fields),
     };
+    let pre_assertions = generate_unit_enum_pre_assertions(&en.fields);
+    let write = quote! {
+        #pre_assertions
+        #write
+    };
 
     PreludeGenerator::new(write, input, name, &writer_var)
         .prefix_map_stream()
         .prefix_magic(&en.magic)
         .prefix_assertions()
+        .prefix_pre_assertions()
         .prefix_endian(&en.endian)
         .prefix_imports()
         .finish()
@@ -75,10 +90,9 @@ impl<'a> EnumGenerator<'a> {
                         .prefix_prelude()
                         .finish()
                 }
-                EnumVariant::Unit(variant) => variant
-                    .magic
-                    .as_ref()
-                    .map(|magic| {
+                EnumVariant::Unit(variant) => {
+                    let pre_assertions = get_assertions(&variant.pre_assertions);
+                    let magic = variant.magic.as_ref().map(|magic| {
                         let magic = magic.match_value();
                         quote! {
                             #WRITE_METHOD (
@@ -88,8 +102,12 @@ impl<'a> EnumGenerator<'a> {
                                 ()
                             )?;
                         }
-                    })
-                    .unwrap_or_default(),
+                    });
+                    quote! {
+                        #(#pre_assertions)*
+                        #magic
+                    }
+                }
             };
 
             quote! {
@@ -115,6 +133,7 @@ impl<'a> EnumGenerator<'a> {
             .prefix_map_stream()
             .prefix_magic(&self.en.magic)
             .prefix_assertions()
+            .prefix_pre_assertions()
             .prefix_endian(&self.en.endian)
             .prefix_imports()
             .finish();
@@ -127,9 +146,38 @@ impl<'a> EnumGenerator<'a> {
     }
 }
 
+// The variant being written is already known, so its pre-assertions only check
+// that it is a variant which could have been read with the same arguments.
+fn generate_unit_enum_pre_assertions(variants: &[UnitEnumField]) -> Option<TokenStream> {
+    let branches = variants
+        .iter()
+        .filter(|variant| !variant.pre_assertions.is_empty())
+        .map(|variant| {
+            let name = &variant.ident;
+            let pre_assertions = get_assertions(&variant.pre_assertions);
+            quote! {
+                Self::#name => {
+                    #(#pre_assertions)*
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (!branches.is_empty()).then(|| {
+        quote! {
+            #[allow(unreachable_patterns)]
+            match self {
+                #(#branches)*
+                _ => {}
+            }
+        }
+    })
+}
+
 fn generate_unit_enum_repr(
     writer_var: &TokenStream,
     repr: &TokenStream,
+    discriminant: Option<&TokenStream>,
     variants: &[UnitEnumField],
 ) -> TokenStream {
     let branches = variants.iter().map(|variant| {
@@ -139,11 +187,25 @@ fn generate_unit_enum_repr(
         }
     });
 
+    let value = if let Some(discriminant) = discriminant {
+        let map_err = get_map_err(SAVED_POSITION, repr.span());
+        quote! {{
+            let #SAVED_POSITION = #SEEK_TRAIT::stream_position(#writer_var)?;
+            <#discriminant as core::convert::TryInto<#repr>>::try_into(match self {
+                #(#branches),*
+            } as #discriminant)#map_err?
+        }}
+    } else {
+        quote! {
+            (match self {
+                #(#branches),*
+            } as #repr)
+        }
+    };
+
     quote! {
         #WRITE_METHOD (
-            &(match self {
-                #(#branches),*
-            } as #repr),
+            &#value,
             #writer_var,
             #OPT,
             (),
@@ -9,7 +9,7 @@ use crate::{
     util::quote_spanned_any,
 };
 use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::spanned::Spanned;
 
 pub(crate) struct PreludeGenerator<'a> {
@@ -45,6 +45,17 @@ impl<'a> PreludeGenerator<'a> {
         self
     }
 
+    pub(super) fn prefix_pre_assertions(mut self) -> Self {
+        let pre_assertions = get_assertions(self.input.pre_assertions());
+        let out = self.out;
+        self.out = quote! {
+            #(#pre_assertions)*
+            #out
+        };
+
+        self
+    }
+
     pub(crate) fn prefix_imports(mut self) -> Self {
         if let Some(imports) = get_destructured_imports(self.input.imports(), self.name, true) {
             let out = self.out;
@@ -60,11 +71,18 @@ impl<'a> PreludeGenerator<'a> {
     pub(crate) fn prefix_magic(mut self, magic: &Magic) -> Self {
         if let Some(magic) = magic {
             let writer_var = &self.writer_var;
-            let magic = magic.match_value();
+            // A magic with alternatives is only written from the field which
+            // holds the one that was read, which is borrowed already
+            let magic = if let Some(name) = magic.field_name() {
+                name.to_token_stream()
+            } else {
+                let magic = magic.match_value();
+                quote! { &#magic }
+            };
             let out = self.out;
             self.out = quote! {
                 #WRITE_METHOD (
-                    &#magic,
+                    #magic,
                     #writer_var,
                     #OPT,
                     ()
@@ -79,9 +97,14 @@ impl<'a> PreludeGenerator<'a> {
 
     pub(crate) fn prefix_endian(mut self, endian: &CondEndian) -> Self {
         let endian = get_endian(endian);
+        let endian_var = self
+            .input
+            .endian_var()
+            .map(|var| quote! { let #var = #OPT; });
         let out = self.out;
         self.out = quote! {
             let #OPT = #endian;
+            #endian_var
             #out
         };
 
@@ -1,7 +1,13 @@
 use super::{prelude::PreludeGenerator, struct_field::write_field};
 use crate::binrw::{
-    codegen::sanitization::{THIS, WRITER},
-    parser::{Input, Struct},
+    codegen::{
+        get_endian,
+        sanitization::{
+            make_ident, ASSERT_ALIGN, ASSERT_OFFSET, BITS_VAR, BIT_WRITER, CHECKSUM_STREAM,
+            CHECKSUM_WRITER, SEEK_TRAIT, THIS, WRITER, WRITE_CHECKSUM,
+        },
+    },
+    parser::{Input, Struct, StructField},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -54,25 +60,148 @@ impl<'input> StructGenerator<'input> {
             .prefix_magic(&self.st.magic)
             .prefix_endian(&self.st.endian)
             .prefix_assertions()
+            .prefix_pre_assertions()
             .finish();
 
         self
     }
 
     pub(super) fn write_fields(mut self) -> Self {
-        let write_fields = self
-            .st
-            .fields
-            .iter()
-            .map(|field| write_field(self.writer_var, field));
+        let writer_var = self.writer_var;
+        let write_fields = self.st.fields.iter().enumerate().map(|(index, field)| {
+            let write_field = write_field(writer_var, field);
+            let (start, end) = self.checksum_positions(field);
+            let pos = self.st.pos_var.as_ref().map(|pos_var| {
+                quote! {
+                    #[allow(unused_variables)]
+                    let #pos_var = #SEEK_TRAIT::stream_position(#writer_var)?;
+                }
+            });
+            // Each run of bit fields starts at a new byte, and the last byte is
+            // written once the run is finished
+            let bits_start = self.st.starts_bit_fields(index).then(|| {
+                quote! {
+                    let mut #BITS_VAR = #BIT_WRITER::new();
+                }
+            });
+            let bits_end = self.st.ends_bit_fields(index).then(|| {
+                quote! {
+                    #BITS_VAR.flush(#writer_var)?;
+                }
+            });
+            quote! {
+                #bits_start
+                #pos
+                #start
+                #write_field
+                #bits_end
+                #end
+            }
+        });
+        let what = self.name.map_or_else(
+            || String::from("end of the variant"),
+            |name| format!("end of `{name}`"),
+        );
+        let assert_offset = self.st.assert_offset.as_ref().map(|offset| {
+            quote! {
+                #ASSERT_OFFSET(#writer_var, #offset, #what)?;
+            }
+        });
+        let assert_align = self.st.assert_align.as_ref().map(|align| {
+            quote! {
+                #ASSERT_ALIGN(#writer_var, #align, #what)?;
+            }
+        });
 
         self.out = quote! {
             #(#write_fields)*
+            #assert_offset
+            #assert_align
         };
 
+        self.wrap_checksums()
+    }
+
+    // Checksum fields are written with their own value as a placeholder, then
+    // patched once all fields have been written, so that the checksum field
+    // can come before or after the data it covers. Since the written data
+    // cannot be read back from the stream, the stream is wrapped to keep a copy
+    // of it.
+    fn wrap_checksums(mut self) -> Self {
+        let writer_var = self.writer_var;
+        let checksums = self
+            .st
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let checksum = field.checksum.as_ref()?;
+                let function = &checksum.function;
+                let start = make_ident(&checksum.first, "checksum_start");
+                let end = make_ident(&checksum.last, "checksum_end");
+                let field_start = make_ident(&field.ident, "checksum_field_start");
+                let field_end = make_ident(&field.ident, "checksum_field_end");
+                let endian = get_endian(&field.endian);
+                Some(quote! {
+                    #WRITE_CHECKSUM(
+                        #writer_var,
+                        #start,
+                        #end,
+                        #function,
+                        (#field_start, #field_end),
+                        #endian,
+                    )?;
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if !checksums.is_empty() {
+            let out = self.out;
+            self.out = quote! {
+                let mut #CHECKSUM_STREAM = #CHECKSUM_WRITER::new(#writer_var);
+                let #writer_var = &mut #CHECKSUM_STREAM;
+                #out
+                #(#checksums)*
+            };
+        }
+
         self
     }
 
+    fn checksum_positions(&self, field: &StructField) -> (TokenStream, TokenStream) {
+        let checksums = self
+            .st
+            .fields
+            .iter()
+            .filter_map(|field| field.checksum.as_ref());
+        let save = |kind| {
+            let ident = make_ident(&field.ident, kind);
+            let writer_var = self.writer_var;
+            quote! {
+                let #ident = #SEEK_TRAIT::stream_position(#writer_var)?;
+            }
+        };
+
+        let mut start = TokenStream::new();
+        let mut end = TokenStream::new();
+        if checksums
+            .clone()
+            .any(|checksum| checksum.first == field.ident)
+        {
+            start.extend(save("checksum_start"));
+        }
+        if field.checksum.is_some() {
+            start.extend(save("checksum_field_start"));
+            end.extend(save("checksum_field_end"));
+        }
+        if checksums
+            .clone()
+            .any(|checksum| checksum.last == field.ident)
+        {
+            end.extend(save("checksum_end"));
+        }
+        (start, end)
+    }
+
     pub(super) fn prefix_borrow_fields(mut self) -> Self {
         let borrow_fields = self.name.map(|name| {
             let pattern = self.st.fields_pattern();
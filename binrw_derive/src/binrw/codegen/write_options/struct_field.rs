@@ -1,17 +1,21 @@
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            get_assertions, get_endian, get_map_err, get_passed_args, get_passed_args_list_len,
+            get_try_calc,
             sanitization::{
-                make_ident, BEFORE_POS, BINWRITE_TRAIT, MAP_WRITER_TYPE_HINT, POS,
-                REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, WRITE_ARGS_TYPE_HINT,
-                WRITE_FN_MAP_OUTPUT_TYPE_HINT, WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT,
-                WRITE_FN_TYPE_HINT, WRITE_FUNCTION, WRITE_MAP_ARGS_TYPE_HINT,
-                WRITE_MAP_INPUT_TYPE_HINT, WRITE_METHOD, WRITE_TRY_MAP_ARGS_TYPE_HINT,
+                make_ident, ARGS_LIST, ASSERT, ASSERT_ALIGN, ASSERT_ERROR_FN, ASSERT_OFFSET,
+                BEFORE_POS, BINWRITE_TRAIT, BITS_VAR, BIT_ORDER, MAP_WRITER_TYPE_HINT, POS,
+                REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT,
+                WRITE_ARGS_LIST_TYPE_HINT, WRITE_ARGS_TYPE_HINT, WRITE_FN_MAP_OUTPUT_TYPE_HINT,
+                WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT, WRITE_FN_TYPE_HINT, WRITE_FUNCTION,
+                WRITE_MAP_ARGS_LIST_TYPE_HINT, WRITE_MAP_ARGS_TYPE_HINT, WRITE_MAP_INPUT_TYPE_HINT,
+                WRITE_METHOD, WRITE_TRY_MAP_ARGS_LIST_TYPE_HINT, WRITE_TRY_MAP_ARGS_TYPE_HINT,
                 WRITE_ZEROES,
             },
+            wrap_field_trace,
         },
-        parser::{FieldMode, Map, StructField},
+        parser::{FieldMode, IfMismatch, Map, StructField},
     },
     util::quote_spanned_any,
 };
@@ -25,6 +29,7 @@ pub(crate) fn write_field(writer_var: &TokenStream, field: &StructField) -> Toke
     StructFieldGenerator::new(field, writer_var)
         .write_field()
         .wrap_map_stream()
+        .wrap_trace()
         .prefix_map_value()
         .prefix_calc_value()
         .wrap_padding()
@@ -85,7 +90,7 @@ impl<'a> StructFieldGenerator<'a> {
     }
 
     fn prefix_write_function(mut self) -> Self {
-        if !self.field.is_written() {
+        if !self.field.is_written() || self.field.bits.is_some() {
             return self;
         }
 
@@ -214,27 +219,92 @@ impl<'a> StructFieldGenerator<'a> {
             })
             .unwrap_or_else(|| quote_spanned! { name.span()=> &#name });
 
-        self.out = quote! {
-            #WRITE_FUNCTION(
-                #name,
-                #writer_var,
-                #endian,
-                #args
-            )?;
+        self.out = if let Some(len) = self.field.bits.as_deref() {
+            // The type is only given when it is known so that a reference to
+            // the field can be coerced to it
+            let ty = self.field.map.is_none().then(|| {
+                let ty = &self.field.ty;
+                quote! { ::<_, #ty> }
+            });
+            let order = self.field.bit_order.as_ref().map_or_else(
+                || quote! { #BIT_ORDER::from(#endian) },
+                ToTokens::to_token_stream,
+            );
+            quote! {
+                #BITS_VAR.write #ty (#writer_var, #order, #name, #len)?;
+            }
+        } else {
+            quote! {
+                #WRITE_FUNCTION(
+                    #name,
+                    #writer_var,
+                    #endian,
+                    #args
+                )?;
+            }
         };
 
         self
     }
 
+    fn wrap_trace(mut self) -> Self {
+        if self.field.is_written() {
+            self.out = wrap_field_trace(
+                self.outer_writer_var,
+                &self.field.ident,
+                "field written",
+                self.out,
+                None,
+            );
+        }
+        self
+    }
+
     fn wrap_condition(mut self) -> Self {
         if let Some(cond) = &self.field.if_cond {
             if cond.alternate.is_none() {
+                let name = &self.field.ident;
                 let condition = &cond.condition;
                 let consequent = self.out;
-                self.out = quote! {
-                    if #condition {
-                        #consequent
+                self.out = match self.field.if_mismatch {
+                    None | Some(IfMismatch::Skip) => quote! {
+                        if #condition {
+                            #consequent
+                        }
+                    },
+                    Some(IfMismatch::Error) => {
+                        let message = format!("`{name}` presence does not match its condition");
+                        quote! {
+                            #ASSERT(
+                                #name.is_some() == (#condition),
+                                #POS,
+                                #ASSERT_ERROR_FN::<_, fn() -> !>::Message(|| #message)
+                            )?;
+                            if #condition {
+                                #consequent
+                            }
+                        }
                     }
+                    Some(IfMismatch::WriteAnyway) => quote! {
+                        if (#condition) || #name.is_some() {
+                            #consequent
+                        }
+                    },
+                };
+            }
+
+            if cond.flag.is_some() {
+                let name = &self.field.ident;
+                let condition = &cond.condition;
+                let message = format!("`{name}` presence does not match its flag");
+                let out = self.out;
+                self.out = quote! {
+                    #ASSERT(
+                        #name.is_some() == #condition,
+                        #POS,
+                        #ASSERT_ERROR_FN::<_, fn() -> !>::Message(|| #message)
+                    )?;
+                    #out
                 };
             }
         }
@@ -257,7 +327,7 @@ impl<'a> StructFieldGenerator<'a> {
     }
 
     fn prefix_args(mut self) -> Self {
-        if !self.field.is_written() {
+        if !self.field.is_written() || self.field.bits.is_some() {
             return self;
         }
 
@@ -270,24 +340,40 @@ impl<'a> StructFieldGenerator<'a> {
         };
 
         let map_fn = map_func_ident(&self.field.ident);
+        let list_len = get_passed_args_list_len(self.field);
         let out = self.out;
         self.out = match &self.field.field_mode {
-            FieldMode::Normal => match &self.field.map {
-                Map::Map(_) => quote! {
+            FieldMode::Normal => match (&self.field.map, list_len) {
+                (Map::Map(_), None) => quote! {
                     let #args = #WRITE_MAP_ARGS_TYPE_HINT(&#map_fn, #args_val);
                     #out
                 },
-                Map::Try(_) | Map::Repr(_) => quote! {
+                (Map::Map(_), Some(len)) => quote! {
+                    let #args = #WRITE_MAP_ARGS_LIST_TYPE_HINT::<_, _, _, _, #len>(&#map_fn, #args_val);
+                    #out
+                },
+                (Map::Try(_) | Map::Repr(_), None) => quote! {
                     let #args = #WRITE_TRY_MAP_ARGS_TYPE_HINT(&#map_fn, #args_val);
                     #out
                 },
-                Map::None => {
+                (Map::Try(_) | Map::Repr(_), Some(len)) => quote! {
+                    let #args = #WRITE_TRY_MAP_ARGS_LIST_TYPE_HINT::<_, _, _, _, _, #len>(&#map_fn, #args_val);
+                    #out
+                },
+                (Map::None, None) => {
                     let ty = &self.field.ty;
                     quote! {
                         let #args: <#ty as #BINWRITE_TRAIT>::Args<'_> = #args_val;
                         #out
                     }
                 }
+                (Map::None, Some(len)) => {
+                    let ty = &self.field.ty;
+                    quote! {
+                        let #args = <<#ty as #BINWRITE_TRAIT>::Args<'_> as #ARGS_LIST<#len>>::from_list(#args_val);
+                        #out
+                    }
+                }
             },
             FieldMode::Calc(_) | FieldMode::TryCalc(_) => quote! {
                 let #args = ();
@@ -295,11 +381,20 @@ impl<'a> StructFieldGenerator<'a> {
             },
             FieldMode::Function(_) => {
                 let ty = &self.field.ty;
-                quote! {
-                    let #args = #WRITE_ARGS_TYPE_HINT::<#ty, _, _, _>(
-                        #WRITE_FUNCTION, #args_val
-                    );
-                    #out
+                if let Some(len) = list_len {
+                    quote! {
+                        let #args = #WRITE_ARGS_LIST_TYPE_HINT::<#ty, _, _, _, #len>(
+                            &#WRITE_FUNCTION, #args_val
+                        );
+                        #out
+                    }
+                } else {
+                    quote! {
+                        let #args = #WRITE_ARGS_TYPE_HINT::<#ty, _, _, _>(
+                            &#WRITE_FUNCTION, #args_val
+                        );
+                        #out
+                    }
                 }
             }
             FieldMode::Default => unreachable!("Ignored fields are not written"),
@@ -420,6 +515,17 @@ fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
             let #BEFORE_POS = #SEEK_TRAIT::stream_position(#writer_var)?;
         }
     });
+    let what = format!("`{}` to start", field.ident);
+    let assert_offset = field.assert_offset.as_ref().map(|offset| {
+        quote! {
+            #ASSERT_OFFSET(#writer_var, #offset, #what)?;
+        }
+    });
+    let assert_align = field.assert_align.as_ref().map(|align| {
+        quote! {
+            #ASSERT_ALIGN(#writer_var, #align, #what)?;
+        }
+    });
     let store_position = field.restore_position.map(|()| {
         quote! {
             let #SAVED_POSITION = #SEEK_TRAIT::stream_position(#writer_var)?;
@@ -431,6 +537,8 @@ fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
         #seek_before
         #pad_before
         #align_before
+        #assert_offset
+        #assert_align
         #pad_size_to_before
     }
 }
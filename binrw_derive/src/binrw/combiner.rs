@@ -1,8 +1,8 @@
 use crate::{
     binrw::{
         codegen::generate_impl,
-        parser::{Enum, EnumVariant, Input, ParseResult, Struct, StructField},
-        Options,
+        parser::{Enum, EnumVariant, FieldMode, Input, ParseResult, Struct, StructField},
+        strict, Options,
     },
     combine_error,
 };
@@ -31,6 +31,9 @@ pub(crate) fn derive(mut derive_input: DeriveInput) -> proc_macro2::TokenStream
         binwrite_input = ParseResult::Partial(binwrite_input.unwrap_tuple().0, error);
     }
 
+    binread_input = strict_check(&derive_input, binread_input, false);
+    binwrite_input = strict_check(&derive_input, binwrite_input, true);
+
     let generated_read_impl = generate_impl::<false>(&derive_input, &binread_input);
     let generated_write_impl = generate_impl::<true>(&derive_input, &binwrite_input);
 
@@ -45,9 +48,29 @@ pub(crate) fn derive(mut derive_input: DeriveInput) -> proc_macro2::TokenStream
     )
 }
 
+fn strict_check(
+    derive_input: &DeriveInput,
+    result: ParseResult<Input>,
+    write: bool,
+) -> ParseResult<Input> {
+    let error = match &result {
+        ParseResult::Ok(input) | ParseResult::Partial(input, _) if input.strict() => {
+            let mut error = strict::unused_imports(derive_input, input, write);
+            if write {
+                if let Some(unavailable) = strict::unavailable_temp_fields(input) {
+                    combine_error(&mut error, unavailable);
+                }
+            }
+            error
+        }
+        _ => None,
+    };
+    strict::add_error(result, error)
+}
+
 /// Check the fields of each input and copy temp state to the other input.
 #[rustfmt::skip]
-fn apply_temp_crossover(
+pub(super) fn apply_temp_crossover(
     binread_result: &mut ParseResult<Input>,
     binwrite_result: &mut ParseResult<Input>,
 ) -> Option<syn::Error> {
@@ -96,9 +119,9 @@ fn apply_temp_crossover_enum(
                     combine_error(&mut all_errors, error);
                 }
             }
-            (EnumVariant::Unit(_), EnumVariant::Unit(_)) => continue,
+            (EnumVariant::Unit(_), EnumVariant::Unit(_)) => {}
             _ => unreachable!("read and write input should always be the same kind"),
-        };
+        }
     }
     all_errors
 }
@@ -118,9 +141,23 @@ fn apply_temp_crossover_struct(
     // Iterate the fields again and set temp flags
     set_fields_temporary(&mut binread_struct.fields, &write_temporary);
     set_fields_temporary(&mut binwrite_struct.fields, &read_temporary);
+
+    apply_magic_field_crossover(binread_struct, binwrite_struct);
+    apply_magic_field_crossover(binwrite_struct, binread_struct);
     None
 }
 
+/// Copies the field which stores the magic to the other input if only one of
+/// them has it, where it is ignored.
+fn apply_magic_field_crossover(from: &Struct, to: &mut Struct) {
+    if let (Some(index), None) = (from.magic_field, to.magic_field) {
+        let mut field = from.fields[index].clone();
+        field.field_mode = FieldMode::Default;
+        to.fields.insert(index, field);
+        to.magic_field = Some(index);
+    }
+}
+
 fn validate_fields_temporary(
     fields: &[StructField],
     read_temporary: &HashSet<syn::Ident>,
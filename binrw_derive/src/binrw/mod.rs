@@ -3,6 +3,7 @@ mod backtrace;
 mod codegen;
 mod combiner;
 mod parser;
+mod strict;
 
 use codegen::generate_impl;
 pub(crate) use combiner::derive as binrw_derive;
@@ -26,12 +27,17 @@ fn clean_attr(derive_input: &mut DeriveInput, binrw_input: Option<&Input>) {
 
     match &mut derive_input.data {
         syn::Data::Struct(st) => {
-            clean_field_attrs(binrw_input, 0, &mut st.fields);
+            clean_field_attrs(binrw_input, 0, &derive_input.vis, &mut st.fields);
         }
         syn::Data::Enum(en) => {
             for (index, variant) in en.variants.iter_mut().enumerate() {
                 clean_struct_attrs(&mut variant.attrs);
-                clean_field_attrs(binrw_input, index, &mut variant.fields);
+                clean_field_attrs(
+                    binrw_input,
+                    index,
+                    &syn::Visibility::Inherited,
+                    &mut variant.fields,
+                );
             }
         }
         syn::Data::Union(union) => {
@@ -43,7 +49,12 @@ fn clean_attr(derive_input: &mut DeriveInput, binrw_input: Option<&Input>) {
 }
 
 #[cfg_attr(coverage_nightly, coverage(off))]
-fn clean_field_attrs(input: Option<&Input>, variant_index: usize, fields: &mut syn::Fields) {
+fn clean_field_attrs(
+    input: Option<&Input>,
+    variant_index: usize,
+    vis: &syn::Visibility,
+    fields: &mut syn::Fields,
+) {
     if let Some(input) = input {
         let fields = match fields {
             syn::Fields::Named(fields) => &mut fields.named,
@@ -64,6 +75,13 @@ fn clean_field_attrs(input: Option<&Input>, variant_index: usize, fields: &mut s
                 }
             })
             .collect();
+
+        for field in input.generated_fields(variant_index) {
+            fields.push(syn::Field {
+                vis: vis.clone(),
+                ..field.clone()
+            });
+        }
     }
 }
 
@@ -128,12 +146,17 @@ pub(super) fn derive_from_input(
 
         match &mut derive_input.data {
             syn::Data::Struct(st) => {
-                clean_field_attrs(binrw_input.as_ref(), 0, &mut st.fields);
+                clean_field_attrs(binrw_input.as_ref(), 0, &derive_input.vis, &mut st.fields);
             }
             syn::Data::Enum(en) => {
                 for (index, variant) in en.variants.iter_mut().enumerate() {
                     clean_struct_attrs(&mut variant.attrs);
-                    clean_field_attrs(binrw_input.as_ref(), index, &mut variant.fields);
+                    clean_field_attrs(
+                        binrw_input.as_ref(),
+                        index,
+                        &syn::Visibility::Inherited,
+                        &mut variant.fields,
+                    );
                 }
             }
             syn::Data::Union(union) => {
@@ -162,7 +185,18 @@ fn parse(
     derive_input: &DeriveInput,
     options: Options,
 ) -> (ParseResult<Input>, proc_macro2::TokenStream) {
-    let binrw_input = Input::from_input(derive_input, options);
+    let mut binrw_input = Input::from_input(derive_input, options);
+    if let ParseResult::Ok(input) | ParseResult::Partial(input, _) = &binrw_input {
+        if input.strict() {
+            let mut error = strict::unused_imports(derive_input, input, options.write);
+            if !options.derive {
+                if let Some(ignored) = strict::ignored_attrs(derive_input, options.write) {
+                    crate::combine_error(&mut error, ignored);
+                }
+            }
+            binrw_input = strict::add_error(binrw_input, error);
+        }
+    }
     let generated_impl = if options.write {
         generate_impl::<true>(derive_input, &binrw_input)
     } else {
@@ -1,7 +1,7 @@
 use super::keywords as kw;
 use crate::meta_types::{
     IdentPatType, IdentTypeMaybeDefault, MetaEnclosedList, MetaExpr, MetaIdent, MetaList, MetaLit,
-    MetaType, MetaValue, MetaVoid,
+    MetaOptionalExpr, MetaType, MetaValue, MetaVoid,
 };
 use syn::{Expr, FieldValue, Token};
 
@@ -11,36 +11,65 @@ pub(super) type Args = MetaEnclosedList<kw::args, Expr, FieldValue>;
 pub(super) type ArgsRaw = MetaExpr<kw::args_raw>;
 pub(super) type AssertLike<Keyword> = MetaList<Keyword, Expr>;
 pub(super) type Assert = AssertLike<kw::assert>;
+pub(super) type AssertAlign = MetaExpr<kw::assert_align>;
+pub(super) type AssertOffset = MetaExpr<kw::assert_offset>;
 pub(super) type Big = MetaVoid<kw::big>;
+pub(super) type BitOrder = MetaExpr<kw::bit_order>;
+pub(super) type Bits = MetaLit<kw::bits>;
 pub(super) type Calc = MetaExpr<kw::calc>;
+pub(super) type Checksum = MetaList<kw::checksum, Expr>;
 pub(super) type Count = MetaExpr<kw::count>;
 pub(super) type Debug = MetaVoid<kw::dbg>;
 pub(super) type Default = MetaVoid<kw::default>;
+pub(super) type DefaultBig = MetaVoid<kw::default_big>;
+pub(super) type DefaultLittle = MetaVoid<kw::default_little>;
+pub(super) type Dump = MetaVoid<kw::dump>;
+pub(super) type DynReader = MetaVoid<kw::dyn_reader>;
+pub(super) type Else = MetaIdent<Token![else]>;
+pub(super) type EndianVar = MetaIdent<kw::endian_var>;
 pub(super) type ErrContext = MetaList<kw::err_context, Expr>;
+pub(super) type ErrorType = MetaType<kw::error>;
+pub(super) type Fallback = MetaVoid<kw::fallback>;
 pub(super) type If = MetaList<Token![if], Expr>;
+pub(super) type IfEofDefault = MetaVoid<kw::if_eof_default>;
 pub(super) type Ignore = MetaVoid<kw::ignore>;
-pub(super) type Import = MetaEnclosedList<kw::import, IdentPatType, IdentTypeMaybeDefault>;
+pub(super) type ImplTryFrom = MetaVoid<kw::impl_try_from>;
+pub(super) type Import = MetaEnclosedList<kw::import, IdentTypeMaybeDefault, IdentTypeMaybeDefault>;
 pub(super) type ImportRaw = MetaValue<kw::import_raw, IdentPatType>;
 pub(super) type IsBig = MetaExpr<kw::is_big>;
 pub(super) type IsLittle = MetaExpr<kw::is_little>;
 pub(super) type Little = MetaVoid<kw::little>;
-pub(super) type Magic = MetaLit<kw::magic>;
+pub(super) type Magic = MetaValue<kw::magic, super::types::MagicLit>;
 pub(super) type Map = MetaExpr<kw::map>;
 pub(super) type MapStream = MetaExpr<kw::map_stream>;
 pub(super) type Offset = MetaExpr<kw::offset>;
+pub(super) type Or = MetaExpr<kw::or>;
 pub(super) type PadAfter = MetaExpr<kw::pad_after>;
 pub(super) type PadBefore = MetaExpr<kw::pad_before>;
 pub(super) type PadSizeTo = MetaExpr<kw::pad_size_to>;
 pub(super) type ParseWith = MetaExpr<kw::parse_with>;
+pub(super) type PosVar = MetaIdent<kw::pos_var>;
 pub(super) type PreAssert = AssertLike<kw::pre_assert>;
+pub(super) type PresentIf = MetaList<kw::present_if, Expr>;
+pub(super) type PreserveUnknown = MetaOptionalExpr<kw::preserve_unknown>;
 pub(super) type Repr = MetaType<kw::repr>;
+pub(super) type ReprErr = MetaExpr<kw::repr_err>;
 pub(super) type RestorePosition = MetaVoid<kw::restore_position>;
 pub(super) type ReturnAllErrors = MetaVoid<kw::return_all_errors>;
 pub(super) type ReturnUnexpectedError = MetaVoid<kw::return_unexpected_error>;
+pub(super) type Schema = MetaVoid<kw::schema>;
 pub(super) type SeekBefore = MetaExpr<kw::seek_before>;
+pub(super) type Since = MetaExpr<kw::since>;
 pub(super) type Stream = MetaIdent<kw::stream>;
+pub(super) type Strict = MetaVoid<kw::strict>;
+pub(super) type Tag = MetaExpr<kw::tag>;
+pub(super) type TagValue = MetaExpr<kw::tag_value>;
 pub(super) type Temp = MetaVoid<kw::temp>;
 pub(super) type Try = MetaVoid<Token![try]>;
 pub(super) type TryCalc = MetaExpr<kw::try_calc>;
 pub(super) type TryMap = MetaExpr<kw::try_map>;
+pub(super) type Until = MetaExpr<kw::until>;
+pub(super) type Version = MetaExpr<kw::version>;
+pub(super) type Visitor = MetaVoid<kw::visitor>;
+pub(super) type WireEq = MetaVoid<kw::wire_eq>;
 pub(super) type WriteWith = MetaExpr<kw::write_with>;
@@ -1,7 +1,10 @@
 use super::{
     attr_struct,
     top_level_attrs::StructAttr,
-    types::{Assert, CondEndian, Condition, ErrContext, FieldMode, Magic, Map, PassedArgs},
+    types::{
+        check_magic_written, Assert, Bits, Checksum, CondEndian, Condition, ErrContext, FieldMode,
+        IfMismatch, Magic, Map, PassedArgs,
+    },
     FromAttrs, FromField, FromInput, ParseResult, SpannedValue, Struct, TrySet,
 };
 use crate::{binrw::Options, combine_error};
@@ -24,6 +27,10 @@ attr_struct! {
         pub(crate) map_stream: Option<TokenStream>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
+        #[from(RW:Bits)]
+        pub(crate) bits: Bits,
+        #[from(RW:BitOrder)]
+        pub(crate) bit_order: Option<TokenStream>,
         #[from(RW:Args, RW:ArgsRaw)]
         pub(crate) args: PassedArgs,
         #[from(RW:Calc, RW:TryCalc, RO:Default, RW:Ignore, RO:ParseWith, WO:WriteWith)]
@@ -32,16 +39,28 @@ attr_struct! {
         pub(crate) count: Option<TokenStream>,
         #[from(RO:Offset)]
         pub(crate) offset: Option<TokenStream>,
-        #[from(RW:If)]
+        #[from(RW:If, RW:PresentIf)]
         pub(crate) if_cond: Option<Condition>,
+        #[from(RW:Since)]
+        pub(crate) since: Option<TokenStream>,
+        #[from(RW:Until)]
+        pub(crate) until: Option<TokenStream>,
+        #[from(WO:Else)]
+        pub(crate) if_mismatch: Option<IfMismatch>,
         #[from(RW:RestorePosition)]
         pub(crate) restore_position: Option<()>,
         #[from(RO:Try)]
         pub(crate) do_try: Option<SpannedValue<()>>,
+        #[from(RO:Or)]
+        pub(crate) try_or: Option<TokenStream>,
+        #[from(RO:IfEofDefault)]
+        pub(crate) eof_default: Option<SpannedValue<()>>,
         #[from(RO:Temp)]
         pub(crate) temp: Option<()>,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
+        #[from(RW:Checksum)]
+        pub(crate) checksum: Option<Checksum>,
         #[from(RO:ErrContext)]
         pub(crate) err_context: Option<ErrContext>,
         #[from(RW:PadBefore)]
@@ -56,6 +75,10 @@ attr_struct! {
         pub(crate) seek_before: Option<TokenStream>,
         #[from(RW:PadSizeTo)]
         pub(crate) pad_size_to: Option<TokenStream>,
+        #[from(WO:AssertOffset)]
+        pub(crate) assert_offset: Option<TokenStream>,
+        #[from(WO:AssertAlign)]
+        pub(crate) assert_align: Option<TokenStream>,
         #[from(RO:Debug)] // TODO is this really RO?
         pub(crate) debug: Option<()>,
     }
@@ -119,8 +142,13 @@ impl StructField {
                 count,
                 offset,
                 if_cond,
+                since,
+                until,
+                if_mismatch,
                 restore_position,
                 do_try,
+                try_or,
+                eof_default,
                 temp,
                 pad_before,
                 pad_after,
@@ -128,7 +156,12 @@ impl StructField {
                 align_after,
                 seek_before,
                 pad_size_to,
-                magic
+                assert_offset,
+                assert_align,
+                checksum,
+                magic,
+                bits,
+                bit_order
             )
     }
 
@@ -142,12 +175,12 @@ impl StructField {
         self.temp = Some(());
     }
 
-    fn validate(&self, _: Options) -> syn::Result<()> {
+    fn validate(&self, options: Options) -> syn::Result<()> {
         let mut all_errors = None::<syn::Error>;
 
-        if self.do_try.is_some() && self.generated_value() {
+        if let Some(do_try) = self.do_try.as_ref().filter(|_| self.generated_value()) {
             //TODO: join with span of read mode somehow
-            let span = self.do_try.as_ref().unwrap().span();
+            let span = do_try.span();
             combine_error(
                 &mut all_errors,
                 syn::Error::new(
@@ -157,6 +190,23 @@ impl StructField {
             );
         }
 
+        if let Some(eof_default) = self.eof_default.as_ref().filter(|_| self.generated_value()) {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(
+                    eof_default.span(),
+                    "`if_eof_default` is incompatible with `default`, `calc`, and `try_calc`",
+                ),
+            );
+        }
+
+        if let (Some(try_or), None, None) = (&self.try_or, &self.do_try, &self.eof_default) {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(try_or.span(), "`or` requires `try` or `if_eof_default`"),
+            );
+        }
+
         if matches!(self.field_mode, FieldMode::TryCalc(_) | FieldMode::Calc(_))
             && self.args.is_some()
         {
@@ -199,12 +249,90 @@ impl StructField {
             }
         }
 
+        let is_versioned = self.since.is_some() || self.until.is_some();
+        if self.if_mismatch.is_some()
+            && !matches!(&self.if_cond, Some(cond) if cond.alternate.is_none() && cond.flag.is_none())
+            && !(self.if_cond.is_none() && is_versioned)
+        {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(
+                    self.field.span(),
+                    "`else` can only be used with an `if` directive which has no alternate value",
+                ),
+            );
+        }
+
+        if options.write {
+            if let Err(error) = check_magic_written(&self.magic) {
+                combine_error(&mut all_errors, error);
+            }
+        }
+
+        self.validate_bits(options, &mut all_errors);
+
         if let Some(error) = all_errors {
             Err(error)
         } else {
             Ok(())
         }
     }
+
+    fn validate_bits(&self, options: Options, all_errors: &mut Option<syn::Error>) {
+        if let (None, Some(bit_order)) = (&self.bits, &self.bit_order) {
+            combine_error(
+                all_errors,
+                syn::Error::new(bit_order.span(), "`bit_order` can only be used with `bits`"),
+            );
+        }
+
+        if let Some(bits) = &self.bits {
+            for (used, name) in [
+                (self.args.is_some(), "args"),
+                (self.count.is_some(), "count"),
+                (self.offset.is_some(), "offset"),
+                (
+                    matches!(self.field_mode, FieldMode::Function(_)),
+                    "parse_with` or `write_with",
+                ),
+                (matches!(self.field_mode, FieldMode::Default), "ignore"),
+                // A calculated value is not read, so it would not consume
+                // its bits
+                (
+                    !options.write
+                        && matches!(self.field_mode, FieldMode::Calc(_) | FieldMode::TryCalc(_)),
+                    "calc` or `try_calc",
+                ),
+                (self.map_stream.is_some(), "map_stream"),
+                (self.magic.is_some(), "magic"),
+                (self.if_cond.is_some(), "if` or `present_if"),
+                (self.since.is_some(), "since"),
+                (self.until.is_some(), "until"),
+                (self.do_try.is_some(), "try"),
+                (self.eof_default.is_some(), "if_eof_default"),
+                (self.restore_position.is_some(), "restore_position"),
+                (self.checksum.is_some(), "checksum"),
+                (self.seek_before.is_some(), "seek_before"),
+                (self.pad_before.is_some(), "pad_before"),
+                (self.pad_after.is_some(), "pad_after"),
+                (self.align_before.is_some(), "align_before"),
+                (self.align_after.is_some(), "align_after"),
+                (self.pad_size_to.is_some(), "pad_size_to"),
+                (self.assert_offset.is_some(), "assert_offset"),
+                (self.assert_align.is_some(), "assert_align"),
+            ] {
+                if used {
+                    combine_error(
+                        all_errors,
+                        syn::Error::new(
+                            bits.span(),
+                            format!("`bits` cannot be used with `{name}`"),
+                        ),
+                    );
+                }
+            }
+        }
+    }
 }
 
 impl FromField for StructField {
@@ -223,21 +351,31 @@ impl FromField for StructField {
             map: <_>::default(),
             map_stream: <_>::default(),
             magic: <_>::default(),
+            bits: <_>::default(),
+            bit_order: <_>::default(),
             args: <_>::default(),
             field_mode: <_>::default(),
             count: <_>::default(),
             offset: <_>::default(),
             if_cond: <_>::default(),
+            since: <_>::default(),
+            until: <_>::default(),
+            if_mismatch: <_>::default(),
             restore_position: <_>::default(),
             do_try: <_>::default(),
+            try_or: <_>::default(),
+            eof_default: <_>::default(),
             temp: <_>::default(),
             assertions: <_>::default(),
+            checksum: <_>::default(),
             pad_before: <_>::default(),
             pad_after: <_>::default(),
             align_before: <_>::default(),
             align_after: <_>::default(),
             seek_before: <_>::default(),
             pad_size_to: <_>::default(),
+            assert_offset: <_>::default(),
+            assert_align: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
             err_context: <_>::default(),
@@ -276,8 +414,10 @@ attr_struct! {
         pub(crate) ident: syn::Ident,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
-        #[from(RO:PreAssert)]
+        #[from(RW:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(RO:TagValue)]
+        pub(crate) tag_value: Option<TokenStream>,
     }
 }
 
@@ -286,6 +426,7 @@ impl From<UnitEnumField> for Struct {
         Self {
             magic: value.magic,
             pre_assertions: value.pre_assertions,
+            tag_value: value.tag_value,
             ..<_>::default()
         }
     }
@@ -299,6 +440,7 @@ impl FromField for UnitEnumField {
             ident: field.ident.clone(),
             magic: <_>::default(),
             pre_assertions: <_>::default(),
+            tag_value: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
         };
@@ -325,7 +467,7 @@ pub(crate) enum EnumVariant {
         ident: syn::Ident,
         options: Box<Struct>,
     },
-    Unit(UnitEnumField),
+    Unit(Box<UnitEnumField>),
 }
 
 impl EnumVariant {
@@ -342,13 +484,27 @@ impl EnumVariant {
             Self::Unit(_) => true,
         }
     }
+
+    pub(crate) fn tag_value(&self) -> Option<&TokenStream> {
+        match self {
+            Self::Variant { options, .. } => options.tag_value.as_ref(),
+            Self::Unit(field) => field.tag_value.as_ref(),
+        }
+    }
+
+    pub(crate) fn is_fallback(&self) -> bool {
+        match self {
+            Self::Variant { options, .. } => options.fallback.is_some(),
+            Self::Unit(_) => false,
+        }
+    }
 }
 
 impl From<EnumVariant> for Struct {
     fn from(value: EnumVariant) -> Self {
         match value {
             EnumVariant::Variant { options, .. } => *options,
-            EnumVariant::Unit(options) => options.into(),
+            EnumVariant::Unit(options) => (*options).into(),
         }
     }
 }
@@ -375,7 +531,8 @@ impl FromField for EnumVariant {
                 ident: variant.ident.clone(),
                 options: Box::new(options),
             }),
-            syn::Fields::Unit => UnitEnumField::from_field(variant, index, options).map(Self::Unit),
+            syn::Fields::Unit => UnitEnumField::from_field(variant, index, options)
+                .map(|field| Self::Unit(Box::new(field))),
         }
     }
 }
@@ -12,18 +12,32 @@ define_keywords! {
     args,
     args_raw,
     assert,
+    assert_align,
+    assert_offset,
     big,
     binread,
+    bit_order,
+    bits,
     br,
     brw,
     binwrite,
     bw,
     calc,
+    checksum,
     count,
     dbg,
     default,
+    default_big,
+    default_little,
+    dump,
+    dyn_reader,
+    endian_var,
     err_context,
+    error,
+    fallback,
+    if_eof_default,
     ignore,
+    impl_try_from,
     import,
     import_raw,
     is_big,
@@ -32,20 +46,36 @@ define_keywords! {
     magic,
     map,
     map_stream,
+    mask,
+    name,
     offset,
+    or,
     pad_after,
     pad_before,
     pad_size_to,
     parse_with,
+    pos_var,
     pre_assert,
+    present_if,
+    preserve_unknown,
     repr,
+    repr_err,
     restore_position,
     return_all_errors,
     return_unexpected_error,
+    schema,
     seek_before,
+    since,
     stream,
+    strict,
+    tag,
+    tag_value,
     temp,
     try_calc,
     try_map,
+    until,
+    version,
+    visitor,
+    wire_eq,
     write_with,
 }
@@ -6,6 +6,9 @@ macro_rules! parse_any {
         ),*
         $(,)?
     }) => {
+        // Lint: Values only live while attributes are parsed, so boxing large
+        // directives would only add allocations
+        #[allow(clippy::large_enum_variant)]
         $vis enum $enum<const WRITE: bool> {
             $(
                 $variant($ty)
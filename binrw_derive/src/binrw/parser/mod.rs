@@ -104,6 +104,10 @@ trait FromInput<Attr: syn::parse::Parse>: FromAttrs<Attr> {
             }
         }
 
+        if let Err(fields_error) = this.finish_fields(options) {
+            combine_error(&mut all_errors, fields_error);
+        }
+
         if let Err(validation_error) = this.validate(options) {
             combine_error(&mut all_errors, validation_error);
         }
@@ -119,6 +123,10 @@ trait FromInput<Attr: syn::parse::Parse>: FromAttrs<Attr> {
 
     fn set_options(&mut self, _: Options) {}
 
+    fn finish_fields(&mut self, _: Options) -> syn::Result<()> {
+        Ok(())
+    }
+
     fn validate(&self, _: Options) -> syn::Result<()>;
 }
 
@@ -198,6 +206,11 @@ mod tests {
         }
     });
 
+    try_error!(conflicting_keyword_default_endian: "conflicting endianness keyword" {
+        #[br(big, default_little)]
+        struct Foo;
+    });
+
     try_error!(conflicting_keyword_enum_error_mode: "conflicting error handling keyword" {
         #[br(return_all_errors, return_unexpected_error)]
         enum Foo {
@@ -205,6 +218,11 @@ mod tests {
         }
     });
 
+    try_error!(import_default_order: "arguments without a default value must come before arguments with one" {
+        #[br(import(a: i32 = 1, b: i32))]
+        struct Foo;
+    });
+
     try_error!(conflicting_keyword_imports: "conflicting import keyword" {
         #[br(import{a: i32}, import_raw(args: (i32, )))]
         struct Foo;
@@ -237,12 +255,82 @@ mod tests {
         }
     });
 
+    try_error!(enum_fallback_magic: "cannot have a `magic`" {
+        enum Foo {
+            #[br(fallback, magic = 1u8)]
+            A(u8),
+        }
+    });
+
+    try_error!(enum_fallback_multiple: "only have one `fallback`" {
+        enum Foo {
+            #[br(fallback)]
+            A(u8),
+            #[br(fallback)]
+            B(u16),
+        }
+    });
+
+    try_error!(enum_fallback_struct: "can only be used on an enum variant" {
+        #[br(fallback)]
+        struct Foo(u8);
+    });
+
     try_error!(enum_missing_magic_repr: "requires either" {
         enum UnitEnum {
             A,
         }
     });
 
+    try_error!(enum_repr_err_without_repr: "`repr_err` requires `repr`" {
+        #[br(repr_err = |value| value)]
+        enum UnitEnum {
+            #[br(magic = 0u8)]
+            A,
+        }
+    });
+
+    try_error!(if_eof_default_calc: "`if_eof_default` is incompatible" {
+        struct Foo {
+            #[br(calc = 1, if_eof_default)]
+            a: u8,
+        }
+    });
+
+    try_error!(magic_alternatives_type: "same type" {
+        #[br(magic(1u8 | 1u16))]
+        struct Foo;
+    });
+
+    try_error!(magic_mask_float: "only a byte string, byte, or int magic" {
+        #[br(magic(1.0f32, mask = 1.0f32))]
+        struct Foo;
+    });
+
+    try_error!(magic_name_unit_variant: "cannot be named" {
+        enum Foo {
+            #[br(magic(0u8 | 1u8, name = kind))]
+            A,
+        }
+    });
+
+    try_error!(magic_mask_len: "same length as the magic" {
+        #[br(magic(b"ab", mask = b"\xff"))]
+        struct Foo;
+    });
+
+    try_error!(enum_tag_value_struct: "can only be used on an enum variant" {
+        #[br(tag_value = 1)]
+        struct Foo(u8);
+    });
+
+    try_error!(enum_tag_value_without_tag: "`tag_value` requires `tag`" {
+        enum Foo {
+            #[br(tag_value = 1)]
+            A(u8),
+        }
+    });
+
     try_error!(err_context_missing: "requires a value" {
         struct Foo {
             #[br(err_context())]
@@ -340,6 +428,38 @@ mod tests {
         struct Foo;
     });
 
+    try_error!(invalid_present_if_missing_mask: "requires a flags expression and a mask" {
+        struct Foo {
+            flags: u8,
+            #[br(present_if(flags))]
+            a: Option<u8>,
+        }
+    });
+
+    try_error!(present_if_if_conflict: "conflicting `if` keyword" {
+        struct Foo {
+            flags: u8,
+            #[br(present_if(flags, 1), if(flags == 1))]
+            a: Option<u8>,
+        }
+    });
+
+    try_error!(since_present_if: "cannot be used with `present_if`" {
+        #[br(import(version: u8), version = version)]
+        struct Foo {
+            flags: u8,
+            #[br(present_if(flags, 1), since(2))]
+            a: Option<u8>,
+        }
+    });
+
+    try_error!(since_without_version: "require a struct-level `version`" {
+        struct Foo {
+            #[br(since(2))]
+            a: u8,
+        }
+    });
+
     try_error!(try_calc_conflict: "`try` is incompatible" {
         struct Foo {
             #[br(try, calc(None))]
@@ -347,6 +467,13 @@ mod tests {
         }
     });
 
+    try_error!(try_or_without_try: "`or` requires `try`" {
+        struct Foo {
+            #[br(or = Some(1))]
+            a: Option<u8>,
+        }
+    });
+
     try_error!(try_default_conflict: "`try` is incompatible" {
         struct Foo {
             #[br(try, default)]
@@ -380,10 +507,6 @@ mod tests {
         }
     });
 
-    try_error!(unsupported_type_enum: "null enums are not supported" {
-        enum Foo {}
-    });
-
     try_error!(unsupported_type_union: "unions are not supported" {
         union Bar {
             a: i32,
@@ -1,9 +1,15 @@
 use super::{
     attr_struct,
-    types::{Assert, CondEndian, EnumErrorMode, Imports, Magic, Map},
-    EnumVariant, FromInput, ParseResult, StructField, TrySet, UnitEnumField,
+    types::{
+        check_magic_written, Assert, CondEndian, Condition, EnumErrorMode, FieldMode, Imports,
+        Magic, Map, PassedArgs, SpannedValue,
+    },
+    EnumVariant, FromField, FromInput, ParseResult, StructField, TrySet, UnitEnumField,
+};
+use crate::binrw::{
+    codegen::sanitization::{POS, READ_UNKNOWN, UNKNOWN_FIELD, VEC},
+    Options,
 };
-use crate::binrw::Options;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{spanned::Spanned, Ident};
@@ -41,6 +47,21 @@ impl Input {
                     )
                 };
 
+                if let ParseResult::Ok(st) = &read_struct {
+                    if let Some(fallback) = &st.fallback {
+                        return ParseResult::Err(syn::Error::new(
+                            fallback.span(),
+                            "`fallback` can only be used on an enum variant",
+                        ));
+                    }
+                    if let Some(tag_value) = &st.tag_value {
+                        return ParseResult::Err(syn::Error::new(
+                            tag_value.span(),
+                            "`tag_value` can only be used on an enum variant",
+                        ));
+                    }
+                }
+
                 if matches!(st.fields, syn::Fields::Unit) {
                     read_struct.map(Self::UnitStruct)
                 } else {
@@ -49,14 +70,13 @@ impl Input {
             }
             syn::Data::Enum(en) => {
                 let variants = &en.variants;
-                if variants.is_empty() {
-                    ParseResult::Err(syn::Error::new(
-                        input.span(),
-                        "null enums are not supported",
-                    ))
-                } else if variants
-                    .iter()
-                    .all(|v| matches!(v.fields, syn::Fields::Unit))
+                // An enum without variants goes through the data enum path
+                // since it cannot have a `repr`, and no value of it exists to
+                // be written
+                if !variants.is_empty()
+                    && variants
+                        .iter()
+                        .all(|v| matches!(v.fields, syn::Fields::Unit))
                 {
                     if options.write {
                         <UnitOnlyEnum as FromInput<UnitEnumAttr<true>>>::from_input(
@@ -71,7 +91,10 @@ impl Input {
                             options,
                         )
                     }
-                    .map(Self::UnitOnlyEnum)
+                    .map(|mut e| {
+                        e.discriminant = discriminant_type(attrs);
+                        Self::UnitOnlyEnum(e)
+                    })
                 } else {
                     if options.write {
                         <Enum as FromInput<EnumAttr<true>>>::from_input(
@@ -106,6 +129,54 @@ impl Input {
         }
     }
 
+    pub(crate) fn impl_try_from(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.impl_try_from.is_some(),
+            Input::Enum(e) => e.impl_try_from.is_some(),
+            Input::UnitOnlyEnum(e) => e.impl_try_from.is_some(),
+        }
+    }
+
+    pub(crate) fn dump(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.dump.is_some(),
+            Input::Enum(e) => e.dump.is_some(),
+            Input::UnitOnlyEnum(e) => e.dump.is_some(),
+        }
+    }
+
+    pub(crate) fn schema(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.schema.is_some(),
+            Input::Enum(e) => e.schema.is_some(),
+            Input::UnitOnlyEnum(e) => e.schema.is_some(),
+        }
+    }
+
+    pub(crate) fn dyn_reader(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.dyn_reader.is_some(),
+            Input::Enum(e) => e.dyn_reader.is_some(),
+            Input::UnitOnlyEnum(e) => e.dyn_reader.is_some(),
+        }
+    }
+
+    pub(crate) fn strict(&self) -> bool {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.strict.is_some(),
+            Input::Enum(e) => e.strict.is_some(),
+            Input::UnitOnlyEnum(e) => e.strict.is_some(),
+        }
+    }
+
+    pub(crate) fn error_type(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.error_type.as_ref(),
+            Input::Enum(e) => e.error_type.as_ref(),
+            Input::UnitOnlyEnum(e) => e.error_type.as_ref(),
+        }
+    }
+
     pub(crate) fn imports(&self) -> &Imports {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.imports,
@@ -143,6 +214,19 @@ impl Input {
         }
     }
 
+    /// Returns the fields generated for a struct or variant, which hold the
+    /// magic which was read and the unknown bytes of `preserve_unknown`.
+    pub(crate) fn generated_fields(&self, variant_index: usize) -> Vec<&syn::Field> {
+        match self {
+            Input::Struct(s) => s.generated_fields(),
+            Input::Enum(e) => match e.variants.get(variant_index) {
+                Some(EnumVariant::Variant { options, .. }) => options.generated_fields(),
+                _ => Vec::new(),
+            },
+            Input::UnitStruct(_) | Input::UnitOnlyEnum(_) => Vec::new(),
+        }
+    }
+
     pub(crate) fn map(&self) -> &Map {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.map,
@@ -175,6 +259,14 @@ impl Input {
         }
     }
 
+    pub(crate) fn endian_var(&self) -> Option<&Ident> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.endian_var.as_ref(),
+            Input::Enum(en) => en.endian_var.as_ref(),
+            Input::UnitOnlyEnum(en) => en.endian_var.as_ref(),
+        }
+    }
+
     pub(crate) fn stream_ident(&self) -> Option<&Ident> {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => s.stream_ident.as_ref(),
@@ -203,22 +295,55 @@ attr_struct! {
     pub(crate) struct Struct {
         #[from(RW:Stream)]
         pub(crate) stream_ident: Option<Ident>,
-        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
+        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle, RW:DefaultBig, RW:DefaultLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:EndianVar)]
+        pub(crate) endian_var: Option<Ident>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
         #[from(RW:MapStream)]
         pub(crate) map_stream: Option<TokenStream>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
+        #[from(RW:BitOrder)]
+        pub(crate) bit_order: Option<TokenStream>,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
-        #[from(RO:PreAssert)]
+        #[from(RW:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(WO:AssertOffset)]
+        pub(crate) assert_offset: Option<TokenStream>,
+        #[from(WO:AssertAlign)]
+        pub(crate) assert_align: Option<TokenStream>,
+        #[from(RO:ImplTryFrom)]
+        pub(crate) impl_try_from: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:DynReader)]
+        pub(crate) dyn_reader: Option<()>,
+        #[from(RW:PreserveUnknown)]
+        pub(crate) preserve_unknown: Option<SpannedValue<Option<TokenStream>>>,
+        #[from(RO:WireEq)]
+        pub(crate) wire_eq: Option<SpannedValue<()>>,
+        #[from(RO:Dump)]
+        pub(crate) dump: Option<SpannedValue<()>>,
+        #[from(RO:Schema)]
+        pub(crate) schema: Option<SpannedValue<()>>,
+        #[from(RO:Fallback)]
+        pub(crate) fallback: Option<SpannedValue<()>>,
+        #[from(RO:TagValue)]
+        pub(crate) tag_value: Option<TokenStream>,
+        #[from(RW:Version)]
+        pub(crate) version: Option<TokenStream>,
+        #[from(RW:PosVar)]
+        pub(crate) pos_var: Option<Ident>,
+        #[from(RW:Strict)]
+        pub(crate) strict: Option<()>,
         pub(crate) fields: Vec<StructField>,
         pub(crate) for_write: bool,
+        pub(crate) magic_field: Option<usize>,
     }
 }
 
@@ -229,6 +354,34 @@ impl Struct {
             .map_or(false, |field| field.generated_ident)
     }
 
+    /// Returns true if the field at `index` is the first of a run of bit
+    /// fields.
+    pub(crate) fn starts_bit_fields(&self, index: usize) -> bool {
+        self.fields[index].bits.is_some() && (index == 0 || self.fields[index - 1].bits.is_none())
+    }
+
+    /// Returns true if the field at `index` is the last of a run of bit
+    /// fields.
+    pub(crate) fn ends_bit_fields(&self, index: usize) -> bool {
+        self.fields[index].bits.is_some()
+            && self
+                .fields
+                .get(index + 1)
+                .map_or(true, |field| field.bits.is_none())
+    }
+
+    /// Returns the fields generated to hold the magic which was read and the
+    /// unknown bytes of `preserve_unknown`.
+    pub(crate) fn generated_fields(&self) -> Vec<&syn::Field> {
+        let magic = self.magic_field.map(|index| &self.fields[index].field);
+        let unknown = self
+            .preserve_unknown
+            .is_some()
+            .then(|| self.fields.last().map(|field| &field.field))
+            .flatten();
+        magic.into_iter().chain(unknown).collect()
+    }
+
     pub(crate) fn iter_permanent_idents(&self) -> impl Iterator<Item = &syn::Ident> + '_ {
         self.fields.iter().filter_map(move |field| {
             if field.is_temp(self.for_write) {
@@ -241,12 +394,155 @@ impl Struct {
 
     pub(crate) fn has_no_attrs(&self) -> bool {
         matches!(self.endian, CondEndian::Inherited)
+            && self.endian_var.is_none()
+            && self.pos_var.is_none()
             && matches!(self.map, Map::None)
             && self.magic.is_none()
             && matches!(self.imports, Imports::None)
+            && self.assert_offset.is_none()
+            && self.assert_align.is_none()
+            && self.preserve_unknown.is_none()
+            && self.fallback.is_none()
+            && self.tag_value.is_none()
+            && self.bit_order.is_none()
             && self.fields.iter().all(StructField::has_no_attrs)
     }
 
+    /// Returns the index of the field with the given name.
+    pub(crate) fn field_index(&self, ident: &Ident) -> Option<usize> {
+        self.fields.iter().position(|field| field.ident == *ident)
+    }
+
+    fn validate_checksums(&self) -> syn::Result<()> {
+        for checksum in self
+            .fields
+            .iter()
+            .filter_map(|field| field.checksum.as_ref())
+        {
+            let index = |ident| {
+                self.field_index(ident).ok_or_else(|| {
+                    syn::Error::new(ident.span(), format!("no field named `{ident}`"))
+                })
+            };
+
+            if index(&checksum.first)? > index(&checksum.last)? {
+                return Err(syn::Error::new(
+                    checksum.last.span(),
+                    "the last field of a checksum range cannot come before the first field",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turns the `since` and `until` directives of each field into a
+    /// condition on the struct-level `version`.
+    fn apply_versions(&mut self) -> syn::Result<()> {
+        for field in &mut self.fields {
+            let (since, until) = (field.since.take(), field.until.take());
+            let span = match (&since, &until) {
+                (Some(since), _) => since.span(),
+                (None, Some(until)) => until.span(),
+                (None, None) => continue,
+            };
+
+            let Some(version) = &self.version else {
+                return Err(syn::Error::new(
+                    span,
+                    "`since` and `until` require a struct-level `version`",
+                ));
+            };
+
+            let condition = match (since, until) {
+                (Some(since), None) => quote::quote! { (#version) >= (#since) },
+                (None, Some(until)) => quote::quote! { (#version) < (#until) },
+                // The bounds may have different types, so they cannot always
+                // be written as a range
+                (Some(since), Some(until)) => quote::quote! {{
+                    #[allow(clippy::manual_range_contains)]
+                    let present = (#version) >= (#since) && (#version) < (#until);
+                    present
+                }},
+                (None, None) => unreachable!(),
+            };
+
+            match &mut field.if_cond {
+                Some(cond) if cond.flag.is_some() => {
+                    return Err(syn::Error::new(
+                        span,
+                        "`since` and `until` cannot be used with `present_if`",
+                    ));
+                }
+                Some(cond) => {
+                    let existing = &cond.condition;
+                    cond.condition = quote::quote! { (#existing) && #condition };
+                }
+                None => {
+                    field.if_cond = Some(Condition {
+                        condition,
+                        alternate: None,
+                        flag: None,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a field named by the `name` of a magic with alternatives, which
+    /// holds the alternative that was read so that the same one is written.
+    ///
+    /// A derive cannot add fields, and unit and tuple structs have no place to
+    /// put a named field, so these are left without one.
+    fn add_magic_field(&mut self, options: Options) -> syn::Result<()> {
+        let Some(magic) = &self.magic else {
+            return Ok(());
+        };
+        let Some(name) = magic.field_name() else {
+            return Ok(());
+        };
+        if options.derive || self.fields.is_empty() || self.is_tuple() {
+            return Ok(());
+        }
+
+        if self.field_index(name).is_some() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("the magic is stored in a field named `{name}`, so no other field can have that name"),
+            ));
+        }
+
+        let span = magic.span();
+        let ty = TokenStream::from(magic.kind());
+        let field = syn::Field {
+            attrs: Vec::new(),
+            vis: syn::Visibility::Inherited,
+            ident: Some(name.clone()),
+            colon_token: Some(syn::Token![:](span)),
+            ty: syn::parse_quote_spanned! {span=> #ty },
+        };
+        let (mut field, error) =
+            StructField::from_field(&field, self.fields.len(), options).unwrap_tuple();
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        // The magic is already read and written by the struct, so the field
+        // only takes its value when reading and is skipped when writing
+        field.field_mode = if options.write {
+            FieldMode::Default
+        } else {
+            FieldMode::Calc(name.to_token_stream())
+        };
+
+        self.magic_field = Some(self.fields.len());
+        self.fields.push(field);
+
+        Ok(())
+    }
+
     pub(crate) fn fields_pattern(&self) -> TokenStream {
         let fields = self.iter_permanent_idents();
 
@@ -274,7 +570,118 @@ impl<const WRITE: bool> FromInput<StructAttr<WRITE>> for Struct {
         self.for_write = options.write;
     }
 
+    fn finish_fields(&mut self, options: Options) -> syn::Result<()> {
+        self.validate_checksums()?;
+
+        if let Some(bit_order) = &self.bit_order {
+            for field in &mut self.fields {
+                if field.bits.is_some() && field.bit_order.is_none() {
+                    field.bit_order = Some(bit_order.clone());
+                }
+            }
+        }
+
+        self.apply_versions()?;
+        self.add_magic_field(options)?;
+
+        let Some(preserve_unknown) = &self.preserve_unknown else {
+            return Ok(());
+        };
+
+        if self.fields.is_empty() {
+            return Err(syn::Error::new(
+                preserve_unknown.span(),
+                "`preserve_unknown` requires at least one known field",
+            ));
+        }
+
+        // The unknown bytes go into a generated field which is also added to
+        // the type definition when the attributes are cleaned
+        let span = preserve_unknown.span();
+        let field = syn::Field {
+            attrs: vec![syn::parse_quote_spanned! {span=> #[doc(hidden)] }],
+            vis: syn::Visibility::Inherited,
+            ident: (!self.is_tuple()).then(|| UNKNOWN_FIELD.to_ident(span)),
+            colon_token: (!self.is_tuple()).then(|| syn::Token![:](span)),
+            ty: syn::parse_quote_spanned! {span=> #VEC<u8> },
+        };
+        let (mut field, error) =
+            StructField::from_field(&field, self.fields.len(), options).unwrap_tuple();
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        if !self.for_write {
+            let size = if let Some(size) = preserve_unknown.as_ref() {
+                quote::quote! { Some(#size) }
+            } else {
+                quote::quote! { None::<u64> }
+            };
+            field.field_mode = FieldMode::Function(READ_UNKNOWN.to_token_stream());
+            field.args =
+                PassedArgs::Tuple(SpannedValue::new(quote::quote! { (#POS, #size) }, span));
+        }
+
+        self.fields.push(field);
+
+        Ok(())
+    }
+
     fn validate(&self, options: Options) -> syn::Result<()> {
+        if let (Some(fallback), true) = (&self.fallback, self.magic.is_some()) {
+            return Err(syn::Error::new(
+                fallback.span(),
+                "a `fallback` variant is read when no other variant matches, so it cannot have a `magic`",
+            ));
+        }
+
+        // Struct-level assertions run before any field is written, so there is
+        // no position to give them
+        if let (Some(pos_var), true) = (&self.pos_var, options.write) {
+            if let Some(assert) = self
+                .assertions
+                .iter()
+                .find(|assert| assert.uses_ident(pos_var))
+            {
+                return Err(syn::Error::new(
+                    assert.kw_span,
+                    format!("`{pos_var}` is only available to a struct-level `assert` when reading; use `br(assert(...))` instead"),
+                ));
+            }
+        }
+
+        if options.write && self.magic_field.is_none() {
+            check_magic_written(&self.magic)?;
+        }
+
+        if let (Some(preserve_unknown), true) = (&self.preserve_unknown, options.derive) {
+            return Err(syn::Error::new(
+                preserve_unknown.span(),
+                if options.write {
+                    "`#[derive(BinWrite)]` cannot add the field for `preserve_unknown`; use `#[binrw]` or `#[binwrite]` instead"
+                } else {
+                    "`#[derive(BinRead)]` cannot add the field for `preserve_unknown`; use `#[binrw]` or `#[binread]` instead"
+                },
+            ));
+        }
+
+        for (directive, value) in [
+            ("wire_eq", &self.wire_eq),
+            ("dump", &self.dump),
+            ("schema", &self.schema),
+        ] {
+            if let Some(value) = value {
+                if self.map.is_some() || self.map_stream.is_some() {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!(
+                            "`{directive}` cannot be used with a struct-level `map` or `map_stream`"
+                        ),
+                    ));
+                }
+            }
+        }
+
         if self.map.is_none() && !options.derive {
             return Ok(());
         }
@@ -310,8 +717,10 @@ attr_struct! {
         pub(crate) ident: Option<syn::Ident>,
         #[from(RW:Stream)]
         pub(crate) stream_ident: Option<Ident>,
-        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
+        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle, RW:DefaultBig, RW:DefaultLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:EndianVar)]
+        pub(crate) endian_var: Option<Ident>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
         #[from(RW:MapStream)]
@@ -322,14 +731,43 @@ attr_struct! {
         pub(crate) imports: Imports,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
-        #[from(RO:PreAssert)]
+        #[from(RW:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
         #[from(RO:ReturnAllErrors, RO:ReturnUnexpectedError)]
         pub(crate) error_mode: EnumErrorMode,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
+        #[from(RO:Visitor)]
+        pub(crate) visitor: Option<()>,
+        #[from(RO:Dump)]
+        pub(crate) dump: Option<()>,
+        #[from(RO:Schema)]
+        pub(crate) schema: Option<()>,
+        #[from(RO:ImplTryFrom)]
+        pub(crate) impl_try_from: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:DynReader)]
+        pub(crate) dyn_reader: Option<()>,
+        #[from(RW:Strict)]
+        pub(crate) strict: Option<()>,
         pub(crate) variants: Vec<EnumVariant>,
     }
 }
 
+impl Enum {
+    /// Returns the variants in the order they are tried when reading, which
+    /// is the order they are declared in except for a `fallback` variant,
+    /// which is always tried last.
+    pub(crate) fn variants_in_read_order(&self) -> impl Iterator<Item = &EnumVariant> + '_ {
+        let (fallback, variants) = self
+            .variants
+            .iter()
+            .partition::<Vec<_>, _>(|variant| variant.is_fallback());
+        variants.into_iter().chain(fallback)
+    }
+}
+
 impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
     type Field = EnumVariant;
 
@@ -338,7 +776,31 @@ impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
         Ok(())
     }
 
-    fn validate(&self, _: Options) -> syn::Result<()> {
+    fn validate(&self, options: Options) -> syn::Result<()> {
+        if options.write {
+            check_magic_written(&self.magic)?;
+        }
+
+        let mut fallbacks = self.variants.iter().filter_map(|variant| match variant {
+            EnumVariant::Variant { options, .. } => options.fallback.as_ref(),
+            EnumVariant::Unit(_) => None,
+        });
+        if let Some(fallback) = fallbacks.nth(1) {
+            return Err(syn::Error::new(
+                fallback.span(),
+                "an enum can only have one `fallback` variant",
+            ));
+        }
+
+        if self.tag.is_none() {
+            if let Some(tag_value) = self.variants.iter().find_map(EnumVariant::tag_value) {
+                return Err(syn::Error::new(
+                    tag_value.span(),
+                    "`tag_value` requires `tag` on the enum",
+                ));
+            }
+        }
+
         if self.map.is_some() {
             if let Some(variant) = self.variants.iter().find(|variant| !variant.has_no_attrs()) {
                 return Err(syn::Error::new(
@@ -357,18 +819,35 @@ attr_struct! {
     pub(crate) struct UnitOnlyEnum {
         #[from(RW:Stream)]
         pub(crate) stream_ident: Option<Ident>,
-        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
+        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle, RW:DefaultBig, RW:DefaultLittle)]
         pub(crate) endian: CondEndian,
+        #[from(RW:EndianVar)]
+        pub(crate) endian_var: Option<Ident>,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
+        #[from(RO:ReprErr)]
+        pub(crate) repr_err: Option<TokenStream>,
         #[from(RW:MapStream)]
         pub(crate) map_stream: Option<TokenStream>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
+        #[from(RO:ImplTryFrom)]
+        pub(crate) impl_try_from: Option<()>,
+        #[from(RO:ErrorType)]
+        pub(crate) error_type: Option<TokenStream>,
+        #[from(RO:DynReader)]
+        pub(crate) dyn_reader: Option<()>,
+        #[from(RO:Dump)]
+        pub(crate) dump: Option<()>,
+        #[from(RO:Schema)]
+        pub(crate) schema: Option<()>,
+        #[from(RW:Strict)]
+        pub(crate) strict: Option<()>,
         pub(crate) fields: Vec<UnitEnumField>,
         pub(crate) is_magic_enum: bool,
+        pub(crate) discriminant: Option<Ident>,
     }
 }
 
@@ -376,12 +855,71 @@ impl UnitOnlyEnum {
     pub(crate) fn is_magic_enum(&self) -> bool {
         self.is_magic_enum
     }
+
+    /// Returns the type which a `repr` value is converted to before it is
+    /// compared with the discriminants of the variants, or `None` if the
+    /// `repr` is a primitive integer which is compared directly.
+    pub(crate) fn repr_discriminant(&self) -> Option<TokenStream> {
+        let repr = self.map.as_repr()?;
+        if is_primitive_int(&repr.to_string()) {
+            None
+        } else {
+            Some(
+                self.discriminant
+                    .as_ref()
+                    .map_or_else(|| quote::quote! { isize }, ToTokens::to_token_stream),
+            )
+        }
+    }
+}
+
+fn is_primitive_int(ty: &str) -> bool {
+    [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ]
+    .contains(&ty)
+}
+
+/// Returns the integer type given in the `#[repr]` attribute of an enum,
+/// which is the type of its discriminants.
+fn discriminant_type(attrs: &[syn::Attribute]) -> Option<Ident> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+            )
+            .ok()
+        })
+        .flatten()
+        .find_map(|meta| match meta {
+            syn::Meta::Path(path) => path
+                .get_ident()
+                .filter(|ident| is_primitive_int(&ident.to_string()))
+                .cloned(),
+            _ => None,
+        })
 }
 
 impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
     type Field = UnitEnumField;
 
     fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
+        if let Some(tag_value) = &field.tag_value {
+            return Err(syn::Error::new(
+                tag_value.span(),
+                "`tag_value` can only be used in an enum with at least one data variant",
+            ));
+        }
+
+        if let Some(magic) = field.magic.as_ref().filter(|magic| magic.name().is_some()) {
+            return Err(syn::Error::new(
+                magic.span(),
+                "the magic of a unit variant cannot be named",
+            ));
+        }
+
         if let (Some(repr), Some(magic)) = (self.map.as_repr(), field.magic.as_ref()) {
             let magic_span = magic.span();
             let span = magic_span.join(repr.span()).unwrap_or(magic_span);
@@ -397,6 +935,20 @@ impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
     }
 
     fn validate(&self, options: Options) -> syn::Result<()> {
+        if options.write {
+            check_magic_written(&self.magic)?;
+            for field in &self.fields {
+                check_magic_written(&field.magic)?;
+            }
+        }
+
+        if let (Some(repr_err), None) = (&self.repr_err, self.map.as_repr()) {
+            return Err(syn::Error::new(
+                repr_err.span(),
+                "`repr_err` requires `repr`",
+            ));
+        }
+
         if self.map.as_repr().is_some() || self.is_magic_enum() {
             Ok(())
         } else if options.write {
@@ -1,6 +1,7 @@
 use crate::{
     binrw::{codegen::sanitization::THIS, parser::attrs},
     meta_types::KeywordToken,
+    util::ident_is_used_in,
 };
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
@@ -23,6 +24,14 @@ pub(crate) struct Assert {
     pub(crate) consequent: Error,
 }
 
+impl Assert {
+    /// Returns true if `ident` is used by the condition or the error.
+    pub(crate) fn uses_ident(&self, ident: &Ident) -> bool {
+        let (Error::Message(error) | Error::Error(error)) = &self.consequent;
+        ident_is_used_in(ident, &self.condition) || ident_is_used_in(ident, error)
+    }
+}
+
 impl<K: Parse + Spanned + Token> TryFrom<attrs::AssertLike<K>> for Assert {
     type Error = syn::Error;
 
@@ -0,0 +1,26 @@
+use super::SpannedValue;
+use crate::{binrw::parser::attrs, meta_types::KeywordToken};
+use syn::Lit;
+
+/// The number of bits used by a bit field.
+pub(crate) type Bits = Option<SpannedValue<u32>>;
+
+impl TryFrom<attrs::Bits> for SpannedValue<u32> {
+    type Error = syn::Error;
+
+    fn try_from(bits: attrs::Bits) -> Result<Self, Self::Error> {
+        let len = match &bits.value {
+            Lit::Int(int) => int.base10_parse::<u32>().ok(),
+            _ => None,
+        }
+        .filter(|len| (1..=64).contains(len))
+        .ok_or_else(|| {
+            syn::Error::new(
+                bits.value.span(),
+                "expected an integer literal between 1 and 64",
+            )
+        })?;
+
+        Ok(Self::new(len, bits.keyword_span()))
+    }
+}
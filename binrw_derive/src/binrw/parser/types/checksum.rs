@@ -0,0 +1,81 @@
+use crate::binrw::parser::attrs;
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{spanned::Spanned, Expr, ExprPath, ExprRange, Ident, RangeLimits};
+
+#[derive(Debug, Clone)]
+pub(crate) struct Checksum {
+    pub(crate) function: TokenStream,
+    /// The first field included in the checksum.
+    pub(crate) first: Ident,
+    /// The last field included in the checksum.
+    pub(crate) last: Ident,
+}
+
+impl TryFrom<attrs::Checksum> for Checksum {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::Checksum) -> Result<Self, Self::Error> {
+        let mut args = value.fields.iter();
+
+        let (Some(function), Some(over)) = (args.next(), args.next()) else {
+            return Err(Self::Error::new(
+                value.ident.span(),
+                "`checksum` requires a checksum function and an `over = first..=last` range of fields as arguments",
+            ));
+        };
+
+        super::assert_all_args_consumed(args, value.ident.span())?;
+
+        let (first, last) = parse_over(over)?;
+
+        Ok(Self {
+            function: function.into_token_stream(),
+            first,
+            last,
+        })
+    }
+}
+
+fn parse_over(over: &Expr) -> syn::Result<(Ident, Ident)> {
+    let range = match over {
+        Expr::Assign(assign) if is_ident(&assign.left, "over") => &*assign.right,
+        _ => {
+            return Err(syn::Error::new(
+                over.span(),
+                "expected `over = first..=last` or `over = field`",
+            ))
+        }
+    };
+
+    match range {
+        Expr::Range(ExprRange {
+            from: Some(start),
+            to: Some(end),
+            limits: RangeLimits::Closed(_),
+            ..
+        }) => Ok((field_ident(start)?, field_ident(end)?)),
+        Expr::Range(range) => Err(syn::Error::new(
+            range.span(),
+            "expected an inclusive range of fields like `first..=last`",
+        )),
+        _ => {
+            let field = field_ident(range)?;
+            Ok((field.clone(), field))
+        }
+    }
+}
+
+fn field_ident(expr: &Expr) -> syn::Result<Ident> {
+    match expr {
+        Expr::Path(ExprPath {
+            path, qself: None, ..
+        }) => path.get_ident().cloned(),
+        _ => None,
+    }
+    .ok_or_else(|| syn::Error::new(expr.span(), "expected a field name"))
+}
+
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Path(ExprPath { path, qself: None, .. }) if path.is_ident(name))
+}
@@ -32,19 +32,16 @@ impl ToTokens for Endian {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum CondEndian {
+    #[default]
     Inherited,
     Fixed(Endian),
+    /// A byte order which is only used when the caller does not give one.
+    Default(Endian),
     Cond(Endian, TokenStream),
 }
 
-impl Default for CondEndian {
-    fn default() -> Self {
-        Self::Inherited
-    }
-}
-
 impl From<attrs::Big> for CondEndian {
     fn from(_: attrs::Big) -> Self {
         Self::Fixed(Endian::Big)
@@ -57,6 +54,18 @@ impl From<attrs::Little> for CondEndian {
     }
 }
 
+impl From<attrs::DefaultBig> for CondEndian {
+    fn from(_: attrs::DefaultBig) -> Self {
+        Self::Default(Endian::Big)
+    }
+}
+
+impl From<attrs::DefaultLittle> for CondEndian {
+    fn from(_: attrs::DefaultLittle) -> Self {
+        Self::Default(Endian::Little)
+    }
+}
+
 impl From<attrs::IsBig> for CondEndian {
     fn from(is_big: attrs::IsBig) -> Self {
         Self::Cond(Endian::Big, is_big.value.to_token_stream())
@@ -1,12 +1,16 @@
-use crate::binrw::parser::attrs;
+use crate::binrw::{codegen::sanitization::FLAG_PRESENT, parser::attrs};
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::spanned::Spanned;
 
+// Lint: `condition` is the clearest name for the condition of a `Condition`.
+#[allow(clippy::struct_field_names)]
 #[derive(Debug, Clone)]
 pub(crate) struct Condition {
     pub(crate) condition: TokenStream,
     pub(crate) alternate: Option<TokenStream>,
+    /// The flags and mask expressions of a `present_if` condition.
+    pub(crate) flag: Option<(TokenStream, TokenStream)>,
 }
 
 impl TryFrom<attrs::If> for Condition {
@@ -31,6 +35,32 @@ impl TryFrom<attrs::If> for Condition {
         Ok(Self {
             condition,
             alternate,
+            flag: None,
+        })
+    }
+}
+
+impl TryFrom<attrs::PresentIf> for Condition {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::PresentIf) -> Result<Self, Self::Error> {
+        let mut args = value.fields.iter();
+
+        let (flags, mask) = if let (Some(flags), Some(mask)) = (args.next(), args.next()) {
+            (flags.into_token_stream(), mask.into_token_stream())
+        } else {
+            return Err(Self::Error::new(
+                value.ident.span(),
+                "`present_if` requires a flags expression and a mask as arguments",
+            ));
+        };
+
+        super::assert_all_args_consumed(args, value.ident.span())?;
+
+        Ok(Self {
+            condition: quote! { #FLAG_PRESENT(#flags, #mask) },
+            alternate: None,
+            flag: Some((flags, mask)),
         })
     }
 }
@@ -3,19 +3,14 @@ use crate::{
     meta_types::KeywordToken,
 };
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
 pub(crate) enum EnumErrorMode {
+    #[default]
     Default,
     ReturnAllErrors,
     ReturnUnexpectedError,
 }
 
-impl Default for EnumErrorMode {
-    fn default() -> Self {
-        Self::Default
-    }
-}
-
 impl From<attrs::ReturnAllErrors> for EnumErrorMode {
     fn from(_: attrs::ReturnAllErrors) -> Self {
         Self::ReturnAllErrors
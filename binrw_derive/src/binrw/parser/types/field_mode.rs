@@ -5,8 +5,9 @@ use crate::{
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum FieldMode {
+    #[default]
     Normal,
     Default,
     Calc(TokenStream),
@@ -14,12 +15,6 @@ pub(crate) enum FieldMode {
     Function(TokenStream),
 }
 
-impl Default for FieldMode {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl From<attrs::Ignore> for FieldMode {
     fn from(_: attrs::Ignore) -> Self {
         Self::Default
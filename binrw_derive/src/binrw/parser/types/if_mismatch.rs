@@ -0,0 +1,30 @@
+use crate::binrw::parser::attrs;
+
+/// What to do when writing a field whose `if` condition does not match whether
+/// its value is present.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum IfMismatch {
+    /// Skip the field whenever the condition is false.
+    Skip,
+    /// Return an error if the presence of the value does not match the
+    /// condition.
+    Error,
+    /// Write the value whenever it is present.
+    WriteAnyway,
+}
+
+impl TryFrom<attrs::Else> for IfMismatch {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::Else) -> Result<Self, <Self as TryFrom<attrs::Else>>::Error> {
+        match value.value.to_string().as_str() {
+            "skip" => Ok(Self::Skip),
+            "error" => Ok(Self::Error),
+            "write_anyway" => Ok(Self::WriteAnyway),
+            _ => Err(syn::Error::new(
+                value.value.span(),
+                "expected one of `skip`, `error`, or `write_anyway`",
+            )),
+        }
+    }
+}
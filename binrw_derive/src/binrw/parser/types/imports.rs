@@ -1,6 +1,6 @@
 use crate::{
     binrw::parser::{attrs, TrySet},
-    meta_types::{Enclosure, IdentPatType, IdentTypeMaybeDefault, KeywordToken},
+    meta_types::{Enclosure, IdentTypeMaybeDefault, KeywordToken},
 };
 use syn::{Ident, Type};
 
@@ -9,49 +9,70 @@ pub(crate) enum Imports {
     #[default]
     None,
     Raw(Ident, Box<Type>),
-    List(Vec<Ident>, Vec<Type>),
+    List(Vec<IdentTypeMaybeDefault>),
     Named(Vec<IdentTypeMaybeDefault>),
 }
 
-fn imports_from_attr(list: Enclosure<IdentPatType, IdentTypeMaybeDefault>) -> Imports {
+impl Imports {
+    /// Returns true if any argument of an `import(...)` list has a default
+    /// value.
+    pub(crate) fn has_list_defaults(&self) -> bool {
+        matches!(self, Imports::List(args) if args.iter().any(|arg| arg.default.is_some()))
+    }
+}
+
+fn imports_from_attr(
+    list: Enclosure<IdentTypeMaybeDefault, IdentTypeMaybeDefault>,
+) -> syn::Result<Imports> {
     match list {
         Enclosure::Paren { fields, .. } => {
             if fields.is_empty() {
-                Imports::None
+                Ok(Imports::None)
             } else {
-                let (idents, tys) = fields
-                    .into_iter()
-                    .map(|field| (field.ident, field.ty))
-                    .unzip();
-                Imports::List(idents, tys)
+                let args = fields.into_iter().collect::<Vec<_>>();
+                if let Some(arg) = args
+                    .iter()
+                    .skip_while(|arg| arg.default.is_none())
+                    .find(|arg| arg.default.is_none())
+                {
+                    return Err(syn::Error::new(
+                        arg.ident.span(),
+                        "arguments without a default value must come before arguments with one",
+                    ));
+                }
+                Ok(Imports::List(args))
             }
         }
         Enclosure::Brace { fields, .. } => {
             if fields.is_empty() {
-                Imports::None
+                Ok(Imports::None)
             } else {
-                Imports::Named(fields.into_iter().collect())
+                Ok(Imports::Named(fields.into_iter().collect()))
             }
         }
     }
 }
 
-impl From<attrs::Import> for Imports {
-    fn from(value: attrs::Import) -> Self {
+impl TryFrom<attrs::Import> for Imports {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::Import) -> syn::Result<Self> {
         imports_from_attr(value.list)
     }
 }
 
-impl From<attrs::ImportRaw> for Imports {
-    fn from(value: attrs::ImportRaw) -> Self {
-        Imports::Raw(value.value.ident, value.value.ty.into())
+impl TryFrom<attrs::ImportRaw> for Imports {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::ImportRaw) -> syn::Result<Self> {
+        Ok(Imports::Raw(value.value.ident, value.value.ty.into()))
     }
 }
 
-impl<T: Into<Imports> + KeywordToken> TrySet<Imports> for T {
+impl<T: TryInto<Imports, Error = syn::Error> + KeywordToken> TrySet<Imports> for T {
     fn try_set(self, to: &mut Imports) -> syn::Result<()> {
         if matches!(*to, Imports::None) {
-            *to = self.into();
+            *to = self.try_into()?;
             Ok(())
         } else {
             Err(syn::Error::new(
@@ -1,8 +1,15 @@
 use super::SpannedValue;
-use crate::{binrw::parser::attrs, meta_types::KeywordToken};
+use crate::{
+    binrw::parser::{attrs, keywords as kw},
+    meta_types::KeywordToken,
+};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Lit;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Ident, Lit, Token,
+};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
 pub(crate) enum Kind {
@@ -24,80 +31,216 @@ impl From<&Kind> for TokenStream {
 pub(crate) type Magic = Option<SpannedValue<Inner>>;
 
 #[derive(Clone, Debug)]
-pub(crate) struct Inner(Kind, TokenStream);
+pub(crate) struct Inner {
+    kind: Kind,
+    values: Vec<TokenStream>,
+    mask: Option<TokenStream>,
+    name: Option<Ident>,
+}
 
 impl Inner {
     pub(crate) fn add_ref(&self) -> TokenStream {
-        match &self.0 {
+        match &self.kind {
             Kind::ByteStr(_) => quote! { & },
             Kind::Numeric(_) => TokenStream::new(),
         }
     }
 
+    /// Returns the value of the magic, which is the first of the alternatives.
     pub(crate) fn deref_value(&self) -> TokenStream {
-        match self.0 {
-            Kind::ByteStr(_) => {
-                let value = &self.1;
-                quote! { *#value }
-            }
-            Kind::Numeric(_) => self.1.clone(),
+        self.deref(&self.values[0])
+    }
+
+    /// Returns every value which is accepted when reading, as values like
+    /// [`Self::deref_value`].
+    pub(crate) fn deref_values(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.values.iter().map(|value| self.deref(value))
+    }
+
+    fn deref(&self, value: &TokenStream) -> TokenStream {
+        match self.kind {
+            Kind::ByteStr(_) => quote! { *#value },
+            Kind::Numeric(_) => value.clone(),
         }
     }
 
+    /// Returns true if reading the magic needs to return the value which was
+    /// read, because there are alternatives or the value is named.
+    pub(crate) fn is_any(&self) -> bool {
+        self.has_alternatives() || self.name.is_some()
+    }
+
+    /// Returns true if more than one value is accepted when reading.
+    pub(crate) fn has_alternatives(&self) -> bool {
+        self.values.len() > 1
+    }
+
+    /// Returns the name of the field which stores the magic which was read, if
+    /// it needs one to know which alternative to write.
+    pub(crate) fn field_name(&self) -> Option<&Ident> {
+        self.name.as_ref().filter(|_| self.has_alternatives())
+    }
+
     pub(crate) fn kind(&self) -> &Kind {
-        &self.0
+        &self.kind
     }
 
     pub(crate) fn match_value(&self) -> &TokenStream {
-        &self.1
+        &self.values[0]
+    }
+
+    pub(crate) fn match_values(&self) -> &[TokenStream] {
+        &self.values
+    }
+
+    /// Returns the mask of the bits which are compared, if only some of them
+    /// are, as a value like [`Self::deref_value`].
+    pub(crate) fn deref_mask(&self) -> Option<TokenStream> {
+        self.mask.as_ref().map(|mask| self.deref(mask))
+    }
+
+    /// Returns the name of the variable which holds the magic which was read.
+    pub(crate) fn name(&self) -> Option<&Ident> {
+        self.name.as_ref()
     }
 
     #[cfg(feature = "verbose-backtrace")]
-    pub(crate) fn into_match_value(self) -> TokenStream {
-        self.1
+    pub(crate) fn into_match_value(mut self) -> TokenStream {
+        self.values.swap_remove(0)
     }
 }
 
+/// Returns an error if a magic with alternatives is written without a field
+/// which stores the alternative that was read.
+pub(crate) fn check_magic_written(magic: &Magic) -> syn::Result<()> {
+    match magic {
+        Some(magic) if magic.has_alternatives() => Err(syn::Error::new(
+            magic.span(),
+            "a `magic` with alternatives can only be written when it has a `name` on a `#[binrw]` or `#[binwrite]` struct or variant with named fields; use `bw(magic = ...)` to choose the magic to write",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The value of a `magic` directive: one or more literals separated by `|`,
+/// with optional `mask = ...` and `name = ...` after them.
+#[derive(Clone, Debug)]
+pub(crate) struct MagicLit {
+    values: Vec<Lit>,
+    mask: Option<Lit>,
+    name: Option<Ident>,
+}
+
+impl Parse for MagicLit {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let mut values = vec![input.parse()?];
+        while input.peek(Token![|]) {
+            input.parse::<Token![|]>()?;
+            values.push(input.parse()?);
+        }
+
+        let mut mask = None;
+        let mut name = None;
+        while input.peek(Token![,]) && (input.peek2(kw::mask) || input.peek2(kw::name)) {
+            input.parse::<Token![,]>()?;
+            if input.peek(kw::mask) {
+                input.parse::<kw::mask>()?;
+                input.parse::<Token![=]>()?;
+                mask = Some(input.parse()?);
+            } else {
+                input.parse::<kw::name>()?;
+                input.parse::<Token![=]>()?;
+                name = Some(input.parse()?);
+            }
+        }
+
+        Ok(Self { values, mask, name })
+    }
+}
+
+fn check_mask(value: &Lit, mask: &Lit) -> syn::Result<()> {
+    match (value, mask) {
+        (Lit::ByteStr(value), Lit::ByteStr(mask)) if value.value().len() == mask.value().len() => {
+            Ok(())
+        }
+        (Lit::ByteStr(_), Lit::ByteStr(_)) => Err(syn::Error::new(
+            mask.span(),
+            "a magic mask must be the same length as the magic",
+        )),
+        (Lit::Byte(_) | Lit::Int(_), Lit::Byte(_) | Lit::Int(_)) => Ok(()),
+        (Lit::ByteStr(_) | Lit::Byte(_) | Lit::Int(_), _) => Err(syn::Error::new(
+            mask.span(),
+            "a magic mask must be the same kind of literal as the magic",
+        )),
+        _ => Err(syn::Error::new(
+            mask.span(),
+            "only a byte string, byte, or int magic can have a mask",
+        )),
+    }
+}
+
+fn lit_kind(value: &Lit) -> syn::Result<Kind> {
+    Ok(match value {
+        Lit::ByteStr(bytes) => Kind::ByteStr(format!("[u8; {}]", bytes.value().len())),
+        Lit::Byte(_) => Kind::Numeric("u8".to_owned()),
+        Lit::Int(i) => {
+            if i.suffix().is_empty() {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!("expected explicit type suffix for integer literal\ne.g {i}u64"),
+                ));
+            }
+            Kind::Numeric(i.suffix().to_owned())
+        }
+        Lit::Float(f) => {
+            if f.suffix().is_empty() {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!(
+                        "expected explicit type suffix for float literal\nvalid values are {f}f32 or {f}f64",
+                    ),
+                ));
+            }
+            Kind::Numeric(f.suffix().to_owned())
+        }
+        Lit::Char(_) | Lit::Str(_) | Lit::Bool(_) | Lit::Verbatim(_) => {
+            return Err(syn::Error::new(
+                value.span(),
+                "expected byte string, byte, float, or int",
+            ))
+        }
+    })
+}
+
 impl TryFrom<attrs::Magic> for SpannedValue<Inner> {
     type Error = syn::Error;
 
     fn try_from(magic: attrs::Magic) -> Result<Self, Self::Error> {
-        let value = &magic.value;
-
-        let kind = match &value {
-            Lit::ByteStr(bytes) => Kind::ByteStr(format!("[u8; {}]", bytes.value().len())),
-            Lit::Byte(_) => Kind::Numeric("u8".to_owned()),
-            Lit::Int(i) => {
-                if i.suffix().is_empty() {
-                    return Err(syn::Error::new(
-                        value.span(),
-                        format!("expected explicit type suffix for integer literal\ne.g {i}u64",),
-                    ));
-                }
-                Kind::Numeric(i.suffix().to_owned())
-            }
-            Lit::Float(f) => {
-                if f.suffix().is_empty() {
-                    return Err(syn::Error::new(
-                        value.span(),
-                        format!(
-                            "expected explicit type suffix for float literal\nvalid values are {f}f32 or {f}f64",
-                        ),
-                    ));
-                }
-                Kind::Numeric(f.suffix().to_owned())
+        let span = magic.keyword_span();
+        let MagicLit { values, mask, name } = magic.value;
+
+        let kind = lit_kind(&values[0])?;
+        for value in &values {
+            if let Some(mask) = &mask {
+                check_mask(value, mask)?;
             }
-            Lit::Char(_) | Lit::Str(_) | Lit::Bool(_) | Lit::Verbatim(_) => {
+
+            if lit_kind(value)? != kind {
                 return Err(syn::Error::new(
                     value.span(),
-                    "expected byte string, byte, float, or int",
-                ))
+                    "all alternative magics must have the same type",
+                ));
             }
-        };
+        }
 
         Ok(Self::new(
-            Inner(kind, value.to_token_stream()),
-            magic.keyword_span(),
+            Inner {
+                kind,
+                values: values.iter().map(ToTokens::to_token_stream).collect(),
+                mask: mask.as_ref().map(ToTokens::to_token_stream),
+                name,
+            },
+            span,
         ))
     }
 }
@@ -7,8 +7,9 @@ use quote::ToTokens;
 
 // Lint: Makes code less clear
 #[allow(clippy::enum_variant_names)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum Map {
+    #[default]
     None,
     Map(TokenStream),
     Try(TokenStream),
@@ -36,12 +37,6 @@ impl Map {
     }
 }
 
-impl Default for Map {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl From<attrs::Map> for Map {
     fn from(map: attrs::Map) -> Self {
         Self::Map(map.value.to_token_stream())
@@ -1,9 +1,12 @@
 mod assert;
+mod bits;
+mod checksum;
 mod cond_endian;
 mod condition;
 mod enum_error_mode;
 mod err_context;
 mod field_mode;
+mod if_mismatch;
 mod imports;
 mod magic;
 mod map;
@@ -11,13 +14,16 @@ mod passed_args;
 mod spanned_value;
 
 pub(crate) use assert::{Assert, Error as AssertionError};
+pub(crate) use bits::Bits;
+pub(crate) use checksum::Checksum;
 pub(crate) use cond_endian::CondEndian;
 pub(crate) use condition::Condition;
 pub(crate) use enum_error_mode::EnumErrorMode;
 pub(crate) use err_context::ErrContext;
 pub(crate) use field_mode::FieldMode;
+pub(crate) use if_mismatch::IfMismatch;
 pub(crate) use imports::Imports;
-pub(crate) use magic::Magic;
+pub(crate) use magic::{check_magic_written, Magic, MagicLit};
 pub(crate) use map::Map;
 pub(crate) use passed_args::PassedArgs;
 pub(crate) use spanned_value::SpannedValue;
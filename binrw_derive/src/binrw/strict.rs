@@ -0,0 +1,331 @@
+//! Extra checks which are enabled by the `strict` directive.
+//!
+//! These report code which compiles but is probably a mistake, such as an
+//! imported argument which is never used. Since a procedural macro cannot emit
+//! warnings on stable Rust, each problem is reported as an error.
+
+use super::{
+    is_binread_attr, is_binwrite_attr,
+    parser::{EnumVariant, FieldMode, Imports, Input, ParseResult, Struct},
+};
+use crate::combine_error;
+use proc_macro2::{Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{DeriveInput, Ident};
+
+/// Adds an error to a parse result, keeping the parsed input so that an
+/// implementation is still generated.
+pub(super) fn add_error(
+    result: ParseResult<Input>,
+    error: Option<syn::Error>,
+) -> ParseResult<Input> {
+    match (result, error) {
+        (ParseResult::Ok(input), Some(error)) => ParseResult::Partial(input, error),
+        (ParseResult::Partial(input, mut all_errors), Some(error)) => {
+            all_errors.combine(error);
+            ParseResult::Partial(input, all_errors)
+        }
+        (result, _) => result,
+    }
+}
+
+/// Reports imported arguments which are not used by any directive.
+///
+/// Arguments whose names start with an underscore are not reported.
+pub(super) fn unused_imports(
+    derive_input: &DeriveInput,
+    input: &Input,
+    write: bool,
+) -> Option<syn::Error> {
+    let imports = match input.imports() {
+        Imports::None => return None,
+        Imports::Raw(ident, _) => vec![ident],
+        Imports::List(args) | Imports::Named(args) => args.iter().map(|arg| &arg.ident).collect(),
+    };
+
+    let mut tokens = TokenStream::new();
+    for attr in all_attrs(derive_input).filter(|attr| is_for_side(attr, write)) {
+        tokens.extend(without_imports(attr.tokens.clone()));
+    }
+    // A default value can use an earlier argument
+    if let Imports::List(args) | Imports::Named(args) = input.imports() {
+        tokens.extend(args.iter().map(|arg| arg.default.to_token_stream()));
+    }
+
+    let mut all_errors = None;
+    for ident in imports {
+        if !ident.to_string().starts_with('_') && find_ident(tokens.clone(), ident, false).is_none()
+        {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(
+                    ident.span(),
+                    format!("imported argument `{ident}` is never used; if this is intentional, prefix it with an underscore"),
+                ),
+            );
+        }
+    }
+    all_errors
+}
+
+/// Reports attributes for the other trait, which are ignored when only one of
+/// `#[binread]` or `#[binwrite]` is used.
+pub(super) fn ignored_attrs(derive_input: &DeriveInput, write: bool) -> Option<syn::Error> {
+    let (ignored, used) = if write {
+        ("br", "binwrite")
+    } else {
+        ("bw", "binread")
+    };
+
+    let mut all_errors = None;
+    for attr in all_attrs(derive_input).filter(|attr| attr.path.is_ident(ignored)) {
+        combine_error(
+            &mut all_errors,
+            syn::Error::new(
+                attr.path
+                    .get_ident()
+                    .map_or_else(Span::call_site, Ident::span),
+                format!("`#[{ignored}]` has no effect with `#[{used}]`; use `#[binrw]` instead"),
+            ),
+        );
+    }
+    all_errors
+}
+
+/// Reports write directives which use a `#[br(temp)]` field that is not
+/// available when writing because it is also `#[bw(ignore)]`.
+pub(super) fn unavailable_temp_fields(write_input: &Input) -> Option<syn::Error> {
+    let mut all_errors = None;
+    match write_input {
+        Input::Struct(st) => {
+            if let Some(error) = unavailable_temp_fields_struct(st) {
+                combine_error(&mut all_errors, error);
+            }
+        }
+        Input::Enum(en) => {
+            for variant in &en.variants {
+                if let EnumVariant::Variant { options, .. } = variant {
+                    if let Some(error) = unavailable_temp_fields_struct(options) {
+                        combine_error(&mut all_errors, error);
+                    }
+                }
+            }
+        }
+        Input::UnitStruct(_) | Input::UnitOnlyEnum(_) => {}
+    }
+    all_errors
+}
+
+fn unavailable_temp_fields_struct(st: &Struct) -> Option<syn::Error> {
+    let unavailable = st
+        .fields
+        .iter()
+        .filter(|field| field.is_temp(true) && matches!(field.field_mode, FieldMode::Default))
+        .map(|field| &field.ident)
+        .collect::<Vec<_>>();
+
+    let mut all_errors = None;
+    for field in &st.fields {
+        for attr in field
+            .field
+            .attrs
+            .iter()
+            .filter(|attr| is_binwrite_attr(attr))
+        {
+            for ident in &unavailable {
+                if let Some(span) = find_ident(attr.tokens.clone(), ident, true) {
+                    combine_error(
+                        &mut all_errors,
+                        syn::Error::new(
+                            span,
+                            format!("`{ident}` is not available when writing because it is `#[br(temp)]` and `#[bw(ignore)]`; use `#[bw(calc)]` on it instead"),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    all_errors
+}
+
+fn all_attrs(derive_input: &DeriveInput) -> impl Iterator<Item = &syn::Attribute> {
+    let fields = match &derive_input.data {
+        syn::Data::Struct(st) => st.fields.iter().collect::<Vec<_>>(),
+        syn::Data::Enum(en) => en.variants.iter().flat_map(|v| &v.fields).collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+    let variant_attrs = match &derive_input.data {
+        syn::Data::Enum(en) => en.variants.iter().flat_map(|v| &v.attrs).collect(),
+        syn::Data::Struct(_) | syn::Data::Union(_) => Vec::new(),
+    };
+
+    derive_input
+        .attrs
+        .iter()
+        .chain(variant_attrs)
+        .chain(fields.into_iter().flat_map(|field| &field.attrs))
+}
+
+fn is_for_side(attr: &syn::Attribute, write: bool) -> bool {
+    if write {
+        is_binwrite_attr(attr)
+    } else {
+        is_binread_attr(attr)
+    }
+}
+
+/// Removes the `import` and `import_raw` directives from the tokens of an
+/// attribute, since they declare the arguments instead of using them.
+fn without_imports(tokens: TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                let mut directives = vec![Vec::new()];
+                for token in group.stream() {
+                    match token {
+                        TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                            directives.push(Vec::new());
+                        }
+                        token => directives.last_mut().unwrap().push(token),
+                    }
+                }
+
+                let directives = directives.into_iter().filter(|directive| {
+                    !matches!(
+                        directive.first(),
+                        Some(TokenTree::Ident(ident)) if ident == "import" || ident == "import_raw"
+                    )
+                });
+                let mut stream = TokenStream::new();
+                for directive in directives {
+                    stream.extend(directive);
+                    stream.extend(quote! { , });
+                }
+                TokenTree::Group(Group::new(Delimiter::Parenthesis, stream)).into()
+            }
+            token => TokenStream::from(token),
+        })
+        .collect()
+}
+
+/// Returns the span of the first use of `ident` in `tokens`.
+///
+/// If `exact` is true, idents which are a method or path segment, or the name
+/// of a field in a struct expression, are not included.
+fn find_ident(tokens: TokenStream, ident: &Ident, exact: bool) -> Option<Span> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    tokens.iter().enumerate().find_map(|(index, token)| match token {
+        TokenTree::Group(group) => find_ident(group.stream(), ident, exact),
+        TokenTree::Ident(found) if found == ident => {
+            let prev = index.checked_sub(1).and_then(|index| tokens.get(index));
+            let is_member = matches!(prev, Some(TokenTree::Punct(punct)) if matches!(punct.as_char(), '.' | ':'));
+            let is_label = matches!(
+                tokens.get(index + 1),
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Alone
+            );
+            (!exact || !(is_member || is_label)).then(|| found.span())
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_ident, ignored_attrs, unavailable_temp_fields, unused_imports};
+    use crate::binrw::{combiner::apply_temp_crossover, parser::Input, Options};
+    use quote::{format_ident, quote};
+    use syn::{parse_quote, DeriveInput};
+
+    fn parse(derive_input: &DeriveInput, write: bool) -> Input {
+        Input::from_input(
+            derive_input,
+            Options {
+                derive: false,
+                write,
+            },
+        )
+        .unwrap()
+    }
+
+    fn error_messages(error: Option<syn::Error>) -> Vec<String> {
+        error
+            .into_iter()
+            .flatten()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn find_ident_exact() {
+        let len = format_ident!("len");
+        assert!(find_ident(quote! { (calc = len + 1) }, &len, true).is_some());
+        assert!(find_ident(quote! { (calc = items.len()) }, &len, true).is_none());
+        assert!(find_ident(quote! { (args { len: 1 }) }, &len, true).is_none());
+        assert!(find_ident(quote! { (args { len: 1 }) }, &len, false).is_some());
+        assert!(find_ident(quote! { (calc = Self::len) }, &len, true).is_none());
+    }
+
+    #[test]
+    fn unused_import() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[br(import(a: u8, _b: u8, c: u8, d: u8 = c))]
+            #[bw(import_raw(e: u8))]
+            struct Test {
+                #[br(count = a)]
+                #[bw(calc = 0)]
+                x: Vec<u8>,
+            }
+        };
+
+        assert!(error_messages(unused_imports(&derive_input, &parse(&derive_input, false), false))
+            .iter()
+            .eq(["imported argument `d` is never used; if this is intentional, prefix it with an underscore"]));
+        assert_eq!(
+            error_messages(unused_imports(
+                &derive_input,
+                &parse(&derive_input, true),
+                true
+            ))
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn ignored_attr() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[br(big)]
+            enum Test {
+                #[bw(magic = 1u8)]
+                A,
+            }
+        };
+
+        assert_eq!(error_messages(ignored_attrs(&derive_input, false)).len(), 1);
+        assert!(ignored_attrs(&derive_input, true).is_some());
+    }
+
+    #[test]
+    fn unavailable_temp_field() {
+        let derive_input: DeriveInput = parse_quote! {
+            struct Test {
+                #[br(temp)]
+                #[bw(ignore)]
+                len: u8,
+                #[br(temp)]
+                #[bw(calc = 1)]
+                count: u8,
+                #[bw(assert(count > len && items.len() > 0))]
+                items: Vec<u8>,
+            }
+        };
+
+        let mut read = crate::binrw::parser::ParseResult::Ok(parse(&derive_input, false));
+        let mut write = crate::binrw::parser::ParseResult::Ok(parse(&derive_input, true));
+        assert!(apply_temp_crossover(&mut read, &mut write).is_none());
+        assert!(error_messages(unavailable_temp_fields(&write.unwrap()))
+            .iter()
+            .eq(["`len` is not available when writing because it is `#[br(temp)]` and `#[bw(ignore)]`; use `#[bw(calc)]` on it instead"]));
+    }
+}
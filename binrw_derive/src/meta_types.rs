@@ -57,6 +57,13 @@ pub(crate) type MetaIdent<Keyword> = MetaValue<Keyword, Ident>;
 /// both are always allowed
 pub(crate) type MetaLit<Keyword> = MetaValue<Keyword, Lit>;
 
+/// `MetaOptionalExpr` represents a key with an optional expr
+/// Takes three forms:
+/// * ident
+/// * ident(expr)
+/// * ident = expr
+pub(crate) type MetaOptionalExpr<Keyword> = MetaOptionalValue<Keyword, Expr>;
+
 #[derive(Debug, Clone)]
 pub(crate) struct MetaValue<Keyword, Value> {
     pub(crate) ident: Keyword,
@@ -113,6 +120,44 @@ impl<Keyword: Token + Spanned, Value> KeywordToken for MetaValue<Keyword, Value>
     }
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct MetaOptionalValue<Keyword, Value> {
+    pub(crate) ident: Keyword,
+    pub(crate) value: Option<Value>,
+}
+
+impl<Keyword: Parse, Value: Parse> Parse for MetaOptionalValue<Keyword, Value> {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let value = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse()?)
+        } else if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(MetaOptionalValue { ident, value })
+    }
+}
+
+impl<Keyword, Value: ToTokens> From<MetaOptionalValue<Keyword, Value>> for Option<TokenStream> {
+    fn from(value: MetaOptionalValue<Keyword, Value>) -> Self {
+        value.value.map(ToTokens::into_token_stream)
+    }
+}
+
+impl<Keyword: Token + Spanned, Value> KeywordToken for MetaOptionalValue<Keyword, Value> {
+    type Token = Keyword;
+
+    fn keyword_span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MetaVoid<Keyword> {
     pub(crate) ident: Keyword,
@@ -65,17 +65,18 @@ impl Builder<'_> {
                 )
             });
 
-            let derives = if self.are_all_fields_optional() {
-                quote!(#[derive(Clone, Default)])
-            } else {
-                quote!(#[derive(Clone)])
-            };
+            let derives = self
+                .are_all_fields_optional()
+                .then(|| quote!(#[derive(Default)]));
+            let clone_impl = self.generate_clone_impl(&user_bounds, &user_generic_args);
             Some(quote!(
                 #derives
                 #[doc = #docs]
                 #vis struct #name < #user_bounds > {
                     #fields
                 }
+
+                #clone_impl
             ))
         } else {
             None
@@ -140,6 +141,39 @@ impl Builder<'_> {
         )
     }
 
+    // Arguments are only cloneable if every field is, so that types which are
+    // never cloned can be used as arguments. The bounds are higher-ranked so
+    // that a field type which does not implement `Clone` is not rejected as a
+    // trivially false bound
+    fn generate_clone_impl(
+        &self,
+        user_bounds: &TokenStream,
+        user_generic_args: &TokenStream,
+    ) -> TokenStream {
+        let name = self.result_name;
+        let clone_bounds = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+            quote! { for<'__binrw_generated_clone> #ty: ::core::clone::Clone }
+        });
+        let clone_fields = self.fields.iter().map(|field| {
+            let name = &field.name;
+            quote! { #name: ::core::clone::Clone::clone(&self.#name) }
+        });
+
+        quote! {
+            impl< #user_bounds > ::core::clone::Clone for #name < #user_generic_args >
+            where
+                #(#clone_bounds,)*
+            {
+                fn clone(&self) -> Self {
+                    Self {
+                        #(#clone_fields,)*
+                    }
+                }
+            }
+        }
+    }
+
     fn user_generic_args(&self) -> TokenStream {
         let args = self.generics.iter().map(|generic| match generic {
             GenericParam::Type(ty) => GenericArgument::Type(Type::Path(syn::TypePath {
@@ -1,4 +1,4 @@
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens, TokenStreamExt};
 
 macro_rules! ident_str {
@@ -51,6 +51,25 @@ impl IdentStr {
     pub(crate) fn to_ident(self, span: Span) -> Ident {
         Ident::new(self.0, span)
     }
+
+    /// Returns true if the identifier appears anywhere in `tokens`, so that
+    /// generated code can skip defining a variable which is never read.
+    pub(crate) fn is_used_in(self, tokens: &TokenStream) -> bool {
+        tokens.clone().into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == self.0,
+            TokenTree::Group(group) => self.is_used_in(&group.stream()),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+}
+
+/// Returns true if `ident` appears anywhere in `tokens`.
+pub(crate) fn ident_is_used_in(ident: &Ident, tokens: &TokenStream) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => ident_is_used_in(ident, &group.stream()),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
 }
 
 impl ToTokens for IdentStr {